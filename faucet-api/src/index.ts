@@ -16,7 +16,10 @@ const PORT = parseInt(process.env.PORT || '3000', 10);
 const RATE_WINDOW_SECS = parseInt(process.env.FAUCET_RATE_WINDOW_SECS || '60', 10);
 const RATE_MAX = parseInt(process.env.FAUCET_RATE_MAX || '2', 10);
 const COOLDOWN_SECS = parseInt(process.env.FAUCET_ADDRESS_COOLDOWN_SECS || '86400', 10);
-const AMOUNT_USEI = process.env.FAUCET_AMOUNT_USEI || '100000';
+// Positive whole usei; zero and negatives are rejected wherever an amount is read
+const POSITIVE_USEI = /^[1-9]\d*$/;
+// Per-request maximum; an unset, zero or malformed FAUCET_AMOUNT_USEI falls back to the default
+const AMOUNT_USEI = POSITIVE_USEI.test(process.env.FAUCET_AMOUNT_USEI || '') ? process.env.FAUCET_AMOUNT_USEI! : '100000';
 const DENOM = process.env.FAUCET_DENOM || 'usei';
 
 // RPCs
@@ -38,6 +41,8 @@ type ChainKey = typeof CHAINS[keyof typeof CHAINS];
 const bodySchema = z.object({
   address: z.string().min(1),
   chain: z.enum([CHAINS.evm, CHAINS.native]),
+  // Optional amount in usei; capped at FAUCET_AMOUNT_USEI
+  amount: z.string().regex(POSITIVE_USEI, 'amount must be a whole number of usei above 0').optional(),
 });
 
 app.get('/', (_req, res) => {
@@ -75,6 +80,9 @@ app.post('/faucet/request', async (req, res) => {
     return res.status(400).json({ error: 'invalid_body', details: parse.error.flatten() });
   }
   const { address, chain } = parse.data;
+  const amountUsei = parse.data.amount && BigInt(parse.data.amount) < BigInt(AMOUNT_USEI)
+    ? parse.data.amount
+    : AMOUNT_USEI;
 
   const ip = (req.headers['x-forwarded-for'] as string)?.split(',')[0]?.trim() || req.socket.remoteAddress || 'unknown';
 
@@ -115,7 +123,7 @@ app.post('/faucet/request', async (req, res) => {
         rpcUrl: rpc,
         privateKey: process.env.FAUCET_PRIVATE_KEY_EVM || process.env.FAUCET_PRIVATE_KEY || '',
         to: address,
-        amountUsei: AMOUNT_USEI,
+        amountUsei,
       });
     } else {
       const rpc = chainRpcUrls[CHAINS.native];
@@ -123,7 +131,7 @@ app.post('/faucet/request', async (req, res) => {
       result = await sendNative({
         rpcUrl: rpc,
         denom: DENOM,
-        amountUsei: AMOUNT_USEI,
+        amountUsei,
         toBech32: address,
         bech32Hrp: process.env.NATIVE_BECH32_HRP || 'sei',
         chainId: process.env.NATIVE_CHAIN_ID || 'atlantic-2',
@@ -135,7 +143,7 @@ app.post('/faucet/request', async (req, res) => {
     // Set cooldown
     await redis.set(cooldownKey, '1', 'EX', COOLDOWN_SECS);
 
-    const resp = { txHash: result.txHash, amount: AMOUNT_USEI, denom: DENOM, chain };
+    const resp = { txHash: result.txHash, amount: amountUsei, denom: DENOM, chain };
     console.log('faucet_request_success', { chain, txHash: result.txHash });
     return res.json(resp);
   } catch (err: any) {
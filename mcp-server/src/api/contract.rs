@@ -56,7 +56,7 @@ pub async fn get_contract_transactions_handler(
 ) -> impl IntoResponse {
     match state
         .sei_client
-        .get_contract_transactions(&params.chain_id, &params.address)
+        .get_contract_transactions(&params.chain_id, &params.address, None, None)
         .await
     {
         Ok(txs) => (StatusCode::OK, Json(txs)).into_response(),
@@ -3,11 +3,13 @@
 use crate::AppState; // FIX: Import AppState
 use axum::{extract::State, http::StatusCode, Json};
 use serde::Deserialize;
+use serde_json::{json, Value};
 
 #[derive(Deserialize)]
 pub struct FaucetRequest {
     pub address: String,
     pub chain_id: String, // required
+    pub amount: Option<u64>, // optional, capped at the configured per-chain max
 }
 
 /// Axum handler for the faucet request endpoint.
@@ -15,15 +17,15 @@ pub struct FaucetRequest {
 pub async fn request_faucet(
     State(state): State<AppState>,
     Json(req): Json<FaucetRequest>,
-) -> Result<Json<String>, (StatusCode, String)> {
+) -> Result<Json<Value>, (StatusCode, String)> {
     // Normalize common aliases users might pass
     let mut chain_id = req.chain_id.trim().to_string();
     if chain_id == "sei-testnet" { chain_id = "sei-evm-testnet".to_string(); }
     if chain_id == "sei-mainnet" { chain_id = "sei-evm-mainnet".to_string(); }
 
-    let rpc_url = match state.config.chain_rpc_urls.get(&chain_id) {
-        Some(u) => u,
-        None => {
+    let rpc_url = match state.sei_client.get_rpc_url(&chain_id) {
+        Ok(u) => u,
+        Err(_) => {
             let keys: Vec<String> = state.config.chain_rpc_urls.keys().cloned().collect();
             return Err((
                 StatusCode::BAD_REQUEST,
@@ -36,15 +38,26 @@ pub async fn request_faucet(
         }
     };
 
+    if req.amount == Some(0) {
+        return Err((StatusCode::BAD_REQUEST, "amount must be greater than zero".to_string()));
+    }
+
     // Cooldowns and rate limits are enforced by the external faucet API now.
 
-    let tx_hash = crate::blockchain::services::faucet::send_faucet_tokens(
+    let resp = crate::blockchain::services::faucet::send_faucet_tokens(
+        state.sei_client.transport(),
         &state.config,
+        &state.faucet_ledger,
         &req.address,
         &state.nonce_manager,
         rpc_url,
         &chain_id,
+        req.amount,
     ).await.map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
 
-    Ok(Json(tx_hash))
+    // Keep the bare tx hash response when no token faucet is configured
+    if resp.token_tx_hash.is_none() && resp.token_error.is_none() {
+        return Ok(Json(json!(resp.tx_hash)));
+    }
+    Ok(Json(json!(resp)))
 }
\ No newline at end of file
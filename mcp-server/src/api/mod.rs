@@ -11,3 +11,4 @@ pub mod tx;
 pub mod discord;
 pub mod docs;
 pub mod seistream;
+pub mod rate_limit;
@@ -0,0 +1,142 @@
+// src/api/rate_limit.rs
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use dashmap::DashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Derives the bucket key for a request; `None` exempts the request from this limiter.
+pub type KeyFn = dyn Fn(&Request) -> Option<String> + Send + Sync;
+
+// Buckets are swept once the map grows past this many keys
+const SWEEP_THRESHOLD: usize = 10_000;
+
+/// Fixed-window request limiter. What a "client" is depends on the key extractor,
+/// so the same type serves per-IP and per-token limits.
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    key: Box<KeyFn>,
+    buckets: DashMap<String, (Instant, u32)>,
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32, window: Duration, key: impl Fn(&Request) -> Option<String> + Send + Sync + 'static) -> Self {
+        Self { limit, window, key: Box::new(key), buckets: DashMap::new() }
+    }
+
+    /// Counts one request against `key`; returns how long to wait when the window is exhausted.
+    pub fn check(&self, key: &str) -> Result<(), Duration> {
+        let now = Instant::now();
+        if self.buckets.len() > SWEEP_THRESHOLD {
+            self.buckets.retain(|_, (start, _)| now.duration_since(*start) < self.window);
+        }
+        let mut bucket = self.buckets.entry(key.to_string()).or_insert((now, 0));
+        let (start, count) = &mut *bucket;
+        if now.duration_since(*start) >= self.window {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= self.limit {
+            return Err(self.window.saturating_sub(now.duration_since(*start)));
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+/// Keys on client IP plus request path, so one noisy route doesn't starve the others.
+pub fn ip_path_key(req: &Request) -> Option<String> {
+    let ip = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string());
+    Some(format!("{}|{}", ip, req.uri().path()))
+}
+
+/// Keys on the bearer token, bounding a token no matter how many IPs share it.
+/// Requests without a token are left to the IP limiter.
+pub fn bearer_token_key(req: &Request) -> Option<String> {
+    req.headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(|token| format!("token|{}", token))
+}
+
+/// Axum middleware: `middleware::from_fn_with_state(limiter, rate_limit)`.
+pub async fn rate_limit(State(limiter): State<Arc<RateLimiter>>, req: Request, next: Next) -> Response {
+    if let Some(key) = (limiter.key)(&req) {
+        if let Err(retry_after) = limiter.check(&key) {
+            let mut resp = (StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded").into_response();
+            let secs = retry_after.as_secs().max(1);
+            if let Ok(v) = HeaderValue::from_str(&secs.to_string()) {
+                resp.headers_mut().insert(header::RETRY_AFTER, v);
+            }
+            return resp;
+        }
+    }
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+
+    fn request(ip: [u8; 4], path: &str, token: Option<&str>) -> Request {
+        let mut builder = axum::http::Request::builder().uri(path);
+        if let Some(t) = token {
+            builder = builder.header(header::AUTHORIZATION, format!("Bearer {}", t));
+        }
+        let mut req = builder.body(Body::empty()).unwrap();
+        req.extensions_mut().insert(ConnectInfo(SocketAddr::from((ip, 4000))));
+        req
+    }
+
+    fn allowed(limiter: &RateLimiter, req: &Request) -> bool {
+        (limiter.key)(req).is_none_or(|k| limiter.check(&k).is_ok())
+    }
+
+    #[test]
+    fn test_ip_path_keying() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60), ip_path_key);
+        let a = request([10, 0, 0, 1], "/api/health", None);
+        assert!(allowed(&limiter, &a));
+        assert!(allowed(&limiter, &a));
+        assert!(!allowed(&limiter, &a));
+        // Other paths and other IPs have their own buckets
+        assert!(allowed(&limiter, &request([10, 0, 0, 1], "/mcp", None)));
+        assert!(allowed(&limiter, &request([10, 0, 0, 2], "/api/health", None)));
+    }
+
+    #[test]
+    fn test_token_keying_spans_ips() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60), bearer_token_key);
+        assert!(allowed(&limiter, &request([10, 0, 0, 1], "/mcp", Some("t1"))));
+        assert!(allowed(&limiter, &request([10, 0, 0, 2], "/mcp", Some("t1"))));
+        assert!(!allowed(&limiter, &request([10, 0, 0, 3], "/mcp/sse", Some("t1"))));
+        assert!(allowed(&limiter, &request([10, 0, 0, 3], "/mcp", Some("t2"))));
+        // No token: exempt here, bounded by the IP limiter instead
+        for _ in 0..5 {
+            assert!(allowed(&limiter, &request([10, 0, 0, 1], "/mcp", None)));
+        }
+    }
+
+    #[test]
+    fn test_window_resets() {
+        let limiter = RateLimiter::new(1, Duration::from_millis(20), ip_path_key);
+        assert!(limiter.check("k").is_ok());
+        assert!(limiter.check("k").is_err());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(limiter.check("k").is_ok());
+    }
+}
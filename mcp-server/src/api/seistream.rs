@@ -57,7 +57,7 @@ pub async fn get_transaction_history_handler(
     Query(PageQuery { page }): Query<PageQuery>,
 ) -> impl IntoResponse {
     let client = Client::new();
-    match crate::blockchain::services::seistream::get_transaction_history(&client, &address, page)
+    match crate::blockchain::services::seistream::get_transaction_history(&client, &address, page, None)
         .await
     {
         Ok(v) => (StatusCode::OK, Json(v)).into_response(),
@@ -75,7 +75,7 @@ pub async fn get_nft_metadata_items_handler(
 ) -> impl IntoResponse {
     let client = Client::new();
     match crate::blockchain::services::seistream::get_nft_metadata_erc721_items(
-        &client, &address, page,
+        &client, &address, page, None,
     )
     .await
     {
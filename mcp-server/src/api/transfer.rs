@@ -15,19 +15,13 @@ pub struct TransferRequest {
     pub to_address: String,
     pub amount: String,
     pub private_key: String,
-    #[serde(default = "default_gas_limit")]
     pub gas_limit: Option<String>,
-    #[serde(default = "default_gas_price")]
     pub gas_price: Option<String>,
 }
 
-fn default_gas_limit() -> Option<String> {
-    Some("100000".to_string()) // Higher gas limit for SEI transfers
-}
-
-fn default_gas_price() -> Option<String> {
-    Some("20000000000".to_string()) // 20 Gwei
-}
+// Built-in fallbacks when neither the request nor DEFAULT_GAS_LIMITS / DEFAULT_GAS_PRICES set a value
+const FALLBACK_GAS_LIMIT: u64 = 100_000; // Higher gas limit for SEI transfers
+const FALLBACK_GAS_PRICE: u64 = 20_000_000_000; // 20 Gwei
 
 #[derive(Debug, Serialize)]
 pub struct TransferResponse {
@@ -48,14 +42,26 @@ pub async fn transfer_sei_handler(
         request.gas_limit,
         request.gas_price
     );
-    let client = SeiClient::new(&state.config.chain_rpc_urls, &state.config.websocket_url);
+    let client = SeiClient::new(&state.config.chain_rpc_urls, &state.config.websocket_url)
+        .with_tx_types(&state.config.tx_types)
+        .with_max_gas_prices(&state.config.max_gas_prices)
+        .with_failover(
+            &state.config.rpc_fallback_urls,
+            state.config.rpc_failure_threshold,
+            std::time::Duration::from_millis(state.config.rpc_unhealthy_cooldown_ms),
+        );
 
+    let config = &state.config;
     let transfer_request = SeiTransferRequest {
         to_address: request.to_address,
         amount: request.amount,
         private_key: request.private_key,
-        gas_limit: request.gas_limit,
-        gas_price: request.gas_price,
+        gas_limit: Some(request.gas_limit.unwrap_or_else(|| {
+            config.default_gas_limit(&chain_id).unwrap_or(FALLBACK_GAS_LIMIT).to_string()
+        })),
+        gas_price: Some(request.gas_price.unwrap_or_else(|| {
+            config.default_gas_price(&chain_id).unwrap_or(FALLBACK_GAS_PRICE).to_string()
+        })),
     };
 
     match client.transfer_sei(&chain_id, &transfer_request).await {
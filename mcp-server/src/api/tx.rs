@@ -83,10 +83,13 @@ pub async fn send_transaction_handler(
 
             // Send via shared nonce manager
             let resp: TransactionResponse = send_evm_transaction(
+                state.sei_client.transport(),
                 &rpc_url,
                 wallet,
                 tx,
                 &state.nonce_manager,
+                state.config.tx_type(&req.chain_id),
+                state.config.max_gas_price(&req.chain_id).map(U256::from),
             ).await.map_err(|e| (StatusCode::BAD_GATEWAY, format!("EVM send failed: {}", e)))?;
 
             Ok(Json(SendTxResponse { tx_hash: resp.tx_hash }))
@@ -116,11 +119,13 @@ pub async fn send_transaction_handler(
             };
 
             let txhash = send_native_transaction_signed(
+                state.sei_client.http(),
                 &state.config,
                 &rpc_url,
                 &from_private_key_hex,
                 &req.to,
                 req.amount_usei,
+                "",
             ).await.map_err(|e| (StatusCode::BAD_GATEWAY, format!("Native send failed: {}", e)))?;
             Ok(Json(SendTxResponse { tx_hash: txhash }))
         }
@@ -0,0 +1,44 @@
+// src/blockchain/broadcasts.rs
+
+//! Per-call record of transactions that have already reached the network.
+//!
+//! A tool call that passes its deadline is answered before it finishes, so anything it broadcast
+//! by then would otherwise go unreported. The send paths record each accepted hash here and the
+//! MCP handler reads the log back when it has to answer early.
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+/// Shared list of transaction hashes broadcast while a tracked future ran.
+pub type BroadcastLog = Arc<Mutex<Vec<String>>>;
+
+tokio::task_local! {
+    static BROADCASTS: BroadcastLog;
+}
+
+/// Runs `fut` with `log` collecting every transaction it broadcasts.
+pub async fn track<F: Future>(log: BroadcastLog, fut: F) -> F::Output {
+    BROADCASTS.scope(log, fut).await
+}
+
+/// Records a transaction the node accepted; a no-op outside [`track`].
+pub fn record(tx_hash: &str) {
+    let _ = BROADCASTS.try_with(|log| {
+        if let Ok(mut hashes) = log.lock() {
+            hashes.push(tx_hash.to_string());
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn records_only_inside_tracked_scope() {
+        record("0xignored");
+        let log = BroadcastLog::default();
+        track(log.clone(), async { record("0xabc") }).await;
+        assert_eq!(*log.lock().unwrap(), vec!["0xabc".to_string()]);
+    }
+}
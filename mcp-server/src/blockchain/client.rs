@@ -1,42 +1,230 @@
 // src/blockchain/client.rs
 
 use crate::blockchain::{
+    endpoint_health::{EndpointHealth, EndpointStatus, HealthTrackingTransport},
     models::*,
     nonce_manager::NonceManager,
-    services::{balance, fees, history, transactions, wallet, contract},
+    services::{balance, fees, history, rpc::{self, RpcTransport}, trace, transactions, wallet, contract},
 };
+use crate::config::TxType;
 use anyhow::{anyhow, Result};
 use ethers_core::types::TransactionRequest;
+use ethers_signers::LocalWallet;
+use futures::future::BoxFuture;
 use std::collections::HashMap;
+use std::sync::Arc;
 use serde_json::Value;
 
+/// The network-facing operations behind `SeiClient`. `HttpBackend` talks to real nodes;
+/// tests swap in their own implementation with `SeiClient::with_backend` to get canned
+/// balances and transaction hashes without any endpoint.
+pub trait ChainBackend: Send + Sync {
+    fn get_balance<'a>(&'a self, rpc_url: &'a str, address: &'a str, is_native: bool) -> BoxFuture<'a, Result<BalanceResponse>>;
+
+    fn send_evm_transaction<'a>(
+        &'a self,
+        rpc_url: &'a str,
+        wallet: LocalWallet,
+        tx_request: TransactionRequest,
+        nonce_manager: &'a NonceManager,
+        policy: transactions::FeePolicy,
+    ) -> BoxFuture<'a, Result<transactions::SentTransaction>>;
+
+    fn quote_gas<'a>(&'a self, rpc_url: &'a str, tx_request: TransactionRequest, tx_type: TxType) -> BoxFuture<'a, Result<transactions::GasQuote>>;
+
+    fn eth_call<'a>(&'a self, rpc_url: &'a str, call_obj: Value, block: &'a str) -> BoxFuture<'a, Result<String>>;
+}
+
+/// `ChainBackend` over JSON-RPC (sent through `transport`) and the Cosmos REST gateway.
+#[derive(Clone)]
+pub struct HttpBackend {
+    client: rpc::HttpClient,
+    transport: Arc<dyn RpcTransport>,
+}
+
+impl HttpBackend {
+    pub fn new(client: rpc::HttpClient) -> Self {
+        Self { transport: Arc::new(client.clone()), client }
+    }
+}
+
+impl ChainBackend for HttpBackend {
+    fn get_balance<'a>(&'a self, rpc_url: &'a str, address: &'a str, is_native: bool) -> BoxFuture<'a, Result<BalanceResponse>> {
+        Box::pin(async move {
+            if is_native {
+                Ok(balance::get_balance(&self.client, rpc_url, address, true).await?)
+            } else {
+                Ok(balance::get_evm_balance_at(self.transport.as_ref(), rpc_url, address, serde_json::json!("latest")).await?)
+            }
+        })
+    }
+
+    fn send_evm_transaction<'a>(
+        &'a self,
+        rpc_url: &'a str,
+        wallet: LocalWallet,
+        tx_request: TransactionRequest,
+        nonce_manager: &'a NonceManager,
+        policy: transactions::FeePolicy,
+    ) -> BoxFuture<'a, Result<transactions::SentTransaction>> {
+        Box::pin(async move {
+            Ok(transactions::send_evm_transaction_with(self.transport.as_ref(), rpc_url, wallet, tx_request, nonce_manager, policy).await?)
+        })
+    }
+
+    fn quote_gas<'a>(&'a self, rpc_url: &'a str, tx_request: TransactionRequest, tx_type: TxType) -> BoxFuture<'a, Result<transactions::GasQuote>> {
+        Box::pin(async move { Ok(transactions::quote_gas(self.transport.as_ref(), rpc_url, &tx_request, tx_type).await?) })
+    }
+
+    fn eth_call<'a>(&'a self, rpc_url: &'a str, call_obj: Value, block: &'a str) -> BoxFuture<'a, Result<String>> {
+        Box::pin(rpc::eth_call(self.transport.as_ref(), rpc_url, call_obj, block))
+    }
+}
+
 #[derive(Clone)]
 pub struct SeiClient {
-    client: reqwest::Client,
-    rpc_urls: HashMap<String, String>,
+    // HTTP client carrying each endpoint's RPC_HEADERS
+    client: rpc::HttpClient,
+    backend: Arc<dyn ChainBackend>,
+    // JSON-RPC transport for calls made outside the backend; records endpoint health like the backend's
+    transport: Arc<HealthTrackingTransport>,
+    // Per chain: the CHAIN_RPC_URLS endpoint, then any RPC_FALLBACK_URLS, in preference order
+    rpc_urls: HashMap<String, Vec<String>>,
+    health: Arc<EndpointHealth>,
+    // Tracing method each endpoint supports, learned on first use
+    trace_methods: Arc<trace::TraceMethodCache>,
+    tx_types: HashMap<String, TxType>,
+    max_gas_prices: HashMap<String, u64>,
     pub websocket_url: String,
 }
 
 impl SeiClient {
     pub fn new(rpc_urls: &HashMap<String, String>, websocket_url: &str) -> Self {
+        let client = rpc::HttpClient::default();
+        let health = Arc::new(EndpointHealth::new(1, std::time::Duration::ZERO));
+        let transport = Arc::new(HealthTrackingTransport { inner: Arc::new(client.clone()), health: health.clone() });
         Self {
-            client: reqwest::Client::new(),
-            rpc_urls: rpc_urls.clone(),
+            backend: Arc::new(HttpBackend { client: client.clone(), transport: transport.clone() }),
+            client,
+            transport,
+            rpc_urls: rpc_urls.iter().map(|(chain, url)| (chain.clone(), vec![url.clone()])).collect(),
+            health,
+            trace_methods: Arc::new(trace::TraceMethodCache::default()),
+            tx_types: HashMap::new(),
+            max_gas_prices: HashMap::new(),
             websocket_url: websocket_url.to_string(),
         }
     }
 
+    /// Sets the per-chain transaction envelope (TX_TYPES); chains left out use `auto`.
+    pub fn with_tx_types(mut self, tx_types: &HashMap<String, TxType>) -> Self {
+        self.tx_types = tx_types.clone();
+        self
+    }
+
+    /// Sets the per-chain gas price ceiling in wei (MAX_GAS_PRICES); chains left out are uncapped.
+    pub fn with_max_gas_prices(mut self, max_gas_prices: &HashMap<String, u64>) -> Self {
+        self.max_gas_prices = max_gas_prices.clone();
+        self
+    }
+
+    /// Sends each endpoint's RPC_HEADERS with every request this client makes to it.
+    pub fn with_rpc_headers(mut self, headers: rpc::EndpointHeaders) -> Self {
+        self.client = rpc::HttpClient::new(reqwest::Client::new(), headers);
+        let inner = Arc::new(self.client.clone());
+        self.with_transport(inner)
+    }
+
+    /// Adds RPC_FALLBACK_URLS behind each chain's primary endpoint, and the failure threshold and
+    /// cooldown after which an endpoint is skipped in favour of the next.
+    pub fn with_failover(mut self, fallback_urls: &HashMap<String, Vec<String>>, failure_threshold: u32, cooldown: std::time::Duration) -> Self {
+        for (chain_id, urls) in fallback_urls {
+            let endpoints = self.rpc_urls.entry(chain_id.clone()).or_default();
+            endpoints.extend(urls.iter().filter(|u| !endpoints.contains(u)).cloned().collect::<Vec<_>>());
+        }
+        self.health = Arc::new(EndpointHealth::new(failure_threshold, cooldown));
+        let inner = self.transport.inner.clone();
+        self.with_transport(inner)
+    }
+
+    /// Routes balance, send, gas and call operations through `backend` instead of the network.
+    pub fn with_backend(mut self, backend: Arc<dyn ChainBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Keeps the HTTP backend but sends its JSON-RPC requests through `transport`.
+    pub fn with_transport(mut self, transport: Arc<dyn RpcTransport>) -> Self {
+        self.transport = Arc::new(HealthTrackingTransport { inner: transport, health: self.health.clone() });
+        self.backend = Arc::new(HttpBackend { client: self.client.clone(), transport: self.transport.clone() });
+        self
+    }
+
+    // TX_TYPES envelope and MAX_GAS_PRICES ceiling for `chain_id`, plus the caller's bump setting and tip
+    fn fee_policy(
+        &self,
+        chain_id: &str,
+        bump: Option<transactions::GasBump>,
+        priority_fee: Option<ethers_core::types::U256>,
+    ) -> transactions::FeePolicy {
+        transactions::FeePolicy {
+            tx_type: self.tx_types.get(chain_id).copied().unwrap_or_default(),
+            max_gas_price: self.max_gas_prices.get(chain_id).map(|&p| ethers_core::types::U256::from(p)),
+            bump,
+            priority_fee,
+        }
+    }
+
+    /// The endpoint to use for `chain_id` right now: the first one not skipped for repeated failures.
     pub fn get_rpc_url(&self, chain_id: &str) -> Result<&String> {
         self.rpc_urls
             .get(chain_id)
+            .and_then(|urls| self.health.select(urls, std::time::Instant::now()))
             .ok_or_else(|| anyhow!("RPC URL not found for chain_id: {}", chain_id))
     }
 
+    /// The JSON-RPC transport for calls made outside this client's own methods. Failures count against
+    /// the endpoint's health, so `get_rpc_url` moves off a failing node as it does for the client's calls.
+    pub fn transport(&self) -> &dyn RpcTransport {
+        self.transport.as_ref()
+    }
+
+    /// The HTTP client for REST and Tendermint requests to the configured endpoints; carries their RPC_HEADERS.
+    pub fn http(&self) -> &rpc::HttpClient {
+        &self.client
+    }
+
+    /// Chains with at least one configured endpoint.
+    pub fn chain_ids(&self) -> Vec<String> {
+        let mut chains: Vec<String> = self.rpc_urls.keys().cloned().collect();
+        chains.sort();
+        chains
+    }
+
+    /// Health of every endpoint configured for `chain_id`, in preference order.
+    /// With `probe`, each is first sent `eth_blockNumber` so the report reflects a fresh attempt.
+    pub async fn endpoint_health(&self, chain_id: &str, probe: bool) -> Result<Vec<(EndpointStatus, Option<u64>)>> {
+        let urls = self.rpc_urls.get(chain_id).ok_or_else(|| anyhow!("RPC URL not found for chain_id: {}", chain_id))?;
+        let mut report = Vec::with_capacity(urls.len());
+        for url in urls {
+            let latest_block = if probe {
+                rpc::rpc_call(self.transport.as_ref(), url, "eth_blockNumber", serde_json::json!([]))
+                    .await
+                    .ok()
+                    .and_then(|v| v.as_str().and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok()))
+            } else {
+                None
+            };
+            report.push((self.health.status(url, std::time::Instant::now()), latest_block));
+        }
+        Ok(report)
+    }
+
     pub async fn get_balance(&self, chain_id: &str, address: &str) -> Result<BalanceResponse> {
         let rpc_url = self.get_rpc_url(chain_id)?;
         let is_native = crate::blockchain::models::ChainType::from_chain_id(chain_id)
             == crate::blockchain::models::ChainType::Native;
-        balance::get_balance(&self.client, rpc_url, address, is_native).await
+        self.backend.get_balance(rpc_url, address, is_native).await
     }
 
     pub async fn create_wallet(&self) -> Result<WalletResponse, WalletGenerationError> {
@@ -56,7 +244,7 @@ impl SeiClient {
         if chain_id != "sei" && chain_id != "sei-testnet" {
             return Err(anyhow!("Transaction history via Seistream API is only supported for 'sei' and 'sei-testnet' chains."));
         }
-        history::get_transaction_history(&self.client, address, limit).await
+        history::get_transaction_history(self.client.inner(), address, limit).await
     }
 
     pub async fn estimate_fees(
@@ -78,7 +266,37 @@ impl SeiClient {
     ) -> Result<TransactionResponse> {
         let rpc_url = self.get_rpc_url(chain_id)?;
         let wallet = wallet::import_wallet(private_key)?.private_key.parse()?;
-        transactions::send_evm_transaction(rpc_url, wallet, tx_request, nonce_manager).await
+        let policy = self.fee_policy(chain_id, None, None);
+        Ok(self.backend.send_evm_transaction(rpc_url, wallet, tx_request, nonce_manager, policy).await?.response)
+    }
+
+    /// `send_transaction` that retries underpriced rejections with raised fees when `bump` is set.
+    /// A `priority_fee` (wei) tips an EIP-1559 send on top of the latest base fee.
+    pub async fn send_transaction_bumped(
+        &self,
+        chain_id: &str,
+        private_key: &str,
+        tx_request: TransactionRequest,
+        nonce_manager: &NonceManager,
+        bump: Option<transactions::GasBump>,
+        priority_fee: Option<ethers_core::types::U256>,
+    ) -> Result<transactions::SentTransaction> {
+        let rpc_url = self.get_rpc_url(chain_id)?;
+        let wallet = wallet::import_wallet(private_key)?.private_key.parse()?;
+        let policy = self.fee_policy(chain_id, bump, priority_fee);
+        self.backend.send_evm_transaction(rpc_url, wallet, tx_request, nonce_manager, policy).await
+    }
+
+    /// Gas limit and fee caps `send_transaction` would use for `tx_request` sent from `from`.
+    pub async fn quote_gas(
+        &self,
+        chain_id: &str,
+        from: ethers_core::types::Address,
+        tx_request: TransactionRequest,
+    ) -> Result<transactions::GasQuote> {
+        let rpc_url = self.get_rpc_url(chain_id)?;
+        let tx_type = self.tx_types.get(chain_id).copied().unwrap_or_default();
+        self.backend.quote_gas(rpc_url, tx_request.from(from), tx_type).await
     }
 
     // FIX: Transfer SEI tokens method
@@ -90,9 +308,15 @@ impl SeiClient {
         let _rpc_url = self.get_rpc_url(chain_id)?;
 
         // Convert to TransactionRequest for EVM transaction
-        let tx_request = TransactionRequest::new()
+        let mut tx_request = TransactionRequest::new()
             .to(request.to_address.parse::<ethers_core::types::Address>()?)
             .value(ethers_core::types::U256::from_dec_str(&request.amount)?);
+        if let Some(g) = &request.gas_limit {
+            tx_request = tx_request.gas(ethers_core::types::U256::from_dec_str(g)?);
+        }
+        if let Some(gp) = &request.gas_price {
+            tx_request = tx_request.gas_price(ethers_core::types::U256::from_dec_str(gp)?);
+        }
 
         // Use the centralized send_transaction method
         self.send_transaction(
@@ -104,16 +328,90 @@ impl SeiClient {
         .await
     }
 
+    /// Call tree of a mined transaction, via whichever tracing API the chain's node offers; `None` when
+    /// it offers neither.
+    pub async fn trace_transaction(&self, chain_id: &str, hash: &str) -> Result<Option<trace::TransactionTrace>> {
+        let rpc_url = self.get_rpc_url(chain_id)?;
+        trace::trace_transaction(&self.trace_methods, self.transport.as_ref(), rpc_url, hash).await
+    }
+
+    /// Executes a read-only `eth_call` against the chain's RPC and returns the raw hex output.
+    pub async fn eth_call(&self, chain_id: &str, call_obj: Value, block: &str) -> Result<String> {
+        let rpc_url = self.get_rpc_url(chain_id)?;
+        self.backend.eth_call(rpc_url, call_obj, block).await
+    }
+
     // --- Contract inspection ---
     pub async fn get_contract(&self, chain_id: &str, address: &str) -> Result<Value> {
-        contract::get_contract(&self.client, chain_id, address).await
+        contract::get_contract(self.client.inner(), chain_id, address).await
     }
 
     pub async fn get_contract_code(&self, chain_id: &str, address: &str) -> Result<Value> {
-        contract::get_contract_code(&self.client, chain_id, address).await
+        contract::get_contract_code(self.client.inner(), chain_id, address).await
+    }
+
+    pub async fn get_contract_abi(&self, chain_id: &str, address: &str) -> Result<Option<Vec<Value>>> {
+        contract::get_contract_abi(self.client.inner(), chain_id, address).await
+    }
+
+    pub async fn get_contract_transactions(
+        &self,
+        chain_id: &str,
+        address: &str,
+        page: Option<u64>,
+        page_size: Option<u64>,
+    ) -> Result<Value> {
+        contract::get_contract_transactions(self.client.inner(), chain_id, address, page, page_size).await
     }
 
-    pub async fn get_contract_transactions(&self, chain_id: &str, address: &str) -> Result<Value> {
-        contract::get_contract_transactions(&self.client, chain_id, address).await
+    /// Base fee of the latest block on `chain_id`, briefly cached (see `transactions::latest_base_fee`).
+    pub async fn latest_base_fee(&self, chain_id: &str) -> Result<(transactions::BaseFee, bool)> {
+        let rpc_url = self.get_rpc_url(chain_id)?;
+        Ok(transactions::latest_base_fee(self.transport.as_ref(), rpc_url).await?)
+    }
+
+    /// Submits a signed EVM transaction and returns the hash the node reports.
+    pub async fn broadcast_raw_transaction(&self, chain_id: &str, raw_tx: &[u8]) -> Result<String> {
+        let rpc_url = self.get_rpc_url(chain_id)?;
+        Ok(transactions::broadcast_raw_transaction(self.transport.as_ref(), rpc_url, raw_tx).await?)
+    }
+
+    /// Deployer and creation transaction of `address`: SeiStream's record where it indexes the chain,
+    /// otherwise (or when it has none) located over RPC. `None` when there is no code at `address`.
+    pub async fn get_contract_creation(&self, chain_id: &str, address: &str) -> Result<Option<contract::ContractCreation>> {
+        if contract::seistream_indexes(chain_id) {
+            let indexed = self.get_contract(chain_id, address).await.ok();
+            if let Some((creator, tx_hash)) = indexed.as_ref().and_then(contract::creation_from_seistream) {
+                // The explorer doesn't report the block; ask the node when one is configured
+                let block_number = match self.get_rpc_url(chain_id) {
+                    Ok(rpc_url) => contract::tx_block_number(self.transport.as_ref(), rpc_url, &tx_hash).await.ok().flatten(),
+                    Err(_) => None,
+                };
+                return Ok(Some(contract::ContractCreation {
+                    creator: Some(creator),
+                    tx_hash: Some(tx_hash),
+                    block_number,
+                    source: "seistream",
+                }));
+            }
+        }
+        let rpc_url = self.get_rpc_url(chain_id)?;
+        let Some(block) = contract::find_creation_block(self.transport.as_ref(), rpc_url, address).await? else {
+            return Ok(None);
+        };
+        let found = contract::find_creation_tx(self.transport.as_ref(), rpc_url, address, block).await?;
+        Ok(Some(contract::ContractCreation {
+            creator: found.as_ref().map(|(from, _)| from.clone()),
+            tx_hash: found.map(|(_, hash)| hash),
+            block_number: Some(block),
+            source: "rpc",
+        }))
+    }
+
+    /// Reads the EIP-1967 implementation slot of `address`; `None` when the slot is empty.
+    pub async fn get_proxy_implementation(&self, chain_id: &str, address: &str) -> Result<Option<ethers_core::types::Address>> {
+        let rpc_url = self.get_rpc_url(chain_id)?;
+        let word = rpc::get_storage_at(self.transport.as_ref(), rpc_url, address, contract::EIP1967_IMPLEMENTATION_SLOT, "latest").await?;
+        Ok(contract::address_from_storage_word(&word))
     }
 }
\ No newline at end of file
@@ -0,0 +1,145 @@
+// src/blockchain/endpoint_health.rs
+
+//! Health of each RPC endpoint, for failing over from CHAIN_RPC_URLS to RPC_FALLBACK_URLS.
+//!
+//! An endpoint whose transport fails RPC_FAILURE_THRESHOLD times in a row is skipped for
+//! RPC_UNHEALTHY_COOLDOWN_MS. Once the cooldown passes, the next call probes it again: a
+//! success restores it, a failure starts another cooldown.
+
+use crate::blockchain::services::rpc::RpcTransport;
+use anyhow::Result;
+use dashmap::DashMap;
+use futures::future::BoxFuture;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Default)]
+struct EndpointState {
+    consecutive_failures: u32,
+    unhealthy_until: Option<Instant>,
+    last_error: Option<String>,
+}
+
+/// One endpoint's health as reported by `health_check_chains`.
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointStatus {
+    pub url: String,
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+    /// Seconds left before a skipped endpoint is tried again.
+    pub retry_in_secs: Option<u64>,
+    pub last_error: Option<String>,
+}
+
+pub struct EndpointHealth {
+    failure_threshold: u32,
+    cooldown: Duration,
+    states: DashMap<String, EndpointState>,
+}
+
+impl EndpointHealth {
+    pub fn new(failure_threshold: u32, cooldown: Duration) -> Self {
+        Self { failure_threshold: failure_threshold.max(1), cooldown, states: DashMap::new() }
+    }
+
+    pub fn record_success(&self, url: &str) {
+        self.states.remove(url);
+    }
+
+    /// Counts a failure; reaching the threshold (or failing a re-probe) starts a cooldown.
+    pub fn record_failure(&self, url: &str, error: &str, now: Instant) {
+        let mut state = self.states.entry(url.to_string()).or_default();
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        state.last_error = Some(error.to_string());
+        if state.consecutive_failures >= self.failure_threshold {
+            state.unhealthy_until = Some(now + self.cooldown);
+        }
+    }
+
+    // When `url` is inside a cooldown, the instant it ends
+    fn cooling_down_until(&self, url: &str, now: Instant) -> Option<Instant> {
+        self.states.get(url).and_then(|s| s.unhealthy_until).filter(|until| *until > now)
+    }
+
+    /// The first endpoint not in a cooldown. When every one is, the one whose cooldown ends first,
+    /// so calls still go somewhere.
+    pub fn select<'a>(&self, urls: &'a [String], now: Instant) -> Option<&'a String> {
+        urls.iter()
+            .find(|url| self.cooling_down_until(url, now).is_none())
+            .or_else(|| urls.iter().min_by_key(|url| self.cooling_down_until(url, now)))
+    }
+
+    pub fn status(&self, url: &str, now: Instant) -> EndpointStatus {
+        let until = self.cooling_down_until(url, now);
+        let state = self.states.get(url);
+        EndpointStatus {
+            url: url.to_string(),
+            healthy: until.is_none(),
+            consecutive_failures: state.as_ref().map_or(0, |s| s.consecutive_failures),
+            retry_in_secs: until.map(|u| (u - now).as_secs()),
+            last_error: state.and_then(|s| s.last_error.clone()),
+        }
+    }
+}
+
+/// Wraps a transport and records each call's outcome against its URL. Only transport failures
+/// (connection errors, non-JSON replies) count; a JSON-RPC `error` is the node answering.
+pub struct HealthTrackingTransport {
+    pub inner: Arc<dyn RpcTransport>,
+    pub health: Arc<EndpointHealth>,
+}
+
+impl RpcTransport for HealthTrackingTransport {
+    fn call<'a>(&'a self, url: &'a str, method: &'a str, params: Value) -> BoxFuture<'a, Result<Value>> {
+        Box::pin(async move {
+            let result = self.inner.call(url, method, params).await;
+            match &result {
+                Ok(_) => self.health.record_success(url),
+                Err(e) => self.health.record_failure(url, &e.to_string(), Instant::now()),
+            }
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unhealthy_endpoint_is_skipped_until_cooldown_ends() {
+        let health = EndpointHealth::new(2, Duration::from_secs(30));
+        let urls = vec!["http://primary".to_string(), "http://fallback".to_string()];
+        let start = Instant::now();
+
+        health.record_failure("http://primary", "connection refused", start);
+        assert_eq!(health.select(&urls, start).unwrap(), "http://primary", "one failure is below the threshold");
+        health.record_failure("http://primary", "connection refused", start);
+        assert_eq!(health.select(&urls, start).unwrap(), "http://fallback");
+        let status = health.status("http://primary", start);
+        assert!(!status.healthy);
+        assert_eq!(status.retry_in_secs, Some(30));
+
+        // Eligible again once the cooldown passes; a failed re-probe starts another cooldown
+        let later = start + Duration::from_secs(31);
+        assert_eq!(health.select(&urls, later).unwrap(), "http://primary");
+        health.record_failure("http://primary", "connection refused", later);
+        assert_eq!(health.select(&urls, later).unwrap(), "http://fallback");
+
+        health.record_success("http://primary");
+        assert_eq!(health.select(&urls, later).unwrap(), "http://primary");
+        assert_eq!(health.status("http://primary", later).consecutive_failures, 0);
+    }
+
+    #[test]
+    fn test_all_unhealthy_picks_soonest_recovery() {
+        let health = EndpointHealth::new(1, Duration::from_secs(30));
+        let urls = vec!["http://a".to_string(), "http://b".to_string()];
+        let start = Instant::now();
+        health.record_failure("http://a", "down", start);
+        health.record_failure("http://b", "down", start - Duration::from_secs(10));
+        assert_eq!(health.select(&urls, start).unwrap(), "http://b");
+    }
+}
@@ -0,0 +1,232 @@
+// src/blockchain/faucet_ledger.rs
+
+//! Running total of faucet dispenses per chain for the current UTC day.
+//!
+//! Per-address cooldowns live in the external faucet API, which does nothing against many fresh
+//! addresses each taking one drip. FAUCET_DAILY_CAPS bounds the chain-wide total instead; this
+//! ledger tracks it and, with FAUCET_LEDGER_PATH set, survives restarts. Like the spending ledger,
+//! a file that exists but can't be read stops the faucet until it is repaired or removed.
+
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tracing::{error, warn};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DailyTotals {
+    day: Option<NaiveDate>,
+    dispensed: HashMap<String, u64>,
+}
+
+impl DailyTotals {
+    // Starts a fresh day once UTC midnight has passed
+    fn roll_over(&mut self, now: DateTime<Utc>) {
+        let today = now.date_naive();
+        if self.day != Some(today) {
+            self.day = Some(today);
+            self.dispensed.clear();
+        }
+    }
+}
+
+/// Today's usage of one chain's daily cap.
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyUsage {
+    pub cap: u64,
+    pub dispensed: u64,
+    pub remaining: u64,
+    pub resets_at: DateTime<Utc>,
+}
+
+/// Part of a day's cap held for a faucet request in flight.
+#[derive(Debug, Clone)]
+pub struct Reservation {
+    day: NaiveDate,
+    chain_id: String,
+    amount: u64,
+}
+
+pub struct FaucetLedger {
+    path: Option<PathBuf>,
+    totals: Mutex<DailyTotals>,
+    // Why the file at `path` could not be loaded; today's totals are unknown while this is set
+    unreadable: Option<String>,
+}
+
+impl FaucetLedger {
+    /// Reads the ledger at `path`; a missing file starts empty. `None` keeps it in memory. A file that
+    /// can't be read or parsed is left untouched and the ledger refuses every reservation.
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let loaded = match path.as_ref().filter(|p| p.exists()) {
+            Some(p) => fs::read_to_string(p).map_err(anyhow::Error::from).and_then(|s| Ok(serde_json::from_str(&s)?)).map_err(|e| {
+                error!("Faucet ledger {} is unreadable, refusing faucet requests: {}", p.display(), e);
+                format!("faucet ledger {} is unreadable ({}); repair or remove it to re-enable the faucet", p.display(), e)
+            }),
+            None => Ok(DailyTotals::default()),
+        };
+        let (totals, unreadable) = match loaded {
+            Ok(totals) => (totals, None),
+            Err(reason) => (DailyTotals::default(), Some(reason)),
+        };
+        Self { path, totals: Mutex::new(totals), unreadable }
+    }
+
+    /// Usage against `cap` on `chain_id` as of `now`.
+    pub fn usage(&self, chain_id: &str, cap: u64, now: DateTime<Utc>) -> Result<DailyUsage> {
+        self.ensure_readable()?;
+        let mut totals = self.totals.lock().unwrap();
+        totals.roll_over(now);
+        let dispensed = totals.dispensed.get(chain_id).copied().unwrap_or(0);
+        let resets_at = (now.date_naive() + chrono::Days::new(1)).and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+        Ok(DailyUsage { cap, dispensed, remaining: cap.saturating_sub(dispensed), resets_at })
+    }
+
+    fn ensure_readable(&self) -> Result<()> {
+        match &self.unreadable {
+            Some(reason) => Err(anyhow!("{}", reason)),
+            None => Ok(()),
+        }
+    }
+
+    /// Holds `amount` (0 when not known up front) against today's cap on `chain_id`, refusing it when the
+    /// remaining cap can't cover it. Checking and counting happen under one lock, so concurrent requests
+    /// cannot both slip under the cap; `release` the reservation if the faucet call then fails.
+    pub fn reserve(&self, chain_id: &str, cap: Option<u64>, amount: u64, now: DateTime<Utc>) -> Result<Reservation> {
+        self.ensure_readable()?;
+        let mut totals = self.totals.lock().unwrap();
+        totals.roll_over(now);
+        let dispensed = totals.dispensed.get(chain_id).copied().unwrap_or(0);
+        if let Some(cap) = cap {
+            let remaining = cap.saturating_sub(dispensed);
+            if remaining == 0 || amount > remaining {
+                let resets_at = (now.date_naive() + chrono::Days::new(1)).and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+                return Err(anyhow!(
+                    "daily faucet limit reached on {}: {} of {} dispensed today; resets at {}",
+                    chain_id,
+                    dispensed,
+                    cap,
+                    resets_at
+                ));
+            }
+        }
+        totals.dispensed.insert(chain_id.to_string(), dispensed.saturating_add(amount));
+        // Only counted once it is on disk, like the spending ledger
+        if let Err(e) = self.persist(&totals) {
+            totals.dispensed.insert(chain_id.to_string(), dispensed);
+            return Err(e);
+        }
+        Ok(Reservation { day: now.date_naive(), chain_id: chain_id.to_string(), amount })
+    }
+
+    /// Gives back a reservation whose dispense never happened.
+    pub fn release(&self, reservation: &Reservation) {
+        self.adjust(reservation, 0);
+    }
+
+    /// Replaces a reservation with the amount the faucet reports it actually sent.
+    pub fn settle(&self, reservation: &Reservation, dispensed: u64) {
+        self.adjust(reservation, dispensed);
+    }
+
+    // Swaps the reserved amount for `amount`; a reservation from an earlier day no longer counts
+    fn adjust(&self, reservation: &Reservation, amount: u64) {
+        let mut totals = self.totals.lock().unwrap();
+        if totals.day != Some(reservation.day) {
+            return;
+        }
+        if let Some(total) = totals.dispensed.get_mut(&reservation.chain_id) {
+            *total = total.saturating_sub(reservation.amount).saturating_add(amount);
+        }
+        if let Err(e) = self.persist(&totals) {
+            warn!("Failed to persist faucet ledger: {}", e);
+        }
+    }
+
+    fn persist(&self, totals: &DailyTotals) -> Result<()> {
+        if let Some(path) = &self.path {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            crate::utils::write_atomic(path, serde_json::to_string(totals)?.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cap_blocks_until_utc_midnight() {
+        let ledger = FaucetLedger::load(None);
+        let morning = "2026-03-01T08:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let held = ledger.reserve("sei-evm-testnet", None, 1_000_000, morning).unwrap();
+        ledger.release(&held);
+
+        ledger.reserve("sei-evm-testnet", Some(1000), 700, morning).unwrap();
+        assert!(ledger.reserve("sei-evm-testnet", Some(1000), 301, morning).is_err());
+        assert!(ledger.reserve("atlantic-2", Some(1000), 1000, morning).is_ok(), "caps are per chain");
+        ledger.reserve("sei-evm-testnet", Some(1000), 300, morning).unwrap();
+        let err = ledger.reserve("sei-evm-testnet", Some(1000), 0, morning).unwrap_err();
+        assert!(err.to_string().contains("daily faucet limit reached"), "{}", err);
+
+        let next_day = "2026-03-02T00:00:01Z".parse::<DateTime<Utc>>().unwrap();
+        let usage = ledger.usage("sei-evm-testnet", 1000, next_day).unwrap();
+        assert_eq!((usage.dispensed, usage.remaining), (0, 1000));
+        assert_eq!(usage.resets_at.to_rfc3339(), "2026-03-03T00:00:00+00:00");
+    }
+
+    #[test]
+    fn test_release_and_settle_adjust_the_reserved_amount() {
+        let ledger = FaucetLedger::load(None);
+        let now = Utc::now();
+        let failed = ledger.reserve("sei-evm-testnet", Some(1000), 600, now).unwrap();
+        ledger.release(&failed);
+        let sent = ledger.reserve("sei-evm-testnet", Some(1000), 600, now).unwrap();
+        ledger.settle(&sent, 450);
+        assert_eq!(ledger.usage("sei-evm-testnet", 1000, now).unwrap().dispensed, 450);
+    }
+
+    #[test]
+    fn test_concurrent_reservations_never_exceed_the_cap() {
+        let ledger = std::sync::Arc::new(FaucetLedger::load(None));
+        let now = Utc::now();
+        let granted: usize = (0..16)
+            .map(|_| {
+                let ledger = ledger.clone();
+                std::thread::spawn(move || ledger.reserve("sei-evm-testnet", Some(1000), 300, now).is_ok())
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|t| usize::from(t.join().unwrap()))
+            .sum();
+        assert_eq!(granted, 3);
+        assert_eq!(ledger.usage("sei-evm-testnet", 1000, now).unwrap().dispensed, 900);
+    }
+
+    #[test]
+    fn test_totals_survive_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("faucet_ledger.json");
+        let now = Utc::now();
+        FaucetLedger::load(Some(path.clone())).reserve("sei-evm-testnet", None, 250, now).unwrap();
+        assert_eq!(FaucetLedger::load(Some(path)).usage("sei-evm-testnet", 1000, now).unwrap().dispensed, 250);
+    }
+
+    #[test]
+    fn test_corrupt_ledger_refuses_requests_and_is_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("faucet_ledger.json");
+        fs::write(&path, "{\"day\":\"2026-03-01\",\"dispe").unwrap();
+        let ledger = FaucetLedger::load(Some(path.clone()));
+        let err = ledger.reserve("sei-evm-testnet", Some(1000), 1, Utc::now()).unwrap_err();
+        assert!(err.to_string().contains("is unreadable"), "{}", err);
+        assert!(ledger.usage("sei-evm-testnet", 1000, Utc::now()).is_err());
+        assert!(fs::read_to_string(&path).unwrap().ends_with("dispe"), "the file is left for an operator");
+    }
+}
@@ -0,0 +1,67 @@
+// src/blockchain/finality.rs
+
+//! Confirmation depth and reorg detection behind `check_finality`.
+//!
+//! Each transaction `check_finality` finds mined is remembered with its block, so a later check
+//! that no longer finds the receipt can tell a reorged-out transaction from one never mined.
+
+use dashmap::DashMap;
+
+/// Where a transaction was last seen mined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinedAt {
+    pub block_number: u64,
+    pub block_hash: String,
+}
+
+/// Transactions seen mined, keyed by chain and lowercased hash.
+#[derive(Debug, Default)]
+pub struct FinalityTracker {
+    seen: DashMap<(String, String), MinedAt>,
+}
+
+impl FinalityTracker {
+    /// Records `tx_hash` as mined at `at`, returning where it was seen before if that differs.
+    pub fn record(&self, chain_id: &str, tx_hash: &str, at: MinedAt) -> Option<MinedAt> {
+        self.seen
+            .insert((chain_id.to_string(), tx_hash.to_lowercase()), at.clone())
+            .filter(|previous| *previous != at)
+    }
+
+    /// Where `tx_hash` was last seen mined on `chain_id`, if ever.
+    pub fn last_seen(&self, chain_id: &str, tx_hash: &str) -> Option<MinedAt> {
+        self.seen.get(&(chain_id.to_string(), tx_hash.to_lowercase())).map(|m| m.clone())
+    }
+}
+
+/// Blocks on top of `block` counting itself, so a block at the chain head has one confirmation.
+pub fn confirmations(latest: u64, block: u64) -> u64 {
+    if latest < block {
+        0
+    } else {
+        latest - block + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirmations_count_the_block_itself() {
+        assert_eq!(confirmations(100, 100), 1);
+        assert_eq!(confirmations(105, 100), 6);
+        assert_eq!(confirmations(99, 100), 0);
+    }
+
+    #[test]
+    fn record_reports_a_moved_transaction() {
+        let tracker = FinalityTracker::default();
+        let at = |n: u64, h: &str| MinedAt { block_number: n, block_hash: h.to_string() };
+        assert_eq!(tracker.record("sei-evm-testnet", "0xAB", at(10, "0x1")), None);
+        assert_eq!(tracker.record("sei-evm-testnet", "0xab", at(10, "0x1")), None);
+        assert_eq!(tracker.record("sei-evm-testnet", "0xab", at(11, "0x2")), Some(at(10, "0x1")));
+        assert_eq!(tracker.last_seen("sei-evm-testnet", "0xAb"), Some(at(11, "0x2")));
+        assert_eq!(tracker.last_seen("sei-evm-mainnet", "0xab"), None);
+    }
+}
@@ -2,7 +2,19 @@
 
 // Declare the `client` module for blockchain interaction.
 pub mod client;
+// Consecutive-failure tracking behind RPC failover
+pub mod endpoint_health;
+// Per-call log of broadcast transactions, reported when a call times out
+pub mod broadcasts;
+// Chain-wide daily faucet totals behind FAUCET_DAILY_CAPS
+pub mod faucet_ledger;
+// Confirmation depth and reorg detection for check_finality
+pub mod finality;
 // Declare the `models` module for blockchain-related data structures.
 pub mod models;
 pub mod nonce_manager;
 pub mod services;
+// Per-sender transfer totals behind SPENDING_BUDGETS
+pub mod spending_ledger;
+// Shared, auto-reconnecting WebSocket connection
+pub mod websocket;
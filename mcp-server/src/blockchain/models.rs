@@ -33,6 +33,125 @@ pub enum CreateWalletError {
     KeyDerivationFailed(String),
 }
 
+/// Categorized failures from the service layer, so callers can tell bad input from a flaky node.
+#[derive(Error, Debug)]
+pub enum SeiError {
+    #[error("RPC error: {0}")]
+    Rpc(String),
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+    #[error("signing failed: {0}")]
+    Signing(String),
+    #[error("insufficient funds: {0}")]
+    InsufficientFunds(String),
+    #[error("not found: {0}")]
+    NotFound(String),
+    /// A node refused to accept a transaction into its mempool.
+    #[error("transaction rejected ({category}): {message}")]
+    Rejected { category: RejectionCategory, message: String },
+}
+
+/// Why a node refused a broadcast, read from its `eth_sendRawTransaction` error message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RejectionCategory {
+    NonceTooLow,
+    InsufficientFunds,
+    AlreadyKnown,
+    Underpriced,
+    IntrinsicGasTooLow,
+    Other,
+}
+
+impl RejectionCategory {
+    /// Classifies a rejection message. Geth-style wording is matched along with the Cosmos SDK
+    /// phrasing Sei nodes sometimes return.
+    pub fn classify(message: &str) -> Self {
+        let m = message.to_lowercase();
+        if m.contains("insufficient funds") {
+            RejectionCategory::InsufficientFunds
+        } else if m.contains("nonce too low") || m.contains("account sequence mismatch") || m.contains("invalid nonce") {
+            RejectionCategory::NonceTooLow
+        } else if m.contains("already known") || m.contains("known transaction") || m.contains("already in mempool") {
+            RejectionCategory::AlreadyKnown
+        } else if m.contains("underpriced") || m.contains("insufficient fee") || m.contains("fee too low") {
+            RejectionCategory::Underpriced
+        } else if m.contains("intrinsic gas too low") {
+            RejectionCategory::IntrinsicGasTooLow
+        } else {
+            RejectionCategory::Other
+        }
+    }
+
+    /// What the caller can do about it.
+    pub fn hint(self) -> &'static str {
+        match self {
+            RejectionCategory::NonceTooLow => "The nonce was already used. Run resync_nonce for the sender and send again; if the earlier transaction is what you wanted, check its receipt instead.",
+            RejectionCategory::InsufficientFunds => "The sender can't cover value plus gas limit times gas price. Fund the account or lower the amount or gas.",
+            RejectionCategory::AlreadyKnown => "The node already has this exact transaction. Don't resend it; wait for its receipt using the same hash.",
+            RejectionCategory::Underpriced => "The gas price is below the node's minimum or too low to replace a pending transaction with the same nonce. Raise gas_price or pass auto_bump: true.",
+            RejectionCategory::IntrinsicGasTooLow => "The gas limit is below the minimum for this transaction. Raise gas_limit or leave it unset to estimate.",
+            RejectionCategory::Other => "The node gave no recognised reason; see the message.",
+        }
+    }
+}
+
+impl std::fmt::Display for RejectionCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = serde_json::to_value(self).ok().and_then(|v| v.as_str().map(str::to_string)).unwrap_or_default();
+        f.write_str(&name)
+    }
+}
+
+impl SeiError {
+    pub fn rpc(e: impl std::fmt::Display) -> Self {
+        SeiError::Rpc(e.to_string())
+    }
+
+    pub fn invalid_input(e: impl std::fmt::Display) -> Self {
+        SeiError::InvalidInput(e.to_string())
+    }
+
+    pub fn signing(e: impl std::fmt::Display) -> Self {
+        SeiError::Signing(e.to_string())
+    }
+
+    /// Classifies an `eth_sendRawTransaction` error. Low balance keeps its own variant, like every
+    /// other error that reports it; the other refusals become `Rejected`.
+    pub fn from_send_error(err: &Value) -> Self {
+        let message = err.get("message").and_then(|m| m.as_str()).unwrap_or_default().to_string();
+        match RejectionCategory::classify(&message) {
+            RejectionCategory::InsufficientFunds => SeiError::InsufficientFunds(message),
+            category => SeiError::Rejected { category, message },
+        }
+    }
+
+    /// The broadcast rejection this error represents, if any.
+    pub fn rejection(&self) -> Option<RejectionCategory> {
+        match self {
+            SeiError::Rejected { category, .. } => Some(*category),
+            SeiError::InsufficientFunds(_) => Some(RejectionCategory::InsufficientFunds),
+            _ => None,
+        }
+    }
+
+    /// Classifies a JSON-RPC `error` object; nodes only signal low balance through the message text.
+    pub fn from_rpc_error(context: &str, err: &Value) -> Self {
+        let message = err.get("message").and_then(|m| m.as_str()).unwrap_or_default();
+        if message.to_lowercase().contains("insufficient funds") {
+            SeiError::InsufficientFunds(message.to_string())
+        } else {
+            SeiError::Rpc(format!("{}: {}", context, err))
+        }
+    }
+}
+
+impl From<reqwest::Error> for SeiError {
+    fn from(e: reqwest::Error) -> Self {
+        SeiError::Rpc(e.to_string())
+    }
+}
+
 // --- Wallet Models ---
 
 /// Dual network wallet containing both EVM and native addresses
@@ -94,7 +213,7 @@ pub struct ImportWalletRequest {
 // --- Balance Models ---
 
 /// Defines the structure for a balance response from the blockchain client.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BalanceResponse {
     pub amount: String,
     pub denom: String,
@@ -121,12 +240,22 @@ pub struct Transaction {
     pub transaction_type: TransactionType,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub contract_address: Option<String>,
+    // Tie-breakers for transactions sharing a block timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transaction_index: Option<u64>,
 }
 
 /// Defines the structure for the transaction history response.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TransactionHistoryResponse {
     pub transactions: Vec<Transaction>,
+    // Set when some upstream items didn't parse and were left out
+    #[serde(default)]
+    pub partial: bool,
+    #[serde(default)]
+    pub skipped_items: usize,
 }
 
 // --- Transfer Models ---
@@ -174,6 +303,28 @@ pub struct TransactionResponse {
     pub tx_hash: String,
 }
 
+/// Defines the structure for a faucet dispense result.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FaucetResponse {
+    pub tx_hash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_tx_hash: Option<String>,
+    /// Why the token transfer failed after the native drip went out
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_error: Option<String>,
+}
+
+impl FaucetResponse {
+    /// All transaction hashes produced by the dispense, native first.
+    pub fn tx_hashes(&self) -> Vec<String> {
+        std::iter::once(self.tx_hash.clone())
+            .chain(self.token_tx_hash.clone())
+            .collect()
+    }
+}
+
 /// Defines the structure for token information response.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TokenInfoResponse {
@@ -348,3 +499,35 @@ pub struct Pagination {
     pub curr_page: u64,
     pub next_page: Option<u64>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_rejection_messages_are_classified() {
+        let cases = [
+            ("nonce too low: next nonce 8, tx nonce 7", RejectionCategory::NonceTooLow),
+            ("account sequence mismatch, expected 8, got 7: incorrect account sequence", RejectionCategory::NonceTooLow),
+            ("insufficient funds for gas * price + value: balance 0", RejectionCategory::InsufficientFunds),
+            ("already known", RejectionCategory::AlreadyKnown),
+            ("tx already in mempool", RejectionCategory::AlreadyKnown),
+            ("transaction underpriced", RejectionCategory::Underpriced),
+            ("replacement transaction underpriced", RejectionCategory::Underpriced),
+            ("insufficient fee: got 1usei required 20usei", RejectionCategory::Underpriced),
+            ("intrinsic gas too low: have 20000, want 21000", RejectionCategory::IntrinsicGasTooLow),
+            ("execution reverted", RejectionCategory::Other),
+        ];
+        for (message, category) in cases {
+            assert_eq!(RejectionCategory::classify(message), category, "{}", message);
+        }
+
+        let err = SeiError::from_send_error(&json!({ "code": -32000, "message": "nonce too low" }));
+        assert_eq!(err.rejection(), Some(RejectionCategory::NonceTooLow));
+        assert_eq!(err.to_string(), "transaction rejected (nonce_too_low): nonce too low");
+        let err = SeiError::from_send_error(&json!({ "code": -32000, "message": "insufficient funds for transfer" }));
+        assert!(matches!(err, SeiError::InsufficientFunds(_)));
+        assert_eq!(err.rejection(), Some(RejectionCategory::InsufficientFunds));
+    }
+}
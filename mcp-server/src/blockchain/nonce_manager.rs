@@ -1,30 +1,66 @@
 // src/blockchain/nonce_manager.rs
 
+use crate::blockchain::services::rpc::{self, RpcTransport};
 use dashmap::DashMap;
 use ethers_core::types::{Address, U256};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
+use tracing::{info, warn};
 
 // Manages nonces for multiple sender addresses to prevent race conditions.
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct NonceManager {
     // Each address gets its own state, protected by a Mutex.
-    // The DashMap allows for concurrent access to different address states.
-    nonces: DashMap<Address, Arc<Mutex<NonceState>>>,
+    // The DashMap allows for concurrent access to different address states; it is shared
+    // between clones so every copy of AppState (and the resync task) sees the same cache.
+    nonces: Arc<DashMap<Address, Arc<Mutex<NonceState>>>>,
+    // Where transaction counts are read from
+    transport: Arc<dyn RpcTransport>,
+}
+
+impl std::fmt::Debug for NonceManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NonceManager").field("nonces", &self.nonces).finish_non_exhaustive()
+    }
 }
 
 #[derive(Debug)]
 struct NonceState {
     next_nonce: Option<U256>,
+    // Where the cached nonce came from, so the resync task can re-read it
+    rpc_url: String,
+    last_used: Instant,
+}
+
+/// Outcome of a resync: the cached next nonce before it, and the chain's `latest` count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NonceResync {
+    pub previous: Option<U256>,
+    pub chain: U256,
+}
+
+impl Default for NonceManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-use std::sync::Arc;
 impl NonceManager {
     pub fn new() -> Self {
         Self {
-            nonces: DashMap::new(),
+            nonces: Arc::new(DashMap::new()),
+            transport: Arc::new(reqwest::Client::new()),
         }
     }
 
+    /// Reads transaction counts through `transport` instead of a fresh HTTP client.
+    pub fn with_transport(mut self, transport: Arc<dyn RpcTransport>) -> Self {
+        self.transport = transport;
+        self
+    }
+
     /// Gets the next valid nonce for a given address.
     /// It locks the specific nonce for the address, fetches it from the RPC if not cached,
     /// increments it, and returns it. This ensures sequential nonces for concurrent requests.
@@ -33,12 +69,7 @@ impl NonceManager {
         address: Address,
         rpc_url: &str, // Pass client/rpc_url to make network calls
     ) -> anyhow::Result<U256> {
-        // Find or insert the nonce state for the given address.
-        let address_nonce_lock = self
-            .nonces
-            .entry(address)
-            .or_insert_with(|| Arc::new(Mutex::new(NonceState { next_nonce: None })))
-            .clone();
+        let address_nonce_lock = self.state_for(address, rpc_url);
 
         // Lock the mutex specifically for this address.
         let mut state = address_nonce_lock.lock().await;
@@ -46,31 +77,84 @@ impl NonceManager {
         let nonce_to_use = match state.next_nonce {
             Some(nonce) => nonce,
             // If we don't have a nonce, fetch the current one from the blockchain.
-            None => {
-                let client = reqwest::Client::new();
-                let payload = serde_json::json!({
-                    "jsonrpc": "2.0",
-                    "method": "eth_getTransactionCount",
-                    "params": [format!("{:?}", address), "latest"],
-                    "id": 1
-                });
-
-                let resp: serde_json::Value = client.post(rpc_url)
-                    .json(&payload)
-                    .send()
-                    .await?
-                    .json()
-                    .await?;
-
-                let nonce_hex = resp["result"].as_str().ok_or_else(|| anyhow::anyhow!("Failed to get nonce from RPC response"))?;
-                let nonce = U256::from_str_radix(nonce_hex.trim_start_matches("0x"), 16)?;
-                nonce
-            }
+            None => fetch_transaction_count(self.transport.as_ref(), rpc_url, address).await?,
         };
 
         // Increment the nonce for the *next* transaction and save it.
         state.next_nonce = Some(nonce_to_use + U256::one());
+        state.rpc_url = rpc_url.to_string();
+        state.last_used = Instant::now();
 
         Ok(nonce_to_use)
     }
-}
\ No newline at end of file
+
+    /// Replaces the cached nonce for `address` with the chain's `latest` transaction count.
+    /// Unlike the background pass this may move the cache backwards, e.g. after a dropped transaction.
+    pub async fn resync(&self, address: Address, rpc_url: &str) -> anyhow::Result<NonceResync> {
+        let lock = self.state_for(address, rpc_url);
+        let mut state = lock.lock().await;
+        let chain = fetch_transaction_count(self.transport.as_ref(), rpc_url, address).await?;
+        let previous = state.next_nonce.replace(chain);
+        state.rpc_url = rpc_url.to_string();
+        Ok(NonceResync { previous, chain })
+    }
+
+    /// Re-reads the chain's count for every address used within `active_window` and advances
+    /// caches that fell behind (transactions sent from elsewhere). A cache ahead of the chain is
+    /// left alone, since our own transactions may still be pending. Returns how many were corrected.
+    pub async fn resync_active(&self, active_window: Duration) -> usize {
+        let active: Vec<(Address, Arc<Mutex<NonceState>>)> = self
+            .nonces
+            .iter()
+            .map(|entry| (*entry.key(), entry.value().clone()))
+            .collect();
+        let mut corrected = 0;
+        for (address, lock) in active {
+            let mut state = lock.lock().await;
+            let Some(cached) = state.next_nonce else { continue };
+            if state.last_used.elapsed() > active_window {
+                continue;
+            }
+            match fetch_transaction_count(self.transport.as_ref(), &state.rpc_url, address).await {
+                Ok(chain) if chain > cached => {
+                    info!("Nonce for {:?} advanced externally: cached {} -> chain {}", address, cached, chain);
+                    state.next_nonce = Some(chain);
+                    corrected += 1;
+                }
+                Ok(_) => {}
+                Err(e) => warn!("Nonce resync for {:?} failed: {}", address, e),
+            }
+        }
+        corrected
+    }
+
+    /// Runs `resync_active` every `interval` for the life of the process.
+    pub fn spawn_resync_task(&self, interval: Duration, active_window: Duration) -> tokio::task::JoinHandle<()> {
+        let this = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                this.resync_active(active_window).await;
+            }
+        })
+    }
+
+    // Find or insert the nonce state for the given address.
+    fn state_for(&self, address: Address, rpc_url: &str) -> Arc<Mutex<NonceState>> {
+        self.nonces
+            .entry(address)
+            .or_insert_with(|| {
+                Arc::new(Mutex::new(NonceState { next_nonce: None, rpc_url: rpc_url.to_string(), last_used: Instant::now() }))
+            })
+            .clone()
+    }
+}
+
+async fn fetch_transaction_count(transport: &dyn RpcTransport, rpc_url: &str, address: Address) -> anyhow::Result<U256> {
+    let result = rpc::rpc_call(transport, rpc_url, "eth_getTransactionCount", json!([format!("{:?}", address), "latest"])).await?;
+    let nonce_hex = result.as_str().ok_or_else(|| anyhow::anyhow!("Failed to get nonce from RPC response"))?;
+    Ok(U256::from_str_radix(nonce_hex.trim_start_matches("0x"), 16)?)
+}
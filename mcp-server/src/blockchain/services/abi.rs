@@ -0,0 +1,391 @@
+// src/blockchain/services/abi.rs
+
+use anyhow::{anyhow, Result};
+use ethers_core::abi::token::{LenientTokenizer, Tokenizer};
+use ethers_core::abi::{Abi, AbiParser, Function, Param, ParamType, RawLog, StateMutability, Token};
+use ethers_core::types::H256;
+use std::str::FromStr;
+use serde_json::{json, Value};
+
+/// Finds a function by name, using the argument count to disambiguate overloads.
+pub fn find_function<'a>(abi: &'a Abi, name: &str, arg_count: usize) -> Result<&'a Function> {
+    let candidates = abi
+        .functions_by_name(name)
+        .map_err(|_| anyhow!("Function '{}' not found in contract ABI", name))?;
+    candidates
+        .iter()
+        .find(|f| f.inputs.len() == arg_count)
+        .ok_or_else(|| {
+            let sigs: Vec<String> = candidates.iter().map(|f| f.signature()).collect();
+            anyhow!(
+                "No overload of '{}' takes {} argument(s). Available: {}",
+                name,
+                arg_count,
+                sigs.join(", ")
+            )
+        })
+}
+
+/// True when calling the function cannot change state (safe for `eth_call`).
+pub fn is_read_only(function: &Function) -> bool {
+    matches!(function.state_mutability, StateMutability::View | StateMutability::Pure)
+}
+
+/// Converts a JSON argument into an ABI token of the given type.
+/// Scalars accept strings or numbers; arrays and tuples accept JSON arrays.
+pub fn json_to_token(kind: &ParamType, value: &Value) -> Result<Token> {
+    match (kind, value) {
+        (ParamType::Array(inner), Value::Array(items)) => Ok(Token::Array(
+            items.iter().map(|v| json_to_token(inner, v)).collect::<Result<_>>()?,
+        )),
+        (ParamType::FixedArray(inner, len), Value::Array(items)) => {
+            if items.len() != *len {
+                return Err(anyhow!("Expected {} element(s) for {}, got {}", len, kind, items.len()));
+            }
+            Ok(Token::FixedArray(
+                items.iter().map(|v| json_to_token(inner, v)).collect::<Result<_>>()?,
+            ))
+        }
+        (ParamType::Tuple(kinds), Value::Array(items)) => {
+            if items.len() != kinds.len() {
+                return Err(anyhow!("Expected {} tuple field(s) for {}, got {}", kinds.len(), kind, items.len()));
+            }
+            Ok(Token::Tuple(
+                kinds.iter().zip(items).map(|(k, v)| json_to_token(k, v)).collect::<Result<_>>()?,
+            ))
+        }
+        (_, Value::String(s)) => LenientTokenizer::tokenize(kind, s)
+            .map_err(|e| anyhow!("Invalid {} value '{}': {}", kind, s, e)),
+        (_, Value::Number(n)) => LenientTokenizer::tokenize(kind, &n.to_string())
+            .map_err(|e| anyhow!("Invalid {} value '{}': {}", kind, n, e)),
+        (ParamType::Bool, Value::Bool(b)) => Ok(Token::Bool(*b)),
+        _ => Err(anyhow!("Cannot convert {} to {}", value, kind)),
+    }
+}
+
+/// Converts a decoded ABI token into JSON. Integers become decimal strings to avoid precision loss.
+pub fn token_to_json(token: &Token) -> Value {
+    match token {
+        Token::Address(a) => json!(format!("{:?}", a)),
+        Token::FixedBytes(b) | Token::Bytes(b) => json!(format!("0x{}", hex::encode(b))),
+        Token::Int(i) => json!(ethers_core::types::I256::from_raw(*i).to_string()),
+        Token::Uint(u) => json!(u.to_string()),
+        Token::Bool(b) => json!(b),
+        Token::String(s) => json!(s),
+        Token::FixedArray(items) | Token::Array(items) | Token::Tuple(items) => {
+            Value::Array(items.iter().map(token_to_json).collect())
+        }
+    }
+}
+
+/// Decodes raw ABI-encoded `data` (e.g. `eth_call` output, without a selector) as the given
+/// Solidity types, such as `["uint256", "address", "string[]", "(uint256,bool)"]`.
+/// The data must be exactly the canonical encoding of those types; anything else is an error.
+pub fn decode_values(types: &[String], data: &[u8]) -> Result<Vec<Value>> {
+    let kinds = types
+        .iter()
+        .map(|t| ethers_core::abi::param_type::Reader::read(t.trim()).map_err(|e| anyhow!("Unknown type '{}': {}", t, e)))
+        .collect::<Result<Vec<ParamType>>>()?;
+    let signature = kinds.iter().map(ToString::to_string).collect::<Vec<_>>().join(",");
+    let mismatch = || anyhow!("Data ({} bytes) does not match the declared types ({})", data.len(), signature);
+    let tokens = ethers_core::abi::decode(&kinds, data).map_err(|_| mismatch())?;
+    // `decode` tolerates trailing bytes and dirty padding; re-encoding catches both
+    if ethers_core::abi::encode(&tokens) != data {
+        return Err(mismatch());
+    }
+    Ok(tokens.iter().map(token_to_json).collect())
+}
+
+/// Encodes a call to `function` from JSON arguments.
+pub fn encode_call(function: &Function, args: &[Value]) -> Result<Vec<u8>> {
+    if args.len() != function.inputs.len() {
+        return Err(anyhow!(
+            "{} expects {} argument(s), got {}",
+            function.signature(),
+            function.inputs.len(),
+            args.len()
+        ));
+    }
+    let tokens: Vec<Token> = function
+        .inputs
+        .iter()
+        .zip(args)
+        .map(|(param, v)| {
+            json_to_token(&param.kind, v).map_err(|e| anyhow!("Argument '{}': {}", param.name, e))
+        })
+        .collect::<Result<_>>()?;
+    function
+        .encode_input(&tokens)
+        .map_err(|e| anyhow!("Failed to encode call: {}", e))
+}
+
+/// Decodes `eth_call` output into named JSON values (positional names for unnamed outputs).
+pub fn decode_output(function: &Function, data: &[u8]) -> Result<Value> {
+    let tokens = function
+        .decode_output(data)
+        .map_err(|e| anyhow!("Failed to decode output of {}: {}", function.name, e))?;
+    let mut out = serde_json::Map::new();
+    for (i, (param, token)) in function.outputs.iter().zip(tokens.iter()).enumerate() {
+        let key = if param.name.is_empty() { i.to_string() } else { param.name.clone() };
+        out.insert(key, token_to_json(token));
+    }
+    Ok(Value::Object(out))
+}
+
+/// Decodes an `eth_getLogs` entry against the ABI's events, matching topic0 to the event signature hash.
+/// Returns `None` for anonymous or unknown events, or when the data doesn't fit the matched event.
+pub fn decode_log(abi: &Abi, log: &Value) -> Option<Value> {
+    let topics: Vec<H256> = log
+        .get("topics")?
+        .as_array()?
+        .iter()
+        .map(|t| t.as_str().and_then(|s| H256::from_str(s).ok()))
+        .collect::<Option<_>>()?;
+    let topic0 = *topics.first()?;
+    let data = hex::decode(log.get("data").and_then(|d| d.as_str()).unwrap_or("0x").trim_start_matches("0x")).ok()?;
+    let event = abi.events().find(|e| !e.anonymous && e.signature() == topic0)?;
+    let parsed = event.parse_log(RawLog { topics, data }).ok()?;
+    let mut args = serde_json::Map::new();
+    for (i, param) in parsed.params.iter().enumerate() {
+        let key = if param.name.is_empty() { i.to_string() } else { param.name.clone() };
+        args.insert(key, token_to_json(&param.value));
+    }
+    let inputs: Vec<String> = event.inputs.iter().map(|p| p.kind.to_string()).collect();
+    Some(json!({
+        "event_name": event.name,
+        "signature": format!("{}({})", event.name, inputs.join(",")),
+        "args": args,
+    }))
+}
+
+// Selectors of the errors Solidity raises without an ABI entry: Error(string) and Panic(uint256).
+const ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Decodes revert data as `Error(string)`, `Panic(uint256)` or a custom error declared in `abi`.
+/// Returns `{kind, signature, reason, ...}`, or `None` for empty data or an unknown selector.
+pub fn decode_revert(data: &[u8], abi: Option<&Abi>) -> Option<Value> {
+    let (selector, body) = (data.get(..4)?, &data[4..]);
+    if selector == ERROR_STRING_SELECTOR {
+        let reason = ethers_core::abi::decode(&[ParamType::String], body).ok()?.pop()?.into_string()?;
+        return Some(json!({ "kind": "error", "signature": "Error(string)", "reason": reason }));
+    }
+    if selector == PANIC_SELECTOR {
+        let code = ethers_core::abi::decode(&[ParamType::Uint(256)], body).ok()?.pop()?.into_uint()?;
+        return Some(json!({
+            "kind": "panic",
+            "signature": "Panic(uint256)",
+            "code": format!("0x{:x}", code),
+            "reason": format!("panic 0x{:x}: {}", code, panic_description(code.low_u64())),
+        }));
+    }
+    let error = abi?.errors().find(|e| e.signature()[..4] == *selector)?;
+    let tokens = error.decode(body).ok()?;
+    let mut args = serde_json::Map::new();
+    for (i, (param, token)) in error.inputs.iter().zip(&tokens).enumerate() {
+        let key = if param.name.is_empty() { i.to_string() } else { param.name.clone() };
+        args.insert(key, token_to_json(token));
+    }
+    let inputs: Vec<String> = error.inputs.iter().map(|p| p.kind.to_string()).collect();
+    let rendered: Vec<String> = tokens.iter().map(|t| t.to_string()).collect();
+    Some(json!({
+        "kind": "custom",
+        "name": error.name,
+        "signature": format!("{}({})", error.name, inputs.join(",")),
+        "args": args,
+        "reason": format!("{}({})", error.name, rendered.join(", ")),
+    }))
+}
+
+// Meaning of the compiler-inserted panic codes.
+fn panic_description(code: u64) -> &'static str {
+    match code {
+        0x01 => "assertion failed",
+        0x11 => "arithmetic overflow or underflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "invalid enum value",
+        0x22 => "corrupted storage byte array",
+        0x31 => "pop on empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "out of memory",
+        0x51 => "call to uninitialized function pointer",
+        _ => "unknown panic code",
+    }
+}
+
+/// Whether a signature was written as a function or an event; `None` when it had no keyword.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureKind {
+    Function,
+    Event,
+}
+
+/// Canonical `name(type,...)` form of a function or event signature, the string that selectors
+/// and topics hash. Accepts `function`/`event` keywords, parameter names, `indexed` and the
+/// `uint`/`int` aliases, e.g. `event Transfer(address indexed from, address indexed to, uint value)`.
+pub fn canonical_signature(signature: &str) -> Result<(String, Option<SignatureKind>)> {
+    let sig = signature.trim();
+    let invalid = |e: ethers_core::abi::ParseError| anyhow!("Invalid signature '{}': {}", sig, e);
+    let mut parser = AbiParser::default();
+    let (name, inputs, kind) = if sig.starts_with("event ") || (!sig.starts_with("function ") && sig.contains(" indexed")) {
+        let event = parser
+            .parse_event(&format!("event {}", sig.trim_start_matches("event ").trim()))
+            .map_err(invalid)?;
+        let kinds = event.inputs.into_iter().map(|p| p.kind).collect::<Vec<_>>();
+        (event.name, kinds, sig.starts_with("event ").then_some(SignatureKind::Event))
+    } else {
+        let function = parser.parse_function(sig).map_err(invalid)?;
+        let kinds = function.inputs.into_iter().map(|p: Param| p.kind).collect::<Vec<_>>();
+        (function.name, kinds, sig.starts_with("function ").then_some(SignatureKind::Function))
+    };
+    let types: Vec<String> = inputs.iter().map(ToString::to_string).collect();
+    Ok((format!("{}({})", name, types.join(",")), kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::utils::keccak256;
+
+    #[test]
+    fn test_canonical_signatures_hash_to_known_selectors() {
+        let (transfer, kind) = canonical_signature("transfer(address,uint256)").unwrap();
+        assert_eq!((transfer.as_str(), kind), ("transfer(address,uint256)", None));
+        assert_eq!(hex::encode(&keccak256(transfer.as_bytes())[..4]), "a9059cbb");
+
+        let (named, kind) = canonical_signature("function transfer(address to, uint amount) returns (bool)").unwrap();
+        assert_eq!((named, kind), (transfer, Some(SignatureKind::Function)));
+
+        let (event, kind) = canonical_signature("event Transfer(address indexed from, address indexed to, uint256 value)").unwrap();
+        assert_eq!((event.as_str(), kind), ("Transfer(address,address,uint256)", Some(SignatureKind::Event)));
+        assert_eq!(
+            hex::encode(keccak256(event.as_bytes())),
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"
+        );
+        assert!(canonical_signature("not a signature").is_err());
+    }
+
+    #[test]
+    fn test_decode_values_static_and_dynamic() {
+        let data = ethers_core::abi::encode(&[
+            Token::Uint(1000.into()),
+            Token::Address("0x000000000000000000000000000000000000dead".parse().unwrap()),
+            Token::String("hello".into()),
+            Token::Array(vec![Token::Uint(1.into()), Token::Uint(2.into())]),
+            Token::Bytes(vec![0xc0, 0xff, 0xee]),
+        ]);
+        let types: Vec<String> = ["uint", "address", "string", "uint256[]", "bytes"].map(String::from).to_vec();
+        let values = decode_values(&types, &data).unwrap();
+        assert_eq!(
+            values,
+            [json!("1000"), json!("0x000000000000000000000000000000000000dead"), json!("hello"), json!(["1", "2"]), json!("0xc0ffee")]
+        );
+
+        // Too short, trailing bytes, and a bool word that isn't 0 or 1
+        assert!(decode_values(&types, &data[..64]).is_err());
+        assert!(decode_values(&types[..2], &data).is_err());
+        assert!(decode_values(&["bool".to_string()], &[7u8; 32]).is_err());
+        assert!(decode_values(&["uint257".to_string()], &data).is_err());
+    }
+
+    fn erc20_abi() -> Abi {
+        serde_json::from_value(json!([
+            {"type": "function", "name": "balanceOf", "stateMutability": "view",
+             "inputs": [{"name": "owner", "type": "address"}],
+             "outputs": [{"name": "", "type": "uint256"}]},
+            {"type": "function", "name": "transfer", "stateMutability": "nonpayable",
+             "inputs": [{"name": "to", "type": "address"}, {"name": "amount", "type": "uint256"}],
+             "outputs": [{"name": "", "type": "bool"}]}
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_encode_transfer_call() {
+        let abi = erc20_abi();
+        let f = find_function(&abi, "transfer", 2).unwrap();
+        assert!(!is_read_only(f));
+        let data = encode_call(
+            f,
+            &[json!("0x000000000000000000000000000000000000dEaD"), json!(1000)],
+        )
+        .unwrap();
+        assert_eq!(hex::encode(&data[..4]), "a9059cbb");
+        assert_eq!(data.len(), 4 + 64);
+    }
+
+    #[test]
+    fn test_decode_balance_output() {
+        let abi = erc20_abi();
+        let f = find_function(&abi, "balanceOf", 1).unwrap();
+        assert!(is_read_only(f));
+        let mut word = [0u8; 32];
+        word[31] = 42;
+        assert_eq!(decode_output(f, &word).unwrap(), json!({ "0": "42" }));
+    }
+
+    #[test]
+    fn test_wrong_arity_is_rejected() {
+        let abi = erc20_abi();
+        assert!(find_function(&abi, "transfer", 1).is_err());
+        assert!(find_function(&abi, "mint", 0).is_err());
+    }
+
+    #[test]
+    fn test_decode_transfer_log() {
+        let abi: Abi = serde_json::from_value(json!([
+            {"type": "event", "name": "Transfer", "anonymous": false, "inputs": [
+                {"name": "from", "type": "address", "indexed": true},
+                {"name": "to", "type": "address", "indexed": true},
+                {"name": "value", "type": "uint256", "indexed": false}
+            ]}
+        ]))
+        .unwrap();
+        let log = json!({
+            "topics": [
+                "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                "0x0000000000000000000000000000000000000000000000000000000000000000",
+                "0x000000000000000000000000000000000000000000000000000000000000dead"
+            ],
+            "data": "0x00000000000000000000000000000000000000000000000000000000000003e8"
+        });
+        let decoded = decode_log(&abi, &log).unwrap();
+        assert_eq!(decoded["event_name"], "Transfer");
+        assert_eq!(decoded["signature"], "Transfer(address,address,uint256)");
+        assert_eq!(decoded["args"]["to"], "0x000000000000000000000000000000000000dead");
+        assert_eq!(decoded["args"]["value"], "1000");
+
+        let unknown = json!({ "topics": ["0x8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925"], "data": "0x" });
+        assert!(decode_log(&abi, &unknown).is_none());
+    }
+
+    #[test]
+    fn test_decode_revert_string_panic_and_custom() {
+        let reason = hex::decode(concat!(
+            "08c379a0",
+            "0000000000000000000000000000000000000000000000000000000000000020",
+            "0000000000000000000000000000000000000000000000000000000000000012",
+            "696e73756666696369656e742066756e64730000000000000000000000000000"
+        ))
+        .unwrap();
+        assert_eq!(decode_revert(&reason, None).unwrap()["reason"], "insufficient funds");
+
+        let panic = hex::decode("4e487b710000000000000000000000000000000000000000000000000000000000000011").unwrap();
+        let decoded = decode_revert(&panic, None).unwrap();
+        assert_eq!(decoded["kind"], "panic");
+        assert_eq!(decoded["code"], "0x11");
+
+        let abi: Abi = serde_json::from_value(json!([
+            {"type": "error", "name": "Unauthorized", "inputs": [{"name": "caller", "type": "address"}]}
+        ]))
+        .unwrap();
+        let selector = &abi.errors().next().unwrap().signature()[..4];
+        let mut custom = selector.to_vec();
+        custom.extend(ethers_core::abi::encode(&[Token::Address(ethers_core::types::Address::repeat_byte(0xab))]));
+        let decoded = decode_revert(&custom, Some(&abi)).unwrap();
+        assert_eq!(decoded["name"], "Unauthorized");
+        assert_eq!(decoded["args"]["caller"], format!("0x{}", "ab".repeat(20)));
+        // Unknown without the ABI
+        assert!(decode_revert(&custom, None).is_none());
+    }
+}
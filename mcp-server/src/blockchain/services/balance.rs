@@ -1,55 +1,112 @@
-use crate::blockchain::models::BalanceResponse;
-use anyhow::{anyhow, Result, Context};
-use reqwest::Client;
+use crate::blockchain::models::{BalanceResponse, SeiError};
+use crate::blockchain::services::rpc::{self, HttpClient, RpcTransport};
 use serde_json::{json, Value};
 use tracing::{error};
 
-pub async fn get_balance(client: &Client, rpc_url: &str, address: &str, is_native: bool) -> Result<BalanceResponse> {
+type Result<T> = std::result::Result<T, SeiError>;
+
+pub async fn get_balance(client: &HttpClient, rpc_url: &str, address: &str, is_native: bool) -> Result<BalanceResponse> {
     if is_native {
-        // Native SEI (Cosmos) balance query
-        let url = format!("{}/cosmos/bank/v1beta1/balances/{}", rpc_url.trim_end_matches('/'), address);
-        let res: Value = client.get(&url).send().await?.json().await?;
-        let balances = res["balances"].as_array().context("No balances array in response")?;
-        // Find the 'usei' denom or fallback to the first
-        let balance = balances.iter().find(|b| b["denom"] == "usei").or(balances.first());
-        if let Some(bal) = balance {
-            let amount = bal["amount"].as_str().unwrap_or("0").to_string();
-            let denom = bal["denom"].as_str().unwrap_or("").to_string();
-            Ok(BalanceResponse { amount, denom })
-        } else {
-            Ok(BalanceResponse { amount: "0".to_string(), denom: "usei".to_string() })
+        // Native SEI (Cosmos) balance query: prefer usei, fall back to the first denom held
+        let mut balances = get_native_balances(client, rpc_url, address).await?;
+        if balances.is_empty() {
+            return Ok(BalanceResponse { amount: "0".to_string(), denom: "usei".to_string() });
         }
+        let primary = balances.iter().position(|b| b.denom == "usei").unwrap_or(0);
+        Ok(balances.swap_remove(primary))
     } else {
-        // EVM balance logic
-        let payload = json!({
-            "jsonrpc": "2.0",
-            "method": "eth_getBalance",
-            "params": [address, "latest"],
-            "id": 1
+        get_evm_balance_at(client, rpc_url, address, json!("latest")).await
+    }
+}
+
+/// EVM balance in wei at `block`: a tag, a hex block number, or an EIP-1898
+/// `{"blockHash": ...}` object. Anything older than the node's retained state
+/// needs an archive node; that rejection is reported as such.
+pub async fn get_evm_balance_at(transport: &dyn RpcTransport, rpc_url: &str, address: &str, block: Value) -> Result<BalanceResponse> {
+    let historical = block != json!("latest") && block != json!("pending");
+    let res = transport.call(rpc_url, "eth_getBalance", json!([address, block])).await.map_err(SeiError::rpc)?;
+    if let Some(err) = res.get("error") {
+        if historical && is_missing_state(err) {
+            return Err(SeiError::Rpc(format!(
+                "The node no longer has state for block {} ({}). Historical balances require an archive node; point this chain's RPC URL at one.",
+                block,
+                err.get("message").and_then(Value::as_str).unwrap_or("state unavailable")
+            )));
+        }
+        return Err(SeiError::from_rpc_error("RPC Error getting balance", err));
+    }
+    let result = res["result"]
+        .as_str()
+        .ok_or_else(|| SeiError::Rpc(format!("RPC response missing 'result' field: {:?}", res)))?;
+    let amount_decimal = u128::from_str_radix(result.trim_start_matches("0x"), 16)
+        .map(|val| val.to_string())
+        .unwrap_or_else(|_| {
+            error!(
+                "Failed to parse hex balance '{}' to u128. Defaulting to '0'.",
+                result
+            );
+            "0".to_string()
         });
-        let res: Value = client
-            .post(rpc_url)
-            .json(&payload)
-            .send()
-            .await?
-            .json()
-            .await?;
-        let result = res["result"]
-            .as_str()
-            .ok_or_else(|| anyhow!("RPC response missing 'result' field: {:?}", res))?;
-        let amount_decimal = u128::from_str_radix(result.trim_start_matches("0x"), 16)
-            .map(|val| val.to_string())
-            .unwrap_or_else(|_| {
-                error!(
-                    "Failed to parse hex balance '{}' to u128. Defaulting to '0'.",
-                    result
-                );
-                "0".to_string()
-            });
-        Ok(BalanceResponse {
-            amount: amount_decimal,
-            // For EVM chains, the native balance is returned in wei
-            denom: "wei".to_string(),
-        })
+    Ok(BalanceResponse {
+        amount: amount_decimal,
+        // For EVM chains, the native balance is returned in wei
+        denom: "wei".to_string(),
+    })
+}
+
+// Errors pruned (non-archive) nodes give for state they have discarded.
+fn is_missing_state(err: &Value) -> bool {
+    let msg = err.get("message").and_then(Value::as_str).unwrap_or_default().to_lowercase();
+    ["missing trie node", "header not found", "pruned", "historical state", "state is not available", "archive", "not available"]
+        .iter()
+        .any(|needle| msg.contains(needle))
+}
+
+/// Lists every denom held by a native address via the Cosmos bank module,
+/// following `pagination.next_key` until all pages are read.
+pub async fn get_native_balances(client: &HttpClient, rest_url: &str, address: &str) -> Result<Vec<BalanceResponse>> {
+    let url = format!("{}/cosmos/bank/v1beta1/balances/{}", rest_url.trim_end_matches('/'), address);
+    let mut balances = Vec::new();
+    let mut next_key: Option<String> = None;
+    loop {
+        let mut req = client.get(&url);
+        if let Some(key) = &next_key {
+            req = req.query(&[("pagination.key", key)]);
+        }
+        let res: Value = rpc::read_json(req.send().await?).await.map_err(SeiError::rpc)?;
+        let page = res["balances"].as_array().ok_or_else(|| bank_query_error(&res, "No balances array in response"))?;
+        balances.extend(page.iter().map(parse_coin));
+        next_key = res["pagination"]["next_key"].as_str().filter(|k| !k.is_empty()).map(str::to_string);
+        if next_key.is_none() {
+            break;
+        }
+    }
+    Ok(balances)
+}
+
+/// Fetches a single denom balance for a native address; missing denoms report zero.
+pub async fn get_native_balance_by_denom(client: &HttpClient, rest_url: &str, address: &str, denom: &str) -> Result<BalanceResponse> {
+    let url = format!("{}/cosmos/bank/v1beta1/balances/{}/by_denom", rest_url.trim_end_matches('/'), address);
+    let res: Value = rpc::read_json(client.get(&url).query(&[("denom", denom)]).send().await?).await.map_err(SeiError::rpc)?;
+    match res.get("balance") {
+        Some(coin) if coin.is_object() => Ok(parse_coin(coin)),
+        _ if res.get("message").is_some() => Err(bank_query_error(&res, "Bank query failed")),
+        _ => Ok(BalanceResponse { amount: "0".to_string(), denom: denom.to_string() }),
+    }
+}
+
+// REST gateway errors are `{code, message}`; gRPC code 3 is a malformed address or denom.
+fn bank_query_error(res: &Value, fallback: &str) -> SeiError {
+    match (res["code"].as_i64(), res["message"].as_str()) {
+        (Some(3), Some(msg)) => SeiError::InvalidInput(msg.to_string()),
+        (_, Some(msg)) => SeiError::Rpc(format!("Bank query failed: {}", msg)),
+        _ => SeiError::rpc(fallback),
+    }
+}
+
+fn parse_coin(coin: &Value) -> BalanceResponse {
+    BalanceResponse {
+        amount: coin["amount"].as_str().unwrap_or("0").to_string(),
+        denom: coin["denom"].as_str().unwrap_or("").to_string(),
     }
 }
@@ -0,0 +1,185 @@
+// src/blockchain/services/blocks.rs
+
+use crate::blockchain::services::rpc::{self, RpcTransport};
+use anyhow::{anyhow, Result};
+use futures::{stream, StreamExt, TryStreamExt};
+use serde_json::{json, Value};
+use std::future::Future;
+
+// Enough halvings for any realistic chain height (2^48 blocks)
+const MAX_SEARCH_ITERATIONS: u32 = 48;
+
+fn parse_hex_u64(v: &Value, field: &str) -> Result<u64> {
+    let s = v.as_str().ok_or_else(|| anyhow!("Block is missing '{}'", field))?;
+    u64::from_str_radix(s.trim_start_matches("0x"), 16).map_err(|_| anyhow!("Invalid hex in '{}': {}", field, s))
+}
+
+/// Returns `(number, timestamp)` for a block tag such as "latest" or a 0x-prefixed number.
+pub async fn get_block_header(transport: &dyn RpcTransport, rpc_url: &str, tag: &str) -> Result<(u64, u64)> {
+    let block = rpc::rpc_call(transport, rpc_url, "eth_getBlockByNumber", json!([tag, false])).await?;
+    if block.is_null() {
+        return Err(anyhow!("Block {} not found", tag));
+    }
+    Ok((parse_hex_u64(&block["number"], "number")?, parse_hex_u64(&block["timestamp"], "timestamp")?))
+}
+
+/// Current chain head from `eth_blockNumber`.
+pub async fn latest_block_number(transport: &dyn RpcTransport, rpc_url: &str) -> Result<u64> {
+    let latest = rpc::rpc_call(transport, rpc_url, "eth_blockNumber", json!([])).await?;
+    parse_hex_u64(&latest, "blockNumber")
+}
+
+/// Hash of canonical block `number`, or `None` when the node has no such block yet.
+pub async fn block_hash(transport: &dyn RpcTransport, rpc_url: &str, number: u64) -> Result<Option<String>> {
+    let block = rpc::rpc_call(transport, rpc_url, "eth_getBlockByNumber", json!([format!("0x{:x}", number), false])).await?;
+    Ok(block["hash"].as_str().map(str::to_string))
+}
+
+/// Finds the block whose timestamp is closest to `target` (Unix seconds).
+/// The latest block is fetched once up front and reused as the search's upper bound.
+pub async fn find_block_by_timestamp(transport: &dyn RpcTransport, rpc_url: &str, target: u64) -> Result<(u64, u64)> {
+    let latest = get_block_header(transport, rpc_url, "latest").await?;
+    nearest_block(latest, target, |n| async move {
+        get_block_header(transport, rpc_url, &format!("0x{:x}", n)).await.map(|(_, ts)| ts)
+    })
+    .await
+}
+
+/// Binary search over block numbers `0..=latest.0`, assuming timestamps are non-decreasing.
+pub async fn nearest_block<F, Fut>(latest: (u64, u64), target: u64, mut timestamp_of: F) -> Result<(u64, u64)>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = Result<u64>>,
+{
+    let (latest_number, latest_ts) = latest;
+    if target >= latest_ts {
+        return Ok(latest);
+    }
+    // Invariant: ts(lo) <= target < ts(hi)
+    let (mut lo, mut lo_ts) = (0u64, timestamp_of(0).await?);
+    if target <= lo_ts {
+        return Ok((lo, lo_ts));
+    }
+    let (mut hi, mut hi_ts) = (latest_number, latest_ts);
+    let mut iterations = 0;
+    while hi - lo > 1 {
+        iterations += 1;
+        if iterations > MAX_SEARCH_ITERATIONS {
+            return Err(anyhow!("Block search did not converge within {} iterations", MAX_SEARCH_ITERATIONS));
+        }
+        let mid = lo + (hi - lo) / 2;
+        let mid_ts = timestamp_of(mid).await?;
+        if mid_ts <= target {
+            (lo, lo_ts) = (mid, mid_ts);
+        } else {
+            (hi, hi_ts) = (mid, mid_ts);
+        }
+    }
+    if target - lo_ts <= hi_ts - target {
+        Ok((lo, lo_ts))
+    } else {
+        Ok((hi, hi_ts))
+    }
+}
+
+/// Summaries of the `count` most recent blocks, newest first, fetching at most `concurrency` at once.
+pub async fn get_recent_blocks(transport: &dyn RpcTransport, rpc_url: &str, count: u64, concurrency: usize) -> Result<Vec<Value>> {
+    let latest = latest_block_number(transport, rpc_url).await?;
+    let oldest = latest.saturating_sub(count.saturating_sub(1));
+    stream::iter((oldest..=latest).rev())
+        .map(|n| async move {
+            let block = rpc::rpc_call(transport, rpc_url, "eth_getBlockByNumber", json!([format!("0x{:x}", n), false])).await?;
+            if block.is_null() {
+                return Err(anyhow!("Block {} not found", n));
+            }
+            summarize_block(&block)
+        })
+        .buffered(concurrency.max(1))
+        .try_collect()
+        .await
+}
+
+/// Number, timestamp, transaction count and gas fullness of an `eth_getBlockByNumber` result.
+pub fn summarize_block(block: &Value) -> Result<Value> {
+    let gas_used = parse_hex_u64(&block["gasUsed"], "gasUsed")?;
+    let gas_limit = parse_hex_u64(&block["gasLimit"], "gasLimit")?;
+    Ok(json!({
+        "number": parse_hex_u64(&block["number"], "number")?,
+        "hash": block["hash"],
+        "timestamp": parse_hex_u64(&block["timestamp"], "timestamp")?,
+        "tx_count": block["transactions"].as_array().map_or(0, Vec::len),
+        "gas_used": gas_used,
+        "gas_limit": gas_limit,
+        "gas_used_ratio": if gas_limit == 0 { 0.0 } else { gas_used as f64 / gas_limit as f64 },
+    }))
+}
+
+/// Averages over block summaries (newest first): block time, transactions per block and fullness.
+pub fn block_stats(blocks: &[Value]) -> Value {
+    if blocks.is_empty() {
+        return json!({ "blocks": 0 });
+    }
+    let n = blocks.len() as f64;
+    let sum = |field: &str| blocks.iter().filter_map(|b| b[field].as_f64()).sum::<f64>();
+    let timestamps: Vec<u64> = blocks.iter().filter_map(|b| b["timestamp"].as_u64()).collect();
+    let avg_block_time = match (timestamps.first(), timestamps.last()) {
+        (Some(newest), Some(oldest)) if timestamps.len() > 1 => {
+            Some(newest.saturating_sub(*oldest) as f64 / (timestamps.len() - 1) as f64)
+        }
+        _ => None,
+    };
+    json!({
+        "blocks": blocks.len(),
+        "avg_block_time_secs": avg_block_time,
+        "avg_tx_count": sum("tx_count") / n,
+        "avg_gas_used_ratio": sum("gas_used_ratio") / n,
+        "total_txs": sum("tx_count") as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_summary_and_stats() {
+        let block = |n: u64, ts: u64, txs: usize, gas: u64| {
+            json!({
+                "number": format!("0x{:x}", n),
+                "hash": format!("0x{:064x}", n),
+                "timestamp": format!("0x{:x}", ts),
+                "transactions": vec!["0x01"; txs],
+                "gasUsed": format!("0x{:x}", gas),
+                "gasLimit": "0x989680"
+            })
+        };
+        let summaries: Vec<Value> = [block(12, 1006, 4, 5_000_000), block(11, 1003, 0, 0), block(10, 1000, 2, 2_500_000)]
+            .iter()
+            .map(|b| summarize_block(b).unwrap())
+            .collect();
+        assert_eq!(summaries[0]["tx_count"], 4);
+        assert_eq!(summaries[0]["gas_used_ratio"], 0.5);
+
+        let stats = block_stats(&summaries);
+        assert_eq!(stats["avg_block_time_secs"], 3.0);
+        assert_eq!(stats["avg_tx_count"], 2.0);
+        assert_eq!(stats["avg_gas_used_ratio"], 0.25);
+        assert_eq!(stats["total_txs"], 6);
+    }
+
+    #[tokio::test]
+    async fn test_nearest_block() {
+        // Block n is produced at 1000 + 2n, except a gap after block 50
+        let ts = |n: u64| if n <= 50 { 1000 + 2 * n } else { 1100 + 2 * n };
+        let latest = (100, ts(100));
+        let find = |target| nearest_block(latest, target, move |n| async move { Ok(ts(n)) });
+
+        assert_eq!(find(1021).await.unwrap(), (10, 1020));
+        assert_eq!(find(1024).await.unwrap(), (12, 1024));
+        assert_eq!(find(500).await.unwrap(), (0, 1000));
+        assert_eq!(find(9999).await.unwrap(), latest);
+        // Inside the gap (block 50 at 1100, block 51 at 1202): nearest side wins
+        assert_eq!(find(1140).await.unwrap(), (50, 1100));
+        assert_eq!(find(1180).await.unwrap(), (51, 1202));
+    }
+}
@@ -1,12 +1,16 @@
 // src/blockchain/services/contract.rs
 
-use anyhow::Result;
+use crate::blockchain::models::{TokenDetails, TokenInfo};
+use crate::blockchain::services::rpc::{self, RpcTransport};
+use anyhow::{anyhow, Result};
+use ethers_core::abi::Abi;
+use ethers_core::types::{Address, H256, U256};
 use reqwest::Client;
-use serde_json::Value;
+use serde::Serialize;
+use serde_json::{json, Value};
 
 // Seistream contract API (chain-agnostic base; network inferred by address)
 const SEISCAN_API_MAINNET: &str = "https://api.seistream.app/contracts/evm";
-const SEISCAN_API_TESTNET: &str = "https://api.seistream.app/contracts/evm";
 
 fn get_seiscan_api_base(chain_id: &str) -> &str {
     // Currently the API host/path does not vary per chain; keep function for future flexibility.
@@ -44,13 +48,68 @@ pub async fn get_contract_code(client: &Client, chain_id: &str, address: &str) -
     }
 }
 
+/// Fetches the verified ABI for a contract as standard ABI JSON entries.
+/// Returns `Ok(None)` only when SeiStream answers successfully without an ABI (an unverified
+/// contract); an unreachable API, an error status or an unreadable body is an error.
+pub async fn get_contract_abi(client: &Client, chain_id: &str, address: &str) -> Result<Option<Vec<Value>>> {
+    let base_url = get_seiscan_api_base(chain_id);
+    let url = format!("{}/{}/code", base_url, address);
+    let res = client.get(&url).send().await?;
+    let status = res.status();
+    let body = res.text().await?;
+    abi_from_response(status, &body)
+}
+
+fn abi_from_response(status: reqwest::StatusCode, body: &str) -> Result<Option<Vec<Value>>> {
+    if !status.is_success() {
+        return Err(anyhow!("SeiStream ABI lookup failed: status={} body={}", status, body));
+    }
+    let v: Value = serde_json::from_str(body).map_err(|e| anyhow!("Invalid SeiStream ABI response: {}", e))?;
+    extract_abi(&v)
+}
+
+/// Parses ABI JSON entries into an `ethers` ABI for encoding/decoding.
+pub fn parse_abi(entries: &[Value]) -> Result<Abi> {
+    serde_json::from_value(Value::Array(entries.to_vec())).map_err(|e| anyhow!("Invalid contract ABI: {}", e))
+}
+
+// Upstream may return the ABI as an array of objects, an array of JSON strings, or one JSON string.
+// No `abi` (or an empty one) means the contract is unverified; one that doesn't parse is an error.
+fn extract_abi(v: &Value) -> Result<Option<Vec<Value>>> {
+    let invalid = |e: serde_json::Error| anyhow!("Invalid ABI in SeiStream response: {}", e);
+    let entries: Vec<Value> = match v.get("abi") {
+        None | Some(Value::Null) => return Ok(None),
+        Some(Value::Array(arr)) => arr
+            .iter()
+            .map(|el| match el {
+                Value::String(s) => serde_json::from_str(s).map_err(invalid),
+                other => Ok(other.clone()),
+            })
+            .collect::<Result<_>>()?,
+        Some(Value::String(s)) if s.trim().is_empty() => return Ok(None),
+        Some(Value::String(s)) => serde_json::from_str(s).map_err(invalid)?,
+        Some(other) => return Err(anyhow!("Unexpected ABI in SeiStream response: {}", other)),
+    };
+    if entries.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(entries))
+    }
+}
+
 pub async fn get_contract_transactions(
     client: &Client,
     chain_id: &str,
     address: &str,
+    page: Option<u64>,
+    page_size: Option<u64>,
 ) -> Result<Value> {
     let base_url = get_seiscan_api_base(chain_id);
-    let url = format!("{}/{}/transactions", base_url, address);
+    let mut url = format!("{}/{}/transactions", base_url, address);
+    let mut query = Vec::new();
+    if let Some(p) = page { query.push(format!("page={}", p)); }
+    if let Some(n) = page_size { query.push(format!("limit={}", n)); }
+    if !query.is_empty() { url.push_str(&format!("?{}", query.join("&"))); }
     let res = client.get(&url).send().await?;
     let status = res.status();
     let body = res.text().await.unwrap_or_default();
@@ -166,3 +225,300 @@ fn normalize_contract_code(v: Value) -> Value {
     })
 }
 
+
+/// EIP-1967 implementation slot: `bytes32(uint256(keccak256("eip1967.proxy.implementation")) - 1)`.
+pub const EIP1967_IMPLEMENTATION_SLOT: &str =
+    "0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc";
+
+/// Extracts the address held in the low 20 bytes of a storage word; an empty slot yields `None`.
+pub fn address_from_storage_word(word: &str) -> Option<Address> {
+    let bytes = hex::decode(word.trim_start_matches("0x")).ok()?;
+    if bytes.len() > 32 || bytes.iter().all(|b| *b == 0) {
+        return None;
+    }
+    let mut padded = [0u8; 32];
+    padded[32 - bytes.len()..].copy_from_slice(&bytes);
+    Some(Address::from_slice(&padded[12..]))
+}
+
+/// Parses a storage slot or value-type mapping key given as 0x-hex (left-padded to 32 bytes) or decimal.
+pub fn parse_storage_word(input: &str) -> Result<H256> {
+    let input = input.trim();
+    let value = match input.strip_prefix("0x").or_else(|| input.strip_prefix("0X")) {
+        Some(h) if h.len() <= 64 => U256::from_str_radix(h, 16).map_err(|_| anyhow!("Invalid hex word '{}'", input))?,
+        Some(_) => return Err(anyhow!("'{}' is longer than 32 bytes", input)),
+        None => U256::from_dec_str(input).map_err(|_| anyhow!("'{}' is neither 0x-hex nor a decimal number", input))?,
+    };
+    let mut word = [0u8; 32];
+    value.to_big_endian(&mut word);
+    Ok(H256(word))
+}
+
+/// Storage slot of `mapping[key]` for a mapping declared at `slot`: `keccak256(key ++ slot)`.
+/// Only value-type keys (uint, address, bytes32, ...) are laid out this way.
+pub fn mapping_slot(key: H256, slot: H256) -> H256 {
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(key.as_bytes());
+    preimage[32..].copy_from_slice(slot.as_bytes());
+    H256(ethers_core::utils::keccak256(preimage))
+}
+
+/// Predicts the address of a contract deployed with CREATE: `keccak256(rlp([deployer, nonce]))[12..]`.
+pub fn compute_create_address(deployer: Address, nonce: u64) -> Address {
+    ethers_core::utils::get_contract_address(deployer, nonce)
+}
+
+/// Predicts the address of a contract deployed with CREATE2 from its salt and init-code hash.
+pub fn compute_create2_address(deployer: Address, salt: [u8; 32], init_code_hash: [u8; 32]) -> Address {
+    ethers_core::utils::get_create2_address_from_hash(deployer, salt, init_code_hash)
+}
+
+/// Next page number for a paged SeiStream listing: the explorer's own hint when present,
+/// otherwise inferred from whether this page came back full.
+pub fn next_page(listing: &Value, page: u64, page_size: u64) -> Option<u64> {
+    let hint = listing
+        .get("pagination")
+        .and_then(|p| p.get("nextPage").or_else(|| p.get("next_page")))
+        .or_else(|| listing.get("nextPage"));
+    if let Some(hint) = hint {
+        return hint.as_u64();
+    }
+    let count = listing.get("items").and_then(|v| v.as_array()).map(|a| a.len() as u64).unwrap_or(0);
+    (page_size > 0 && count >= page_size).then_some(page + 1)
+}
+
+/// Classifies a SeiScan contract payload by its verification flags, strongest first.
+pub fn verification_status(contract: &Value) -> &'static str {
+    let flag = |key: &str| contract.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+    if flag("fullyVerified") {
+        "fully_verified"
+    } else if flag("partiallyVerified") {
+        "partially_verified"
+    } else if flag("verified") {
+        "verified"
+    } else {
+        "unverified"
+    }
+}
+
+/// Whether SeiStream's contract API covers `chain_id`; elsewhere only the RPC node can answer.
+pub fn seistream_indexes(chain_id: &str) -> bool {
+    chain_id == "sei-evm-mainnet"
+}
+
+/// Who deployed a contract, and where. `creator` and `tx_hash` stay `None` when a factory
+/// deployed it through an internal call, since no top-level transaction created it.
+#[derive(Debug, Clone, Serialize)]
+pub struct ContractCreation {
+    pub creator: Option<String>,
+    pub tx_hash: Option<String>,
+    pub block_number: Option<u64>,
+    /// "seistream" or "rpc"
+    pub source: &'static str,
+}
+
+/// Deployer and creation transaction from a SeiStream contract payload (`creatorAddress`/`txHash`).
+pub fn creation_from_seistream(contract: &Value) -> Option<(String, String)> {
+    let field = |key: &str| contract.get(key).and_then(|v| v.as_str()).filter(|s| !s.is_empty()).map(str::to_string);
+    Some((field("creatorAddress")?, field("txHash")?))
+}
+
+/// Token details (holders, transfers, supply as SeiStream last indexed them) from a SeiStream contract
+/// payload; `None` for contracts it doesn't list as a token.
+pub fn token_details(contract: &Value) -> Option<TokenDetails> {
+    serde_json::from_value::<TokenInfo>(contract.get("token")?.clone()).ok().map(|info| info.token)
+}
+
+fn parse_hex_u64(v: &Value, what: &str) -> Result<u64> {
+    v.as_str()
+        .and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok())
+        .ok_or_else(|| anyhow!("{} returned an invalid block number: {}", what, v))
+}
+
+async fn has_code(transport: &dyn RpcTransport, rpc_url: &str, address: &str, block: u64) -> Result<bool> {
+    let code = rpc::rpc_call(transport, rpc_url, "eth_getCode", json!([address, format!("0x{:x}", block)])).await?;
+    let code = code.as_str().ok_or_else(|| anyhow!("eth_getCode returned a non-string result: {}", code))?;
+    Ok(!code.trim_start_matches("0x").is_empty())
+}
+
+/// First block at which `address` has code, found by bisecting `eth_getCode` over `0..=latest`.
+/// `None` when there is no code at the latest block. Needs a node that serves historical state,
+/// and assumes the contract was never self-destructed and redeployed.
+pub async fn find_creation_block(transport: &dyn RpcTransport, rpc_url: &str, address: &str) -> Result<Option<u64>> {
+    let latest = rpc::rpc_call(transport, rpc_url, "eth_blockNumber", json!([])).await?;
+    let latest = parse_hex_u64(&latest, "eth_blockNumber")?;
+    if !has_code(transport, rpc_url, address, latest).await? {
+        return Ok(None);
+    }
+    // Invariant: code exists at `hi`
+    let (mut lo, mut hi) = (0, latest);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if has_code(transport, rpc_url, address, mid).await? {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    Ok(Some(hi))
+}
+
+/// The top-level transaction in `block` whose receipt names `address` as the created contract,
+/// as `(from, hash)`. `None` when no such transaction exists, i.e. a factory deployed it.
+pub async fn find_creation_tx(transport: &dyn RpcTransport, rpc_url: &str, address: &str, block: u64) -> Result<Option<(String, String)>> {
+    let block_obj = rpc::rpc_call(transport, rpc_url, "eth_getBlockByNumber", json!([format!("0x{:x}", block), true])).await?;
+    if block_obj.is_null() {
+        return Err(anyhow!("Block {} not found", block));
+    }
+    let txs = block_obj["transactions"].as_array().cloned().unwrap_or_default();
+    for tx in txs.iter().filter(|tx| tx["to"].is_null()) {
+        let Some(hash) = tx["hash"].as_str() else {
+            continue;
+        };
+        let receipt = rpc::rpc_call(transport, rpc_url, "eth_getTransactionReceipt", json!([hash])).await?;
+        if receipt["contractAddress"].as_str().is_some_and(|a| a.eq_ignore_ascii_case(address)) {
+            return Ok(Some((tx["from"].as_str().unwrap_or_default().to_string(), hash.to_string())));
+        }
+    }
+    Ok(None)
+}
+
+/// Block number of a mined transaction, from its receipt; `None` if the node doesn't know it.
+pub async fn tx_block_number(transport: &dyn RpcTransport, rpc_url: &str, tx_hash: &str) -> Result<Option<u64>> {
+    let receipt = rpc::rpc_call(transport, rpc_url, "eth_getTransactionReceipt", json!([tx_hash])).await?;
+    if receipt.is_null() {
+        return Ok(None);
+    }
+    parse_hex_u64(&receipt["blockNumber"], "eth_getTransactionReceipt").map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_creation_from_seistream_needs_both_fields() {
+        let full = json!({ "creatorAddress": "0xabc", "txHash": "0xdef" });
+        assert_eq!(creation_from_seistream(&full), Some(("0xabc".to_string(), "0xdef".to_string())));
+        assert_eq!(creation_from_seistream(&json!({ "creatorAddress": "0xabc", "txHash": null })), None);
+        assert_eq!(creation_from_seistream(&json!({ "creatorAddress": "", "txHash": "0xdef" })), None);
+    }
+
+    #[test]
+    fn test_token_details_from_seistream_contract() {
+        let contract = json!({ "token": { "type": "ERC-20", "token": {
+            "hash": "0xabc", "name": "USD Coin", "symbol": "USDC", "decimals": "6", "totalSupply": "1000000",
+            "id": "1", "address": "0xabc", "pointerType": "", "pointeeAddress": "", "pointerAddress": "",
+            "isBaseAsset": false, "isPointer": false, "holders": 42, "transfers": "1337"
+        }}});
+        let token = token_details(&contract).expect("token listed");
+        assert_eq!((token.symbol.as_str(), token.holders, token.transfers.as_str()), ("USDC", 42, "1337"));
+        assert!(token_details(&json!({ "token": null })).is_none());
+        assert!(token_details(&json!({ "hash": "0xabc" })).is_none());
+    }
+
+    #[test]
+    fn test_extract_abi_accepts_objects_and_strings() {
+        let entry = json!({"type": "function", "name": "totalSupply", "inputs": [], "outputs": [{"name": "", "type": "uint256"}], "stateMutability": "view"});
+        let as_objects = json!({ "abi": [entry.clone()] });
+        let as_strings = json!({ "abi": [entry.to_string()] });
+        let as_blob = json!({ "abi": json!([entry.clone()]).to_string() });
+        for v in [as_objects, as_strings, as_blob] {
+            let abi = extract_abi(&v).unwrap().expect("abi present");
+            assert_eq!(abi, vec![entry.clone()]);
+            assert!(parse_abi(&abi).unwrap().function("totalSupply").is_ok());
+        }
+    }
+
+    #[test]
+    fn test_extract_abi_missing_for_unverified() {
+        assert!(extract_abi(&json!({ "message": "not found" })).unwrap().is_none());
+        assert!(extract_abi(&json!({ "abi": [] })).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_failed_abi_lookups_are_errors() {
+        let ok = reqwest::StatusCode::OK;
+        assert!(abi_from_response(reqwest::StatusCode::BAD_GATEWAY, "").is_err(), "an error status is not 'unverified'");
+        assert!(abi_from_response(ok, "<html>rate limited</html>").is_err());
+        assert!(abi_from_response(ok, r#"{"abi": "[{not json"}"#).is_err());
+        assert!(abi_from_response(ok, r#"{"abi": null}"#).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_address_from_storage_word() {
+        let word = "0x000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+        let addr = address_from_storage_word(word).unwrap();
+        assert_eq!(format!("{:?}", addr), "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+        assert!(address_from_storage_word(&format!("0x{}", "0".repeat(64))).is_none());
+        assert!(address_from_storage_word("0x").is_none());
+        assert!(address_from_storage_word("0xzz").is_none());
+    }
+
+    #[test]
+    fn test_compute_create_address() {
+        let deployer: Address = "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0".parse().unwrap();
+        let expected: Address = "0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d".parse().unwrap();
+        assert_eq!(compute_create_address(deployer, 0), expected);
+        let expected: Address = "0x343c43a37d37dff08ae8c4a11544c718abb4fcf8".parse().unwrap();
+        assert_eq!(compute_create_address(deployer, 1), expected);
+    }
+
+    #[test]
+    fn test_compute_create2_address_eip1014_vectors() {
+        use ethers_core::utils::keccak256;
+        // (deployer, salt, init_code, expected) from the EIP-1014 examples
+        let vectors = [
+            ("0x0000000000000000000000000000000000000000", [0u8; 32], vec![0x00], "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38"),
+            ("0xdeadbeef00000000000000000000000000000000", [0u8; 32], vec![0x00], "0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3"),
+            ("0x00000000000000000000000000000000deadbeef", {
+                let mut salt = [0u8; 32];
+                salt[28..].copy_from_slice(&[0xca, 0xfe, 0xba, 0xbe]);
+                salt
+            }, hex::decode("deadbeef").unwrap(), "0x60f3f640a8508fC6a86d45DF051962668E1e8AC7"),
+        ];
+        for (deployer, salt, init_code, expected) in vectors {
+            let deployer: Address = deployer.parse().unwrap();
+            let expected: Address = expected.parse().unwrap();
+            assert_eq!(compute_create2_address(deployer, salt, keccak256(init_code)), expected);
+        }
+    }
+
+    #[test]
+    fn test_next_page() {
+        assert_eq!(next_page(&json!({"items": [1, 2]}), 1, 2), Some(2));
+        assert_eq!(next_page(&json!({"items": [1]}), 3, 2), None);
+        assert_eq!(next_page(&json!({"items": [1, 2], "pagination": {"nextPage": null}}), 1, 2), None);
+        assert_eq!(next_page(&json!({"items": [], "pagination": {"nextPage": 7}}), 1, 2), Some(7));
+    }
+
+    #[test]
+    fn test_verification_status_prefers_strongest_flag() {
+        let full = json!({"verified": true, "partiallyVerified": false, "fullyVerified": true});
+        let partial = json!({"verified": true, "partiallyVerified": true, "fullyVerified": false});
+        assert_eq!(verification_status(&full), "fully_verified");
+        assert_eq!(verification_status(&partial), "partially_verified");
+        assert_eq!(verification_status(&json!({"verified": true})), "verified");
+        assert_eq!(verification_status(&json!({"raw": "not found"})), "unverified");
+    }
+
+    #[test]
+    fn test_parse_storage_word_hex_and_decimal() {
+        assert_eq!(parse_storage_word("0x2").unwrap(), parse_storage_word("2").unwrap());
+        assert_eq!(parse_storage_word("10").unwrap(), H256::from_low_u64_be(10));
+        assert!(parse_storage_word(&format!("0x{}", "1".repeat(65))).is_err());
+        assert!(parse_storage_word("abc").is_err());
+    }
+
+    #[test]
+    fn test_mapping_slot_matches_solidity_layout() {
+        // balances[0x...01] for `mapping(address => uint256) balances` at slot 0
+        let key = parse_storage_word("0x0000000000000000000000000000000000000001").unwrap();
+        let slot = mapping_slot(key, H256::zero());
+        assert_eq!(
+            format!("{:?}", slot),
+            "0xada5013122d395ba3c54772283fb069b10426056ef8ca54750cb9bb552a59e7d"
+        );
+    }
+}
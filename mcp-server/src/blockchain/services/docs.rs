@@ -4,3 +4,34 @@
 pub fn get_sei_docs_url() -> &'static str {
     "https://docs.sei.io/"
 }
+
+/// Returns true only for well-formed `https://` URLs that are safe to hand to a browser launcher.
+/// Rejects other schemes, whitespace/control characters and anything that could be read as a flag.
+pub fn is_safe_browser_url(url: &str) -> bool {
+    if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return false;
+    }
+    match reqwest::Url::parse(url) {
+        Ok(parsed) => parsed.scheme() == "https" && parsed.host_str().is_some_and(|h| !h.is_empty()),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_docs_url_is_safe() {
+        assert!(is_safe_browser_url(get_sei_docs_url()));
+    }
+
+    #[test]
+    fn test_rejects_unsafe_urls() {
+        assert!(!is_safe_browser_url("http://docs.sei.io/"));
+        assert!(!is_safe_browser_url("file:///etc/passwd"));
+        assert!(!is_safe_browser_url("--help"));
+        assert!(!is_safe_browser_url("https://docs.sei.io/ ; rm -rf ~"));
+        assert!(!is_safe_browser_url("https://docs.sei.io/\n"));
+    }
+}
@@ -0,0 +1,235 @@
+// src/blockchain/services/erc20.rs
+
+use crate::blockchain::services::{multicall, rpc::{self, RpcTransport}};
+use anyhow::{anyhow, Context, Result};
+use ethers_core::abi::{decode, encode, ParamType, Token};
+use ethers_core::types::transaction::eip712::EIP712Domain;
+use ethers_core::types::{Address, U256};
+use ethers_core::utils::keccak256;
+use serde_json::{json, Value};
+
+// Read-only ERC-20 views over `eth_call`.
+
+fn selector(signature: &str) -> Vec<u8> {
+    keccak256(signature.as_bytes())[..4].to_vec()
+}
+
+async fn call_raw(transport: &dyn RpcTransport, rpc_url: &str, token: &str, data: Vec<u8>) -> Result<Vec<u8>> {
+    let call = json!({ "to": token, "data": format!("0x{}", hex::encode(data)) });
+    let out = rpc::eth_call(transport, rpc_url, call, "latest").await?;
+    hex::decode(out.trim_start_matches("0x")).context("eth_call returned invalid hex")
+}
+
+async fn call_uint(transport: &dyn RpcTransport, rpc_url: &str, token: &str, data: Vec<u8>) -> Result<U256> {
+    let bytes = call_raw(transport, rpc_url, token, data).await?;
+    if bytes.len() < 32 {
+        return Err(anyhow!("Contract {} returned no data; is it an ERC-20?", token));
+    }
+    Ok(U256::from_big_endian(&bytes[..32]))
+}
+
+pub async fn total_supply(transport: &dyn RpcTransport, rpc_url: &str, token: &str) -> Result<U256> {
+    call_uint(transport, rpc_url, token, selector("totalSupply()")).await
+}
+
+pub async fn decimals(transport: &dyn RpcTransport, rpc_url: &str, token: &str) -> Result<u8> {
+    let d = call_uint(transport, rpc_url, token, selector("decimals()")).await?;
+    u8::try_from(d.as_u64()).map_err(|_| anyhow!("Implausible decimals() value {}", d))
+}
+
+pub async fn balance_of(transport: &dyn RpcTransport, rpc_url: &str, token: &str, holder: Address) -> Result<U256> {
+    let mut data = selector("balanceOf(address)");
+    data.extend(encode(&[Token::Address(holder)]));
+    call_uint(transport, rpc_url, token, data).await
+}
+
+async fn call_string(transport: &dyn RpcTransport, rpc_url: &str, token: &str, signature: &str) -> Result<String> {
+    let bytes = call_raw(transport, rpc_url, token, selector(signature)).await?;
+    match decode(&[ParamType::String], &bytes) {
+        Ok(mut tokens) => Ok(tokens.pop().and_then(Token::into_string).unwrap_or_default()),
+        Err(_) => Err(anyhow!("Contract {} returned no string from {}", token, signature)),
+    }
+}
+
+pub async fn name(transport: &dyn RpcTransport, rpc_url: &str, token: &str) -> Result<String> {
+    call_string(transport, rpc_url, token, "name()").await
+}
+
+/// The EIP-712 domain version some permit tokens expose; `None` when the token has no `version()`.
+pub async fn version(transport: &dyn RpcTransport, rpc_url: &str, token: &str) -> Option<String> {
+    call_string(transport, rpc_url, token, "version()").await.ok()
+}
+
+/// ERC-2612 `nonces(owner)`.
+pub async fn nonces(transport: &dyn RpcTransport, rpc_url: &str, token: &str, owner: Address) -> Result<U256> {
+    let mut data = selector("nonces(address)");
+    data.extend(encode(&[Token::Address(owner)]));
+    call_uint(transport, rpc_url, token, data).await
+}
+
+/// ERC-2612 `DOMAIN_SEPARATOR()`; `None` when the call reverts or returns no word, i.e. no permit support.
+pub async fn domain_separator(transport: &dyn RpcTransport, rpc_url: &str, token: &str) -> Option<[u8; 32]> {
+    let bytes = call_raw(transport, rpc_url, token, selector("DOMAIN_SEPARATOR()")).await.ok()?;
+    bytes.get(..32)?.try_into().ok()
+}
+
+/// Fields of an ERC-2612 `Permit` besides the token's EIP-712 domain.
+#[derive(Debug, Clone)]
+pub struct Permit {
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+    pub nonce: U256,
+    pub deadline: U256,
+}
+
+/// The EIP-712 typed data a token's `permit()` verifies.
+pub fn permit_typed_data(name: &str, version: &str, chain_id: u64, token: Address, permit: &Permit) -> Value {
+    json!({
+        "types": {
+            "EIP712Domain": [
+                {"name": "name", "type": "string"},
+                {"name": "version", "type": "string"},
+                {"name": "chainId", "type": "uint256"},
+                {"name": "verifyingContract", "type": "address"}
+            ],
+            "Permit": [
+                {"name": "owner", "type": "address"},
+                {"name": "spender", "type": "address"},
+                {"name": "value", "type": "uint256"},
+                {"name": "nonce", "type": "uint256"},
+                {"name": "deadline", "type": "uint256"}
+            ]
+        },
+        "primaryType": "Permit",
+        "domain": { "name": name, "version": version, "chainId": chain_id, "verifyingContract": format!("{:?}", token) },
+        "message": {
+            "owner": format!("{:?}", permit.owner),
+            "spender": format!("{:?}", permit.spender),
+            "value": permit.value.to_string(),
+            "nonce": permit.nonce.to_string(),
+            "deadline": permit.deadline.to_string(),
+        }
+    })
+}
+
+/// Domain separator for `name`/`version` on `chain_id`, to compare with the token's `DOMAIN_SEPARATOR()`.
+pub fn permit_domain_separator(name: &str, version: &str, chain_id: u64, token: Address) -> [u8; 32] {
+    EIP712Domain {
+        name: Some(name.to_string()),
+        version: Some(version.to_string()),
+        chain_id: Some(chain_id.into()),
+        verifying_contract: Some(token),
+        salt: None,
+    }
+    .separator()
+}
+
+/// A holder's balance of one token with the token's `decimals()` and `symbol()`; `None` where the call failed.
+#[derive(Debug, Clone, Default)]
+pub struct TokenBalance {
+    pub balance: Option<U256>,
+    pub decimals: Option<u8>,
+    pub symbol: Option<String>,
+}
+
+fn decode_uint(bytes: &[u8]) -> Option<U256> {
+    bytes.get(..32).map(U256::from_big_endian)
+}
+
+fn decode_decimals(bytes: &[u8]) -> Option<u8> {
+    decode_uint(bytes).and_then(|d| u8::try_from(d).ok())
+}
+
+fn decode_string(bytes: &[u8]) -> Option<String> {
+    decode(&[ParamType::String], bytes).ok()?.pop()?.into_string()
+}
+
+/// `balanceOf(holder)`, `decimals()` and `symbol()` for each token. With a Multicall3 address all of
+/// them go out in one `eth_call`; returns whether that happened, since a failed multicall (or none
+/// configured) falls back to calling each token in turn.
+pub async fn token_balances(
+    transport: &dyn RpcTransport,
+    rpc_url: &str,
+    multicall: Option<&str>,
+    holder: Address,
+    tokens: &[Address],
+) -> (Vec<TokenBalance>, bool) {
+    let mut balance_call = selector("balanceOf(address)");
+    balance_call.extend(encode(&[Token::Address(holder)]));
+    let per_token = [balance_call, selector("decimals()"), selector("symbol()")];
+
+    if let Some(multicall) = multicall {
+        let calls: Vec<(Address, Vec<u8>)> =
+            tokens.iter().flat_map(|t| per_token.iter().map(move |data| (*t, data.clone()))).collect();
+        if let Ok(results) = multicall::aggregate3(transport, rpc_url, multicall, &calls).await {
+            let balances = results
+                .chunks(per_token.len())
+                .map(|r| TokenBalance {
+                    balance: r[0].as_deref().and_then(decode_uint),
+                    decimals: r[1].as_deref().and_then(decode_decimals),
+                    symbol: r[2].as_deref().and_then(decode_string),
+                })
+                .collect();
+            return (balances, true);
+        }
+    }
+
+    let mut balances = Vec::with_capacity(tokens.len());
+    for token in tokens {
+        let token = format!("{:?}", token);
+        balances.push(TokenBalance {
+            balance: balance_of(transport, rpc_url, &token, holder).await.ok(),
+            decimals: decimals(transport, rpc_url, &token).await.ok(),
+            symbol: call_string(transport, rpc_url, &token, "symbol()").await.ok(),
+        });
+    }
+    (balances, false)
+}
+
+/// Renders a raw amount with `decimals` places, trimming trailing zeros ("1.5", "42").
+pub fn format_units(amount: U256, decimals: u8) -> String {
+    let digits = amount.to_string();
+    let d = decimals as usize;
+    if d == 0 {
+        return digits;
+    }
+    let padded = format!("{:0>width$}", digits, width = d + 1);
+    let (whole, frac) = padded.split_at(padded.len() - d);
+    let frac = frac.trim_end_matches('0');
+    if frac.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, frac)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_units() {
+        assert_eq!(format_units(U256::from(1_500_000_000_000_000_000u128), 18), "1.5");
+        assert_eq!(format_units(U256::from(42), 0), "42");
+        assert_eq!(format_units(U256::from(5), 6), "0.000005");
+        assert_eq!(format_units(U256::from(3_000_000), 6), "3");
+    }
+
+    #[test]
+    fn test_permit_domain_separator_matches_typed_data() {
+        use ethers_core::types::transaction::eip712::{Eip712, TypedData};
+        let token: Address = "0x5FbDB2315678afecb367f032d93F642f64180aa3".parse().unwrap();
+        let permit = Permit { owner: Address::zero(), spender: Address::zero(), value: 1.into(), nonce: 0.into(), deadline: 2.into() };
+        let typed: TypedData = serde_json::from_value(permit_typed_data("USD Coin", "2", 1329, token, &permit)).unwrap();
+        assert_eq!(typed.domain_separator().unwrap(), permit_domain_separator("USD Coin", "2", 1329, token));
+        assert_ne!(permit_domain_separator("USD Coin", "1", 1329, token), permit_domain_separator("USD Coin", "2", 1329, token));
+        assert_eq!(hex::encode(selector("DOMAIN_SEPARATOR()")), "3644e515");
+    }
+
+    #[test]
+    fn test_selectors() {
+        assert_eq!(hex::encode(selector("totalSupply()")), "18160ddd");
+        assert_eq!(hex::encode(selector("balanceOf(address)")), "70a08231");
+    }
+}
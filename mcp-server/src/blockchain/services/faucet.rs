@@ -1,20 +1,35 @@
 // src/blockchain/services/faucet.rs
 
-use crate::blockchain::models::ChainType;
+use crate::blockchain::faucet_ledger::FaucetLedger;
+use crate::blockchain::models::{ChainType, FaucetResponse, SeiError};
+use crate::blockchain::services::rpc::RpcTransport;
+use crate::blockchain::services::transactions::send_evm_transaction;
 use crate::config::Config;
 use anyhow::{Context, Result};
+use ethers_core::abi::{encode, Token};
+use ethers_core::types::{Address, Bytes, TransactionRequest, U256};
+use ethers_core::utils::keccak256;
+use ethers_signers::{LocalWallet, Signer};
 use serde::Deserialize;
 use serde_json::json;
-use tracing::info;
+use std::str::FromStr;
+use tracing::{info, warn};
 
-/// Sends faucet tokens via a standard EVM transaction.
+/// Sends faucet tokens via the external faucet API, plus the configured test ERC-20 (if any).
+/// Refuses once FAUCET_DAILY_CAPS for the chain is used up; the drip is held against `ledger` while the
+/// API is called and given back if the call fails. The token transfer goes out through `transport`; if it
+/// fails, the result still carries the native tx hash with the failure in `token_error`.
+#[allow(clippy::too_many_arguments)]
 pub async fn send_faucet_tokens(
+    transport: &dyn RpcTransport,
     config: &Config,
+    ledger: &FaucetLedger,
     recipient_address: &str,
-    _nonce_manager: &crate::blockchain::nonce_manager::NonceManager,
-    _rpc_url: &str,
+    nonce_manager: &crate::blockchain::nonce_manager::NonceManager,
+    rpc_url: &str,
     chain_id: &str,
-) -> Result<String> {
+    amount: Option<u64>,
+) -> Result<FaucetResponse> {
     let chain_type = ChainType::from_chain_id(chain_id);
 
     // Map ChainType to faucet API chain labels
@@ -23,23 +38,71 @@ pub async fn send_faucet_tokens(
         ChainType::Native => "sei-native-testnet",
     };
 
+    // Cap the requested amount at the per-chain maximum; without a request, a configured maximum is sent
+    // and otherwise the API picks its default
+    let amount = match amount {
+        Some(0) => return Err(SeiError::InvalidInput("Faucet amount must be greater than zero".into()).into()),
+        Some(requested) => Some(requested.min(config.faucet_amount(chain_id))),
+        None => config.faucet_amounts.get(chain_id).copied(),
+    };
+
+    let reservation = ledger.reserve(chain_id, config.faucet_daily_caps.get(chain_id).copied(), amount.unwrap_or(0), chrono::Utc::now())?;
+    let parsed = match request_drip(config, recipient_address, faucet_chain, amount).await {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            ledger.release(&reservation);
+            return Err(e);
+        }
+    };
+    ledger.settle(&reservation, parsed.amount.as_deref().and_then(|a| a.parse::<u64>().ok()).or(amount).unwrap_or(0));
+
+    // Optionally dispense the configured test token from the tx key. The native drip has already gone
+    // out by now, so a failed token transfer is reported alongside its hash rather than as an error.
+    let (mut token_tx_hash, mut token_error) = (None, None);
+    if let (ChainType::Evm, Some(token)) = (chain_type, config.faucet_tokens.get(chain_id)) {
+        match send_faucet_token(transport, config, recipient_address, nonce_manager, rpc_url, chain_id, &token.contract, &token.amount).await {
+            Ok(hash) => token_tx_hash = Some(hash),
+            Err(e) => {
+                warn!("Native faucet tx {} succeeded but token transfer failed: {:#}", parsed.tx_hash, e);
+                token_error = Some(format!("{:#}", e));
+            }
+        }
+    }
+
+    Ok(FaucetResponse {
+        tx_hash: parsed.tx_hash,
+        amount: parsed.amount.or(amount.map(|a| a.to_string())),
+        token_tx_hash,
+        token_error,
+    })
+}
+
+#[derive(Deserialize)]
+struct FaucetApiResponse {
+    #[serde(rename = "txHash")]
+    tx_hash: String,
+    #[serde(default)]
+    amount: Option<String>,
+}
+
+// One POST to the external faucet API.
+async fn request_drip(config: &Config, recipient_address: &str, faucet_chain: &str, amount: Option<u64>) -> Result<FaucetApiResponse> {
     info!("Requesting faucet via API for {} on {}", recipient_address, faucet_chain);
 
     let client = reqwest::Client::new();
     let url = format!("{}/faucet/request", config.faucet_api_url.trim_end_matches('/'));
 
-    #[derive(Deserialize)]
-    struct FaucetResponse {
-        #[serde(rename = "txHash")] 
-        tx_hash: String,
+    let mut body = json!({
+        "address": recipient_address,
+        "chain": faucet_chain,
+    });
+    if let Some(a) = amount {
+        body["amount"] = json!(a.to_string());
     }
 
     let resp = client
         .post(&url)
-        .json(&json!({
-            "address": recipient_address,
-            "chain": faucet_chain,
-        }))
+        .json(&body)
         .send()
         .await
         .context("Failed to call faucet API")?;
@@ -50,6 +113,52 @@ pub async fn send_faucet_tokens(
         anyhow::bail!("Faucet API error: status={} body={}", status, text);
     }
 
-    let parsed: FaucetResponse = resp.json().await.context("Invalid faucet API response")?;
-    Ok(parsed.tx_hash)
-}
\ No newline at end of file
+    resp.json().await.context("Invalid faucet API response")
+}
+
+/// The faucet's sending account on `chain_id`: derived from TX_PRIVATE_KEY_EVM on EVM chains,
+/// DEFAULT_SENDER_ADDRESS (or FAUCET_ADDRESS) on native chains. The key itself never leaves this function.
+pub fn faucet_address(config: &Config, chain_id: &str) -> Option<String> {
+    match ChainType::from_chain_id(chain_id) {
+        ChainType::Evm => LocalWallet::from_str(&config.tx_private_key_evm)
+            .ok()
+            .map(|wallet| format!("{:?}", wallet.address())),
+        ChainType::Native => config.default_sender_address.clone(),
+    }
+}
+
+/// Whether `balance` is under the FAUCET_LOW_BALANCE threshold for `chain_id`; `None` when no threshold is set.
+pub fn is_low_balance(config: &Config, chain_id: &str, balance: U256) -> Option<bool> {
+    let threshold = U256::from_dec_str(config.faucet_low_balance.get(chain_id)?.trim()).ok()?;
+    Some(balance < threshold)
+}
+
+/// Sends an ERC-20 `transfer(address,uint256)` from the configured tx key.
+#[allow(clippy::too_many_arguments)]
+async fn send_faucet_token(
+    transport: &dyn RpcTransport,
+    config: &Config,
+    recipient_address: &str,
+    nonce_manager: &crate::blockchain::nonce_manager::NonceManager,
+    rpc_url: &str,
+    chain_id: &str,
+    contract_address: &str,
+    amount: &str,
+) -> Result<String> {
+    let wallet = LocalWallet::from_str(&config.tx_private_key_evm)
+        .context("Token faucet requires TX_PRIVATE_KEY_EVM to be set")?;
+    let contract = Address::from_str(contract_address).context("Invalid faucet token contract address")?;
+    let recipient = Address::from_str(recipient_address).context("Invalid recipient EVM address format")?;
+    let value = U256::from_dec_str(amount).context("Invalid faucet token amount")?;
+
+    let mut data = keccak256("transfer(address,uint256)".as_bytes())[0..4].to_vec();
+    data.extend(encode(&[Token::Address(recipient), Token::Uint(value)]));
+
+    info!("Dispensing faucet token {} to {}", contract_address, recipient_address);
+    let tx_request = TransactionRequest::new()
+        .to(contract)
+        .data(Bytes::from(data))
+        .value(U256::zero());
+    let response = send_evm_transaction(transport, rpc_url, wallet, tx_request, nonce_manager, config.tx_type(chain_id), config.max_gas_price(chain_id).map(U256::from)).await?;
+    Ok(response.tx_hash)
+}
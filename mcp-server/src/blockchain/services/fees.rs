@@ -1,12 +1,14 @@
 use anyhow::{Result, anyhow};
 use ethers_core::types::U256;
-use reqwest::Client;
+use serde::Serialize;
 use serde_json::{Value, json};
-use tracing::info;
+use tracing::{debug, info};
 use crate::blockchain::models::{EstimateFeesRequest, EstimateFeesResponse};
+use crate::blockchain::services::rpc::{self, HttpClient, RpcTransport};
+use crate::blockchain::services::{blocks, mempool};
 
 pub async fn estimate_fees(
-    client: &Client,
+    client: &HttpClient,
     rpc_url: &str,
     request: &EstimateFeesRequest,
 ) -> Result<EstimateFeesResponse> {
@@ -83,3 +85,155 @@ pub async fn estimate_fees(
         denom: "usei".to_string(),
     })
 }
+
+// Blocks of history sampled for base fees, tips and block time
+const FEE_HISTORY_BLOCKS: u64 = 20;
+
+/// Rough time-to-inclusion for a transaction at a given gas price.
+#[derive(Debug, Serialize)]
+pub struct InclusionEstimate {
+    /// "fast", "medium" or "slow".
+    pub bucket: &'static str,
+    pub blocks_min: u64,
+    pub blocks_max: u64,
+    pub seconds_min: f64,
+    pub seconds_max: f64,
+    pub block_time_secs: f64,
+    /// Base fee expected for the next block, when the node reports fee history.
+    pub next_base_fee: Option<String>,
+    /// "medium" when mempool prices were available, "low" otherwise.
+    pub confidence: &'static str,
+    pub basis: &'static str,
+}
+
+fn bucket_blocks(bucket: &str) -> (u64, u64) {
+    match bucket {
+        "fast" => (1, 2),
+        "medium" => (2, 6),
+        _ => (6, 30),
+    }
+}
+
+/// Buckets `gas_price` against what the chain has recently accepted.
+/// Below the next base fee is always slow; otherwise the tip is ranked against the pending pool when
+/// known, falling back to the median recent tips (`reward_p10` / `reward_p50`).
+pub fn classify_inclusion(
+    gas_price: U256,
+    next_base_fee: Option<U256>,
+    reward_p10: U256,
+    reward_p50: U256,
+    pending_prices: Option<&[U256]>,
+) -> &'static str {
+    let base = next_base_fee.unwrap_or_default();
+    if gas_price < base {
+        return "slow";
+    }
+    if let Some(prices) = pending_prices.filter(|p| !p.is_empty()) {
+        let outbid_by = prices.iter().filter(|p| **p > gas_price).count() as f64 / prices.len() as f64;
+        return match outbid_by {
+            r if r < 0.25 => "fast",
+            r if r < 0.75 => "medium",
+            _ => "slow",
+        };
+    }
+    let tip = gas_price - base;
+    if tip >= reward_p50 {
+        "fast"
+    } else if tip >= reward_p10 {
+        "medium"
+    } else {
+        "slow"
+    }
+}
+
+fn hex_u256(v: &Value) -> Option<U256> {
+    U256::from_str_radix(v.as_str()?.trim_start_matches("0x"), 16).ok()
+}
+
+// Median of one reward percentile column across the sampled blocks.
+fn median_reward(rewards: &[Value], column: usize) -> U256 {
+    let mut col: Vec<U256> = rewards.iter().filter_map(|r| hex_u256(&r[column])).collect();
+    col.sort();
+    col.get(col.len() / 2).copied().unwrap_or_default()
+}
+
+/// Estimates how long a transaction paying `gas_price` (wei) waits for inclusion, from
+/// `eth_feeHistory`, recent block times and, when the node exposes it, `txpool_content`.
+pub async fn estimate_inclusion_time(transport: &dyn RpcTransport, rpc_url: &str, gas_price: U256) -> Result<InclusionEstimate> {
+    let (latest, latest_ts) = blocks::get_block_header(transport, rpc_url, "latest").await?;
+    let sample_start = latest.saturating_sub(FEE_HISTORY_BLOCKS);
+    let (_, start_ts) = blocks::get_block_header(transport, rpc_url, &format!("0x{:x}", sample_start)).await?;
+    let block_time_secs = if latest > sample_start {
+        latest_ts.saturating_sub(start_ts) as f64 / (latest - sample_start) as f64
+    } else {
+        1.0
+    };
+
+    let history = rpc::rpc_call(
+        transport,
+        rpc_url,
+        "eth_feeHistory",
+        json!([format!("0x{:x}", FEE_HISTORY_BLOCKS), "latest", [10, 50]]),
+    )
+    .await;
+    let (next_base_fee, reward_p10, reward_p50) = match &history {
+        Ok(h) => {
+            let rewards = h["reward"].as_array().map(Vec::as_slice).unwrap_or_default();
+            let next = h["baseFeePerGas"].as_array().and_then(|fees| fees.last()).and_then(hex_u256);
+            (next, median_reward(rewards, 0), median_reward(rewards, 1))
+        }
+        Err(e) => {
+            debug!("eth_feeHistory unavailable, using eth_gasPrice: {}", e);
+            // Treat the node's suggested price as the median: meeting it is medium, 20% over is fast
+            let suggested = hex_u256(&rpc::rpc_call(transport, rpc_url, "eth_gasPrice", json!([])).await?)
+                .ok_or_else(|| anyhow!("Invalid eth_gasPrice result"))?;
+            (None, suggested, suggested * 6 / 5)
+        }
+    };
+
+    let pending = rpc::rpc_call(transport, rpc_url, "txpool_content", json!([]))
+        .await
+        .ok()
+        .map(|content| mempool::pending_gas_prices(&content))
+        .filter(|p| !p.is_empty());
+
+    let bucket = classify_inclusion(gas_price, next_base_fee, reward_p10, reward_p50, pending.as_deref());
+    let (blocks_min, blocks_max) = bucket_blocks(bucket);
+    let basis = match (&pending, &history) {
+        (Some(_), _) => "mempool gas price distribution",
+        (None, Ok(_)) => "recent base fees and priority tips",
+        (None, Err(_)) => "node gas price suggestion",
+    };
+    Ok(InclusionEstimate {
+        bucket,
+        blocks_min,
+        blocks_max,
+        seconds_min: blocks_min as f64 * block_time_secs,
+        seconds_max: blocks_max as f64 * block_time_secs,
+        block_time_secs,
+        next_base_fee: next_base_fee.map(|f| f.to_string()),
+        confidence: if pending.is_some() { "medium" } else { "low" },
+        basis,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_inclusion() {
+        let gwei = |n: u64| U256::from(n) * U256::exp10(9);
+        // Below base fee never lands soon
+        assert_eq!(classify_inclusion(gwei(1), Some(gwei(2)), gwei(0), gwei(1), None), "slow");
+        // Tip ranking without mempool data
+        assert_eq!(classify_inclusion(gwei(4), Some(gwei(2)), gwei(1), gwei(2), None), "fast");
+        assert_eq!(classify_inclusion(gwei(3), Some(gwei(2)), gwei(1), gwei(2), None), "medium");
+        assert_eq!(classify_inclusion(gwei(2), Some(gwei(2)), gwei(1), gwei(2), None), "slow");
+        // Mempool rank wins when available
+        let pool = [gwei(1), gwei(2), gwei(3), gwei(10)];
+        assert_eq!(classify_inclusion(gwei(10), None, gwei(90), gwei(90), Some(&pool)), "fast");
+        assert_eq!(classify_inclusion(gwei(2), None, U256::zero(), U256::zero(), Some(&pool)), "medium");
+        assert_eq!(classify_inclusion(gwei(1), None, U256::zero(), U256::zero(), Some(&pool)), "slow");
+    }
+}
@@ -1,9 +1,13 @@
 use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
 use reqwest::Client;
-use serde::Deserialize;
-use tracing::{debug, info};
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use std::cmp::Ordering;
+use tracing::{debug, info, warn};
 
 use crate::blockchain::models::{Transaction, TransactionHistoryResponse, TransactionType};
+use crate::blockchain::services::rpc;
 
 // --- Helper Structs for Deserializing the Seistream API Response ---
 
@@ -19,13 +23,50 @@ struct SeiApiTransaction {
     to_address: Option<String>,
     value: String,
     timestamp: String,
+    #[serde(default, rename = "blockNumber", alias = "block_number", alias = "height", deserialize_with = "lenient_u64")]
+    block_number: Option<u64>,
+    #[serde(default, rename = "transactionIndex", alias = "transaction_index", alias = "index", deserialize_with = "lenient_u64")]
+    transaction_index: Option<u64>,
+}
+
+// Accepts a JSON number, a decimal string, or a 0x hex string; anything else reads as absent.
+fn lenient_u64<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<u64>, D::Error> {
+    Ok(match Value::deserialize(deserializer)? {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => match s.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => s.parse().ok(),
+        },
+        _ => None,
+    })
 }
 
 /// Represents the top-level structure of the Seistream API response.
-/// The key field is `items`, not `txs` or `transactions`.
+/// The key field is `items`, not `txs` or `transactions`. Items stay raw so that one malformed
+/// entry is skipped on its own instead of failing the whole page.
 #[derive(Deserialize, Debug)]
 struct SeiApiResponse {
-    items: Vec<SeiApiTransaction>,
+    items: Vec<Value>,
+}
+
+impl SeiApiResponse {
+    /// The items that parse, plus how many didn't.
+    fn transactions(self) -> (Vec<SeiApiTransaction>, usize) {
+        let mut skipped = 0;
+        let parsed = self
+            .items
+            .into_iter()
+            .filter_map(|item| match serde_json::from_value::<SeiApiTransaction>(item) {
+                Ok(tx) => Some(tx),
+                Err(e) => {
+                    warn!("Skipping malformed Seistream history item: {}", e);
+                    skipped += 1;
+                    None
+                }
+            })
+            .collect();
+        (parsed, skipped)
+    }
 }
 
 /// Fetches transaction history for a given address using the public Seistream API.
@@ -49,21 +90,19 @@ pub async fn get_transaction_history(
     );
 
     // Perform the GET request and get the response text for debugging.
-    let response_text = client.get(&api_url).send().await?.text().await?;
+    let response = client.get(&api_url).send().await?;
+    let status = response.status().as_u16();
+    let response_text = response.text().await?;
     debug!("Received response from Seistream API: {}", response_text);
 
     // Deserialize the JSON response text into our corrected structs.
-    let api_response: SeiApiResponse = serde_json::from_str(&response_text).map_err(|e| {
-        anyhow!(
-            "Error decoding Seistream API response: {}. Response body: {}",
-            e,
-            response_text
-        )
-    })?;
+    let api_response: SeiApiResponse = rpc::parse_json_body(&response_text, status)
+        .map_err(|e| anyhow!("Error decoding Seistream API response: {}", e))?;
+
+    let (items, skipped_items) = api_response.transactions();
 
     // Map the API response to our internal `Transaction` model.
-    let transactions: Vec<Transaction> = api_response
-        .items // Use .items, which matches the actual API response
+    let mut transactions: Vec<Transaction> = items
         .into_iter()
         .map(|tx| Transaction {
             tx_hash: tx.hash,
@@ -74,9 +113,156 @@ pub async fn get_transaction_history(
             timestamp: tx.timestamp,
             transaction_type: TransactionType::Native,
             contract_address: None,
+            block_number: tx.block_number,
+            transaction_index: tx.transaction_index,
         })
         .collect();
+    sort_transactions(&mut transactions);
 
     // Return the final response structure.
-    Ok(TransactionHistoryResponse { transactions })
+    Ok(TransactionHistoryResponse { transactions, partial: skipped_items > 0, skipped_items })
+}
+
+/// Orders transactions newest first: by timestamp, then block number, then index within the block,
+/// with the hash as a last resort so repeated queries always list the same data in the same order.
+pub fn sort_transactions(transactions: &mut [Transaction]) {
+    transactions.sort_by(|a, b| {
+        compare_timestamps(&b.timestamp, &a.timestamp)
+            .then_with(|| b.block_number.cmp(&a.block_number))
+            .then_with(|| b.transaction_index.cmp(&a.transaction_index))
+            .then_with(|| a.tx_hash.cmp(&b.tx_hash))
+    });
+}
+
+// RFC 3339 timestamps compare as instants (so offsets and precision don't matter); others lexically.
+fn compare_timestamps(a: &str, b: &str) -> Ordering {
+    match (DateTime::parse_from_rfc3339(a), DateTime::parse_from_rfc3339(b)) {
+        (Ok(a), Ok(b)) => a.with_timezone(&Utc).cmp(&b.with_timezone(&Utc)),
+        _ => a.cmp(b),
+    }
+}
+
+/// Renders transactions as RFC 4180 CSV with a header row.
+pub fn transactions_to_csv(transactions: &[Transaction]) -> String {
+    let mut out = String::from("hash,from,to,amount,denom,timestamp,type,block_number,transaction_index\n");
+    for tx in transactions {
+        let kind = match tx.transaction_type {
+            TransactionType::Native => "native",
+            TransactionType::ERC20 => "erc20",
+        };
+        let block_number = tx.block_number.map(|n| n.to_string()).unwrap_or_default();
+        let transaction_index = tx.transaction_index.map(|n| n.to_string()).unwrap_or_default();
+        let row = [
+            tx.tx_hash.as_str(),
+            tx.from_address.as_str(),
+            tx.to_address.as_str(),
+            tx.amount.as_str(),
+            tx.denom.as_str(),
+            tx.timestamp.as_str(),
+            kind,
+            block_number.as_str(),
+            transaction_index.as_str(),
+        ]
+        .map(csv_field)
+        .join(",");
+        out.push_str(&row);
+        out.push('\n');
+    }
+    out
+}
+
+// Quotes a field when it contains a delimiter, quote, or line break, doubling embedded quotes.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transactions_to_csv_escapes_fields() {
+        let txs = vec![Transaction {
+            tx_hash: "0xabc".into(),
+            from_address: "0x1".into(),
+            to_address: "N/A".into(),
+            amount: "1,000".into(),
+            denom: "say \"hi\"".into(),
+            timestamp: "2024-01-01T00:00:00Z".into(),
+            transaction_type: TransactionType::Native,
+            contract_address: None,
+            block_number: Some(7),
+            transaction_index: None,
+        }];
+        assert_eq!(
+            transactions_to_csv(&txs),
+            "hash,from,to,amount,denom,timestamp,type,block_number,transaction_index\n0xabc,0x1,N/A,\"1,000\",\"say \"\"hi\"\"\",2024-01-01T00:00:00Z,native,7,\n"
+        );
+    }
+
+    fn tx(hash: &str, timestamp: &str, block_number: Option<u64>, transaction_index: Option<u64>) -> Transaction {
+        Transaction {
+            tx_hash: hash.into(),
+            from_address: "0x1".into(),
+            to_address: "0x2".into(),
+            amount: "1".into(),
+            denom: "usei".into(),
+            timestamp: timestamp.into(),
+            transaction_type: TransactionType::Native,
+            contract_address: None,
+            block_number,
+            transaction_index,
+        }
+    }
+
+    #[test]
+    fn test_sort_breaks_timestamp_ties_deterministically() {
+        let same = "2024-01-01T00:00:00Z";
+        let expected = ["0xnewest", "0xb10i2", "0xb10i1", "0xb9", "0xaa", "0xab"];
+        let mut txs = vec![
+            tx("0xab", same, None, None),
+            tx("0xb10i1", same, Some(10), Some(1)),
+            tx("0xb9", same, Some(9), Some(5)),
+            tx("0xnewest", "2024-01-01T00:00:01+00:00", Some(11), Some(0)),
+            tx("0xaa", same, None, None),
+            tx("0xb10i2", "2024-01-01T00:00:00.000Z", Some(10), Some(2)),
+        ];
+        for _ in 0..3 {
+            sort_transactions(&mut txs);
+            let order: Vec<&str> = txs.iter().map(|t| t.tx_hash.as_str()).collect();
+            assert_eq!(order, expected);
+            txs.reverse();
+        }
+    }
+
+    #[test]
+    fn test_api_block_fields_accept_numbers_and_strings() {
+        let parsed: SeiApiResponse = serde_json::from_value(serde_json::json!({ "items": [
+            { "hash": "0x1", "from": "0xa", "value": "1", "timestamp": "t", "blockNumber": 12, "transactionIndex": "0x3" },
+            { "hash": "0x2", "from": "0xa", "value": "1", "timestamp": "t", "height": "13" }
+        ]}))
+        .unwrap();
+        let (items, skipped) = parsed.transactions();
+        assert_eq!(skipped, 0);
+        assert_eq!((items[0].block_number, items[0].transaction_index), (Some(12), Some(3)));
+        assert_eq!((items[1].block_number, items[1].transaction_index), (Some(13), None));
+    }
+
+    #[test]
+    fn test_malformed_item_is_skipped_alone() {
+        let parsed: SeiApiResponse = serde_json::from_value(serde_json::json!({ "items": [
+            { "hash": "0x1", "from": "0xa", "value": "1", "timestamp": "t" },
+            { "hash": "0x2", "from": 42, "value": "1", "timestamp": "t" },
+            "not a transaction",
+            { "hash": "0x3", "from": "0xa", "to": "0xb", "value": "2", "timestamp": "t" }
+        ]}))
+        .unwrap();
+        let (items, skipped) = parsed.transactions();
+        assert_eq!(items.iter().map(|t| t.hash.as_str()).collect::<Vec<_>>(), ["0x1", "0x3"]);
+        assert_eq!(skipped, 2);
+    }
 }
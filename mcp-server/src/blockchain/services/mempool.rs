@@ -0,0 +1,143 @@
+// src/blockchain/services/mempool.rs
+
+use crate::blockchain::services::rpc::{self, RpcTransport};
+use anyhow::{anyhow, Result};
+use ethers_core::types::U256;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracing::debug;
+
+/// A transaction from the address that the node holds but has not mined.
+#[derive(Debug, Serialize, PartialEq)]
+pub struct PendingTransaction {
+    pub hash: String,
+    pub nonce: u64,
+    pub to: Option<String>,
+    /// Decimal wei; `maxFeePerGas` for EIP-1559 transactions without a `gasPrice`.
+    pub gas_price: String,
+    pub value: String,
+    /// "pending" (executable) or "queued" (blocked on a nonce gap).
+    pub pool: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PendingReport {
+    /// "txpool" when the node exposed its pool, "nonce_gap" when only the count could be derived.
+    pub source: &'static str,
+    pub count: u64,
+    pub latest_nonce: u64,
+    pub pending_nonce: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transactions: Option<Vec<PendingTransaction>>,
+}
+
+fn hex_to_u256(v: &Value) -> Option<U256> {
+    U256::from_str_radix(v.as_str()?.trim_start_matches("0x"), 16).ok()
+}
+
+async fn nonce_at(transport: &dyn RpcTransport, rpc_url: &str, address: &str, tag: &str) -> Result<u64> {
+    let v = rpc::rpc_call(transport, rpc_url, "eth_getTransactionCount", json!([address, tag])).await?;
+    hex_to_u256(&v)
+        .map(|n| n.as_u64())
+        .ok_or_else(|| anyhow!("Invalid transaction count: {}", v))
+}
+
+/// Picks `address`'s entries out of a `txpool_content` result, ordered by nonce.
+/// Pool keys are checksummed, so the match is case-insensitive.
+pub fn pending_from_txpool(content: &Value, address: &str) -> Vec<PendingTransaction> {
+    let mut txs: Vec<PendingTransaction> = ["pending", "queued"]
+        .iter()
+        .filter_map(|pool| content.get(*pool)?.as_object().map(|senders| (*pool, senders)))
+        .flat_map(|(pool, senders)| {
+            senders
+                .iter()
+                .filter(|(sender, _)| sender.eq_ignore_ascii_case(address))
+                .flat_map(|(_, by_nonce)| by_nonce.as_object().into_iter().flat_map(|m| m.values()))
+                .map(move |tx| PendingTransaction {
+                    hash: tx["hash"].as_str().unwrap_or_default().to_string(),
+                    nonce: hex_to_u256(&tx["nonce"]).map(|n| n.as_u64()).unwrap_or_default(),
+                    to: tx["to"].as_str().map(str::to_string),
+                    gas_price: hex_to_u256(&tx["gasPrice"])
+                        .or_else(|| hex_to_u256(&tx["maxFeePerGas"]))
+                        .unwrap_or_default()
+                        .to_string(),
+                    value: hex_to_u256(&tx["value"]).unwrap_or_default().to_string(),
+                    pool: pool.to_string(),
+                })
+        })
+        .collect();
+    txs.sort_by_key(|tx| tx.nonce);
+    txs
+}
+
+/// Gas prices of every executable ("pending") transaction in a `txpool_content` result.
+pub fn pending_gas_prices(content: &Value) -> Vec<U256> {
+    content["pending"]
+        .as_object()
+        .into_iter()
+        .flat_map(|senders| senders.values())
+        .filter_map(|by_nonce| by_nonce.as_object())
+        .flat_map(|m| m.values())
+        .filter_map(|tx| hex_to_u256(&tx["gasPrice"]).or_else(|| hex_to_u256(&tx["maxFeePerGas"])))
+        .collect()
+}
+
+/// Reports `address`'s unmined transactions via `txpool_content`.
+/// Nodes without the txpool namespace still get a count from the latest/pending nonce gap.
+pub async fn get_pending_transactions(transport: &dyn RpcTransport, rpc_url: &str, address: &str) -> Result<PendingReport> {
+    let latest_nonce = nonce_at(transport, rpc_url, address, "latest").await?;
+    let pending_nonce = nonce_at(transport, rpc_url, address, "pending").await?;
+
+    match rpc::rpc_call(transport, rpc_url, "txpool_content", json!([])).await {
+        Ok(content) => {
+            let txs = pending_from_txpool(&content, address);
+            Ok(PendingReport {
+                source: "txpool",
+                count: txs.len() as u64,
+                latest_nonce,
+                pending_nonce,
+                transactions: Some(txs),
+            })
+        }
+        Err(e) => {
+            debug!("txpool_content unavailable, falling back to nonce gap: {}", e);
+            Ok(PendingReport {
+                source: "nonce_gap",
+                count: pending_nonce.saturating_sub(latest_nonce),
+                latest_nonce,
+                pending_nonce,
+                transactions: None,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pending_from_txpool_filters_and_orders() {
+        let content = json!({
+            "pending": {
+                "0xAbCdEf0000000000000000000000000000000001": {
+                    "8": {"hash": "0xb", "nonce": "0x8", "to": "0xdead", "gasPrice": "0x3b9aca00", "value": "0x0"}
+                },
+                "0x0000000000000000000000000000000000000002": {
+                    "1": {"hash": "0xother", "nonce": "0x1", "gasPrice": "0x1", "value": "0x1"}
+                }
+            },
+            "queued": {
+                "0xabcdef0000000000000000000000000000000001": {
+                    "10": {"hash": "0xc", "nonce": "0xa", "to": null, "maxFeePerGas": "0x77359400", "value": "0xde0b6b3a7640000"}
+                }
+            }
+        });
+        let txs = pending_from_txpool(&content, "0xabcdef0000000000000000000000000000000001");
+        assert_eq!(txs.len(), 2);
+        assert_eq!((txs[0].nonce, txs[0].pool.as_str(), txs[0].gas_price.as_str()), (8, "pending", "1000000000"));
+        assert_eq!(txs[1].to, None);
+        assert_eq!((txs[1].gas_price.as_str(), txs[1].value.as_str()), ("2000000000", "1000000000000000000"));
+        assert_eq!(txs[1].pool, "queued");
+    }
+}
@@ -1,11 +1,21 @@
+pub mod abi;
 pub mod balance;
+pub mod blocks;
 pub mod contract;
+pub mod erc20;
 pub mod event;
 pub mod faucet;
 pub mod fees;
 pub mod history;
+pub mod mempool;
+pub mod multicall;
+pub mod native_tx;
+pub mod nft;
 pub mod transactions;
 pub mod wallet;
 pub mod discord;
 pub mod docs;
 pub mod seistream;
+pub mod spam;
+pub mod trace;
+pub mod rpc;
@@ -0,0 +1,70 @@
+// src/blockchain/services/multicall.rs
+
+use crate::blockchain::services::rpc::{self, RpcTransport};
+use anyhow::{anyhow, Context, Result};
+use ethers_core::abi::{decode, encode, ParamType, Token};
+use ethers_core::types::Address;
+use ethers_core::utils::keccak256;
+use serde_json::json;
+
+// Batched read-only calls through Multicall3 (https://www.multicall3.com).
+
+/// Multicall3's address on every chain it is deployed to with the standard keyless deployment.
+pub const MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+
+fn aggregate3_calldata(calls: &[(Address, Vec<u8>)]) -> Vec<u8> {
+    let mut data = keccak256(b"aggregate3((address,bool,bytes)[])")[..4].to_vec();
+    let calls = calls
+        .iter()
+        .map(|(target, call_data)| Token::Tuple(vec![Token::Address(*target), Token::Bool(true), Token::Bytes(call_data.clone())]))
+        .collect();
+    data.extend(encode(&[Token::Array(calls)]));
+    data
+}
+
+fn decode_aggregate3(bytes: &[u8], expected: usize) -> Result<Vec<Option<Vec<u8>>>> {
+    let kind = ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Bool, ParamType::Bytes])));
+    let results = decode(&[kind], bytes)
+        .ok()
+        .and_then(|mut t| t.pop())
+        .and_then(Token::into_array)
+        .ok_or_else(|| anyhow!("Multicall3 returned an undecodable result"))?;
+    if results.len() != expected {
+        return Err(anyhow!("Multicall3 returned {} results for {} calls", results.len(), expected));
+    }
+    Ok(results
+        .into_iter()
+        .map(|r| match r.into_tuple().as_deref() {
+            Some([Token::Bool(true), Token::Bytes(data)]) => Some(data.clone()),
+            _ => None,
+        })
+        .collect())
+}
+
+/// Runs `calls` (target, calldata) in one `eth_call` to Multicall3's `aggregate3` with failures
+/// allowed; each entry is the call's return data, or `None` where that call reverted.
+pub async fn aggregate3(transport: &dyn RpcTransport, rpc_url: &str, multicall: &str, calls: &[(Address, Vec<u8>)]) -> Result<Vec<Option<Vec<u8>>>> {
+    let call = json!({ "to": multicall, "data": format!("0x{}", hex::encode(aggregate3_calldata(calls))) });
+    let out = rpc::eth_call(transport, rpc_url, call, "latest").await?;
+    let bytes = hex::decode(out.trim_start_matches("0x")).context("eth_call returned invalid hex")?;
+    decode_aggregate3(&bytes, calls.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aggregate3_round_trip() {
+        let calldata = aggregate3_calldata(&[(Address::zero(), vec![0x31, 0x3c, 0xe5, 0x67])]);
+        assert_eq!(hex::encode(&calldata[..4]), "82ad56cb");
+
+        let reply = encode(&[Token::Array(vec![
+            Token::Tuple(vec![Token::Bool(true), Token::Bytes(vec![1, 2])]),
+            Token::Tuple(vec![Token::Bool(false), Token::Bytes(vec![])]),
+        ])]);
+        assert_eq!(decode_aggregate3(&reply, 2).unwrap(), vec![Some(vec![1, 2]), None]);
+        assert!(decode_aggregate3(&reply, 3).is_err());
+        assert!(decode_aggregate3(&[], 1).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+// src/blockchain/services/native_tx.rs
+
+use crate::blockchain::models::SeiError;
+use crate::blockchain::services::rpc::{self, HttpClient};
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::STANDARD as BASE64STD;
+use base64::Engine;
+use serde_json::{json, Value};
+
+/// Looks up a native (Cosmos) transaction by hash and summarizes its result: code, gas and
+/// decoded events. Uses the Tendermint RPC `/tx` endpoint when `tendermint_url` is set,
+/// otherwise the REST gateway's `/cosmos/tx/v1beta1/txs/{hash}`.
+pub async fn get_native_tx(client: &HttpClient, tendermint_url: Option<&str>, rest_url: &str, hash: &str) -> Result<Value> {
+    let hash = hash.trim().trim_start_matches("0x").to_uppercase();
+    if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(SeiError::InvalidInput(format!("'{}' is not a 32-byte hex transaction hash", hash)).into());
+    }
+    let not_found = || anyhow::Error::from(SeiError::NotFound(format!("Transaction {} not found", hash)));
+    match tendermint_url {
+        Some(url) => {
+            let url = format!("{}/tx", url.trim_end_matches('/'));
+            let res: Value = rpc::read_json(client.get(&url).query(&[("hash", format!("0x{}", hash))]).send().await?).await?;
+            if let Some(err) = res.get("error") {
+                let detail = format!("{} {}", err["message"].as_str().unwrap_or_default(), err["data"].as_str().unwrap_or_default());
+                if detail.contains("not found") {
+                    return Err(not_found());
+                }
+                return Err(SeiError::Rpc(format!("Tendermint /tx error: {}", err)).into());
+            }
+            let result = &res["result"];
+            let tx_result = &result["tx_result"];
+            Ok(summarize(&hash, &result["height"], tx_result, &tx_result["log"]))
+        }
+        None => {
+            let url = format!("{}/cosmos/tx/v1beta1/txs/{}", rest_url.trim_end_matches('/'), hash);
+            let res: Value = rpc::read_json(client.get(&url).send().await?).await?;
+            let Some(tx_response) = res.get("tx_response") else {
+                let message = res["message"].as_str().unwrap_or_default();
+                if message.contains("not found") {
+                    return Err(not_found());
+                }
+                return Err(anyhow!("Unexpected tx query response: {}", res));
+            };
+            Ok(summarize(&hash, &tx_response["height"], tx_response, &tx_response["raw_log"]))
+        }
+    }
+}
+
+// Shared shape for both sources: Tendermint's `tx_result` and the gateway's `tx_response` agree on these fields.
+fn summarize(hash: &str, height: &Value, result: &Value, log: &Value) -> Value {
+    let number = |v: &Value| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok()));
+    let code = number(&result["code"]).unwrap_or(0);
+    let codespace = result["codespace"].as_str().filter(|s| !s.is_empty());
+    json!({
+        "hash": hash,
+        "height": number(height),
+        "code": code,
+        "success": code == 0,
+        "codespace": codespace,
+        "log": log.as_str().filter(|s| !s.is_empty()),
+        "gas_wanted": number(&result["gas_wanted"]),
+        "gas_used": number(&result["gas_used"]),
+        "events": decode_events(&result["events"]),
+    })
+}
+
+/// Flattens ABCI events into `{type, attributes: [{key, value}]}`. Tendermint before 0.37
+/// base64-encodes attribute keys and values; when every key decodes to an identifier-like
+/// string the node is taken to be encoding, and values are decoded too.
+pub fn decode_events(events: &Value) -> Vec<Value> {
+    let events = events.as_array().map(Vec::as_slice).unwrap_or_default();
+    let attributes = |e: &Value| e["attributes"].as_array().cloned().unwrap_or_default();
+    let encoded = events
+        .iter()
+        .flat_map(attributes)
+        .all(|a| a["key"].as_str().and_then(decode_base64).is_some_and(|k| looks_like_key(&k)))
+        && events.iter().any(|e| !attributes(e).is_empty());
+    events
+        .iter()
+        .map(|event| {
+            let attrs: Vec<Value> = attributes(event)
+                .iter()
+                .map(|a| {
+                    let field = |name: &str| {
+                        let raw = a[name].as_str().unwrap_or_default();
+                        if encoded {
+                            decode_base64(raw).unwrap_or_else(|| raw.to_string())
+                        } else {
+                            raw.to_string()
+                        }
+                    };
+                    json!({ "key": field("key"), "value": field("value") })
+                })
+                .collect();
+            json!({ "type": event["type"], "attributes": attrs })
+        })
+        .collect()
+}
+
+fn decode_base64(s: &str) -> Option<String> {
+    BASE64STD.decode(s).ok().and_then(|bytes| String::from_utf8(bytes).ok())
+}
+
+fn looks_like_key(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64_attributes_are_decoded() {
+        let events = json!([{ "type": "transfer", "attributes": [
+            { "key": "cmVjaXBpZW50", "value": "c2VpMXJlY2lwaWVudA==", "index": true },
+            { "key": "YW1vdW50", "value": "MTAwdXNlaQ==" }
+        ]}]);
+        let decoded = decode_events(&events);
+        assert_eq!(decoded[0]["type"], "transfer");
+        assert_eq!(decoded[0]["attributes"][0], json!({ "key": "recipient", "value": "sei1recipient" }));
+        assert_eq!(decoded[0]["attributes"][1]["value"], "100usei");
+    }
+
+    #[test]
+    fn test_plain_attributes_are_kept() {
+        // "coin" is valid base64 but decodes to bytes that are not UTF-8
+        let events = json!([{ "type": "coin_received", "attributes": [
+            { "key": "receiver", "value": "sei1abc" },
+            { "key": "coin", "value": "5usei" }
+        ]}]);
+        let decoded = decode_events(&events);
+        assert_eq!(decoded[0]["attributes"][0], json!({ "key": "receiver", "value": "sei1abc" }));
+        assert_eq!(decoded[0]["attributes"][1]["value"], "5usei");
+    }
+}
@@ -0,0 +1,122 @@
+// src/blockchain/services/nft.rs
+
+use crate::blockchain::services::rpc::{self, RpcTransport};
+use anyhow::{anyhow, Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64STD;
+use base64::Engine;
+use ethers_core::abi::{decode, encode, ParamType, Token};
+use ethers_core::types::U256;
+use ethers_core::utils::keccak256;
+use reqwest::Client;
+use serde_json::{json, Value};
+
+// Used when IPFS_GATEWAY is unset or empty
+pub const DEFAULT_IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+
+async fn call_string(transport: &dyn RpcTransport, rpc_url: &str, contract: &str, signature: &str, token_id: U256) -> Result<String> {
+    let mut data = keccak256(signature.as_bytes())[..4].to_vec();
+    data.extend(encode(&[Token::Uint(token_id)]));
+    let call = json!({ "to": contract, "data": format!("0x{}", hex::encode(data)) });
+    let out = rpc::eth_call(transport, rpc_url, call, "latest").await?;
+    let bytes = hex::decode(out.trim_start_matches("0x")).context("eth_call returned invalid hex")?;
+    match decode(&[ParamType::String], &bytes)?.pop() {
+        Some(Token::String(s)) if !s.is_empty() => Ok(s),
+        _ => Err(anyhow!("{} returned no URI", signature)),
+    }
+}
+
+/// Reads the metadata URI for `token_id`, trying ERC-721 `tokenURI` then ERC-1155 `uri`.
+/// Returns the URI (with any ERC-1155 `{id}` placeholder filled) and the standard that answered.
+pub async fn token_uri(transport: &dyn RpcTransport, rpc_url: &str, contract: &str, token_id: U256) -> Result<(String, &'static str)> {
+    match call_string(transport, rpc_url, contract, "tokenURI(uint256)", token_id).await {
+        Ok(uri) => Ok((uri, "ERC-721")),
+        Err(erc721_err) => call_string(transport, rpc_url, contract, "uri(uint256)", token_id)
+            .await
+            .map(|uri| (substitute_id(&uri, token_id), "ERC-1155"))
+            .map_err(|_| anyhow!("Contract {} has neither tokenURI nor uri for token {}: {}", contract, token_id, erc721_err)),
+    }
+}
+
+/// ERC-1155 clients replace `{id}` with the token id as 64 lowercase hex digits.
+pub fn substitute_id(uri: &str, token_id: U256) -> String {
+    uri.replace("{id}", &format!("{:064x}", token_id))
+}
+
+/// Maps `ipfs://` URIs onto an HTTP gateway; other URIs are returned unchanged.
+pub fn resolve_uri(uri: &str, gateway: &str) -> String {
+    let Some(path) = uri.strip_prefix("ipfs://") else {
+        return uri.to_string();
+    };
+    let gateway = if gateway.is_empty() { DEFAULT_IPFS_GATEWAY } else { gateway };
+    format!("{}/{}", gateway.trim_end_matches('/'), path.trim_start_matches("ipfs/"))
+}
+
+/// Decodes on-chain `data:` metadata (base64 or plain JSON); `None` when `uri` isn't a data URI.
+pub fn decode_data_uri(uri: &str) -> Option<Result<Value>> {
+    let rest = uri.strip_prefix("data:")?;
+    let (header, payload) = match rest.split_once(',') {
+        Some(parts) => parts,
+        None => return Some(Err(anyhow!("Malformed data URI"))),
+    };
+    let body = if header.ends_with(";base64") {
+        match BASE64STD.decode(payload) {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Err(e) => return Some(Err(anyhow!("Invalid base64 in data URI: {}", e))),
+        }
+    } else {
+        payload.to_string()
+    };
+    Some(serde_json::from_str(&body).context("Data URI does not contain JSON metadata"))
+}
+
+/// Loads the metadata JSON behind `uri`, decoding data URIs locally and fetching anything else.
+pub async fn fetch_metadata(client: &Client, uri: &str, gateway: &str) -> Result<Value> {
+    if let Some(decoded) = decode_data_uri(uri) {
+        return decoded;
+    }
+    let url = resolve_uri(uri, gateway);
+    rpc::read_json(client.get(&url).send().await?).await
+}
+
+/// The image reference from metadata (`image`, or the `image_url` some collections use), gateway-resolved.
+pub fn image_url(metadata: &Value, gateway: &str) -> Option<String> {
+    ["image", "image_url"]
+        .iter()
+        .find_map(|key| metadata.get(*key)?.as_str().filter(|s| !s.is_empty()))
+        .map(|img| resolve_uri(img, gateway))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_uri() {
+        let gw = "https://gateway.example/ipfs/";
+        assert_eq!(resolve_uri("ipfs://QmHash/1.json", gw), "https://gateway.example/ipfs/QmHash/1.json");
+        assert_eq!(resolve_uri("ipfs://ipfs/QmHash", gw), "https://gateway.example/ipfs/QmHash");
+        assert_eq!(resolve_uri("ipfs://QmHash", ""), "https://ipfs.io/ipfs/QmHash");
+        assert_eq!(resolve_uri("https://x.test/1", gw), "https://x.test/1");
+    }
+
+    #[test]
+    fn test_substitute_id() {
+        assert_eq!(
+            substitute_id("https://x.test/{id}.json", U256::from(314)),
+            "https://x.test/000000000000000000000000000000000000000000000000000000000000013a.json"
+        );
+    }
+
+    #[test]
+    fn test_data_uri_metadata() {
+        let b64 = format!("data:application/json;base64,{}", BASE64STD.encode(r#"{"name":"A","image":"ipfs://QmImg"}"#));
+        let meta = decode_data_uri(&b64).unwrap().unwrap();
+        assert_eq!(meta["name"], "A");
+        assert_eq!(image_url(&meta, "").as_deref(), Some("https://ipfs.io/ipfs/QmImg"));
+
+        let plain = decode_data_uri(r#"data:application/json;utf8,{"name":"B"}"#).unwrap().unwrap();
+        assert_eq!(plain["name"], "B");
+        assert!(decode_data_uri("https://x.test").is_none());
+        assert!(decode_data_uri("data:application/json;base64,!!").unwrap().is_err());
+    }
+}
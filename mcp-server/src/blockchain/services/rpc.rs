@@ -0,0 +1,474 @@
+// src/blockchain/services/rpc.rs
+
+use crate::config::Config;
+use crate::utils::{poll_until, PollSettings};
+use anyhow::{anyhow, Result};
+use futures::future::BoxFuture;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use reqwest::{Client, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::info;
+
+// How much of a non-JSON body to quote back in errors.
+const BODY_SNIPPET_BYTES: usize = 200;
+
+/// RPC_HEADERS by endpoint base URL; every request to that URL or a path under it carries them.
+#[derive(Debug, Clone, Default)]
+pub struct EndpointHeaders {
+    by_base_url: HashMap<String, HeaderMap>,
+}
+
+impl EndpointHeaders {
+    /// Each chain's RPC_HEADERS for its CHAIN_RPC_URLS, RPC_FALLBACK_URLS and TENDERMINT_RPC_URLS endpoints.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let mut registry = Self::default();
+        for (chain_id, headers) in &config.rpc_headers {
+            let urls = [config.chain_rpc_urls.get(chain_id), config.tendermint_rpc_urls.get(chain_id)]
+                .into_iter()
+                .flatten()
+                .chain(config.rpc_fallback_urls.get(chain_id).into_iter().flatten());
+            for url in urls {
+                registry.register(url, &headers.0)?;
+            }
+            info!(chain_id = %chain_id, headers = ?headers, "RPC headers configured");
+        }
+        Ok(registry)
+    }
+
+    /// Registers `headers` for requests to `base_url` and any path below it. Values are marked
+    /// sensitive so they print as redacted in request debug output.
+    pub fn register(&mut self, base_url: &str, headers: &HashMap<String, String>) -> Result<()> {
+        let mut map = HeaderMap::new();
+        for (name, value) in headers {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(|_| anyhow!("Invalid header name '{}'", name))?;
+            let mut value = HeaderValue::from_str(value).map_err(|_| anyhow!("Invalid value for header '{}'", name))?;
+            value.set_sensitive(true);
+            map.insert(name, value);
+        }
+        self.by_base_url.insert(base_url.trim_end_matches('/').to_string(), map);
+        Ok(())
+    }
+
+    /// Headers registered for the endpoint `url` belongs to (longest matching base URL); empty when none.
+    pub fn for_url(&self, url: &str) -> HeaderMap {
+        self.by_base_url
+            .iter()
+            .filter(|(base, _)| url.strip_prefix(base.as_str()).is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '?'])))
+            .max_by_key(|(base, _)| base.len())
+            .map(|(_, headers)| headers.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// A `reqwest::Client` that adds the configured RPC_HEADERS to requests for the endpoints they belong to.
+#[derive(Clone, Default)]
+pub struct HttpClient {
+    client: Client,
+    headers: Arc<EndpointHeaders>,
+}
+
+impl HttpClient {
+    pub fn new(client: Client, headers: EndpointHeaders) -> Self {
+        Self { client, headers: Arc::new(headers) }
+    }
+
+    pub fn get(&self, url: &str) -> RequestBuilder {
+        self.client.get(url).headers(self.headers.for_url(url))
+    }
+
+    pub fn post(&self, url: &str) -> RequestBuilder {
+        self.client.post(url).headers(self.headers.for_url(url))
+    }
+
+    /// The underlying client, for third-party APIs that get no RPC_HEADERS.
+    pub fn inner(&self) -> &Client {
+        &self.client
+    }
+}
+
+impl From<Client> for HttpClient {
+    fn from(client: Client) -> Self {
+        Self { client, headers: Arc::default() }
+    }
+}
+
+/// Carries JSON-RPC requests to a node. `call` returns the whole response object, so an
+/// `error` member reaches the caller for classification instead of becoming a transport error.
+/// `HttpClient` and a bare `reqwest::Client` are the real implementations; tests implement it with canned replies.
+pub trait RpcTransport: Send + Sync {
+    fn call<'a>(&'a self, url: &'a str, method: &'a str, params: Value) -> BoxFuture<'a, Result<Value>>;
+}
+
+fn post_rpc(request: RequestBuilder, method: &str, params: Value) -> BoxFuture<'static, Result<Value>> {
+    let payload = json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": params,
+        "id": 1
+    });
+    Box::pin(async move { read_json(request.json(&payload).send().await?).await })
+}
+
+impl RpcTransport for Client {
+    fn call<'a>(&'a self, url: &'a str, method: &'a str, params: Value) -> BoxFuture<'a, Result<Value>> {
+        post_rpc(self.post(url), method, params)
+    }
+}
+
+impl RpcTransport for HttpClient {
+    fn call<'a>(&'a self, url: &'a str, method: &'a str, params: Value) -> BoxFuture<'a, Result<Value>> {
+        post_rpc(self.post(url), method, params)
+    }
+}
+
+/// Sends a single JSON-RPC request and returns its `result`, surfacing RPC-level errors.
+pub async fn rpc_call(transport: &dyn RpcTransport, rpc_url: &str, method: &str, params: Value) -> Result<Value> {
+    let resp = transport.call(rpc_url, method, params).await?;
+    if let Some(err) = resp.get("error") {
+        return Err(anyhow!("RPC Error calling {}: {}", method, err));
+    }
+    resp.get("result")
+        .cloned()
+        .ok_or_else(|| anyhow!("RPC response for {} missing 'result' field: {:?}", method, resp))
+}
+
+/// Executes `eth_call` for `call_obj` at `block` and returns the raw hex output.
+pub async fn eth_call(transport: &dyn RpcTransport, rpc_url: &str, call_obj: Value, block: &str) -> Result<String> {
+    let result = rpc_call(transport, rpc_url, "eth_call", json!([call_obj, block])).await?;
+    result
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow!("eth_call returned a non-string result: {}", result))
+}
+
+/// `eth_call` with an optional geth-style state-override set as the third parameter
+/// (`{address: {balance, nonce, code, state, stateDiff}}`). Not every node accepts it.
+pub async fn eth_call_with_overrides(
+    transport: &dyn RpcTransport,
+    rpc_url: &str,
+    call_obj: Value,
+    block: &str,
+    overrides: Option<&Value>,
+) -> Result<String> {
+    let params = match overrides {
+        Some(o) => json!([call_obj, block, o]),
+        None => json!([call_obj, block]),
+    };
+    let result = rpc_call(transport, rpc_url, "eth_call", params).await?;
+    result
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow!("eth_call returned a non-string result: {}", result))
+}
+
+/// Heuristic for "the node rejected the state-override parameter" as opposed to the call reverting.
+pub fn is_override_unsupported(err: &str) -> bool {
+    let e = err.to_lowercase();
+    e.contains("-32602")
+        || e.contains("too many arguments")
+        || e.contains("invalid argument 2")
+        || e.contains("not supported")
+        || e.contains("unsupported")
+}
+
+/// Runs `eth_getLogs` for `filter` and returns the log objects.
+pub async fn get_logs(transport: &dyn RpcTransport, rpc_url: &str, filter: Value) -> Result<Vec<Value>> {
+    let result = rpc_call(transport, rpc_url, "eth_getLogs", json!([filter])).await?;
+    match result {
+        Value::Array(logs) => Ok(logs),
+        other => Err(anyhow!("eth_getLogs returned a non-array result: {}", other)),
+    }
+}
+
+/// `eth_getLogs` that survives per-request limits on public nodes: a range the node rejects as too
+/// wide (or as matching too many logs) is halved and each half retried, down to single blocks.
+/// With `chunk_size` set the range is queried in chunks of that many blocks from the start.
+/// Logs come back in block order either way.
+pub async fn get_logs_chunked(transport: &dyn RpcTransport, rpc_url: &str, filter: Value, chunk_size: Option<u64>) -> Result<Vec<Value>> {
+    // blockHash filters name a single block; nothing to split
+    if filter.get("blockHash").is_some() {
+        return get_logs(transport, rpc_url, filter).await;
+    }
+    if chunk_size.is_none() {
+        match get_logs(transport, rpc_url, filter.clone()).await {
+            Err(e) if is_range_limit_error(&e.to_string()) => {}
+            result => return result,
+        }
+    }
+    let (from, to) = resolve_log_range(transport, rpc_url, &filter).await?;
+    let step = chunk_size.filter(|c| *c > 0).unwrap_or(to.saturating_sub(from) + 1);
+    let mut logs = Vec::new();
+    let mut start = from;
+    while start <= to {
+        let end = start.saturating_add(step - 1).min(to);
+        logs.extend(get_logs_split(transport, rpc_url, &filter, start, end).await?);
+        if end == u64::MAX {
+            break;
+        }
+        start = end + 1;
+    }
+    Ok(logs)
+}
+
+fn get_logs_split<'a>(transport: &'a dyn RpcTransport, rpc_url: &'a str, filter: &'a Value, from: u64, to: u64) -> BoxFuture<'a, Result<Vec<Value>>> {
+    Box::pin(async move {
+        let mut ranged = filter.clone();
+        ranged["fromBlock"] = json!(format!("0x{:x}", from));
+        ranged["toBlock"] = json!(format!("0x{:x}", to));
+        match get_logs(transport, rpc_url, ranged).await {
+            Err(e) if from < to && is_range_limit_error(&e.to_string()) => {
+                let mid = from + (to - from) / 2;
+                let mut logs = get_logs_split(transport, rpc_url, filter, from, mid).await?;
+                logs.extend(get_logs_split(transport, rpc_url, filter, mid + 1, to).await?);
+                Ok(logs)
+            }
+            result => result,
+        }
+    })
+}
+
+// Numeric bounds for the filter's fromBlock/toBlock; tags other than "earliest" mean the head.
+async fn resolve_log_range(transport: &dyn RpcTransport, rpc_url: &str, filter: &Value) -> Result<(u64, u64)> {
+    let bound = |field: &str| -> Result<Option<u64>> {
+        match filter.get(field).and_then(Value::as_str).unwrap_or("latest") {
+            "earliest" => Ok(Some(0)),
+            t if t.starts_with("0x") => u64::from_str_radix(&t[2..], 16)
+                .map(Some)
+                .map_err(|_| anyhow!("Invalid {} '{}'", field, t)),
+            _ => Ok(None),
+        }
+    };
+    let (from, to) = (bound("fromBlock")?, bound("toBlock")?);
+    let head = if from.is_none() || to.is_none() {
+        let latest = rpc_call(transport, rpc_url, "eth_blockNumber", json!([])).await?;
+        let latest = latest.as_str().ok_or_else(|| anyhow!("eth_blockNumber returned {}", latest))?;
+        Some(u64::from_str_radix(latest.trim_start_matches("0x"), 16)?)
+    } else {
+        None
+    };
+    let (from, to) = (from.or(head).unwrap_or_default(), to.or(head).unwrap_or_default());
+    if from > to {
+        return Err(anyhow!("fromBlock {} is after toBlock {}", from, to));
+    }
+    Ok((from, to))
+}
+
+/// Whether an `eth_getLogs` failure is the node's range or result-count limit rather than a bad filter.
+pub fn is_range_limit_error(err: &str) -> bool {
+    let e = err.to_lowercase();
+    [
+        "query returned more than",
+        "block range",
+        "range too large",
+        "range is too large",
+        "too many blocks",
+        "exceed maximum",
+        "exceeds the limit",
+        "limit exceeded",
+        "response size exceeded",
+        "too many results",
+    ]
+    .iter()
+    .any(|needle| e.contains(needle))
+}
+
+/// The node's EIP-155 chain id (`eth_chainId`).
+pub async fn chain_id(transport: &dyn RpcTransport, rpc_url: &str) -> Result<u64> {
+    let result = rpc_call(transport, rpc_url, "eth_chainId", json!([])).await?;
+    result
+        .as_str()
+        .and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok())
+        .ok_or_else(|| anyhow!("eth_chainId returned an invalid result: {}", result))
+}
+
+/// Reads a raw 32-byte storage word via `eth_getStorageAt`.
+pub async fn get_storage_at(transport: &dyn RpcTransport, rpc_url: &str, address: &str, slot: &str, block: &str) -> Result<String> {
+    let result = rpc_call(transport, rpc_url, "eth_getStorageAt", json!([address, slot, block])).await?;
+    result
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| anyhow!("eth_getStorageAt returned a non-string result: {}", result))
+}
+
+/// Polls `eth_getTransactionReceipt` until the transaction is mined or `poll` runs out.
+/// Returns `None` on timeout; the transaction may still be pending.
+pub async fn wait_for_receipt(transport: &dyn RpcTransport, rpc_url: &str, tx_hash: &str, poll: PollSettings) -> Result<Option<Value>> {
+    poll_until(poll, || async {
+        let receipt = rpc_call(transport, rpc_url, "eth_getTransactionReceipt", json!([tx_hash])).await?;
+        Ok((!receipt.is_null()).then_some(receipt))
+    })
+    .await
+}
+
+/// Re-runs a mined transaction as an `eth_call` at its block to recover why it reverted.
+/// Returns the node's error object (`message`, usually `data`), or `None` if the replay succeeds.
+pub async fn replay_transaction(transport: &dyn RpcTransport, rpc_url: &str, tx_hash: &str) -> Result<Option<Value>> {
+    let tx = rpc_call(transport, rpc_url, "eth_getTransactionByHash", json!([tx_hash])).await?;
+    if tx.is_null() {
+        return Err(anyhow!("Transaction {} not found", tx_hash));
+    }
+    let mut call = json!({ "from": tx["from"], "data": tx["input"], "value": tx["value"], "gas": tx["gas"] });
+    if !tx["to"].is_null() {
+        call["to"] = tx["to"].clone();
+    }
+    let block = tx["blockNumber"].as_str().unwrap_or("latest");
+    call_error(transport, rpc_url, call, block).await
+}
+
+/// Runs `call` through `eth_call` at `block` and returns the node's JSON-RPC error, or `None`
+/// when the call succeeds.
+pub async fn call_error(transport: &dyn RpcTransport, rpc_url: &str, call: Value, block: &str) -> Result<Option<Value>> {
+    let resp = transport.call(rpc_url, "eth_call", json!([call, block])).await?;
+    Ok(resp.get("error").cloned())
+}
+
+/// Raw revert bytes from an `eth_call` error; nodes put them in `data` as hex or as `{data: hex}`.
+pub fn revert_data(error: &Value) -> Option<Vec<u8>> {
+    let data = error.get("data")?;
+    let hex_str = data.as_str().or_else(|| data.get("data").and_then(Value::as_str))?;
+    hex::decode(hex_str.trim_start_matches("0x")).ok().filter(|d| !d.is_empty())
+}
+
+/// Reads a response body as JSON. A non-JSON body (typically an HTML page from a misconfigured
+/// URL) becomes a readable error quoting the start of the body instead of a bare serde error.
+pub async fn read_json<T: DeserializeOwned>(resp: reqwest::Response) -> Result<T> {
+    let status = resp.status().as_u16();
+    let body = resp.text().await?;
+    parse_json_body(&body, status)
+}
+
+/// Parses `body` as JSON into `T`, distinguishing "not JSON at all" from "JSON of the wrong shape".
+pub fn parse_json_body<T: DeserializeOwned>(body: &str, status: u16) -> Result<T> {
+    let value: Value = serde_json::from_str(body).map_err(|_| {
+        anyhow!(
+            "RPC endpoint returned non-JSON response (is the URL correct?) [HTTP {}]: {}",
+            status,
+            body_snippet(body)
+        )
+    })?;
+    serde_json::from_value(value)
+        .map_err(|e| anyhow!("Unexpected JSON response shape [HTTP {}]: {}. Body: {}", status, e, body_snippet(body)))
+}
+
+fn body_snippet(body: &str) -> String {
+    let trimmed = body.trim();
+    if trimmed.len() <= BODY_SNIPPET_BYTES {
+        return trimmed.to_string();
+    }
+    let mut end = BODY_SNIPPET_BYTES;
+    while !trimmed.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…", &trimmed[..end])
+}
+
+/// Transport answering every request with `(self.0)(method, params)`, for unit tests.
+#[cfg(test)]
+pub(crate) struct FnTransport<F>(pub F);
+
+#[cfg(test)]
+impl<F: Fn(&str, &Value) -> Value + Send + Sync> RpcTransport for FnTransport<F> {
+    fn call<'a>(&'a self, _url: &'a str, method: &'a str, params: Value) -> BoxFuture<'a, Result<Value>> {
+        let reply = (self.0)(method, &params);
+        Box::pin(async move { Ok(reply) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Node at block 299 with one log per block that rejects ranges wider than 64 blocks.
+    fn range_limited_node(calls: &std::sync::Mutex<Vec<(u64, u64)>>) -> impl Fn(&str, &Value) -> Value + '_ {
+        move |method: &str, params: &Value| {
+            let block = |v: &Value| u64::from_str_radix(v.as_str().unwrap().trim_start_matches("0x"), 16).unwrap();
+            match method {
+                "eth_blockNumber" => json!({ "jsonrpc": "2.0", "id": 1, "result": "0x12b" }),
+                _ => {
+                    let f = &params[0];
+                    if !f["fromBlock"].as_str().unwrap().starts_with("0x") {
+                        return json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32005, "message": "query returned more than 10000 results" } });
+                    }
+                    let (from, to) = (block(&f["fromBlock"]), block(&f["toBlock"]));
+                    calls.lock().unwrap().push((from, to));
+                    if to - from >= 64 {
+                        return json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32000, "message": "block range too wide" } });
+                    }
+                    let logs: Vec<Value> = (from..=to).map(|n| json!({ "blockNumber": format!("0x{:x}", n) })).collect();
+                    json!({ "jsonrpc": "2.0", "id": 1, "result": logs })
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rejected_log_range_is_split() {
+        let calls = std::sync::Mutex::new(Vec::new());
+        let transport = FnTransport(range_limited_node(&calls));
+        let logs = get_logs_chunked(&transport, "mock", json!({ "fromBlock": "earliest", "toBlock": "latest" }), None).await.unwrap();
+        let blocks: Vec<&str> = logs.iter().map(|l| l["blockNumber"].as_str().unwrap()).collect();
+        assert_eq!(blocks.len(), 300);
+        assert_eq!((blocks[0], blocks[299]), ("0x0", "0x12b"));
+        assert!(blocks.windows(2).all(|w| u64::from_str_radix(&w[0][2..], 16).unwrap() < u64::from_str_radix(&w[1][2..], 16).unwrap()));
+        assert_eq!(calls.lock().unwrap()[0], (0, 299));
+    }
+
+    #[tokio::test]
+    async fn test_configured_chunk_size_skips_the_wide_query() {
+        let calls = std::sync::Mutex::new(Vec::new());
+        let transport = FnTransport(range_limited_node(&calls));
+        let logs = get_logs_chunked(&transport, "mock", json!({ "fromBlock": "0x0", "toBlock": "0x63" }), Some(50)).await.unwrap();
+        assert_eq!(logs.len(), 100);
+        assert_eq!(*calls.lock().unwrap(), [(0, 49), (50, 99)]);
+    }
+
+    #[tokio::test]
+    async fn test_other_log_errors_are_not_split() {
+        let transport = FnTransport(|_: &str, _: &Value| json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32602, "message": "invalid topic" } }));
+        let err = get_logs_chunked(&transport, "mock", json!({ "fromBlock": "0x0", "toBlock": "0x10" }), None).await.unwrap_err();
+        assert!(err.to_string().contains("invalid topic"));
+    }
+
+    #[tokio::test]
+    async fn test_rpc_call_surfaces_error_object() {
+        let transport = FnTransport(|method: &str, _: &Value| match method {
+            "eth_blockNumber" => json!({ "jsonrpc": "2.0", "id": 1, "result": "0x10" }),
+            _ => json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32601, "message": "method not found" } }),
+        });
+        assert_eq!(rpc_call(&transport, "mock", "eth_blockNumber", json!([])).await.unwrap(), "0x10");
+        let err = rpc_call(&transport, "mock", "eth_foo", json!([])).await.unwrap_err().to_string();
+        assert!(err.contains("eth_foo") && err.contains("method not found"), "{}", err);
+    }
+
+    #[test]
+    fn test_html_body_yields_readable_error() {
+        let html = format!("<!DOCTYPE html><html><body>{}</body></html>", "x".repeat(500));
+        let err = parse_json_body::<Value>(&html, 200).unwrap_err().to_string();
+        assert!(err.contains("non-JSON response (is the URL correct?)"), "{}", err);
+        assert!(err.contains("<!DOCTYPE html>"));
+        assert!(err.len() < 400, "body should be truncated: {}", err.len());
+    }
+
+    #[test]
+    fn test_json_body_parses() {
+        let v: Value = parse_json_body(r#"{"result":"0x1"}"#, 200).unwrap();
+        assert_eq!(v["result"], "0x1");
+        let err = parse_json_body::<Vec<u8>>(r#"{"result":"0x1"}"#, 200).unwrap_err().to_string();
+        assert!(err.starts_with("Unexpected JSON response shape"));
+    }
+
+    #[test]
+    fn test_endpoint_headers_match_base_url_and_paths_below_it() {
+        let headers: HashMap<String, String> = [("x-api-key".to_string(), "secret".to_string())].into();
+        let mut registry = EndpointHeaders::default();
+        registry.register("http://node.test:8545/", &headers).unwrap();
+        assert_eq!(registry.for_url("http://node.test:8545")["x-api-key"], "secret");
+        assert_eq!(registry.for_url("http://node.test:8545/cosmos/tx/v1beta1/txs").len(), 1);
+        assert!(registry.for_url("http://node.test:85450").is_empty(), "a longer port is a different endpoint");
+        assert!(registry.for_url("http://other.test").is_empty());
+        assert!(format!("{:?}", registry.for_url("http://node.test:8545")).contains("Sensitive"));
+    }
+}
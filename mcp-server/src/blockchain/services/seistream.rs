@@ -1,5 +1,7 @@
 // src/blockchain/services/seistream.rs
 
+use crate::blockchain::services::contract;
+use crate::utils::Pagination;
 use anyhow::Result;
 use reqwest::Client;
 use serde_json::Value;
@@ -24,9 +26,9 @@ pub async fn get_transaction_info(client: &Client, tx_hash: &str) -> Result<Valu
         .unwrap_or_else(|_| serde_json::json!({"status": status.as_u16(), "raw": body})))
 }
 
-pub async fn get_transaction_history(client: &Client, address: &str, page: Option<u64>) -> Result<Value> {
+pub async fn get_transaction_history(client: &Client, address: &str, page: Option<u64>, page_size: Option<u64>) -> Result<Value> {
     let mut url = format!("{}/accounts/evm/{}/transactions", BASE, address);
-    if let Some(p) = page { url.push_str(&format!("?page={}", p)); }
+    url.push_str(&page_query(page, page_size));
     let res = client.get(&url).send().await?;
     let status = res.status();
     let body = res.text().await.unwrap_or_default();
@@ -34,12 +36,54 @@ pub async fn get_transaction_history(client: &Client, address: &str, page: Optio
         .unwrap_or_else(|_| serde_json::json!({"status": status.as_u16(), "raw": body})))
 }
 
-pub async fn get_nft_metadata_erc721_items(client: &Client, contract: &str, page: Option<u64>) -> Result<Value> {
+pub async fn get_nft_metadata_erc721_items(client: &Client, contract: &str, page: Option<u64>, page_size: Option<u64>) -> Result<Value> {
     let mut url = format!("{}/tokens/evm/erc721/{}/items", BASE, contract);
-    if let Some(p) = page { url.push_str(&format!("?page={}", p)); }
+    url.push_str(&page_query(page, page_size));
     let res = client.get(&url).send().await?;
     let status = res.status();
     let body = res.text().await.unwrap_or_default();
     Ok(serde_json::from_str::<Value>(&body)
         .unwrap_or_else(|_| serde_json::json!({"status": status.as_u16(), "raw": body})))
 }
+
+// `?page=..&limit=..` for the explorer's paged listings, or nothing when neither is set
+fn page_query(page: Option<u64>, page_size: Option<u64>) -> String {
+    let mut query = Vec::new();
+    if let Some(p) = page { query.push(format!("page={}", p)); }
+    if let Some(n) = page_size { query.push(format!("limit={}", n)); }
+    if query.is_empty() { String::new() } else { format!("?{}", query.join("&")) }
+}
+
+/// `Pagination` for a paged explorer listing: the total (`pagination.rows`) and next page come from
+/// the explorer when it reports them; otherwise more pages are assumed while pages come back full.
+pub fn listing_pagination(listing: &Value, page: u64, page_size: u64) -> Pagination {
+    let block = listing.get("pagination");
+    let total = block
+        .and_then(|p| p.get("rows").or_else(|| p.get("total")))
+        .or_else(|| listing.get("total"))
+        .and_then(Value::as_u64);
+    let next_page = contract::next_page(listing, page, page_size);
+    Pagination { page, page_size, total, has_more: next_page.is_some(), next_page }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_listing_pagination() {
+        let reported = json!({ "items": [1, 2], "pagination": { "pages": 3, "rows": 6, "currPage": 1, "nextPage": 2 } });
+        let p = listing_pagination(&reported, 1, 2);
+        assert_eq!(p, Pagination { page: 1, page_size: 2, total: Some(6), has_more: true, next_page: Some(2) });
+
+        let last = json!({ "items": [5, 6], "pagination": { "pages": 3, "rows": 6, "currPage": 3, "nextPage": null } });
+        assert_eq!(listing_pagination(&last, 3, 2).next_page, None);
+
+        // No pagination block: a short page is the last one
+        let p = listing_pagination(&json!({ "items": [1] }), 4, 2);
+        assert_eq!((p.total, p.has_more), (None, false));
+        assert_eq!(page_query(Some(2), Some(25)), "?page=2&limit=25");
+        assert_eq!(page_query(None, None), "");
+    }
+}
@@ -0,0 +1,128 @@
+// src/blockchain/services/spam.rs
+
+//! Heuristics for airdropped scam tokens and address-poisoning transfers in
+//! SeiStream history items. Each check is cheap and local (no extra requests),
+//! so they flag likely spam rather than prove it:
+//!
+//! - `zero_value_unverified_token`: a token transfer of zero from a contract the
+//!   explorer has not verified (the usual "fake transfer" event spam).
+//! - `lookalike_address`: the counterparty is not the owner but shares its first
+//!   and last four hex characters (address poisoning, hoping the victim copies it).
+//! - `scam_token_name`: the token name or symbol carries a URL or a call to action
+//!   such as "claim" or "visit".
+
+use serde_json::Value;
+
+// Leading/trailing hex characters poisoners copy from the target address.
+const LOOKALIKE_CHARS: usize = 4;
+
+const SCAM_NAME_PATTERNS: &[&str] = &[
+    "http", "www.", ".com", ".io", ".xyz", ".org", ".net", "t.me", "claim", "visit", "reward", "airdrop", "voucher",
+];
+
+/// Reasons `item` looks like spam from `owner`'s point of view; empty when none apply.
+pub fn spam_reasons(item: &Value, owner: &str) -> Vec<&'static str> {
+    let mut reasons = Vec::new();
+    if is_token_transfer(item) && is_zero(&item["value"]) && !is_verified(item) {
+        reasons.push("zero_value_unverified_token");
+    }
+    if let Some(counterparty) = counterparty(item, owner) {
+        if is_lookalike(&counterparty, owner) {
+            reasons.push("lookalike_address");
+        }
+    }
+    if token_labels(item).any(|label| {
+        let label = label.to_lowercase();
+        SCAM_NAME_PATTERNS.iter().any(|p| label.contains(p))
+    }) {
+        reasons.push("scam_token_name");
+    }
+    reasons
+}
+
+// SeiStream marks token transfers with a token object, a contract address, or a type naming the standard.
+fn is_token_transfer(item: &Value) -> bool {
+    item["token"].is_object()
+        || item["tokenAddress"].is_string()
+        || item["contractAddress"].is_string()
+        || item["type"]
+            .as_str()
+            .is_some_and(|t| t.to_lowercase().contains("erc20") || t.to_lowercase().contains("token"))
+}
+
+fn is_zero(value: &Value) -> bool {
+    match value {
+        Value::Number(n) => n.as_f64() == Some(0.0),
+        Value::String(s) => {
+            let s = s.trim().trim_start_matches("0x");
+            !s.is_empty() && s.chars().all(|c| c == '0' || c == '.')
+        }
+        _ => false,
+    }
+}
+
+fn is_verified(item: &Value) -> bool {
+    [&item["token"]["verified"], &item["token"]["isVerified"], &item["verified"], &item["isVerified"]]
+        .iter()
+        .any(|v| v.as_bool() == Some(true))
+}
+
+// The address on the other side of the transfer, or None for self-transfers and items without parties.
+fn counterparty(item: &Value, owner: &str) -> Option<String> {
+    let from = item["from"].as_str()?;
+    let to = item["to"].as_str().unwrap_or_default();
+    let other = if from.eq_ignore_ascii_case(owner) { to } else { from };
+    (!other.is_empty() && !other.eq_ignore_ascii_case(owner)).then(|| other.to_string())
+}
+
+fn is_lookalike(candidate: &str, owner: &str) -> bool {
+    let (a, b) = (candidate.to_lowercase(), owner.to_lowercase());
+    let (a, b) = (a.trim_start_matches("0x"), b.trim_start_matches("0x"));
+    if a.len() != b.len() || a.len() < LOOKALIKE_CHARS * 2 || a == b {
+        return false;
+    }
+    a[..LOOKALIKE_CHARS] == b[..LOOKALIKE_CHARS] && a[a.len() - LOOKALIKE_CHARS..] == b[b.len() - LOOKALIKE_CHARS..]
+}
+
+fn token_labels(item: &Value) -> impl Iterator<Item = &str> {
+    [&item["token"]["name"], &item["token"]["symbol"], &item["tokenName"], &item["tokenSymbol"]]
+        .into_iter()
+        .filter_map(Value::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const OWNER: &str = "0x1234aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa5678";
+
+    #[test]
+    fn test_zero_value_unverified_token_is_flagged() {
+        let item = json!({ "from": "0x9999999999999999999999999999999999999999", "to": OWNER, "value": "0", "token": { "symbol": "USDC" } });
+        assert_eq!(spam_reasons(&item, OWNER), ["zero_value_unverified_token"]);
+
+        let verified = json!({ "from": "0x9999999999999999999999999999999999999999", "to": OWNER, "value": "0", "token": { "symbol": "USDC", "verified": true } });
+        assert!(spam_reasons(&verified, OWNER).is_empty());
+    }
+
+    #[test]
+    fn test_lookalike_counterparty_is_flagged() {
+        let item = json!({ "from": OWNER, "to": "0x1234bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb5678", "value": "1000" });
+        assert_eq!(spam_reasons(&item, OWNER), ["lookalike_address"]);
+        let self_transfer = json!({ "from": OWNER, "to": OWNER.to_uppercase().replace("0X", "0x"), "value": "1" });
+        assert!(spam_reasons(&self_transfer, OWNER).is_empty());
+    }
+
+    #[test]
+    fn test_url_in_token_name_is_flagged() {
+        let item = json!({ "from": "0x9999999999999999999999999999999999999999", "to": OWNER, "value": "5", "token": { "name": "Visit sei-bonus.xyz to claim", "verified": false } });
+        assert_eq!(spam_reasons(&item, OWNER), ["scam_token_name"]);
+    }
+
+    #[test]
+    fn test_plain_native_transfer_is_clean() {
+        let item = json!({ "from": "0x9999999999999999999999999999999999999999", "to": OWNER, "value": "0" });
+        assert!(spam_reasons(&item, OWNER).is_empty());
+    }
+}
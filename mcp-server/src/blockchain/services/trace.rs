@@ -0,0 +1,253 @@
+// src/blockchain/services/trace.rs
+
+use crate::blockchain::services::rpc::{self, RpcTransport};
+use anyhow::Result;
+use dashmap::DashMap;
+use ethers_core::types::U256;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::time::{Duration, Instant};
+
+// Internal call trees of mined transactions, from geth's callTracer or parity/OpenEthereum's trace module.
+
+/// The tracing API a node answered with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceMethod {
+    /// `debug_traceTransaction` with the built-in `callTracer`.
+    Debug,
+    /// Parity-style `trace_transaction`.
+    Parity,
+}
+
+impl TraceMethod {
+    pub fn rpc_method(&self) -> &'static str {
+        match self {
+            TraceMethod::Debug => "debug_traceTransaction",
+            TraceMethod::Parity => "trace_transaction",
+        }
+    }
+}
+
+// How long a node found to offer no tracing is taken at its word; tracing may be switched on later
+const UNSUPPORTED_TTL: Duration = Duration::from_secs(600);
+
+/// The tracing method each RPC URL answered with. A node that offered neither is asked again once
+/// `UNSUPPORTED_TTL` has passed.
+#[derive(Default)]
+pub struct TraceMethodCache {
+    methods: DashMap<String, (Option<TraceMethod>, Instant)>,
+}
+
+impl TraceMethodCache {
+    /// `Some(None)` while `rpc_url` is known to offer no tracing; `None` when it has to be probed.
+    pub fn known(&self, rpc_url: &str, now: Instant) -> Option<Option<TraceMethod>> {
+        let (method, since) = *self.methods.get(rpc_url)?;
+        match method {
+            None if now.duration_since(since) >= UNSUPPORTED_TTL => None,
+            _ => Some(method),
+        }
+    }
+
+    pub fn remember(&self, rpc_url: &str, method: Option<TraceMethod>, now: Instant) {
+        self.methods.insert(rpc_url.to_string(), (method, now));
+    }
+}
+
+/// One call in a transaction's call tree, without the opcode-level detail.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CallFrame {
+    /// CALL, DELEGATECALL, STATICCALL, CREATE, CREATE2 or SELFDESTRUCT.
+    #[serde(rename = "type")]
+    pub call_type: String,
+    pub from: String,
+    /// The callee; the new contract for creations, the beneficiary for a self-destruct.
+    pub to: Option<String>,
+    /// Wei as a decimal string.
+    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<CallFrame>,
+}
+
+impl CallFrame {
+    /// Calls nested under this one, and how many of them moved value.
+    pub fn internal_counts(&self) -> (usize, usize) {
+        self.calls.iter().fold((0, 0), |(calls, transfers), c| {
+            let (nested, nested_transfers) = c.internal_counts();
+            let moved = usize::from(c.value != "0");
+            (calls + 1 + nested, transfers + moved + nested_transfers)
+        })
+    }
+
+    /// A `callTracer` frame and its children.
+    pub fn from_call_tracer(frame: &Value) -> CallFrame {
+        CallFrame {
+            call_type: frame["type"].as_str().unwrap_or("CALL").to_uppercase(),
+            from: text(&frame["from"]).unwrap_or_default(),
+            to: text(&frame["to"]),
+            value: wei(&frame["value"]),
+            error: text(&frame["error"]),
+            calls: frame["calls"].as_array().map(|c| c.iter().map(CallFrame::from_call_tracer).collect()).unwrap_or_default(),
+        }
+    }
+
+    /// Rebuilds the tree from parity's flat trace list, nesting each entry by its `traceAddress`.
+    /// `None` when the list has no top-level call (an unknown transaction).
+    pub fn from_parity(traces: &[Value]) -> Option<CallFrame> {
+        let mut root: Option<CallFrame> = None;
+        for trace in traces {
+            let path: Vec<usize> = trace["traceAddress"]
+                .as_array()
+                .map(|p| p.iter().filter_map(|i| i.as_u64()).map(|i| i as usize).collect())
+                .unwrap_or_default();
+            let frame = parity_frame(trace);
+            match (path.split_last(), root.as_mut()) {
+                (None, _) => root = Some(frame),
+                (Some((_, parents)), Some(root)) => {
+                    let parent = parents.iter().try_fold(root, |node, &i| node.calls.get_mut(i));
+                    if let Some(parent) = parent {
+                        parent.calls.push(frame);
+                    }
+                }
+                (Some(_), None) => {}
+            }
+        }
+        root
+    }
+}
+
+fn text(v: &Value) -> Option<String> {
+    v.as_str().filter(|s| !s.is_empty()).map(str::to_string)
+}
+
+// Hex quantity as decimal wei; absent means no value moved
+fn wei(v: &Value) -> String {
+    v.as_str()
+        .and_then(|s| U256::from_str_radix(s.trim_start_matches("0x"), 16).ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn parity_frame(trace: &Value) -> CallFrame {
+    let action = &trace["action"];
+    let (call_type, from, to, value) = match trace["type"].as_str().unwrap_or_default() {
+        "create" => ("CREATE".to_string(), &action["from"], &trace["result"]["address"], &action["value"]),
+        "suicide" => ("SELFDESTRUCT".to_string(), &action["address"], &action["refundAddress"], &action["balance"]),
+        _ => (action["callType"].as_str().unwrap_or("call").to_uppercase(), &action["from"], &action["to"], &action["value"]),
+    };
+    CallFrame { call_type, from: text(from).unwrap_or_default(), to: text(to), value: wei(value), error: text(&trace["error"]), calls: Vec::new() }
+}
+
+/// A transaction's call tree and the node's unsimplified trace output.
+#[derive(Debug, Clone)]
+pub struct TransactionTrace {
+    pub method: TraceMethod,
+    /// `None` when the node knows no such transaction.
+    pub root: Option<CallFrame>,
+    pub raw: Value,
+}
+
+/// Whether a node error means the tracing method itself is missing or disabled (JSON-RPC -32601, or
+/// geth's wording for it), as opposed to the trace failing.
+pub fn is_method_unavailable(err: &str) -> bool {
+    let e = err.to_lowercase();
+    e.contains("-32601") || e.contains("method not found") || e.contains("does not exist/is not available")
+}
+
+async fn trace_with(transport: &dyn RpcTransport, rpc_url: &str, method: TraceMethod, hash: &str) -> Result<TransactionTrace> {
+    let params = match method {
+        TraceMethod::Debug => json!([hash, { "tracer": "callTracer" }]),
+        TraceMethod::Parity => json!([hash]),
+    };
+    let raw = rpc::rpc_call(transport, rpc_url, method.rpc_method(), params).await?;
+    let root = match method {
+        TraceMethod::Debug => Some(&raw).filter(|r| r.is_object()).map(CallFrame::from_call_tracer),
+        TraceMethod::Parity => raw.as_array().and_then(|traces| CallFrame::from_parity(traces)),
+    };
+    Ok(TransactionTrace { method, root, raw })
+}
+
+/// Traces `hash` with `debug_traceTransaction` (callTracer), falling back to `trace_transaction`.
+/// Which one the node supports is remembered in `cache`; `None` when it offers neither.
+pub async fn trace_transaction(
+    cache: &TraceMethodCache,
+    transport: &dyn RpcTransport,
+    rpc_url: &str,
+    hash: &str,
+) -> Result<Option<TransactionTrace>> {
+    let candidates = match cache.known(rpc_url, Instant::now()) {
+        Some(None) => return Ok(None),
+        Some(Some(method)) => vec![method],
+        None => vec![TraceMethod::Debug, TraceMethod::Parity],
+    };
+    for method in candidates {
+        match trace_with(transport, rpc_url, method, hash).await {
+            Ok(trace) => {
+                cache.remember(rpc_url, Some(method), Instant::now());
+                return Ok(Some(trace));
+            }
+            Err(e) if is_method_unavailable(&e.to_string()) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    cache.remember(rpc_url, None, Instant::now());
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parity_traces_nest_by_trace_address() {
+        let traces = json!([
+            { "type": "call", "traceAddress": [], "action": { "callType": "call", "from": "0xa", "to": "0xb", "value": "0x0" } },
+            { "type": "call", "traceAddress": [0], "action": { "callType": "delegatecall", "from": "0xb", "to": "0xc", "value": "0x0" } },
+            { "type": "call", "traceAddress": [0, 0], "action": { "callType": "call", "from": "0xb", "to": "0xd", "value": "0xa" } },
+            { "type": "create", "traceAddress": [1], "action": { "from": "0xb", "value": "0x0" }, "result": { "address": "0xe" } },
+            { "type": "suicide", "traceAddress": [2], "action": { "address": "0xe", "refundAddress": "0xa", "balance": "0x5" } }
+        ]);
+        let root = CallFrame::from_parity(traces.as_array().unwrap()).unwrap();
+        assert_eq!(root.calls.len(), 3);
+        assert_eq!(root.calls[0].call_type, "DELEGATECALL");
+        assert_eq!(root.calls[0].calls[0].value, "10");
+        assert_eq!((root.calls[1].call_type.as_str(), root.calls[1].to.as_deref()), ("CREATE", Some("0xe")));
+        assert_eq!((root.calls[2].call_type.as_str(), root.calls[2].value.as_str()), ("SELFDESTRUCT", "5"));
+        assert_eq!(root.internal_counts(), (4, 2));
+        assert!(CallFrame::from_parity(&[]).is_none());
+    }
+
+    #[test]
+    fn test_only_missing_methods_count_as_unavailable() {
+        assert!(is_method_unavailable(r#"RPC Error calling debug_traceTransaction: {"code":-32601,"message":"Method not found"}"#));
+        assert!(is_method_unavailable("the method debug_traceTransaction does not exist/is not available"));
+        assert!(!is_method_unavailable(r#"{"code":-32000,"message":"transaction 0xab does not exist"}"#));
+        assert!(!is_method_unavailable(r#"{"code":-32000,"message":"historical state not available"}"#));
+    }
+
+    #[test]
+    fn test_unsupported_nodes_are_probed_again_after_ttl() {
+        let cache = TraceMethodCache::default();
+        let start = Instant::now();
+        cache.remember("http://archive", Some(TraceMethod::Parity), start);
+        cache.remember("http://pruned", None, start);
+        let later = start + UNSUPPORTED_TTL;
+        assert_eq!(cache.known("http://archive", later), Some(Some(TraceMethod::Parity)));
+        assert_eq!(cache.known("http://pruned", start + Duration::from_secs(1)), Some(None));
+        assert_eq!(cache.known("http://pruned", later), None);
+    }
+
+    #[test]
+    fn test_call_tracer_frames() {
+        let frame = json!({
+            "type": "CALL", "from": "0xa", "to": "0xb", "value": "0x1",
+            "calls": [{ "type": "STATICCALL", "from": "0xb", "to": "0xc", "error": "execution reverted" }]
+        });
+        let root = CallFrame::from_call_tracer(&frame);
+        assert_eq!(root.value, "1");
+        assert_eq!(root.calls[0].value, "0");
+        assert_eq!(root.calls[0].error.as_deref(), Some("execution reverted"));
+        assert_eq!(root.internal_counts(), (1, 0));
+    }
+}
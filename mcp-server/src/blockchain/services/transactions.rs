@@ -1,13 +1,17 @@
 // src/blockchain/services/transactions.rs
 
-use crate::blockchain::{models::TransactionResponse, nonce_manager::NonceManager};
-use anyhow::{anyhow, Result, Context};
-use ethers_core::types::{TransactionRequest, U64, U256};
+use crate::blockchain::{broadcasts, models::{RejectionCategory, SeiError, TransactionResponse}, nonce_manager::NonceManager};
+use dashmap::DashMap;
+use ethers_core::types::transaction::eip2718::TypedTransaction;
+use ethers_core::types::{Eip1559TransactionRequest, TransactionRequest, U64, U256};
 use ethers_signers::{LocalWallet, Signer};
-use reqwest::Client;
-use serde_json::json;
-use crate::config::Config;
+use serde_json::{json, Value};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tracing::warn;
+use crate::config::{Config, TxType};
 use crate::blockchain::models::ChainType;
+use crate::blockchain::services::rpc::{self, HttpClient, RpcTransport};
 // Cosmos (native) signing
 use cosmrs::crypto::secp256k1::SigningKey as CosmosSigningKey;
 use cosmrs::tx::{SignDoc, SignerInfo, AuthInfo, Body, Fee};
@@ -15,118 +19,375 @@ use cosmrs::Any;
 use cosmrs::proto::cosmos::{
     bank::v1beta1::MsgSend,
     base::v1beta1::Coin,
+    feegrant::v1beta1::{BasicAllowance, MsgGrantAllowance},
 };
 use prost::Message as _;
 use base64::engine::general_purpose::STANDARD as BASE64STD;
 use base64::Engine;
 
+type Result<T> = std::result::Result<T, SeiError>;
+
+// Whether each RPC URL answers eth_feeHistory, probed on first `auto` send
+static FEE_HISTORY_SUPPORT: OnceLock<DashMap<String, bool>> = OnceLock::new();
+
+/// Whether the node behind `rpc_url` supports EIP-1559 fee data. Probed once per URL and cached.
+pub async fn supports_eip1559(transport: &dyn RpcTransport, rpc_url: &str) -> bool {
+    let cache = FEE_HISTORY_SUPPORT.get_or_init(DashMap::new);
+    if let Some(supported) = cache.get(rpc_url) {
+        return *supported;
+    }
+    let supported = rpc::rpc_call(transport, rpc_url, "eth_feeHistory", json!(["0x1", "latest", []]))
+        .await
+        .map(|history| history["baseFeePerGas"].as_array().is_some_and(|fees| fees.iter().any(Value::is_string)))
+        .unwrap_or(false);
+    cache.insert(rpc_url.to_string(), supported);
+    supported
+}
+
+/// How long `latest_base_fee` reuses a fetched base fee; roughly a couple of Sei blocks.
+pub const BASE_FEE_TTL: Duration = Duration::from_secs(2);
+
+// Latest base fee per RPC URL with when it was fetched, so bursts of sends share one block lookup
+static BASE_FEES: OnceLock<DashMap<String, (Instant, BaseFee)>> = OnceLock::new();
+
+/// The EIP-1559 base fee of the block at `block_number`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BaseFee {
+    pub block_number: u64,
+    /// `None` when the block carries no `baseFeePerGas`, as on chains without EIP-1559.
+    pub base_fee_per_gas: Option<U256>,
+}
+
+impl BaseFee {
+    /// Number and `baseFeePerGas` of an `eth_getBlockByNumber` result; `None` for a missing block.
+    pub fn from_block(block: &Value) -> Option<BaseFee> {
+        let quantity = |v: &Value| v.as_str().and_then(|s| U256::from_str_radix(s.trim_start_matches("0x"), 16).ok());
+        let block_number = quantity(&block["number"])?.low_u64();
+        Some(BaseFee { block_number, base_fee_per_gas: quantity(&block["baseFeePerGas"]) })
+    }
+}
+
+/// Base fee of the latest block, cached per RPC URL for `BASE_FEE_TTL`. The flag is true when the
+/// value came from the cache.
+pub async fn latest_base_fee(transport: &dyn RpcTransport, rpc_url: &str) -> Result<(BaseFee, bool)> {
+    let cache = BASE_FEES.get_or_init(DashMap::new);
+    if let Some(entry) = cache.get(rpc_url) {
+        let (fetched_at, fee) = *entry;
+        if fetched_at.elapsed() < BASE_FEE_TTL {
+            return Ok((fee, true));
+        }
+    }
+    let block = rpc::rpc_call(transport, rpc_url, "eth_getBlockByNumber", json!(["latest", false]))
+        .await
+        .map_err(SeiError::rpc)?;
+    let fee = BaseFee::from_block(&block).ok_or_else(|| SeiError::rpc("eth_getBlockByNumber returned no latest block"))?;
+    cache.insert(rpc_url.to_string(), (Instant::now(), fee));
+    Ok((fee, false))
+}
+
+// EIP-1559 (max_fee_per_gas, max_priority_fee_per_gas): max fee leaves room for the base fee to double
+// before inclusion. The base fee is the latest block's (see `latest_base_fee`), falling back to
+// eth_feeHistory's; the tip is `priority_fee` when given, else the median reward of the latest block.
+async fn eip1559_fees(transport: &dyn RpcTransport, rpc_url: &str, priority_fee: Option<U256>) -> Result<(U256, U256)> {
+    let latest = latest_base_fee(transport, rpc_url).await.ok().and_then(|(fee, _)| fee.base_fee_per_gas);
+    if let (Some(base_fee), Some(tip)) = (latest, priority_fee) {
+        return Ok((base_fee * 2 + tip, tip));
+    }
+    let history = rpc::rpc_call(transport, rpc_url, "eth_feeHistory", json!(["0x1", "latest", [50]]))
+        .await
+        .map_err(SeiError::rpc)?;
+    let quantity = |v: &Value| v.as_str().and_then(|s| U256::from_str_radix(s.trim_start_matches("0x"), 16).ok());
+    let base_fee = match latest {
+        Some(base_fee) => base_fee,
+        None => history["baseFeePerGas"]
+            .as_array()
+            .and_then(|fees| fees.last())
+            .and_then(quantity)
+            .ok_or_else(|| SeiError::rpc("eth_feeHistory returned no baseFeePerGas"))?,
+    };
+    let tip = priority_fee.or_else(|| quantity(&history["reward"][0][0])).unwrap_or_default();
+    Ok((base_fee * 2 + tip, tip))
+}
+
+/// Gas limit and fee caps an EVM transaction is sent with.
+#[derive(Debug, Clone, Copy)]
+pub struct GasQuote {
+    pub gas_limit: U256,
+    /// Legacy gas price, or the EIP-1559 max fee per gas.
+    pub max_fee_per_gas: U256,
+    /// Set only for EIP-1559 envelopes.
+    pub max_priority_fee_per_gas: Option<U256>,
+}
+
+impl GasQuote {
+    /// Upper bound on the fee in wei (gas limit at the max fee per gas).
+    pub fn max_fee(&self) -> U256 {
+        self.gas_limit * self.max_fee_per_gas
+    }
+
+    /// Both fee caps raised by `percent` (rounded up, and always by at least 1 wei).
+    pub fn bumped(&self, percent: u64) -> GasQuote {
+        let bump = |fee: U256| {
+            let raised = (fee * (100 + percent) + 99) / 100;
+            raised.max(fee + 1)
+        };
+        GasQuote {
+            gas_limit: self.gas_limit,
+            max_fee_per_gas: bump(self.max_fee_per_gas),
+            max_priority_fee_per_gas: self.max_priority_fee_per_gas.map(bump),
+        }
+    }
+}
+
+/// Resolves the gas limit and fees `send_evm_transaction` would use for `tx` (which must carry `from`),
+/// estimating whatever the caller left unset. An explicit gas price becomes both EIP-1559 fee caps.
+pub async fn quote_gas(transport: &dyn RpcTransport, rpc_url: &str, tx: &TransactionRequest, tx_type: TxType) -> Result<GasQuote> {
+    quote_gas_with_tip(transport, rpc_url, tx, tx_type, None).await
+}
+
+/// `quote_gas` with a caller-chosen EIP-1559 priority fee; the max fee is then built on the latest
+/// base fee. Ignored for legacy envelopes and when `tx` carries an explicit gas price.
+pub async fn quote_gas_with_tip(
+    transport: &dyn RpcTransport,
+    rpc_url: &str,
+    tx: &TransactionRequest,
+    tx_type: TxType,
+    priority_fee: Option<U256>,
+) -> Result<GasQuote> {
+    // If gas is not provided, estimate it via eth_estimateGas
+    let gas_limit = match tx.gas {
+        Some(gas) => gas,
+        None => {
+            let call_obj = serde_json::to_value(tx).map_err(SeiError::invalid_input)?;
+            let estimate_resp = transport
+                .call(rpc_url, "eth_estimateGas", json!([call_obj]))
+                .await
+                .map_err(SeiError::rpc)?;
+            if let Some(err) = estimate_resp.get("error") {
+                return Err(SeiError::from_rpc_error("RPC Error estimating gas", err));
+            }
+            let gas_hex = estimate_resp["result"].as_str().ok_or_else(|| SeiError::rpc("Failed to get gas estimate"))?;
+            U256::from_str_radix(gas_hex.trim_start_matches("0x"), 16).map_err(SeiError::rpc)?
+        }
+    };
+
+    let use_eip1559 = match tx_type {
+        TxType::Legacy => false,
+        TxType::Eip1559 => true,
+        TxType::Auto => supports_eip1559(transport, rpc_url).await,
+    };
+    let (max_fee_per_gas, max_priority_fee_per_gas) = match (use_eip1559, tx.gas_price) {
+        (true, Some(gas_price)) => (gas_price, Some(gas_price)),
+        (true, None) => {
+            let (max_fee, tip) = eip1559_fees(transport, rpc_url, priority_fee).await?;
+            (max_fee, Some(tip))
+        }
+        (false, Some(gas_price)) => (gas_price, None),
+        (false, None) => (legacy_gas_price(transport, rpc_url).await?, None),
+    };
+    Ok(GasQuote { gas_limit, max_fee_per_gas, max_priority_fee_per_gas })
+}
+
+/// What a sweep of `balance` can send once the fee for `fees` is paid, leaving the account at zero;
+/// `None` when the balance does not cover the fee. Exact when the send uses the full gas limit at
+/// exactly `max_fee_per_gas`, as a plain transfer with a pinned gas price does.
+pub fn sweep_value(balance: U256, fees: &GasQuote) -> Option<U256> {
+    balance.checked_sub(fees.max_fee()).filter(|v| !v.is_zero())
+}
+
+/// Refuses fees above the chain's MAX_GAS_PRICES cap (wei per gas; the legacy price or the EIP-1559
+/// max fee). A price equal to the cap is allowed; no cap allows anything.
+pub fn check_gas_price_cap(fees: &GasQuote, max_gas_price: Option<U256>) -> Result<()> {
+    match max_gas_price {
+        Some(cap) if fees.max_fee_per_gas > cap => Err(SeiError::InvalidInput(format!(
+            "gas price exceeds configured maximum: {} wei > {} wei",
+            fees.max_fee_per_gas, cap
+        ))),
+        _ => Ok(()),
+    }
+}
+
 /// A centralized, secure function for sending any EVM transaction.
 /// It uses the NonceManager to prevent race conditions.
+/// `tx_type` picks the envelope; an explicit gas price becomes both EIP-1559 fee caps.
+/// Fees above `max_gas_price`, estimated or supplied, are refused before signing.
 pub async fn send_evm_transaction(
+    transport: &dyn RpcTransport,
     rpc_url: &str,
     wallet: LocalWallet,
     tx_request: TransactionRequest,
     nonce_manager: &NonceManager,
+    tx_type: TxType,
+    max_gas_price: Option<U256>,
 ) -> Result<TransactionResponse> {
-    let client = Client::new();
+    let policy = FeePolicy { tx_type, max_gas_price, ..FeePolicy::default() };
+    send_evm_transaction_with(transport, rpc_url, wallet, tx_request, nonce_manager, policy)
+        .await
+        .map(|sent| sent.response)
+}
+
+/// Opt-in fee bumping for sends the node rejects as underpriced.
+#[derive(Debug, Clone, Copy)]
+pub struct GasBump {
+    /// Raise both fee caps by this percentage per retry.
+    pub percent: u64,
+    /// Give up after this many bumped retries.
+    pub max_bumps: u32,
+}
+
+/// How a send picks and limits its fees: the envelope, an optional gas price ceiling in wei
+/// (MAX_GAS_PRICES), opt-in bumping, and an optional EIP-1559 priority fee in wei.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FeePolicy {
+    pub tx_type: TxType,
+    pub max_gas_price: Option<U256>,
+    pub bump: Option<GasBump>,
+    pub priority_fee: Option<U256>,
+}
+
+/// A broadcast transaction with the fees it finally went out with.
+#[derive(Debug)]
+pub struct SentTransaction {
+    pub response: TransactionResponse,
+    pub fees: GasQuote,
+    pub bumps: u32,
+}
+
+/// Whether the node refused a transaction for paying too little ("transaction underpriced",
+/// "replacement transaction underpriced"), the case `GasBump` retries.
+pub fn is_underpriced(err: &SeiError) -> bool {
+    err.rejection() == Some(RejectionCategory::Underpriced)
+}
+
+/// `send_evm_transaction`, retrying underpriced rejections with raised fees when `policy.bump` is set.
+/// Every attempt reuses the same nonce, so a retry replaces rather than queues behind the first,
+/// and no bump may raise the fee past `policy.max_gas_price`.
+pub async fn send_evm_transaction_with(
+    transport: &dyn RpcTransport,
+    rpc_url: &str,
+    wallet: LocalWallet,
+    tx_request: TransactionRequest,
+    nonce_manager: &NonceManager,
+    policy: FeePolicy,
+) -> Result<SentTransaction> {
+    let FeePolicy { tx_type, max_gas_price, bump, priority_fee } = policy;
     let from_address = wallet.address();
 
     // FIX: Get the next sequential nonce from the manager.
-    let nonce = nonce_manager.get_next_nonce(from_address, rpc_url).await?;
+    let nonce = nonce_manager.get_next_nonce(from_address, rpc_url).await.map_err(SeiError::rpc)?;
 
     // Get chain ID from the node.
-    let chain_id_payload = json!({
-        "jsonrpc": "2.0",
-        "method": "eth_chainId",
-        "params": [],
-        "id": 1
-    });
+    let chain_id_response = transport.call(rpc_url, "eth_chainId", json!([])).await.map_err(SeiError::rpc)?;
 
-    let chain_id_response: serde_json::Value = client.post(rpc_url)
-        .json(&chain_id_payload)
-        .send().await?.json().await?;
-        
-    let chain_id_hex = chain_id_response["result"].as_str().context("Failed to get chain_id from RPC")?;
-    let chain_id = U64::from_str_radix(chain_id_hex.trim_start_matches("0x"), 16)?;
+    let chain_id_hex = chain_id_response["result"].as_str()
+        .ok_or_else(|| SeiError::rpc("Failed to get chain_id from RPC"))?;
+    let chain_id = U64::from_str_radix(chain_id_hex.trim_start_matches("0x"), 16).map_err(SeiError::rpc)?;
 
     // Populate the final transaction request
-    let mut tx = tx_request
+    let tx = tx_request
         .from(from_address)
         .nonce(nonce)
         .chain_id(chain_id.as_u64());
 
-    // If gas is not provided, estimate it via eth_estimateGas
-    if tx.gas.is_none() {
-        let call_obj = serde_json::to_value(&tx)?;
-        let estimate_payload = json!({
-            "jsonrpc": "2.0",
-            "method": "eth_estimateGas",
-            "params": [call_obj],
-            "id": 1
-        });
-        let estimate_resp: serde_json::Value = client.post(rpc_url)
-            .json(&estimate_payload)
-            .send().await?
-            .json().await?;
-        if let Some(err) = estimate_resp.get("error") {
-            return Err(anyhow!("RPC Error estimating gas: {}", err));
+    let mut fees = quote_gas_with_tip(transport, rpc_url, &tx, tx_type, priority_fee).await?;
+    check_gas_price_cap(&fees, max_gas_price)?;
+    let mut bumps = 0;
+    loop {
+        let typed: TypedTransaction = match fees.max_priority_fee_per_gas {
+            Some(tip) => {
+                let mut eip1559 = Eip1559TransactionRequest::new()
+                    .from(from_address)
+                    .nonce(nonce)
+                    .chain_id(chain_id.as_u64())
+                    .gas(fees.gas_limit)
+                    .max_fee_per_gas(fees.max_fee_per_gas)
+                    .max_priority_fee_per_gas(tip);
+                eip1559.to = tx.to.clone();
+                eip1559.value = tx.value;
+                eip1559.data = tx.data.clone();
+                eip1559.into()
+            }
+            None => tx.clone().gas(fees.gas_limit).gas_price(fees.max_fee_per_gas).into(),
+        };
+        match (sign_and_broadcast(transport, rpc_url, &wallet, &typed).await, bump) {
+            (Err(e), Some(b)) if is_underpriced(&e) && bumps < b.max_bumps => {
+                fees = fees.bumped(b.percent);
+                check_gas_price_cap(&fees, max_gas_price)?;
+                bumps += 1;
+                warn!(nonce = %nonce, bumps, max_fee_per_gas = %fees.max_fee_per_gas, "transaction underpriced, retrying with higher fees");
+            }
+            (result, _) => return result.map(|response| SentTransaction { response, fees, bumps }),
         }
-        let gas_hex = estimate_resp["result"].as_str().context("Failed to get gas estimate")?;
-        let gas = U256::from_str_radix(gas_hex.trim_start_matches("0x"), 16)?;
-        tx = tx.gas(gas);
     }
+}
 
-    // If gas price not provided, fetch eth_gasPrice and use legacy gas_price
-    if tx.gas_price.is_none() {
-        let gp_payload = json!({
-            "jsonrpc": "2.0",
-            "method": "eth_gasPrice",
-            "params": [],
-            "id": 1
-        });
-        let gp_resp: serde_json::Value = client.post(rpc_url)
-            .json(&gp_payload)
-            .send().await?
-            .json().await?;
-        if let Some(err) = gp_resp.get("error") {
-            return Err(anyhow!("RPC Error getting gasPrice: {}", err));
-        }
-        let gp_hex = gp_resp["result"].as_str().context("Failed to get gasPrice")?;
-        let gp = U256::from_str_radix(gp_hex.trim_start_matches("0x"), 16)?;
-        tx = tx.gas_price(gp);
-    }
+/// A signed EVM transaction decoded offline, with its hash and recovered sender.
+#[derive(Debug, Clone)]
+pub struct DecodedTransaction {
+    /// keccak256 of the raw bytes; the hash the network will report once broadcast.
+    pub hash: ethers_core::types::H256,
+    pub from: ethers_core::types::Address,
+    pub tx: TypedTransaction,
+    pub signature: ethers_core::types::Signature,
+}
 
-    // Sign the transaction
-    let signature = wallet.sign_transaction(&tx.clone().into()).await?;
-    let raw_tx = tx.rlp_signed(&signature);
+/// Decodes a signed raw transaction (legacy, EIP-2930 or EIP-1559 envelope) and recovers its
+/// signer, without touching the network.
+pub fn decode_signed_transaction(raw_hex: &str) -> Result<DecodedTransaction> {
+    let raw_hex = raw_hex.trim();
+    let bytes = hex::decode(raw_hex.strip_prefix("0x").unwrap_or(raw_hex))
+        .map_err(|_| SeiError::InvalidInput("raw transaction must be 0x-prefixed hex".into()))?;
+    if bytes.is_empty() {
+        return Err(SeiError::InvalidInput("raw transaction is empty".into()));
+    }
+    let (tx, signature) = TypedTransaction::decode_signed(&ethers_core::utils::rlp::Rlp::new(&bytes))
+        .map_err(|e| SeiError::InvalidInput(format!("Not a signed EVM transaction: {}", e)))?;
+    let from = signature
+        .recover(tx.sighash())
+        .map_err(|e| SeiError::InvalidInput(format!("Cannot recover the signer: {}", e)))?;
+    Ok(DecodedTransaction { hash: ethers_core::utils::keccak256(&bytes).into(), from, tx, signature })
+}
 
-    // Send the raw transaction
+/// Submits an already-signed transaction with `eth_sendRawTransaction` and returns its hash.
+/// A refusal comes back classified (`SeiError::Rejected` or `InsufficientFunds`).
+pub async fn broadcast_raw_transaction(transport: &dyn RpcTransport, rpc_url: &str, raw_tx: &[u8]) -> Result<String> {
     let params = json!([format!("0x{}", hex::encode(raw_tx))]);
-    let payload = json!({
-        "jsonrpc": "2.0",
-        "method": "eth_sendRawTransaction",
-        "params": params,
-        "id": 1,
-    });
-
-    let response: serde_json::Value = client.post(rpc_url)
-        .json(&payload)
-        .send().await?.json().await?;
+    let response = transport.call(rpc_url, "eth_sendRawTransaction", params).await.map_err(SeiError::rpc)?;
 
     if let Some(error) = response.get("error") {
-        return Err(anyhow!("RPC Error sending transaction: {}", error));
+        return Err(SeiError::from_send_error(error));
     }
 
     let tx_hash = response["result"]
         .as_str()
-        .ok_or_else(|| anyhow!("Failed to extract transaction hash from response"))?;
+        .ok_or_else(|| SeiError::rpc("Failed to extract transaction hash from response"))?;
+    broadcasts::record(tx_hash);
+    Ok(tx_hash.to_string())
+}
+
+// Signs `typed` and submits it with eth_sendRawTransaction.
+async fn sign_and_broadcast(transport: &dyn RpcTransport, rpc_url: &str, wallet: &LocalWallet, typed: &TypedTransaction) -> Result<TransactionResponse> {
+    // Sign the transaction
+    let signature = wallet.sign_transaction(typed).await.map_err(SeiError::signing)?;
+    let raw_tx = typed.rlp_signed(&signature);
 
-    Ok(TransactionResponse {
-        tx_hash: tx_hash.to_string(),
-    })
+    let tx_hash = broadcast_raw_transaction(transport, rpc_url, &raw_tx).await?;
+    Ok(TransactionResponse { tx_hash })
+}
+
+// Current legacy gas price from eth_gasPrice.
+async fn legacy_gas_price(transport: &dyn RpcTransport, rpc_url: &str) -> Result<U256> {
+    let gp_resp = transport.call(rpc_url, "eth_gasPrice", json!([])).await.map_err(SeiError::rpc)?;
+    if let Some(err) = gp_resp.get("error") {
+        return Err(SeiError::from_rpc_error("RPC Error getting gasPrice", err));
+    }
+    let gp_hex = gp_resp["result"].as_str().ok_or_else(|| SeiError::rpc("Failed to get gasPrice"))?;
+    let gp = U256::from_str_radix(gp_hex.trim_start_matches("0x"), 16).map_err(SeiError::rpc)?;
+    Ok(gp)
 }
 
 pub async fn send_native_transaction(
+    client: &HttpClient,
     config: &Config,
     recipient_address: &str,
     amount: u64,
@@ -163,116 +424,263 @@ pub async fn send_native_transaction(
     });
 
     // Broadcast the tx (assumes /txs endpoint, adjust for your node)
-    let client = reqwest::Client::new();
-    let res = client.post(format!("{}/txs", rpc_url))
+    let res = client.post(&format!("{}/txs", rpc_url))
         .json(&tx_body)
         .send()
         .await
-        .context("Failed to send native SEI tx")?;
+        .map_err(|e| SeiError::Rpc(format!("Failed to send native SEI tx: {}", e)))?;
 
-    let res_json: serde_json::Value = res.json().await.context("Failed to parse tx response")?;
+    let res_json: serde_json::Value = rpc::read_json(res)
+        .await
+        .map_err(|e| SeiError::Rpc(format!("Failed to parse tx response: {}", e)))?;
     if let Some(error) = res_json.get("error") {
-        return Err(anyhow!("Native SEI tx error: {}", error));
+        return Err(SeiError::from_rpc_error("Native SEI tx error", error));
     }
     let tx_hash = res_json.get("txhash")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| anyhow!("No txhash in native SEI response"))?;
+        .ok_or_else(|| SeiError::rpc("No txhash in native SEI response"))?;
+    broadcasts::record(tx_hash);
     Ok(tx_hash.to_string())
 }
 
+// Cosmos signing key for a 0x-hex secp256k1 key, with its bech32 address.
+fn native_signer(config: &Config, private_key_hex: &str) -> Result<(CosmosSigningKey, String)> {
+    let priv_bytes = hex::decode(private_key_hex.strip_prefix("0x").unwrap_or(private_key_hex))
+        .map_err(|e| SeiError::InvalidInput(format!("Invalid private key hex: {}", e)))?;
+    let signing_key = CosmosSigningKey::from_slice(&priv_bytes)
+        .map_err(|e| SeiError::InvalidInput(format!("Invalid Cosmos private key bytes: {}", e)))?;
+    let address = signing_key
+        .public_key()
+        .account_id(config.native_bech32_hrp.as_str())
+        .map_err(|e| SeiError::Signing(format!("Failed to derive bech32 address from key: {}", e)))?
+        .to_string();
+    Ok((signing_key, address))
+}
+
+/// Bech32 sender address `send_native_transaction_signed` would use for this key.
+pub fn native_sender_address(config: &Config, private_key_hex: &str) -> Result<String> {
+    native_signer(config, private_key_hex).map(|(_, address)| address)
+}
+
+/// Fee (in the native denom) and gas limit attached to native bank sends.
+pub fn native_fee(config: &Config) -> (u64, u64) {
+    (config.native_fee_amount, config.native_gas_limit)
+}
+
 /// Send a native (Cosmos) bank send transaction signed with the provided private key (hex).
 pub async fn send_native_transaction_signed(
+    client: &HttpClient,
     config: &Config,
     rpc_url: &str,
     from_private_key_hex: &str,
     to_address: &str,
     amount_usei: u64,
+    memo: &str,
 ) -> Result<String> {
-    // Build signer and derive from address
-    let priv_bytes = if let Some(stripped) = from_private_key_hex.strip_prefix("0x") { hex::decode(stripped)? } else { hex::decode(from_private_key_hex)? };
-    let signing_key = CosmosSigningKey::from_slice(&priv_bytes)
-        .map_err(|e| anyhow!("Invalid Cosmos private key bytes: {}", e))?;
-    let public_key = signing_key.public_key();
-    let from_account_id = public_key
-        .account_id(config.native_bech32_hrp.as_str())
-        .map_err(|e| anyhow!("Failed to derive bech32 address from key: {}", e))?;
-    let from_address = from_account_id.to_string();
+    let (signing_key, from_address) = native_signer(config, from_private_key_hex)?;
+    let msg = bank_send(config, &from_address, to_address, amount_usei);
+    sign_and_broadcast_native(client, config, rpc_url, &signing_key, &from_address, vec![msg], memo, None).await
+}
+
+/// `send_native_transaction_signed` with the fee paid from `granter`'s fee allowance to the sender.
+/// A missing or exhausted grant is reported as `NotFound`.
+#[allow(clippy::too_many_arguments)]
+pub async fn send_native_transaction_with_feegrant(
+    client: &HttpClient,
+    config: &Config,
+    rpc_url: &str,
+    from_private_key_hex: &str,
+    to_address: &str,
+    amount_usei: u64,
+    granter: &str,
+    memo: &str,
+) -> Result<String> {
+    let (signing_key, from_address) = native_signer(config, from_private_key_hex)?;
+    let msg = bank_send(config, &from_address, to_address, amount_usei);
+    sign_and_broadcast_native(client, config, rpc_url, &signing_key, &from_address, vec![msg], memo, Some(granter)).await
+}
+
+/// Grants `grantee` a basic fee allowance paid by the signing key's account: optionally capped at
+/// `spend_limit` (native denom) and ending at `expiration`.
+pub async fn grant_fee_allowance(
+    client: &HttpClient,
+    config: &Config,
+    rpc_url: &str,
+    granter_private_key_hex: &str,
+    grantee: &str,
+    spend_limit: Option<u64>,
+    expiration: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<String> {
+    let (signing_key, granter) = native_signer(config, granter_private_key_hex)?;
+    parse_account_id(grantee, "grantee")?;
+    let allowance = BasicAllowance {
+        spend_limit: spend_limit
+            .map(|amount| vec![Coin { denom: config.native_denom.clone(), amount: amount.to_string() }])
+            .unwrap_or_default(),
+        expiration: expiration.map(|t| cosmrs::proto::Timestamp { seconds: t.timestamp(), nanos: t.timestamp_subsec_nanos() as i32 }),
+    };
+    let msg = MsgGrantAllowance {
+        granter: granter.clone(),
+        grantee: grantee.to_string(),
+        allowance: Some(Any {
+            type_url: "/cosmos.feegrant.v1beta1.BasicAllowance".to_string(),
+            value: allowance.encode_to_vec(),
+        }),
+    };
+    let any_msg = Any {
+        type_url: "/cosmos.feegrant.v1beta1.MsgGrantAllowance".to_string(),
+        value: msg.encode_to_vec(),
+    };
+    sign_and_broadcast_native(client, config, rpc_url, &signing_key, &granter, vec![any_msg], "", None).await
+}
+
+fn bank_send(config: &Config, from_address: &str, to_address: &str, amount_usei: u64) -> Any {
+    let msg = MsgSend {
+        from_address: from_address.to_string(),
+        to_address: to_address.to_string(),
+        amount: vec![Coin { denom: config.native_denom.clone(), amount: amount_usei.to_string() }],
+    };
+    Any {
+        type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+        value: msg.encode_to_vec(),
+    }
+}
+
+fn parse_account_id(address: &str, field: &str) -> Result<cosmrs::AccountId> {
+    address
+        .parse()
+        .map_err(|e| SeiError::InvalidInput(format!("Invalid {} address '{}': {}", field, address, e)))
+}
+
+// Signs `msgs` as one tx from `from_address` and broadcasts it (sync mode), returning the tx hash.
+// With `fee_granter` set, the fee comes out of that account's allowance to the sender.
+#[allow(clippy::too_many_arguments)]
+async fn sign_and_broadcast_native(
+    client: &HttpClient,
+    config: &Config,
+    rpc_url: &str,
+    signing_key: &CosmosSigningKey,
+    from_address: &str,
+    msgs: Vec<Any>,
+    memo: &str,
+    fee_granter: Option<&str>,
+) -> Result<String> {
+    let granter = fee_granter.map(|g| parse_account_id(g, "granter")).transpose()?;
+    if !memo.is_empty() {
+        let limit = max_memo_bytes(client, rpc_url).await;
+        if memo.len() > limit {
+            return Err(SeiError::InvalidInput(format!(
+                "memo is {} bytes; the chain allows at most {}",
+                memo.len(),
+                limit
+            )));
+        }
+    }
 
     // Query account number and sequence
-    let client = Client::new();
-    let acct_res: serde_json::Value = client
-        .get(format!("{}/cosmos/auth/v1beta1/accounts/{}", rpc_url, from_address))
-        .send().await?
-        .json().await?;
+    let acct_res: serde_json::Value = rpc::read_json(client
+        .get(&format!("{}/cosmos/auth/v1beta1/accounts/{}", rpc_url, from_address))
+        .send().await?).await.map_err(SeiError::rpc)?;
+    if acct_res.get("account").is_none() {
+        // Unfunded accounts don't exist on chain yet
+        return Err(SeiError::NotFound(format!("Account {} not found on chain", from_address)));
+    }
     let base_acct = acct_res["account"].clone();
     // handle either base_account nested or direct fields
     let (account_number, sequence) = if base_acct.get("base_account").is_some() {
         let ba = &base_acct["base_account"];
         (
-            ba["account_number"].as_str().ok_or_else(|| anyhow!("missing account_number"))?.parse::<u64>()?,
-            ba["sequence"].as_str().ok_or_else(|| anyhow!("missing sequence"))?.parse::<u64>()?,
+            ba["account_number"].as_str().and_then(|v| v.parse::<u64>().ok()).ok_or_else(|| SeiError::rpc("missing account_number"))?,
+            ba["sequence"].as_str().and_then(|v| v.parse::<u64>().ok()).ok_or_else(|| SeiError::rpc("missing sequence"))?,
         )
     } else {
         (
-            base_acct["account_number"].as_str().ok_or_else(|| anyhow!("missing account_number"))?.parse::<u64>()?,
-            base_acct["sequence"].as_str().ok_or_else(|| anyhow!("missing sequence"))?.parse::<u64>()?,
+            base_acct["account_number"].as_str().and_then(|v| v.parse::<u64>().ok()).ok_or_else(|| SeiError::rpc("missing account_number"))?,
+            base_acct["sequence"].as_str().and_then(|v| v.parse::<u64>().ok()).ok_or_else(|| SeiError::rpc("missing sequence"))?,
         )
     };
 
-    // Construct MsgSend
-    let msg = MsgSend {
-        from_address: from_address.clone(),
-        to_address: to_address.to_string(),
-        amount: vec![Coin { denom: config.native_denom.clone(), amount: amount_usei.to_string() }],
-    };
-    let any_msg = Any {
-        type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
-        value: msg.encode_to_vec(),
-    };
-
     // Tx body
-    let body = Body::new(vec![any_msg], "", 0u32);
+    let body = Body::new(msgs, memo, 0u32);
 
     // Fee
-    let fee_amount = cosmrs::Coin::new(config.native_fee_amount as u128, &config.native_denom)
-        .map_err(|e| anyhow!("invalid fee coin: {}", e))?;
-    let fee = Fee::from_amount_and_gas(fee_amount, config.native_gas_limit);
+    let (fee_usei, gas_limit) = native_fee(config);
+    let fee_amount = cosmrs::Coin::new(fee_usei as u128, &config.native_denom)
+        .map_err(|e| SeiError::InvalidInput(format!("invalid fee coin: {}", e)))?;
+    let mut fee = Fee::from_amount_and_gas(fee_amount, gas_limit);
+    fee.granter = granter;
 
     // Signer info
-    let signer_info = SignerInfo::single_direct(Some(public_key), sequence);
+    let signer_info = SignerInfo::single_direct(Some(signing_key.public_key()), sequence);
     let auth_info = AuthInfo { signer_infos: vec![signer_info], fee };
 
     // SignDoc
     let sign_doc = SignDoc::new(
         &body,
         &auth_info,
-        &config.native_chain_id.parse().context("invalid native chain id")?,
+        &config.native_chain_id.parse().map_err(|e| SeiError::InvalidInput(format!("invalid native chain id: {}", e)))?,
         account_number,
-    ).map_err(|e| anyhow!("signdoc error: {}", e))?;
-    let tx_raw = sign_doc.sign(&signing_key).map_err(|e| anyhow!("sign error: {}", e))?;
+    ).map_err(|e| SeiError::Signing(format!("signdoc error: {}", e)))?;
+    let tx_raw = sign_doc.sign(signing_key).map_err(|e| SeiError::Signing(format!("sign error: {}", e)))?;
 
     // Broadcast
-    let tx_bytes = tx_raw.to_bytes().map_err(|e| anyhow!("encode tx error: {}", e))?;
+    let tx_bytes = tx_raw.to_bytes().map_err(|e| SeiError::Signing(format!("encode tx error: {}", e)))?;
     let payload = json!({
         "tx_bytes": BASE64STD.encode(tx_bytes),
         "mode": "BROADCAST_MODE_SYNC"
     });
-    let resp: serde_json::Value = client
-        .post(format!("{}/cosmos/tx/v1beta1/txs", rpc_url))
+    let resp: serde_json::Value = rpc::read_json(client
+        .post(&format!("{}/cosmos/tx/v1beta1/txs", rpc_url))
         .json(&payload)
-        .send().await?
-        .json().await?;
+        .send().await?).await.map_err(SeiError::rpc)?;
 
-    if let Some(err) = resp.get("code").and_then(|c| c.as_i64()).filter(|code| *code != 0) {
-        return Err(anyhow!("native tx failed with code {}: {}", err, resp));
+    let code = resp["tx_response"]["code"].as_i64().or_else(|| resp["code"].as_i64()).unwrap_or(0);
+    if code != 0 {
+        let raw_log = resp["tx_response"]["raw_log"].as_str().unwrap_or_default();
+        if let Some(granter) = fee_granter.filter(|_| is_missing_fee_grant(raw_log)) {
+            return Err(SeiError::NotFound(format!(
+                "No usable fee grant from {} to {}; create one with grant_fee_allowance or check its limit and expiry ({})",
+                granter, from_address, raw_log
+            )));
+        }
+        // Cosmos SDK sdkerrors.ErrInsufficientFunds
+        if code == 5 {
+            return Err(SeiError::InsufficientFunds(raw_log.to_string()));
+        }
+        return Err(SeiError::Rpc(format!("native tx failed with code {}: {}", code, resp)));
     }
     let txhash = resp["tx_response"]["txhash"].as_str()
         .or_else(|| resp["txhash"].as_str())
-        .ok_or_else(|| anyhow!("missing txhash in response"))?;
+        .ok_or_else(|| SeiError::rpc("missing txhash in response"))?;
+    broadcasts::record(txhash);
     Ok(txhash.to_string())
 }
 
+/// Memo limit used when the chain's auth params can't be read (the Cosmos SDK default).
+pub const DEFAULT_MAX_MEMO_BYTES: usize = 256;
+
+// The auth module's `max_memo_characters`, which the ante handler compares against the memo's byte length.
+async fn max_memo_bytes(client: &HttpClient, rest_url: &str) -> usize {
+    let url = format!("{}/cosmos/auth/v1beta1/params", rest_url.trim_end_matches('/'));
+    let params: Option<Value> = match client.get(&url).send().await {
+        Ok(resp) => rpc::read_json(resp).await.ok(),
+        Err(_) => None,
+    };
+    params
+        .as_ref()
+        .and_then(|p| p["params"]["max_memo_characters"].as_str())
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_MEMO_BYTES)
+}
+
+// The feegrant ante handler's rejections: no grant at all, or one that is spent or expired.
+fn is_missing_fee_grant(raw_log: &str) -> bool {
+    let log = raw_log.to_lowercase();
+    log.contains("fee-grant not found") || log.contains("fee allowance") || log.contains("fee limit exceeded")
+}
+
 pub async fn send_transaction(
+    client: &HttpClient,
     config: &Config,
     chain_id: &str,
     recipient_address: &str,
@@ -287,9 +695,9 @@ pub async fn send_transaction(
             use std::str::FromStr;
 
             let wallet = LocalWallet::from_str(&config.tx_private_key_evm)
-                .context("Failed to load sender wallet from private key")?;
+                .map_err(|e| SeiError::Signing(format!("Failed to load sender wallet from private key: {}", e)))?;
             let recipient = Address::from_str(recipient_address)
-                .context("Invalid recipient EVM address format")?;
+                .map_err(|e| SeiError::InvalidInput(format!("Invalid recipient EVM address format: {}", e)))?;
             let value = U256::from(amount);
             let gas_limit = U256::from(config.native_gas_limit);
             let gas_price = U256::from(config.native_fee_amount);
@@ -301,15 +709,19 @@ pub async fn send_transaction(
                 .gas_price(gas_price);
 
             let tx_response = send_evm_transaction(
+                client,
                 rpc_url,
                 wallet,
                 tx_request,
-                nonce_manager
+                nonce_manager,
+                config.tx_type(chain_id),
+                config.max_gas_price(chain_id).map(U256::from),
             ).await?;
             Ok(tx_response.tx_hash)
         }
         ChainType::Native => {
             send_native_transaction(
+                client,
                 config,
                 recipient_address,
                 amount,
@@ -318,4 +730,148 @@ pub async fn send_transaction(
             ).await
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blockchain::services::rpc::FnTransport;
+    use ethers_core::utils::rlp::Rlp;
+    use std::sync::{Arc, Mutex};
+
+    const KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    // Node with nonce 7 that rejects the first `rejections` broadcasts with `rejection`;
+    // returns the transport and the nonces of every broadcast it saw.
+    fn node(rejections: usize, rejection: &'static str) -> (Arc<dyn RpcTransport>, Arc<Mutex<Vec<U256>>>) {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let seen = sent.clone();
+        let transport = FnTransport(move |method: &str, params: &Value| {
+            let result = match method {
+                "eth_getTransactionCount" => json!("0x7"),
+                "eth_chainId" => json!("0x530"),
+                "eth_estimateGas" => json!("0x5208"),
+                "eth_sendRawTransaction" => {
+                    let bytes = hex::decode(params[0].as_str().unwrap().trim_start_matches("0x")).unwrap();
+                    let (tx, _) = TypedTransaction::decode_signed(&Rlp::new(&bytes)).unwrap();
+                    let mut sent = seen.lock().unwrap();
+                    sent.push(*tx.nonce().unwrap());
+                    if sent.len() <= rejections {
+                        return json!({ "jsonrpc": "2.0", "id": 1, "error": { "code": -32000, "message": rejection } });
+                    }
+                    json!(format!("0x{:064x}", sent.len()))
+                }
+                _ => Value::Null,
+            };
+            json!({ "jsonrpc": "2.0", "id": 1, "result": result })
+        });
+        (Arc::new(transport), sent)
+    }
+
+    async fn send(transport: &Arc<dyn RpcTransport>, nonces: &NonceManager, bump: Option<GasBump>) -> Result<SentTransaction> {
+        send_capped(transport, nonces, None, bump).await
+    }
+
+    // Sends at a 1 gwei gas price under an optional MAX_GAS_PRICES cap.
+    async fn send_capped(transport: &Arc<dyn RpcTransport>, nonces: &NonceManager, cap: Option<u64>, bump: Option<GasBump>) -> Result<SentTransaction> {
+        let tx = TransactionRequest::new().to(ethers_core::types::Address::zero()).value(1).gas_price(1_000_000_000u64);
+        let policy = FeePolicy { tx_type: TxType::Legacy, max_gas_price: cap.map(U256::from), bump, ..FeePolicy::default() };
+        send_evm_transaction_with(transport.as_ref(), "mock", KEY.parse().unwrap(), tx, nonces, policy).await
+    }
+
+    #[tokio::test]
+    async fn test_sequential_sends_take_consecutive_nonces() {
+        let (transport, sent) = node(0, "");
+        let nonces = NonceManager::new().with_transport(transport.clone());
+        send(&transport, &nonces, None).await.unwrap();
+        send(&transport, &nonces, None).await.unwrap();
+        assert_eq!(*sent.lock().unwrap(), [U256::from(7), U256::from(8)]);
+    }
+
+    #[tokio::test]
+    async fn test_underpriced_send_is_retried_at_same_nonce() {
+        let (transport, sent) = node(1, "transaction underpriced");
+        let nonces = NonceManager::new().with_transport(transport.clone());
+        let bump = GasBump { percent: 10, max_bumps: 2 };
+        let result = send(&transport, &nonces, Some(bump)).await.unwrap();
+        assert_eq!(result.bumps, 1);
+        assert_eq!(result.fees.max_fee_per_gas, U256::from(1_100_000_000u64));
+        assert_eq!(*sent.lock().unwrap(), [U256::from(7), U256::from(7)]);
+    }
+
+    #[tokio::test]
+    async fn test_gas_price_cap() {
+        let (transport, sent) = node(0, "");
+        let nonces = NonceManager::new().with_transport(transport.clone());
+        send_capped(&transport, &nonces, None, None).await.expect("no cap set");
+        send_capped(&transport, &nonces, Some(1_000_000_000), None).await.expect("price equal to the cap");
+        let err = send_capped(&transport, &nonces, Some(999_999_999), None).await.unwrap_err();
+        assert!(matches!(&err, SeiError::InvalidInput(m) if m.contains("gas price exceeds configured maximum")), "{:?}", err);
+        assert_eq!(sent.lock().unwrap().len(), 2, "over-cap send must not be broadcast");
+
+        // A bump past the cap stops retrying instead of overpaying
+        let (transport, sent) = node(1, "transaction underpriced");
+        let nonces = NonceManager::new().with_transport(transport.clone());
+        let bump = GasBump { percent: 10, max_bumps: 2 };
+        let err = send_capped(&transport, &nonces, Some(1_050_000_000), Some(bump)).await.unwrap_err();
+        assert!(err.to_string().contains("gas price exceeds configured maximum"), "{}", err);
+        assert_eq!(sent.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_base_fee_is_cached_and_backs_priority_fee_quotes() {
+        let lookups = Arc::new(Mutex::new(0));
+        let counted = lookups.clone();
+        let transport = FnTransport(move |method: &str, _params: &Value| {
+            let result = match method {
+                "eth_getBlockByNumber" => {
+                    *counted.lock().unwrap() += 1;
+                    json!({ "number": "0x10", "baseFeePerGas": "0x64" })
+                }
+                _ => Value::Null,
+            };
+            json!({ "jsonrpc": "2.0", "id": 1, "result": result })
+        });
+        let url = "mock-base-fee";
+        let (fee, cached) = latest_base_fee(&transport, url).await.unwrap();
+        assert_eq!(fee, BaseFee { block_number: 16, base_fee_per_gas: Some(U256::from(100)) });
+        assert!(!cached);
+
+        // A tip alone is enough: the max fee is built on the cached base fee, without eth_feeHistory
+        let tx = TransactionRequest::new().from(ethers_core::types::Address::zero()).gas(21_000);
+        let quote = quote_gas_with_tip(&transport, url, &tx, TxType::Eip1559, Some(U256::from(3))).await.unwrap();
+        assert_eq!(quote.max_priority_fee_per_gas, Some(U256::from(3)));
+        assert_eq!(quote.max_fee_per_gas, U256::from(203));
+        assert_eq!(*lookups.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_base_fee_from_block() {
+        let fee = BaseFee::from_block(&json!({ "number": "0x5" })).unwrap();
+        assert_eq!(fee.base_fee_per_gas, None, "pre-EIP-1559 blocks carry no base fee");
+        assert!(BaseFee::from_block(&Value::Null).is_none());
+    }
+
+    #[test]
+    fn test_sweep_value_subtracts_the_exact_fee() {
+        let fees = GasQuote { gas_limit: 21_000.into(), max_fee_per_gas: 1_000_000_000u64.into(), max_priority_fee_per_gas: None };
+        let one_sei = U256::exp10(18);
+        assert_eq!(sweep_value(one_sei, &fees), Some(one_sei - U256::from(21_000_000_000_000u64)));
+        assert_eq!(sweep_value(one_sei, &fees).unwrap() + fees.max_fee(), one_sei);
+        // A balance equal to or below the fee leaves nothing to send
+        assert_eq!(sweep_value(U256::from(21_000_000_000_000u64), &fees), None);
+        assert_eq!(sweep_value(U256::from(1), &fees), None);
+        assert_eq!(sweep_value(U256::zero(), &fees), None);
+    }
+
+    #[tokio::test]
+    async fn test_rejections_are_classified() {
+        let (transport, _) = node(1, "insufficient funds for gas * price + value");
+        let nonces = NonceManager::new().with_transport(transport.clone());
+        let err = send(&transport, &nonces, None).await.unwrap_err();
+        assert!(matches!(err, SeiError::InsufficientFunds(_)), "{:?}", err);
+
+        let (transport, _) = node(1, "transaction underpriced");
+        let err = send(&transport, &NonceManager::new().with_transport(transport.clone()), None).await.unwrap_err();
+        assert!(is_underpriced(&err), "{:?}", err);
+    }
+}
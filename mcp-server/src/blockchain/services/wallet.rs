@@ -10,6 +10,9 @@ use crate::blockchain::models::{ChainType, DualNetworkWallet, ImportWalletError,
 use secrecy::{Secret, SecretString};
 use bip32::{DerivationPath, XPrv};
 
+// Length of mnemonics generated when the caller doesn't ask for one
+pub const DEFAULT_MNEMONIC_WORDS: usize = 24;
+
 // Network-specific derivation paths
 const SEI_NATIVE_HD_PATH: &str = "m/44'/118'/0'/0/0"; // Cosmos path
 const SEI_EVM_HD_PATH: &str = "m/44'/60'/0'/0/0";    // Ethereum path
@@ -27,14 +30,25 @@ impl SecureWalletManager {
 
     /// Generate a secure wallet for the specified network
     pub fn generate_wallet(&self) -> Result<WalletResponse, WalletGenerationError> {
+        self.generate_wallet_with(DEFAULT_MNEMONIC_WORDS, "")
+    }
+
+    /// Generate a wallet from a fresh `word_count`-word (12 or 24) mnemonic. A non-empty BIP-39
+    /// `passphrase` is mixed into the seed, so the same phrase is needed again to import it.
+    pub fn generate_wallet_with(&self, word_count: usize, passphrase: &str) -> Result<WalletResponse, WalletGenerationError> {
         info!("Generating secure wallet for {:?} network", self.chain_type);
-        
+
+        // BIP-39: 32 bits of entropy per 3 words
+        let entropy_len = match word_count {
+            12 | 24 => word_count / 3 * 4,
+            n => return Err(WalletGenerationError::KeyGenerationFailed(format!("unsupported mnemonic length {} (use 12 or 24)", n))),
+        };
         let mut entropy = [0u8; 32];
-        rand::thread_rng().fill_bytes(&mut entropy);
-        let mnemonic = Mnemonic::from_entropy_in(Language::English, &entropy).unwrap();
+        rand::thread_rng().fill_bytes(&mut entropy[..entropy_len]);
+        let mnemonic = Mnemonic::from_entropy_in(Language::English, &entropy[..entropy_len])?;
         let phrase = mnemonic.to_string();
 
-        let seed = mnemonic.to_seed("");
+        let seed = mnemonic.to_seed(passphrase);
         let private_key = self.derive_network_key(&seed)?;
         
         let mut dual_wallet = DualNetworkWallet::from_private_key(&private_key.to_bytes());
@@ -48,10 +62,16 @@ impl SecureWalletManager {
 
     /// Import wallet with network-specific validation
     pub fn import_wallet(&self, input: &str) -> Result<WalletResponse, ImportWalletError> {
+        self.import_wallet_with_passphrase(input, "")
+    }
+
+    /// Import a wallet; `passphrase` is the BIP-39 passphrase used when the mnemonic was created
+    /// and is ignored for raw private keys.
+    pub fn import_wallet_with_passphrase(&self, input: &str, passphrase: &str) -> Result<WalletResponse, ImportWalletError> {
         info!("Importing wallet for {:?} network", self.chain_type);
-        
+
         if let Ok(mnemonic) = Mnemonic::from_str(input) {
-            let seed = mnemonic.to_seed("");
+            let seed = mnemonic.to_seed(passphrase);
             let private_key = self.derive_network_key(&seed)
                 .map_err(|e| ImportWalletError::InvalidMnemonic(e.to_string()))?;
             
@@ -179,17 +199,112 @@ pub fn create_wallet_for_network(chain_type: ChainType) -> Result<WalletResponse
     manager.generate_wallet()
 }
 
+/// EVM wallet from a new `word_count`-word mnemonic, seeded with an optional BIP-39 passphrase.
+pub fn create_wallet_with_options(word_count: usize, passphrase: &str) -> Result<WalletResponse, WalletGenerationError> {
+    SecureWalletManager::new(ChainType::Evm).generate_wallet_with(word_count, passphrase)
+}
+
 pub fn import_wallet(input: &str) -> Result<WalletResponse, ImportWalletError> {
     // Default to EVM for backward compatibility
     let manager = SecureWalletManager::new(ChainType::Evm);
     manager.import_wallet(input)
 }
 
+/// EVM import of a mnemonic created with a BIP-39 passphrase (or a raw private key).
+pub fn import_wallet_with_passphrase(input: &str, passphrase: &str) -> Result<WalletResponse, ImportWalletError> {
+    SecureWalletManager::new(ChainType::Evm).import_wallet_with_passphrase(input, passphrase)
+}
+
 pub fn import_wallet_for_network(chain_type: ChainType, input: &str) -> Result<WalletResponse, ImportWalletError> {
     let manager = SecureWalletManager::new(chain_type);
     manager.import_wallet(input)
 }
 
+/// Outcome of a vanity search: the wallet if one matched within the limits, and how many keys were tried.
+#[derive(Debug)]
+pub struct VanitySearch {
+    pub wallet: Option<WalletResponse>,
+    pub attempts: u64,
+}
+
+/// Generates random keys until the EVM address (after `0x`) starts with `prefix`, giving up after
+/// `max_attempts` keys or `time_limit`. Case-sensitive matching compares against the EIP-55 checksum form.
+/// CPU-bound: call it from a blocking thread, not the async executor.
+pub fn generate_vanity_address(prefix: &str, case_sensitive: bool, max_attempts: u64, time_limit: std::time::Duration) -> VanitySearch {
+    let wanted = if case_sensitive { prefix.to_string() } else { prefix.to_lowercase() };
+    let started = std::time::Instant::now();
+    let mut rng = rand::thread_rng();
+    let mut secret = [0u8; 32];
+    let mut attempts = 0;
+    while attempts < max_attempts {
+        // Checking the clock every key would dominate the loop
+        if attempts % 1024 == 0 && started.elapsed() >= time_limit {
+            break;
+        }
+        attempts += 1;
+        rng.fill_bytes(&mut secret);
+        let Ok(signing_key) = SigningKey::from_slice(&secret) else {
+            continue;
+        };
+        let public_key = signing_key.verifying_key().to_encoded_point(false);
+        let address = Address::from_slice(&keccak256(&public_key.as_bytes()[1..])[12..]);
+        let candidate = if case_sensitive {
+            ethers_core::utils::to_checksum(&address, None)
+        } else {
+            format!("{:?}", address)
+        };
+        if candidate[2..].starts_with(&wanted) {
+            let wallet = DualNetworkWallet::from_private_key(&secret);
+            return VanitySearch {
+                wallet: Some(WalletResponse {
+                    address: ethers_core::utils::to_checksum(&address, None),
+                    private_key: wallet.private_key_hex(),
+                    mnemonic: None,
+                }),
+                attempts,
+            };
+        }
+    }
+    VanitySearch { wallet: None, attempts }
+}
+
+/// An EIP-712 signature with the digest it covers.
+#[derive(Debug, Clone)]
+pub struct TypedDataSignature {
+    pub digest: [u8; 32],
+    pub signature: ethers_core::types::Signature,
+}
+
+/// Checks that `typed_data` has the EIP-712 shape (`domain`, `types`, `primaryType`, `message`)
+/// and that `primaryType` is defined, then signs its digest with `wallet`.
+/// A JSON-stringified payload, as ethers.js sends, is accepted too.
+pub fn sign_typed_data(wallet: &ethers_signers::LocalWallet, typed_data: &serde_json::Value) -> Result<TypedDataSignature> {
+    use ethers_core::types::transaction::eip712::{Eip712, TypedData};
+    let parsed;
+    let value = match typed_data {
+        serde_json::Value::String(s) => {
+            parsed = serde_json::from_str(s).map_err(|e| anyhow::anyhow!("typed_data is not valid JSON: {}", e))?;
+            &parsed
+        }
+        v => v,
+    };
+    for (field, want_object) in [("domain", true), ("types", true), ("primaryType", false), ("message", true)] {
+        match value.get(field) {
+            Some(v) if v.is_object() == want_object && (want_object || v.is_string()) => {}
+            Some(_) => return Err(anyhow::anyhow!("typed_data.{} must be {}", field, if want_object { "an object" } else { "a string" })),
+            None => return Err(anyhow::anyhow!("typed_data is missing '{}'", field)),
+        }
+    }
+    let primary = value["primaryType"].as_str().unwrap_or_default();
+    if value["types"].get(primary).is_none() {
+        return Err(anyhow::anyhow!("primaryType '{}' is not defined in typed_data.types", primary));
+    }
+    let typed: TypedData = serde_json::from_value(value.clone()).map_err(|e| anyhow::anyhow!("Malformed typed_data: {}", e))?;
+    let digest = typed.encode_eip712().map_err(|e| anyhow::anyhow!("Cannot encode typed_data: {}", e))?;
+    let signature = wallet.sign_hash(digest.into())?;
+    Ok(TypedDataSignature { digest, signature })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -220,4 +335,108 @@ mod tests {
         let addr = wallet.address_for_network(ChainType::Evm);
         assert!(addr.starts_with("0x") && addr.len() == 42, "evm address invalid: {}", addr);
     }
+
+    #[test]
+    fn test_vanity_address_matches_prefix() {
+        let search = generate_vanity_address("a", false, 100_000, std::time::Duration::from_secs(10));
+        let wallet = search.wallet.expect("a one-character prefix is found quickly");
+        assert!(wallet.address.to_lowercase().starts_with("0xa"));
+        assert!(search.attempts >= 1);
+        // The returned key really controls the address
+        let imported = import_wallet(&format!("0x{}", wallet.private_key)).unwrap();
+        assert_eq!(imported.address.to_lowercase(), wallet.address.to_lowercase());
+
+        let search = generate_vanity_address("B", true, 100_000, std::time::Duration::from_secs(10));
+        assert!(search.wallet.unwrap().address.starts_with("0xB"));
+    }
+
+    #[test]
+    fn test_vanity_search_stops_at_attempt_limit() {
+        let search = generate_vanity_address("deadbeefcafe", false, 50, std::time::Duration::from_secs(10));
+        assert!(search.wallet.is_none());
+        assert_eq!(search.attempts, 50);
+    }
+
+    // The "Mail" example from the EIP-712 specification, signed by keccak256("cow")
+    fn mail_typed_data() -> serde_json::Value {
+        serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    {"name": "name", "type": "string"},
+                    {"name": "version", "type": "string"},
+                    {"name": "chainId", "type": "uint256"},
+                    {"name": "verifyingContract", "type": "address"}
+                ],
+                "Person": [{"name": "name", "type": "string"}, {"name": "wallet", "type": "address"}],
+                "Mail": [
+                    {"name": "from", "type": "Person"},
+                    {"name": "to", "type": "Person"},
+                    {"name": "contents", "type": "string"}
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {"name": "Ether Mail", "version": "1", "chainId": 1, "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"},
+            "message": {
+                "from": {"name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"},
+                "to": {"name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"},
+                "contents": "Hello, Bob!"
+            }
+        })
+    }
+
+    #[test]
+    fn test_sign_typed_data_matches_spec_vector() {
+        let wallet: ethers_signers::LocalWallet = hex::encode(keccak256("cow")).parse().unwrap();
+        let signed = sign_typed_data(&wallet, &mail_typed_data()).unwrap();
+        assert_eq!(hex::encode(signed.digest), "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2");
+        assert_eq!(
+            signed.signature.to_string(),
+            "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c"
+        );
+
+        // ethers.js passes the payload JSON-stringified
+        let stringified = serde_json::Value::String(mail_typed_data().to_string());
+        assert_eq!(sign_typed_data(&wallet, &stringified).unwrap().digest, signed.digest);
+    }
+
+    #[test]
+    fn test_sign_typed_data_rejects_malformed_input() {
+        let wallet: ethers_signers::LocalWallet = hex::encode(keccak256("cow")).parse().unwrap();
+        let mut missing = mail_typed_data();
+        missing.as_object_mut().unwrap().remove("message");
+        assert!(sign_typed_data(&wallet, &missing).unwrap_err().to_string().contains("missing 'message'"));
+
+        let mut undefined = mail_typed_data();
+        undefined["primaryType"] = serde_json::json!("Letter");
+        assert!(sign_typed_data(&wallet, &undefined).unwrap_err().to_string().contains("'Letter' is not defined"));
+
+        let mut bad_field = mail_typed_data();
+        bad_field["message"]["from"]["wallet"] = serde_json::json!("not an address");
+        assert!(sign_typed_data(&wallet, &bad_field).is_err());
+    }
+
+    #[test]
+    fn test_mnemonic_word_counts() {
+        for words in [12, 24] {
+            let wallet = create_wallet_with_options(words, "").unwrap();
+            assert_eq!(wallet.mnemonic.unwrap().split_whitespace().count(), words);
+        }
+        assert!(create_wallet_with_options(15, "").is_err());
+    }
+
+    #[test]
+    fn test_passphrase_changes_derived_address() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let plain = import_wallet_with_passphrase(phrase, "").unwrap();
+        let with_pass = import_wallet_with_passphrase(phrase, "TREZOR").unwrap();
+        assert_eq!(plain.address, import_wallet(phrase).unwrap().address);
+        assert_ne!(plain.address, with_pass.address);
+        // Known BIP-39/BIP-44 vectors for m/44'/60'/0'/0/0
+        assert_eq!(plain.address.to_lowercase(), "0x9858effd232b4033e47d90003d41ec34ecaeda94");
+
+        let created = create_wallet_with_options(12, "hunter2").unwrap();
+        let mnemonic = created.mnemonic.clone().unwrap();
+        assert_eq!(import_wallet_with_passphrase(&mnemonic, "hunter2").unwrap().address, created.address);
+        assert_ne!(import_wallet(&mnemonic).unwrap().address, created.address);
+    }
 }
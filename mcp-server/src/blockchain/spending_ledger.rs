@@ -0,0 +1,259 @@
+// src/blockchain/spending_ledger.rs
+
+//! Cumulative spend per sender across every tool that moves funds, checked against SPENDING_BUDGETS.
+//!
+//! A budget allows `amount` base units (wei, or usei on native chains) per `window_secs`. A sender's
+//! window opens with its first transfer and lasts `window_secs`; the next transfer after that opens a
+//! fresh one. With SPENDING_LEDGER_PATH set the windows survive restarts, so restarting the server
+//! does not hand an agent a new allowance. A ledger file that exists but can't be read fails closed:
+//! every transfer under a budget is refused until an operator repairs or removes it.
+
+use crate::config::SpendingBudget;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use ethers_core::types::U256;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tracing::{error, warn};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct SpendWindow {
+    started_at: DateTime<Utc>,
+    spent: U256,
+}
+
+/// One sender's use of its budget in the current window.
+#[derive(Debug, Clone, Copy)]
+pub struct BudgetStatus {
+    pub budget: U256,
+    pub spent: U256,
+    pub remaining: U256,
+    pub window_secs: u64,
+    /// When the current window ends; `None` while no window is open (nothing spent recently).
+    pub resets_at: Option<DateTime<Utc>>,
+}
+
+/// Spend held against a budget for a transfer in flight. Dropping it gives the spend back, so a send
+/// that fails or is abandoned part-way never leaves a phantom charge; `commit` it once the transfer is out.
+#[derive(Debug)]
+#[must_use = "dropping a reservation releases it"]
+pub struct Reservation {
+    ledger: Arc<SpendingLedger>,
+    key: String,
+    amount: U256,
+    committed: bool,
+}
+
+impl Reservation {
+    /// Keeps the spend against the budget: the transfer was sent.
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl Drop for Reservation {
+    fn drop(&mut self) {
+        if !self.committed {
+            self.ledger.release(&self.key, self.amount);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SpendingLedger {
+    path: Option<PathBuf>,
+    windows: Mutex<HashMap<String, SpendWindow>>,
+    // Why the file at `path` could not be loaded; budgets can't be trusted while this is set
+    unreadable: Option<String>,
+}
+
+// Senders are tracked by lowercased address per chain, whatever name the budget was configured under
+fn ledger_key(chain_id: &str, sender: &str) -> String {
+    format!("{}:{}", chain_id, sender.to_lowercase())
+}
+
+impl SpendingLedger {
+    /// Reads the ledger at `path`; a missing file starts empty. `None` keeps it in memory. A file that
+    /// can't be read or parsed is left untouched and the ledger refuses every reservation.
+    pub fn load(path: Option<PathBuf>) -> Self {
+        let loaded = match path.as_ref().filter(|p| p.exists()) {
+            Some(p) => fs::read_to_string(p).map_err(anyhow::Error::from).and_then(|s| Ok(serde_json::from_str(&s)?)).map_err(|e| {
+                error!("Spending ledger {} is unreadable, refusing budgeted transfers: {}", p.display(), e);
+                format!("spending ledger {} is unreadable ({}); repair or remove it to allow budgeted transfers", p.display(), e)
+            }),
+            None => Ok(HashMap::new()),
+        };
+        let (windows, unreadable) = match loaded {
+            Ok(windows) => (windows, None),
+            Err(reason) => (HashMap::new(), Some(reason)),
+        };
+        Self { path, windows: Mutex::new(windows), unreadable }
+    }
+
+    /// Where `sender` on `chain_id` stands against `budget` as of `now`.
+    pub fn status(&self, chain_id: &str, sender: &str, budget: &SpendingBudget, now: DateTime<Utc>) -> Result<BudgetStatus> {
+        self.ensure_readable()?;
+        let windows = self.windows.lock().unwrap();
+        Ok(Self::status_of(windows.get(&ledger_key(chain_id, sender)), budget, now))
+    }
+
+    fn ensure_readable(&self) -> Result<()> {
+        match &self.unreadable {
+            Some(reason) => Err(anyhow!("{}", reason)),
+            None => Ok(()),
+        }
+    }
+
+    fn status_of(window: Option<&SpendWindow>, budget: &SpendingBudget, now: DateTime<Utc>) -> BudgetStatus {
+        let limit = budget.amount_wei();
+        let resets_at = |w: &SpendWindow| w.started_at + chrono::Duration::seconds(budget.window_secs as i64);
+        let open = window.filter(|w| resets_at(w) > now);
+        let spent = open.map(|w| w.spent).unwrap_or_default();
+        BudgetStatus {
+            budget: limit,
+            spent,
+            remaining: limit.saturating_sub(spent),
+            window_secs: budget.window_secs,
+            resets_at: open.map(resets_at),
+        }
+    }
+
+    /// Holds `amount` against `budget`, refusing it when the window's remaining allowance can't cover it.
+    /// Checking and recording happen under one lock, so concurrent transfers cannot both slip under.
+    pub fn reserve(self: &Arc<Self>, chain_id: &str, sender: &str, budget: &SpendingBudget, amount: U256, now: DateTime<Utc>) -> Result<Reservation> {
+        self.ensure_readable()?;
+        let key = ledger_key(chain_id, sender);
+        let mut windows = self.windows.lock().unwrap();
+        let status = Self::status_of(windows.get(&key), budget, now);
+        if amount > status.remaining {
+            let resets = status.resets_at.map(|t| format!("; resets at {}", t.to_rfc3339())).unwrap_or_default();
+            return Err(anyhow!(
+                "spending budget exceeded for {} on {}: {} wei requested, {} of {} wei left this window{}",
+                sender,
+                chain_id,
+                amount,
+                status.remaining,
+                status.budget,
+                resets
+            ));
+        }
+        let window = match windows.get(&key) {
+            Some(w) if status.resets_at.is_some() => SpendWindow { started_at: w.started_at, spent: w.spent + amount },
+            _ => SpendWindow { started_at: now, spent: amount },
+        };
+        // Only counted once it is on disk: a failed write must not leave a phantom spend behind
+        let previous = windows.insert(key.clone(), window);
+        if let Err(e) = self.persist(&windows) {
+            match previous {
+                Some(w) => windows.insert(key, w),
+                None => windows.remove(&key),
+            };
+            return Err(e);
+        }
+        Ok(Reservation { ledger: self.clone(), key, amount, committed: false })
+    }
+
+    // Gives back a reservation whose transfer was never sent
+    fn release(&self, key: &str, amount: U256) {
+        let mut windows = self.windows.lock().unwrap();
+        if let Some(window) = windows.get_mut(key) {
+            window.spent = window.spent.saturating_sub(amount);
+        }
+        if let Err(e) = self.persist(&windows) {
+            warn!("Failed to persist spending ledger: {}", e);
+        }
+    }
+
+    fn persist(&self, windows: &HashMap<String, SpendWindow>) -> Result<()> {
+        if let Some(path) = &self.path {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            crate::utils::write_atomic(path, serde_json::to_string(windows)?.as_bytes())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SENDER: &str = "0x000000000000000000000000000000000000dEaD";
+
+    fn budget() -> SpendingBudget {
+        SpendingBudget { amount: "1000".to_string(), window_secs: 3600 }
+    }
+
+    fn at(time: &str) -> DateTime<Utc> {
+        time.parse().unwrap()
+    }
+
+    #[test]
+    fn test_spend_up_to_budget_then_refused_until_window_resets() {
+        let ledger = Arc::new(SpendingLedger::load(None));
+        let start = at("2026-03-01T08:00:00Z");
+        ledger.reserve("sei-evm-testnet", SENDER, &budget(), U256::from(600), start).unwrap().commit();
+        ledger.reserve("sei-evm-testnet", SENDER, &budget(), U256::from(400), at("2026-03-01T08:30:00Z")).unwrap().commit();
+
+        let status = ledger.status("sei-evm-testnet", &SENDER.to_lowercase(), &budget(), at("2026-03-01T08:45:00Z")).unwrap();
+        assert_eq!((status.spent, status.remaining), (U256::from(1000), U256::zero()));
+        assert_eq!(status.resets_at, Some(at("2026-03-01T09:00:00Z")), "the window runs from its first transfer");
+        let err = ledger.reserve("sei-evm-testnet", SENDER, &budget(), U256::one(), at("2026-03-01T08:45:00Z")).unwrap_err();
+        assert!(err.to_string().contains("spending budget exceeded"), "{}", err);
+        assert!(ledger.reserve("atlantic-2", SENDER, &budget(), U256::from(1000), start).is_ok(), "budgets are per chain");
+
+        let later = at("2026-03-01T09:00:00Z");
+        let status = ledger.status("sei-evm-testnet", SENDER, &budget(), later).unwrap();
+        assert_eq!((status.spent, status.resets_at), (U256::zero(), None));
+        ledger.reserve("sei-evm-testnet", SENDER, &budget(), U256::from(1000), later).unwrap().commit();
+    }
+
+    #[test]
+    fn test_dropped_reservation_frees_the_allowance() {
+        let ledger = Arc::new(SpendingLedger::load(None));
+        let now = Utc::now();
+        let held = ledger.reserve("sei-evm-testnet", SENDER, &budget(), U256::from(900), now).unwrap();
+        assert!(ledger.reserve("sei-evm-testnet", SENDER, &budget(), U256::from(200), now).is_err());
+        drop(held);
+        ledger.reserve("sei-evm-testnet", SENDER, &budget(), U256::from(200), now).unwrap().commit();
+        assert_eq!(ledger.status("sei-evm-testnet", SENDER, &budget(), now).unwrap().spent, U256::from(200), "a commit keeps the spend");
+    }
+
+    #[test]
+    fn test_failed_persist_does_not_count_the_spend() {
+        let dir = tempfile::tempdir().unwrap();
+        let blocker = dir.path().join("not-a-dir");
+        fs::write(&blocker, "").unwrap();
+        // A file where the ledger's directory should be makes every write fail
+        let ledger = Arc::new(SpendingLedger::load(Some(blocker.join("spending_ledger.json"))));
+        let now = Utc::now();
+        assert!(ledger.reserve("sei-evm-testnet", SENDER, &budget(), U256::from(250), now).is_err());
+        assert_eq!(ledger.status("sei-evm-testnet", SENDER, &budget(), now).unwrap().spent, U256::zero());
+    }
+
+    #[test]
+    fn test_windows_survive_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("spending_ledger.json");
+        let now = Utc::now();
+        Arc::new(SpendingLedger::load(Some(path.clone()))).reserve("sei-evm-testnet", SENDER, &budget(), U256::from(250), now).unwrap().commit();
+        let status = SpendingLedger::load(Some(path)).status("sei-evm-testnet", SENDER, &budget(), now).unwrap();
+        assert_eq!(status.spent, U256::from(250));
+    }
+
+    #[test]
+    fn test_corrupt_ledger_refuses_transfers_and_is_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("spending_ledger.json");
+        fs::write(&path, "{\"sei-evm-testnet:0xdead\": {\"started_at\"").unwrap();
+        let ledger = Arc::new(SpendingLedger::load(Some(path.clone())));
+        let err = ledger.reserve("sei-evm-testnet", SENDER, &budget(), U256::one(), Utc::now()).unwrap_err();
+        assert!(err.to_string().contains("is unreadable"), "{}", err);
+        assert!(ledger.status("sei-evm-testnet", SENDER, &budget(), Utc::now()).is_err());
+        assert!(fs::read_to_string(&path).unwrap().ends_with("\"started_at\""), "the file is left for an operator");
+    }
+}
@@ -0,0 +1,168 @@
+// src/blockchain/websocket.rs
+
+use async_tungstenite::tokio::connect_async;
+use async_tungstenite::tungstenite::Message;
+use chrono::{DateTime, Utc};
+use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tracing::{info, warn};
+
+/// Snapshot of the managed connection, reported by the `websocket_status` tool.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct WsStatus {
+    pub configured: bool,
+    pub connected: bool,
+    pub url: String,
+    pub last_error: Option<String>,
+    pub last_connected_at: Option<DateTime<Utc>>,
+    pub reconnect_attempts: u64,
+}
+
+/// Owns a single long-lived WebSocket connection to the node and keeps it alive with
+/// exponential-backoff reconnects. Subscription tools share it instead of dialing per call.
+pub struct WsManager {
+    url: String,
+    backoff_min: Duration,
+    backoff_max: Duration,
+    status: RwLock<WsStatus>,
+    started: AtomicBool,
+    outbound_tx: mpsc::UnboundedSender<String>,
+    outbound_rx: tokio::sync::Mutex<Option<mpsc::UnboundedReceiver<String>>>,
+    inbound: broadcast::Sender<String>,
+}
+
+impl WsManager {
+    pub fn new(url: &str, backoff_min: Duration, backoff_max: Duration) -> Self {
+        let (outbound_tx, outbound_rx) = mpsc::unbounded_channel();
+        let (inbound, _) = broadcast::channel(256);
+        Self {
+            url: url.to_string(),
+            backoff_min,
+            backoff_max: backoff_max.max(backoff_min),
+            status: RwLock::new(WsStatus {
+                configured: !url.is_empty(),
+                url: url.to_string(),
+                ..WsStatus::default()
+            }),
+            started: AtomicBool::new(false),
+            outbound_tx,
+            outbound_rx: tokio::sync::Mutex::new(Some(outbound_rx)),
+            inbound,
+        }
+    }
+
+    pub fn status(&self) -> WsStatus {
+        self.status.read().map(|s| s.clone()).unwrap_or_default()
+    }
+
+    /// Queues a text frame; it is delivered once the connection is (re)established.
+    pub fn send(&self, text: String) -> bool {
+        self.outbound_tx.send(text).is_ok()
+    }
+
+    /// Receives every text frame the node pushes.
+    pub fn subscribe(&self) -> broadcast::Receiver<String> {
+        self.inbound.subscribe()
+    }
+
+    /// Spawns the connection task once; later calls and an unconfigured URL are no-ops.
+    pub fn start(self: &Arc<Self>) {
+        if self.url.is_empty() || self.started.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let this = Arc::clone(self);
+        tokio::spawn(async move { this.run().await });
+    }
+
+    async fn run(self: Arc<Self>) {
+        let Some(mut outbound) = self.outbound_rx.lock().await.take() else {
+            return;
+        };
+        let mut backoff = self.backoff_min;
+        loop {
+            match connect_async(self.url.as_str()).await {
+                Ok((stream, _)) => {
+                    info!("WebSocket connected to {}", self.url);
+                    backoff = self.backoff_min;
+                    self.update(|s| {
+                        s.connected = true;
+                        s.last_connected_at = Some(Utc::now());
+                    });
+                    let (mut sink, mut source) = stream.split();
+                    let err = loop {
+                        tokio::select! {
+                            frame = source.next() => match frame {
+                                Some(Ok(Message::Text(text))) => {
+                                    let _ = self.inbound.send(text);
+                                }
+                                Some(Ok(Message::Ping(payload))) => {
+                                    if let Err(e) = sink.send(Message::Pong(payload)).await {
+                                        break e.to_string();
+                                    }
+                                }
+                                Some(Ok(Message::Close(_))) | None => break "connection closed by peer".to_string(),
+                                Some(Ok(_)) => {}
+                                Some(Err(e)) => break e.to_string(),
+                            },
+                            Some(text) = outbound.recv() => {
+                                if let Err(e) = sink.send(Message::Text(text)).await {
+                                    break e.to_string();
+                                }
+                            }
+                        }
+                    };
+                    self.update(|s| {
+                        s.connected = false;
+                        s.last_error = Some(err.clone());
+                    });
+                    warn!("WebSocket to {} dropped: {}", self.url, err);
+                }
+                Err(e) => {
+                    self.update(|s| {
+                        s.connected = false;
+                        s.last_error = Some(e.to_string());
+                    });
+                    warn!("WebSocket connect to {} failed: {}", self.url, e);
+                }
+            }
+            self.update(|s| s.reconnect_attempts += 1);
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(self.backoff_max);
+        }
+    }
+
+    fn update(&self, f: impl FnOnce(&mut WsStatus)) {
+        if let Ok(mut status) = self.status.write() {
+            f(&mut status);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_unreachable_endpoint_records_error_and_retries() {
+        let ws = Arc::new(WsManager::new("ws://127.0.0.1:1", Duration::from_millis(10), Duration::from_millis(20)));
+        ws.start();
+        ws.start();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let status = ws.status();
+        assert!(status.configured);
+        assert!(!status.connected);
+        assert!(status.last_error.is_some());
+        assert!(status.reconnect_attempts >= 2);
+    }
+
+    #[test]
+    fn test_unconfigured_manager_reports_not_configured() {
+        let ws = Arc::new(WsManager::new("", Duration::from_millis(10), Duration::from_millis(20)));
+        ws.start();
+        assert!(!ws.status().configured);
+    }
+}
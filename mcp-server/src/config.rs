@@ -2,15 +2,151 @@
 
 use std::collections::HashMap;
 use std::env;
+use std::fmt;
+use std::path::PathBuf;
 use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+/// Extra HTTP headers sent with every request to one chain's RPC, such as a provider API key.
+/// `Debug` prints header names only, so values never reach the logs.
+#[derive(Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub struct RpcHeaders(pub HashMap<String, String>);
+
+impl fmt::Debug for RpcHeaders {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.0.keys().map(|name| (name, "<redacted>"))).finish()
+    }
+}
+
+/// Display names for the built-in Sei chains (canonical ids, see `normalize_chain_id`).
+pub const DEFAULT_NETWORK_NAMES: &[(&str, &str)] = &[
+    ("sei-evm-testnet", "Sei Atlantic-2 Testnet (EVM)"),
+    ("sei-evm-mainnet", "Sei Pacific-1 Mainnet (EVM)"),
+    ("atlantic-2", "Sei Atlantic-2 Testnet (Cosmos)"),
+    ("pacific-1", "Sei Pacific-1 Mainnet (Cosmos)"),
+];
+
+/// Block explorer link templates for the built-in Sei chains. `{kind}` becomes `tx` or `address`
+/// and `{id}` the hash or address.
+pub const DEFAULT_EXPLORER_URLS: &[(&str, &str)] = &[
+    ("sei-evm-testnet", "https://seitrace.com/{kind}/{id}?chain=atlantic-2"),
+    ("sei-evm-mainnet", "https://seitrace.com/{kind}/{id}?chain=pacific-1"),
+    ("atlantic-2", "https://seitrace.com/{kind}/{id}?chain=atlantic-2"),
+    ("pacific-1", "https://seitrace.com/{kind}/{id}?chain=pacific-1"),
+];
+
+/// Built-in EVM chains with Multicall3 at its canonical address (`multicall::MULTICALL3_ADDRESS`).
+pub const DEFAULT_MULTICALL3_CHAINS: &[&str] = &["sei-evm-testnet", "sei-evm-mainnet"];
+
+/// Confirmations a block needs before `check_finality` calls it final, where FINALITY_CONFIRMATIONS
+/// has no entry. Sei finalizes a block as soon as it is committed.
+pub const DEFAULT_FINALITY_CONFIRMATIONS: u64 = 1;
+
+/// Most a single faucet request dispenses (usei) on chains without a FAUCET_AMOUNTS entry; matches the
+/// faucet API's own FAUCET_AMOUNT_USEI default.
+pub const DEFAULT_FAUCET_AMOUNT: u64 = 100_000;
+
+/// A test ERC-20 token dispensed by the faucet alongside the native gas token.
+#[derive(Clone, Debug, Deserialize)]
+pub struct FaucetToken {
+    pub contract: String,
+    /// Amount in the token's smallest unit.
+    pub amount: String,
+}
+
+/// An ERC-20 whose metadata is trusted from config instead of read from the contract.
+#[derive(Clone, Debug, Deserialize)]
+pub struct KnownToken {
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// How much one sender may transfer per rolling window (SPENDING_BUDGETS).
+#[derive(Clone, Debug, Deserialize)]
+pub struct SpendingBudget {
+    /// Base units (wei, or usei on native chains), as a decimal string.
+    pub amount: String,
+    pub window_secs: u64,
+}
+
+impl SpendingBudget {
+    /// `amount` as a number; validated when the config is loaded.
+    pub fn amount_wei(&self) -> ethers_core::types::U256 {
+        ethers_core::types::U256::from_dec_str(self.amount.trim()).unwrap_or_default()
+    }
+}
+
+/// Which EVM transaction envelope to build when the caller doesn't force one.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TxType {
+    Legacy,
+    Eip1559,
+    /// EIP-1559 when the node answers `eth_feeHistory` (probed once per RPC URL), legacy otherwise.
+    #[default]
+    Auto,
+}
 
 // A struct to hold all configuration, loaded once at startup from the .env file.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct Config {
     pub port: u16,
     pub chain_rpc_urls: HashMap<String, String>,
+    // Per-chain backup RPC endpoints, used in order while the ones before them are failing
+    pub rpc_fallback_urls: HashMap<String, Vec<String>>,
+    // Consecutive transport failures that mark an endpoint unhealthy, and how long it is then skipped
+    pub rpc_failure_threshold: u32,
+    pub rpc_unhealthy_cooldown_ms: u64,
     pub websocket_url: String,
+    // Reconnect backoff bounds for the shared WebSocket connection
+    pub ws_backoff_min_ms: u64,
+    pub ws_backoff_max_ms: u64,
     pub faucet_api_url: String,
+    // Per-chain maximum faucet amount (usei); requests above this are capped. Defaults to DEFAULT_FAUCET_AMOUNT
+    pub faucet_amounts: HashMap<String, u64>,
+    // Per-chain total the faucet may dispense per UTC day, in the same unit; unset = no limit
+    pub faucet_daily_caps: HashMap<String, u64>,
+    // Where the daily faucet totals are kept across restarts; unset = in memory only
+    pub faucet_ledger_path: Option<PathBuf>,
+    // Per-chain optional ERC-20 token dispensed from the tx key
+    pub faucet_tokens: HashMap<String, FaucetToken>,
+    // Per-chain trusted ERC-20 metadata keyed by contract address; skips (and overrides) decimals() calls
+    pub known_tokens: HashMap<String, HashMap<String, KnownToken>>,
+    // Optional per-chain ceiling (smallest unit, decimal string) on any single transfer; unset = no limit
+    pub max_transfer_amounts: HashMap<String, String>,
+    // Per-chain transaction envelope for EVM sends; chains left out use `auto`
+    pub tx_types: HashMap<String, TxType>,
+    // Per-chain faucet balance (smallest unit, as a string) below which get_faucet_status reports "low"
+    pub faucet_low_balance: HashMap<String, String>,
+    // Per-chain USD price of one whole native token, used by preview_transfer; unset = no USD figures
+    pub usd_prices: HashMap<String, f64>,
+    // Per-chain display names shown as `network_name` in responses; overrides DEFAULT_NETWORK_NAMES
+    pub network_display_names: HashMap<String, String>,
+    // Per-chain explorer link templates (`{kind}`, `{id}`); overrides DEFAULT_EXPLORER_URLS
+    pub explorer_urls: HashMap<String, String>,
+    // Per-chain Multicall3 address; an empty string turns batching off for that chain
+    pub multicall3_addresses: HashMap<String, String>,
+    // Per-chain confirmations check_finality requires; defaults to DEFAULT_FINALITY_CONFIRMATIONS
+    pub finality_confirmations: HashMap<String, u64>,
+    // Per-chain Tendermint RPC endpoints for native chains (chain_rpc_urls holds their REST gateway)
+    pub tendermint_rpc_urls: HashMap<String, String>,
+    // Per-chain extra headers (e.g. API keys) for that chain's RPC and Tendermint endpoints
+    pub rpc_headers: HashMap<String, RpcHeaders>,
+    // Optional per-chain gas limit / gas price (wei) for transfers that don't pass their own;
+    // chains without an entry fall back to RPC estimation
+    pub default_gas_limits: HashMap<String, u64>,
+    pub default_gas_prices: HashMap<String, u64>,
+    // Optional per-chain ceiling on the gas price (wei) of any EVM send, estimated or supplied
+    pub max_gas_prices: HashMap<String, u64>,
+    // Optional per-chain eth_getLogs chunk size in blocks, for nodes that cap the queried range
+    pub log_chunk_sizes: HashMap<String, u64>,
+    // `auto_bump` transfers: fee increase per retry (percent) and how many retries to allow
+    pub gas_bump_percent: u64,
+    pub max_gas_bumps: u32,
+    // HTTP gateway used to resolve ipfs:// NFT metadata and image URIs
+    pub ipfs_gateway: String,
     // Kept for non-faucet tx paths
     pub tx_private_key_evm: String,
     pub default_sender_address: Option<String>,
@@ -23,6 +159,53 @@ pub struct Config {
     pub discord_webhook_url: Option<String>,
     pub discord_bot_token: Option<String>,
     pub discord_channel_id: Option<String>,
+    // Opt-in: let redirect_to_seidocs spawn the local browser (off for headless servers)
+    pub open_docs_in_browser: bool,
+    // Maximum accepted MCP request line length in bytes (stdio transport)
+    pub mcp_max_line_bytes: usize,
+    // Requests per minute on the HTTP server per client IP and path, and per bearer token on /mcp; 0 = unlimited
+    pub rate_limit_per_ip: u32,
+    pub rate_limit_per_token: u32,
+    // Chains reachable through MCP tools; empty exposes every chain in chain_rpc_urls
+    pub mcp_exposed_chains: Vec<String>,
+    // `initialize` overrides: instructions text (the chain list is still appended) and capabilities
+    // merged over the defaults by top-level key
+    pub mcp_instructions: Option<String>,
+    // Tools MCP clients may see and call: only enabled_tools when set, never disabled_tools
+    pub enabled_tools: Vec<String>,
+    pub disabled_tools: Vec<String>,
+    // Tag each tool response and its tracing span with a fresh correlation_id (UUID)
+    pub correlation_ids: bool,
+    // Per-chain transfer budgets keyed by wallet name, 0x address or "*" for any sender
+    pub spending_budgets: HashMap<String, HashMap<String, SpendingBudget>>,
+    // Where the spending windows are kept across restarts; unset = in memory only
+    pub spending_ledger_path: Option<PathBuf>,
+    pub mcp_capabilities: serde_json::Map<String, serde_json::Value>,
+    // Opt-in MCP over HTTP/SSE on the HTTP server and the bearer token guarding it; serving it without a
+    // token takes an explicit MCP_HTTP_ALLOW_UNAUTHENTICATED
+    pub mcp_http_enabled: bool,
+    pub mcp_http_token: Option<String>,
+    pub mcp_http_allow_unauthenticated: bool,
+    // Upper bound on list items returned by history/logs tools to keep responses LLM-sized
+    pub mcp_max_items: usize,
+    // Idle timeout for unlock_storage session tokens
+    pub wallet_session_idle_secs: u64,
+    // How long a multisig transfer proposal stays open for approvals
+    pub proposal_ttl_secs: u64,
+    // Optional append-only JSONL audit trail of transfers and faucet drips
+    pub audit_log_path: Option<PathBuf>,
+    // Opt-in rpc_passthrough tool; it can broadcast raw transactions around every other check
+    pub allow_rpc_passthrough: bool,
+    // Reject dust-sized base-unit transfer amounts (likely whole-token figures) unless the call sets confirm_amount
+    pub strict_amounts: bool,
+    // Background nonce resync: how often to run (0 disables) and how recently an address must have sent
+    pub nonce_resync_interval_secs: u64,
+    pub nonce_active_window_secs: u64,
+    // Receipt/balance polling: delay between checks (0 = 2000ms) and how many checks before giving up (0 = until the timeout)
+    pub poll_interval_ms: u64,
+    pub max_poll_attempts: u32,
+    // Most blocks get_recent_blocks fetches per call
+    pub max_recent_blocks: u64,
 }
 
 impl Config {
@@ -43,8 +226,53 @@ impl Config {
                 .parse()
                 .context("PORT must be a valid number")?,
             chain_rpc_urls,
+            rpc_fallback_urls: json_env("RPC_FALLBACK_URLS")?,
+            rpc_failure_threshold: env::var("RPC_FAILURE_THRESHOLD")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .context("RPC_FAILURE_THRESHOLD must be a valid number")?,
+            rpc_unhealthy_cooldown_ms: env::var("RPC_UNHEALTHY_COOLDOWN_MS")
+                .unwrap_or_else(|_| "30000".to_string())
+                .parse()
+                .context("RPC_UNHEALTHY_COOLDOWN_MS must be a valid number")?,
             websocket_url: env::var("WEBSOCKET_URL").unwrap_or_else(|_| "".to_string()),
+            ws_backoff_min_ms: env::var("WS_BACKOFF_MIN_MS")
+                .unwrap_or_else(|_| "500".to_string())
+                .parse()
+                .context("WS_BACKOFF_MIN_MS must be a valid number")?,
+            ws_backoff_max_ms: env::var("WS_BACKOFF_MAX_MS")
+                .unwrap_or_else(|_| "30000".to_string())
+                .parse()
+                .context("WS_BACKOFF_MAX_MS must be a valid number")?,
             faucet_api_url: env::var("FAUCET_API_URL").context("FAUCET_API_URL must be set to the faucet HTTP base URL, e.g. https://your-faucet.onrender.com")?,
+            faucet_amounts: faucet_amounts_env()?,
+            faucet_daily_caps: json_env("FAUCET_DAILY_CAPS")?,
+            faucet_ledger_path: env::var("FAUCET_LEDGER_PATH").ok().filter(|p| !p.trim().is_empty()).map(PathBuf::from),
+            faucet_tokens: json_env("FAUCET_TOKENS")?,
+            known_tokens: json_env("KNOWN_TOKENS")?,
+            max_transfer_amounts: json_env("MAX_TRANSFER_AMOUNTS")?,
+            faucet_low_balance: json_env("FAUCET_LOW_BALANCE")?,
+            usd_prices: json_env("USD_PRICES")?,
+            network_display_names: json_env("NETWORK_DISPLAY_NAMES")?,
+            explorer_urls: explorer_urls_env()?,
+            multicall3_addresses: json_env("MULTICALL3_ADDRESSES")?,
+            finality_confirmations: json_env("FINALITY_CONFIRMATIONS")?,
+            tendermint_rpc_urls: json_env("TENDERMINT_RPC_URLS")?,
+            rpc_headers: rpc_headers_env()?,
+            tx_types: json_env("TX_TYPES")?,
+            default_gas_limits: json_env("DEFAULT_GAS_LIMITS")?,
+            log_chunk_sizes: json_env("LOG_CHUNK_SIZES")?,
+            default_gas_prices: json_env("DEFAULT_GAS_PRICES")?,
+            max_gas_prices: json_env("MAX_GAS_PRICES")?,
+            gas_bump_percent: env::var("GAS_BUMP_PERCENT")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .context("GAS_BUMP_PERCENT must be a valid number")?,
+            max_gas_bumps: env::var("MAX_GAS_BUMPS")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .context("MAX_GAS_BUMPS must be a valid number")?,
+            ipfs_gateway: env::var("IPFS_GATEWAY").unwrap_or_else(|_| "https://ipfs.io/ipfs/".to_string()),
             // Neutral names with backward-compatible fallbacks
             tx_private_key_evm: env::var("TX_PRIVATE_KEY_EVM")
                 .or_else(|_| env::var("FAUCET_PRIVATE_KEY_EVM"))
@@ -68,6 +296,235 @@ impl Config {
             discord_webhook_url: env::var("DISCORD_WEBHOOK_URL").ok(),
             discord_bot_token: env::var("DISCORD_BOT_TOKEN").ok(),
             discord_channel_id: env::var("DISCORD_CHANNEL_ID").ok(),
+            open_docs_in_browser: bool_env("OPEN_DOCS_IN_BROWSER"),
+            mcp_max_line_bytes: env::var("MCP_MAX_LINE_BYTES")
+                .unwrap_or_else(|_| "1048576".to_string())
+                .parse()
+                .context("MCP_MAX_LINE_BYTES must be a valid number")?,
+            rate_limit_per_ip: env::var("RATE_LIMIT_PER_IP")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("RATE_LIMIT_PER_IP must be a valid number")?,
+            rate_limit_per_token: env::var("RATE_LIMIT_PER_TOKEN")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("RATE_LIMIT_PER_TOKEN must be a valid number")?,
+            mcp_exposed_chains: json_env("MCP_EXPOSED_CHAINS")?,
+            mcp_instructions: env::var("MCP_INSTRUCTIONS").ok().filter(|i| !i.trim().is_empty()),
+            enabled_tools: json_env("ENABLED_TOOLS")?,
+            disabled_tools: json_env("DISABLED_TOOLS")?,
+            correlation_ids: bool_env("CORRELATION_IDS"),
+            spending_budgets: spending_budgets_env()?,
+            spending_ledger_path: env::var("SPENDING_LEDGER_PATH").ok().filter(|p| !p.trim().is_empty()).map(PathBuf::from),
+            mcp_capabilities: json_env("MCP_CAPABILITIES")?,
+            mcp_http_enabled: bool_env("MCP_HTTP_ENABLED"),
+            mcp_http_token: env::var("MCP_HTTP_TOKEN").ok().filter(|t| !t.trim().is_empty()),
+            mcp_http_allow_unauthenticated: bool_env("MCP_HTTP_ALLOW_UNAUTHENTICATED"),
+            mcp_max_items: env::var("MCP_MAX_ITEMS")
+                .unwrap_or_else(|_| "100".to_string())
+                .parse()
+                .context("MCP_MAX_ITEMS must be a valid number")?,
+            wallet_session_idle_secs: env::var("WALLET_SESSION_IDLE_SECS")
+                .unwrap_or_else(|_| "900".to_string())
+                .parse()
+                .context("WALLET_SESSION_IDLE_SECS must be a valid number")?,
+            proposal_ttl_secs: env::var("PROPOSAL_TTL_SECS")
+                .unwrap_or_else(|_| "86400".to_string())
+                .parse()
+                .context("PROPOSAL_TTL_SECS must be a valid number")?,
+            audit_log_path: env::var("AUDIT_LOG_PATH").ok().filter(|p| !p.trim().is_empty()).map(PathBuf::from),
+            allow_rpc_passthrough: bool_env("ALLOW_RPC_PASSTHROUGH"),
+            strict_amounts: bool_env("STRICT_AMOUNTS"),
+            nonce_resync_interval_secs: env::var("NONCE_RESYNC_INTERVAL_SECS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .context("NONCE_RESYNC_INTERVAL_SECS must be a valid number")?,
+            nonce_active_window_secs: env::var("NONCE_ACTIVE_WINDOW_SECS")
+                .unwrap_or_else(|_| "600".to_string())
+                .parse()
+                .context("NONCE_ACTIVE_WINDOW_SECS must be a valid number")?,
+            poll_interval_ms: env::var("POLL_INTERVAL_MS")
+                .unwrap_or_else(|_| "2000".to_string())
+                .parse()
+                .context("POLL_INTERVAL_MS must be a valid number")?,
+            max_poll_attempts: env::var("MAX_POLL_ATTEMPTS")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .context("MAX_POLL_ATTEMPTS must be a valid number")?,
+            max_recent_blocks: env::var("MAX_RECENT_BLOCKS")
+                .unwrap_or_else(|_| "50".to_string())
+                .parse()
+                .context("MAX_RECENT_BLOCKS must be a valid number")?,
         })
     }
-}
\ No newline at end of file
+
+    /// Configured default gas limit for `chain_id`, if any.
+    pub fn default_gas_limit(&self, chain_id: &str) -> Option<u64> {
+        self.default_gas_limits.get(chain_id).copied()
+    }
+
+    /// Blocks per eth_getLogs request on `chain_id`, if LOG_CHUNK_SIZES limits it.
+    pub fn log_chunk_size(&self, chain_id: &str) -> Option<u64> {
+        self.log_chunk_sizes.get(chain_id).copied().filter(|n| *n > 0)
+    }
+
+    /// Whether MCP clients may list and call `tool`. DISABLED_TOOLS wins over ENABLED_TOOLS; an empty
+    /// ENABLED_TOOLS enables everything.
+    pub fn tool_enabled(&self, tool: &str) -> bool {
+        let listed = |tools: &[String]| tools.iter().any(|t| t.trim() == tool);
+        !listed(&self.disabled_tools) && (self.enabled_tools.is_empty() || listed(&self.enabled_tools))
+    }
+
+    /// The SPENDING_BUDGETS entry covering a sender on `chain_id`: the first of `keys` (wallet name,
+    /// then address; addresses match case-insensitively) with an entry, else the chain's "*" entry.
+    pub fn spending_budget(&self, chain_id: &str, keys: &[&str]) -> Option<&SpendingBudget> {
+        let budgets = self.spending_budgets.get(chain_id)?;
+        keys.iter()
+            .find_map(|key| budgets.iter().find(|(k, _)| k.as_str() == *key || (k.starts_with("0x") && k.eq_ignore_ascii_case(key))))
+            .map(|(_, budget)| budget)
+            .or_else(|| budgets.get("*"))
+    }
+
+    /// Confirmations after which `check_finality` treats a block on `chain_id` as final.
+    pub fn finality_confirmations(&self, chain_id: &str) -> u64 {
+        self.finality_confirmations.get(chain_id).copied().unwrap_or(DEFAULT_FINALITY_CONFIRMATIONS).max(1)
+    }
+
+    /// KNOWN_TOKENS entry for `address` on `chain_id`; addresses match case-insensitively.
+    pub fn known_token(&self, chain_id: &str, address: &str) -> Option<&KnownToken> {
+        self.known_tokens
+            .get(chain_id)?
+            .iter()
+            .find(|(a, _)| a.eq_ignore_ascii_case(address.trim()))
+            .map(|(_, token)| token)
+    }
+
+    /// Most a single faucet request may dispense on `chain_id` (usei).
+    pub fn faucet_amount(&self, chain_id: &str) -> u64 {
+        self.faucet_amounts.get(chain_id).copied().unwrap_or(DEFAULT_FAUCET_AMOUNT)
+    }
+
+    /// Configured default gas price (wei) for `chain_id`, if any.
+    pub fn default_gas_price(&self, chain_id: &str) -> Option<u64> {
+        self.default_gas_prices.get(chain_id).copied()
+    }
+
+    /// Configured gas price ceiling (wei) for `chain_id`, if any.
+    pub fn max_gas_price(&self, chain_id: &str) -> Option<u64> {
+        self.max_gas_prices.get(chain_id).copied()
+    }
+
+    /// Human-readable name for `chain_id`: NETWORK_DISPLAY_NAMES, then the built-in Sei names, else the id itself.
+    pub fn network_name(&self, chain_id: &str) -> String {
+        self.network_display_names
+            .get(chain_id)
+            .cloned()
+            .or_else(|| {
+                DEFAULT_NETWORK_NAMES
+                    .iter()
+                    .find(|(id, _)| *id == chain_id)
+                    .map(|(_, name)| name.to_string())
+            })
+            .unwrap_or_else(|| chain_id.to_string())
+    }
+
+    /// Explorer page for transaction `hash` on `chain_id`, when the chain has a template.
+    pub fn explorer_tx_url(&self, chain_id: &str, hash: &str) -> Option<String> {
+        self.explorer_url(chain_id, "tx", hash)
+    }
+
+    /// Explorer page for `address` on `chain_id`, when the chain has a template.
+    pub fn explorer_address_url(&self, chain_id: &str, address: &str) -> Option<String> {
+        self.explorer_url(chain_id, "address", address)
+    }
+
+    // EXPLORER_URLS, then the built-in Sei templates
+    fn explorer_url(&self, chain_id: &str, kind: &str, id: &str) -> Option<String> {
+        let template = self.explorer_urls.get(chain_id).map(String::as_str).or_else(|| {
+            DEFAULT_EXPLORER_URLS.iter().find(|(chain, _)| *chain == chain_id).map(|(_, t)| *t)
+        })?;
+        Some(template.replace("{kind}", kind).replace("{id}", id.trim()))
+    }
+
+    /// Multicall3 contract on `chain_id`: MULTICALL3_ADDRESSES, then the canonical address on the built-in
+    /// EVM chains. `None` means calls go out one at a time.
+    pub fn multicall3_address(&self, chain_id: &str) -> Option<&str> {
+        match self.multicall3_addresses.get(chain_id) {
+            Some(address) => Some(address.trim()).filter(|a| !a.is_empty()),
+            None => DEFAULT_MULTICALL3_CHAINS
+                .contains(&chain_id)
+                .then_some(crate::blockchain::services::multicall::MULTICALL3_ADDRESS),
+        }
+    }
+
+    /// Transaction envelope for EVM sends on `chain_id` (`auto` unless TX_TYPES says otherwise).
+    pub fn tx_type(&self, chain_id: &str) -> TxType {
+        self.tx_types.get(chain_id).copied().unwrap_or_default()
+    }
+}
+
+// Reads a boolean flag; only "1"/"true"/"yes" (any case) enable it.
+fn bool_env(key: &str) -> bool {
+    env::var(key)
+        .map(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
+}
+
+// Parses an optional JSON-valued environment variable, falling back to the type's default when unset.
+// RPC_HEADERS, rejecting names or values that can't go on the wire. Errors name the header, never its value.
+fn rpc_headers_env() -> Result<HashMap<String, RpcHeaders>> {
+    let headers: HashMap<String, RpcHeaders> = json_env("RPC_HEADERS")?;
+    for (chain_id, RpcHeaders(map)) in &headers {
+        for (name, value) in map {
+            reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .with_context(|| format!("RPC_HEADERS: invalid header name '{}' for {}", name, chain_id))?;
+            reqwest::header::HeaderValue::from_str(value)
+                .with_context(|| format!("RPC_HEADERS: invalid value for header '{}' on {}", name, chain_id))?;
+        }
+    }
+    Ok(headers)
+}
+
+// EXPLORER_URLS; a template without `{id}` would link every hash to the same page.
+fn explorer_urls_env() -> Result<HashMap<String, String>> {
+    let templates: HashMap<String, String> = json_env("EXPLORER_URLS")?;
+    if let Some((chain_id, _)) = templates.iter().find(|(_, t)| !t.contains("{id}")) {
+        anyhow::bail!("EXPLORER_URLS: the template for {} has no {{id}} placeholder", chain_id);
+    }
+    Ok(templates)
+}
+
+// FAUCET_AMOUNTS; a zero maximum would let every request through as the faucet API's default amount.
+fn faucet_amounts_env() -> Result<HashMap<String, u64>> {
+    let amounts: HashMap<String, u64> = json_env("FAUCET_AMOUNTS")?;
+    if let Some((chain_id, _)) = amounts.iter().find(|(_, amount)| **amount == 0) {
+        anyhow::bail!("FAUCET_AMOUNTS: the amount for {} must be above 0", chain_id);
+    }
+    Ok(amounts)
+}
+
+// SPENDING_BUDGETS; a zero-length window or an unparseable amount would make the budget meaningless.
+fn spending_budgets_env() -> Result<HashMap<String, HashMap<String, SpendingBudget>>> {
+    let budgets: HashMap<String, HashMap<String, SpendingBudget>> = json_env("SPENDING_BUDGETS")?;
+    for (chain_id, senders) in &budgets {
+        for (sender, budget) in senders {
+            if budget.window_secs == 0 || ethers_core::types::U256::from_dec_str(budget.amount.trim()).is_err() {
+                anyhow::bail!(
+                    "SPENDING_BUDGETS: {} on {} needs a decimal wei amount and a window_secs above 0",
+                    sender,
+                    chain_id
+                );
+            }
+        }
+    }
+    Ok(budgets)
+}
+
+fn json_env<T: DeserializeOwned + Default>(key: &str) -> Result<T> {
+    match env::var(key) {
+        Ok(raw) if !raw.trim().is_empty() => {
+            serde_json::from_str(&raw).with_context(|| format!("Invalid {} JSON format", key))
+        }
+        _ => Ok(T::default()),
+    }
+}
@@ -1,6 +1,11 @@
 // src/lib.rs
 
+// The tools/list schema is one large `json!` literal.
+#![recursion_limit = "256"]
+
+use dashmap::DashMap;
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 use tokio::sync::Mutex;
 use std::path::PathBuf;
 
@@ -15,6 +20,80 @@ pub struct AppState {
     pub nonce_manager: blockchain::nonce_manager::NonceManager,
     pub wallet_storage: Arc<Mutex<mcp::wallet_storage::WalletStorage>>,
     pub wallet_storage_path: Arc<PathBuf>,
+    // Set when the storage file failed to load; reported to the next wallet operation
+    pub wallet_storage_error: Arc<std::sync::Mutex<Option<String>>>,
+    // Cancellation tokens for in-flight MCP requests, keyed by connection and JSON-encoded request id so
+    // one client cannot cancel another's request that happens to share an id
+    pub in_flight: Arc<DashMap<(String, String), CancellationToken>>,
+    // Unlock sessions so managed-wallet calls need not resend the master password
+    pub sessions: Arc<mcp::session::SessionStore>,
+    // Shared WebSocket connection; started by main when WEBSOCKET_URL is set
+    pub ws: Arc<blockchain::websocket::WsManager>,
+    // Today's faucet totals per chain, checked against FAUCET_DAILY_CAPS
+    pub faucet_ledger: Arc<blockchain::faucet_ledger::FaucetLedger>,
+    // Transfer totals per sender, checked against SPENDING_BUDGETS
+    pub spending_ledger: Arc<blockchain::spending_ledger::SpendingLedger>,
+    // Transactions check_finality has seen mined, to spot them being reorged out later
+    pub finality: Arc<blockchain::finality::FinalityTracker>,
+}
+
+impl AppState {
+    /// Builds the shared state with empty wallet storage; call `load_wallet_storage` to read the file.
+    pub fn new(config: config::Config, wallet_storage_path: PathBuf) -> Self {
+        let rpc_headers = blockchain::services::rpc::EndpointHeaders::from_config(&config).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring RPC_HEADERS: {}", e);
+            Default::default()
+        });
+        for tool in config.enabled_tools.iter().chain(&config.disabled_tools) {
+            if !mcp::handler::tool_registry().iter().any(|t| t.name == tool.trim()) {
+                tracing::warn!("ENABLED_TOOLS/DISABLED_TOOLS names unknown tool '{}'", tool);
+            }
+        }
+        let sei_client = blockchain::client::SeiClient::new(&config.chain_rpc_urls, &config.websocket_url)
+            .with_tx_types(&config.tx_types)
+            .with_max_gas_prices(&config.max_gas_prices)
+            .with_rpc_headers(rpc_headers)
+            .with_failover(
+                &config.rpc_fallback_urls,
+                config.rpc_failure_threshold,
+                std::time::Duration::from_millis(config.rpc_unhealthy_cooldown_ms),
+            );
+        let session_idle = std::time::Duration::from_secs(config.wallet_session_idle_secs);
+        let ws = blockchain::websocket::WsManager::new(
+            &config.websocket_url,
+            std::time::Duration::from_millis(config.ws_backoff_min_ms),
+            std::time::Duration::from_millis(config.ws_backoff_max_ms),
+        );
+        let faucet_ledger = blockchain::faucet_ledger::FaucetLedger::load(config.faucet_ledger_path.clone());
+        let spending_ledger = blockchain::spending_ledger::SpendingLedger::load(config.spending_ledger_path.clone());
+        let nonce_manager = blockchain::nonce_manager::NonceManager::new().with_transport(Arc::new(sei_client.http().clone()));
+        Self {
+            config,
+            sei_client,
+            nonce_manager,
+            wallet_storage: Arc::new(Mutex::new(mcp::wallet_storage::WalletStorage::default())),
+            wallet_storage_path: Arc::new(wallet_storage_path),
+            wallet_storage_error: Arc::default(),
+            in_flight: Arc::new(DashMap::new()),
+            sessions: Arc::new(mcp::session::SessionStore::new(session_idle)),
+            ws: Arc::new(ws),
+            faucet_ledger: Arc::new(faucet_ledger),
+            spending_ledger: Arc::new(spending_ledger),
+            finality: Arc::default(),
+        }
+    }
+
+    /// Loads wallets from `wallet_storage_path`. A load failure (e.g. a corrupt file, which is backed up)
+    /// leaves the storage empty and is reported to the next wallet operation instead of being swallowed.
+    pub async fn load_wallet_storage(&self) {
+        match mcp::wallet_storage::load_wallet_storage(&self.wallet_storage_path) {
+            Ok(storage) => *self.wallet_storage.lock().await = storage,
+            Err(e) => {
+                tracing::error!("{:#}", e);
+                *self.wallet_storage_error.lock().unwrap() = Some(format!("{:#}", e));
+            }
+        }
+    }
 }
 
 pub mod api;
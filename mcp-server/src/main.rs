@@ -1,6 +1,6 @@
 // src/main.rs
 
-use axum::{routing::get, routing::post, Router};
+use axum::{middleware, routing::get, routing::post, Router};
 use sei_mcp_server_rs::AppState;
 use sei_mcp_server_rs::{
     api::{
@@ -13,6 +13,7 @@ use sei_mcp_server_rs::{
         wallet::{create_wallet_handler, import_wallet_handler},
         discord::post_discord_handler,
         docs::redirect_to_seidocs_handler,
+        rate_limit::{bearer_token_key, ip_path_key, rate_limit, RateLimiter},
         seistream::{
             get_chain_info_handler,
             get_transaction_info_handler,
@@ -20,26 +21,24 @@ use sei_mcp_server_rs::{
             get_nft_metadata_items_handler,
         },
     },
-    blockchain::client::SeiClient,
-    blockchain::nonce_manager::NonceManager,
     config::Config,
-    mcp::wallet_storage::{get_wallet_storage_path, WalletStorage},
-    mcp::{
-        handler::handle_mcp_request,
-        protocol::{error_codes, Request, Response},
-    },
+    mcp::transport::{http_router as mcp_http_router, run_stdio_loop},
+    mcp::wallet_storage::get_wallet_storage_path,
 };
 use std::env;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::io::{self, AsyncBufReadExt, AsyncWriteExt};
-use tokio::sync::Mutex;
+use std::time::Duration;
+use tokio::io;
 use tower_http::{cors::CorsLayer, trace::TraceLayer};
 // removed HandleErrorLayer-based mapping; ConcurrencyLimit is not used
-use tracing::{debug, error, info};
+use tracing::{error, info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 // Removed rpassword import - no longer needed for startup
 
+// Window for RATE_LIMIT_PER_IP / RATE_LIMIT_PER_TOKEN
+const RATE_WINDOW: Duration = Duration::from_secs(60);
+
 // --- HTTP Server Logic ---
 async fn run_http_server(state: AppState) {
     let app = Router::new()
@@ -78,7 +77,34 @@ async fn run_http_server(state: AppState) {
             "/api/tokens/evm/erc721/:address/items",
             get(get_nft_metadata_items_handler),
         )
-        .with_state(state.clone()) // Use the shared state
+        .with_state(state.clone()); // Use the shared state
+
+    let unguarded = state.config.mcp_http_token.is_none();
+    if state.config.mcp_http_enabled && unguarded && !state.config.mcp_http_allow_unauthenticated {
+        error!("MCP_HTTP_ENABLED without MCP_HTTP_TOKEN: not serving /mcp (set MCP_HTTP_ALLOW_UNAUTHENTICATED=true to serve it anyway)");
+    }
+    let app = if state.config.mcp_http_enabled && (!unguarded || state.config.mcp_http_allow_unauthenticated) {
+        if unguarded {
+            warn!("MCP_HTTP_ALLOW_UNAUTHENTICATED is set: /mcp accepts unauthenticated requests");
+        }
+        info!("MCP over HTTP enabled at /mcp (SSE at /mcp/sse)");
+        let mut mcp = mcp_http_router(state.clone());
+        if state.config.rate_limit_per_token > 0 {
+            let by_token = Arc::new(RateLimiter::new(state.config.rate_limit_per_token, RATE_WINDOW, bearer_token_key));
+            mcp = mcp.layer(middleware::from_fn_with_state(by_token, rate_limit));
+        }
+        app.merge(mcp)
+    } else {
+        app
+    };
+    // Applied outermost, so token-limited /mcp requests must also pass the IP limit
+    let app = if state.config.rate_limit_per_ip > 0 {
+        let by_ip = Arc::new(RateLimiter::new(state.config.rate_limit_per_ip, RATE_WINDOW, ip_path_key));
+        app.layer(middleware::from_fn_with_state(by_ip, rate_limit))
+    } else {
+        app
+    };
+    let app = app
         .layer(TraceLayer::new_for_http())
         .layer(CorsLayer::permissive())
         ;
@@ -93,59 +119,8 @@ async fn run_http_server(state: AppState) {
 async fn run_mcp_server(state: AppState) {
     info!("🚀 Starting MCP server on stdin/stdout...");
 
-    let mut stdin = io::BufReader::new(io::stdin());
-    let mut stdout = io::stdout();
-
-    loop {
-        let mut line = String::new();
-
-        match stdin.read_line(&mut line).await {
-            Ok(0) => {
-                info!("EOF received, shutting down MCP server");
-                break;
-            }
-            Ok(_) => {
-                let line = line.trim();
-                if line.is_empty() {
-                    continue;
-                }
-
-                debug!("Received: {}", line);
-
-                let response = match serde_json::from_str::<Request>(line) {
-                    Ok(request) => {
-                        // FIX: Pass shared state to the handler.
-                        handle_mcp_request(request, state.clone()).await
-                    }
-                    Err(parse_error) => {
-                        error!("JSON parse error: {}", parse_error);
-                        Some(Response::error(
-                            serde_json::Value::Null,
-                            error_codes::PARSE_ERROR,
-                            format!("Parse error: {}", parse_error),
-                        ))
-                    }
-                };
-
-                if let Some(response) = response {
-                    if let Ok(response_json) = serde_json::to_string(&response) {
-                        debug!("Sending: {}", response_json);
-                        if let Err(e) = stdout
-                            .write_all(format!("{}\n", response_json).as_bytes())
-                            .await
-                        {
-                            error!("Failed to write response: {}", e);
-                            break;
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                error!("Failed to read from stdin: {}", e);
-                break;
-            }
-        }
-    }
+    let max_line_len = state.config.mcp_max_line_bytes;
+    run_stdio_loop(io::BufReader::new(io::stdin()), io::stdout(), state, max_line_len).await;
 
     info!("MCP server shutting down");
 }
@@ -170,10 +145,6 @@ async fn main() {
         }
     };
 
-    // FIX: Initialize all shared state here, once.
-    let sei_client = SeiClient::new(&config.chain_rpc_urls, &config.websocket_url);
-    let nonce_manager = NonceManager::new();
-
     // Initialize wallet storage path but don't require master password on startup
     let wallet_storage_path = match get_wallet_storage_path() {
         Ok(path) => path,
@@ -183,16 +154,17 @@ async fn main() {
         }
     };
 
-    // Create empty wallet storage - will be initialized when user first registers a wallet
-    let storage = WalletStorage::default();
-
-    let app_state = AppState {
-        config,
-        sei_client,
-        nonce_manager,
-        wallet_storage: Arc::new(Mutex::new(storage)),
-        wallet_storage_path: Arc::new(wallet_storage_path),
-    };
+    // FIX: Initialize all shared state here, once.
+    // Existing wallets are loaded now; without a file, storage is initialized when the user first registers a wallet
+    let app_state = AppState::new(config, wallet_storage_path);
+    app_state.load_wallet_storage().await;
+    app_state.ws.start();
+    if app_state.config.nonce_resync_interval_secs > 0 {
+        app_state.nonce_manager.spawn_resync_task(
+            Duration::from_secs(app_state.config.nonce_resync_interval_secs),
+            Duration::from_secs(app_state.config.nonce_active_window_secs),
+        );
+    }
 
     // Determine run mode
     let args: Vec<String> = env::args().collect();
@@ -0,0 +1,64 @@
+// src/mcp/audit.rs
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use tracing::warn;
+
+/// One line of the fund-movement audit trail.
+/// Only public facts are representable here, so keys and passwords cannot end up in the log.
+#[derive(Debug, Serialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub tool: String,
+    pub chain_id: String,
+    pub from: Option<String>,
+    pub to: String,
+    pub amount: String,
+    pub tx_hash: String,
+    // JSON-RPC method forwarded by rpc_passthrough
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rpc_method: Option<String>,
+}
+
+impl AuditEntry {
+    pub fn new(tool: &str, chain_id: &str, from: Option<String>, to: &str, amount: &str, tx_hash: &str) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            tool: tool.to_string(),
+            chain_id: chain_id.to_string(),
+            from,
+            to: to.to_string(),
+            amount: amount.to_string(),
+            tx_hash: tx_hash.to_string(),
+            rpc_method: None,
+        }
+    }
+
+    /// An `rpc_passthrough` call; `tx_hash` is set when the method broadcast a transaction.
+    pub fn passthrough(chain_id: &str, method: &str, tx_hash: &str) -> Self {
+        Self {
+            rpc_method: Some(method.to_string()),
+            ..Self::new("rpc_passthrough", chain_id, None, "", "", tx_hash)
+        }
+    }
+}
+
+/// Appends `entry` as a JSON line to the audit log, if one is configured.
+/// Best-effort: the operation has already happened, so a failed write only logs a warning.
+pub fn record(path: Option<&Path>, entry: &AuditEntry) {
+    let Some(path) = path else {
+        return;
+    };
+    let result = serde_json::to_string(entry)
+        .map_err(std::io::Error::other)
+        .and_then(|line| {
+            let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+            writeln!(file, "{}", line)
+        });
+    if let Err(e) = result {
+        warn!("Failed to write audit entry to {}: {}", path.display(), e);
+    }
+}
@@ -1,25 +1,179 @@
 // src/mcp/handler.rs
 
-use crate::blockchain::models::ChainType;
+use crate::blockchain::models::{BalanceResponse, ChainType, SeiError};
 use crate::{
     blockchain::{
+        broadcasts, finality, spending_ledger,
         models::WalletResponse,
-        services::{transactions, wallet},
+        services::{abi, balance, blocks, contract, erc20, faucet, fees, mempool, native_tx, nft, rpc, spam, transactions, wallet},
     },
     mcp::{
+        audit,
         protocol::{error_codes, Request, Response},
         wallet_storage,
     },
     utils, AppState,
 };
-use ethers_core::abi::{encode, Token};
+use ethers_core::abi::{encode, EventExt, Token};
 use ethers_core::types::{Address, Bytes, TransactionRequest, U256};
 use ethers_core::utils::keccak256;
 use ethers_signers::{LocalWallet, Signer};
 use reqwest::Client;
 use serde_json::{json, Value};
+use futures::future::BoxFuture;
+use futures::StreamExt;
 use std::str::FromStr;
-use tracing::{error, info};
+use std::sync::OnceLock;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, info_span, warn, Instrument};
+use zeroize::Zeroizing;
+
+/// JSON-RPC methods answered outside tool dispatch, with what each does (reported by `list_methods`).
+const PROTOCOL_METHODS: &[(&str, &str)] = &[
+    ("initialize", "MCP handshake: server info, protocol version and capabilities"),
+    ("ping", "Liveness probe; returns an empty result"),
+    ("tools/list", "Every tool with its description and input schema"),
+    ("tools/call", "Runs a tool: params {\"name\": <tool>, \"arguments\": {...}}"),
+    ("notifications/cancelled", "Notification (no id): abandons the in-flight request named by params.requestId"),
+];
+
+// get_balances_multi: most addresses per call, and how many balance reads run at once
+const MAX_MULTI_BALANCE_ADDRESSES: usize = 100;
+const MULTI_BALANCE_CONCURRENCY: usize = 8;
+const MAX_TOKEN_BALANCE_CONTRACTS: usize = 100;
+
+// get_recent_blocks: blocks returned when `count` is omitted, and how many are fetched at once
+const DEFAULT_RECENT_BLOCKS: u64 = 10;
+const RECENT_BLOCKS_CONCURRENCY: usize = 8;
+
+// generate_vanity_address: longest prefix accepted, and hard caps on the search
+const MAX_VANITY_PREFIX_LEN: usize = 8;
+const MAX_VANITY_ATTEMPTS: u64 = 5_000_000;
+const MAX_VANITY_SECONDS: u64 = 60;
+
+// sign_permit: deadline when the caller doesn't set one
+const DEFAULT_PERMIT_TTL_SECS: u64 = 3600;
+
+/// Tools whose `to_address` may name a contact from the address book instead of an address.
+const CONTACT_RECIPIENT_TOOLS: &[&str] = &[
+    "transfer_evm",
+    "transfer_sei",
+    "transfer_sei_with_feegrant",
+    "preview_transfer",
+    "transfer_nft_evm",
+    "transfer_from_wallet",
+    "sweep_wallet",
+    "propose_transfer",
+    "quote_fee",
+];
+
+/// Tools that can put a transaction on chain. They always run to completion: dropping one mid-flight, on
+/// a `_timeout_ms` deadline or a cancellation, could lose track of a transaction it already sent.
+const BROADCASTING_TOOLS: &[&str] = &[
+    "broadcast_raw_transaction",
+    "request_faucet",
+    "transfer_from_wallet",
+    "sweep_wallet",
+    "approve_proposal",
+    "transfer_evm",
+    "transfer_sei",
+    "transfer_sei_with_feegrant",
+    "grant_fee_allowance",
+    "transfer_nft_evm",
+    "call_contract_by_abi",
+    "deploy_contract",
+    "rpc_passthrough",
+];
+
+/// Tools that may also be called as a JSON-RPC method of the same name, rewritten to `tools/call`.
+/// Kept to the CLI conveniences: anything touching stored wallets goes through `tools/call` only.
+const DIRECT_METHOD_TOOLS: &[&str] = &[
+    "get_balance",
+    "request_faucet",
+    "transfer_evm",
+    "transfer_sei",
+    "transfer_nft_evm",
+    "search_events",
+    "get_contract",
+    "get_contract_code",
+    "get_contract_transactions",
+    "redirect_to_seidocs",
+    "get_chain_info",
+    "get_transaction_info",
+    "get_transaction_history",
+    "get_nft_metadata",
+    "list_methods",
+];
+
+// batch_query: most sub-requests per call, and how many run at once
+const MAX_BATCH_QUERIES: usize = 25;
+const BATCH_QUERY_CONCURRENCY: usize = 8;
+
+/// Tools `batch_query` may run: reads that neither change chain or wallet state nor take secrets.
+/// `call_contract_by_abi` qualifies only without a `private_key`, which would make it send.
+const BATCHABLE_TOOLS: &[&str] = &[
+    "get_balance",
+    "get_balances_multi",
+    "get_balance_at_block",
+    "get_token_supply",
+    "get_token_balances",
+    "get_token_stats",
+    "get_nft_token_uri",
+    "get_nft_metadata",
+    "get_contract",
+    "get_contract_code",
+    "get_contract_abi",
+    "get_contract_creation",
+    "get_contract_transactions",
+    "get_proxy_implementation",
+    "get_storage_at",
+    "call_contract_by_abi",
+    "eth_call_raw",
+    "get_chain_info",
+    "get_transaction_info",
+    "get_transaction_receipt",
+    "check_finality",
+    "get_transaction_trace",
+    "get_native_tx",
+    "get_transaction_history",
+    "get_recent_blocks",
+    "get_block_by_timestamp",
+    "search_events",
+    "get_decoded_events",
+    "get_faucet_status",
+    "faucet_daily_remaining",
+    "get_spending_status",
+    "estimate_inclusion_time",
+    "get_base_fee",
+    "quote_fee",
+    "compute_selector",
+    "compute_contract_address",
+    "decode_abi",
+    "resolve_chain_id",
+    "get_explorer_url",
+];
+
+/// Recognized chain_id aliases (after case/separator normalization) and the canonical id each maps to.
+pub const CHAIN_ID_ALIASES: &[(&str, &str)] = &[
+    // Accept: sei-testnet, sei-evm-testnet, sei evm testnet, sei_testnet, etc.
+    ("sei-testnet", "sei-evm-testnet"),
+    ("sei-evm-testnet", "sei-evm-testnet"),
+    ("sei-evm-test", "sei-evm-testnet"),
+    ("sei-evm-t", "sei-evm-testnet"),
+    ("sei-evm", "sei-evm-testnet"),
+    ("sei-mainnet", "sei-evm-mainnet"),
+    ("sei-evm-mainnet", "sei-evm-mainnet"),
+    ("sei-evm-main", "sei-evm-mainnet"),
+    ("sei-main", "sei-evm-mainnet"),
+    // Native aliases
+    ("atlantic-2", "atlantic-2"),
+    ("sei-native-testnet", "atlantic-2"),
+    ("sei-native", "atlantic-2"),
+    ("sei-testnet-native", "atlantic-2"),
+    ("pacific-1", "pacific-1"),
+    ("sei-native-mainnet", "pacific-1"),
+    ("sei-mainnet-native", "pacific-1"),
+];
 
 // Normalize common chain_id aliases users might pass via MCP
 pub fn normalize_chain_id(input: &str) -> String {
@@ -32,33 +186,11 @@ pub fn normalize_chain_id(input: &str) -> String {
         s = s.replace("--", "-");
     }
 
-    // Common aliases for EVM networks
-    // Accept: sei-testnet, sei-evm-testnet, sei evm testnet, sei_testnet, etc.
-    if s == "sei-testnet"
-        || s == "sei-evm-testnet"
-        || s == "sei-evm-test"
-        || s == "sei-evm-t"
-        || s == "sei-evm"
-    {
-        return "sei-evm-testnet".to_string();
-    }
-    if s == "sei-mainnet" || s == "sei-evm-mainnet" || s == "sei-evm-main" || s == "sei-main" {
-        return "sei-evm-mainnet".to_string();
-    }
-
-    // Native aliases
-    if s == "atlantic-2"
-        || s == "sei-native-testnet"
-        || s == "sei-native"
-        || s == "sei-testnet-native"
-    {
-        return "atlantic-2".to_string();
-    }
-    if s == "pacific-1" || s == "sei-native-mainnet" || s == "sei-mainnet-native" {
-        return "pacific-1".to_string();
-    }
-
-    s
+    CHAIN_ID_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == s)
+        .map(|(_, canonical)| canonical.to_string())
+        .unwrap_or(s)
 }
 
 // Use the get_required_arg from utils module
@@ -78,7 +210,7 @@ fn infer_evm_chain_from_args(args: &Value) -> Option<String> {
     let mut blob = String::new();
     for key in candidates.iter() {
         if let Some(s) = args.get(*key).and_then(|v| v.as_str()) {
-            blob.push_str(" ");
+            blob.push(' ');
             blob.push_str(s);
         }
     }
@@ -114,23 +246,608 @@ fn make_texty_result(text: String, payload: Value) -> Value {
     }
 }
 
+// Locks the wallet storage, first reporting (once) a storage file that failed to load at startup.
+async fn lock_wallet_storage<'a>(
+    state: &'a AppState,
+    req_id: &Value,
+) -> Result<tokio::sync::MutexGuard<'a, wallet_storage::WalletStorage>, Response> {
+    if let Some(err) = state.wallet_storage_error.lock().unwrap().take() {
+        return Err(Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, err));
+    }
+    Ok(state.wallet_storage.lock().await)
+}
+
+// Encrypts `private_key` into the managed storage under `wallet_name` and persists it to disk.
+async fn store_wallet(
+    state: &AppState,
+    wallet_name: &str,
+    private_key: &str,
+    address: String,
+    master_password: &str,
+    req_id: &Value,
+) -> Result<(), Response> {
+    let mut storage = lock_wallet_storage(state, req_id).await?;
+    // The first registration sets the master password
+    if !storage.is_initialized() {
+        // Contacts need no password, so they may already exist
+        let contacts = std::mem::take(&mut storage.contacts);
+        *storage = wallet_storage::WalletStorage::new(master_password);
+        storage.contacts = contacts;
+    }
+    if !storage.verify_master_password(master_password) {
+        return Err(Response::error(
+            req_id.clone(),
+            error_codes::INTERNAL_ERROR,
+            "Invalid master password".into(),
+        ));
+    }
+
+    storage
+        .add_wallet(wallet_name.to_string(), private_key, address, master_password)
+        .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+
+    persist_storage(state, &storage, req_id)
+}
+
+// Swaps a contact name in `to_address` for the contact's address. Address-shaped values are left
+// to the tool (contact names can never take that shape), so the same input always means the same
+// recipient. A contact saved for another chain is refused rather than reused.
+async fn resolve_contact_recipient(args: &mut Value, state: &AppState, req_id: &Value) -> Result<(), Response> {
+    let Some(to) = args.get("to_address").and_then(Value::as_str) else {
+        return Ok(());
+    };
+    if wallet_storage::looks_like_address(to) {
+        return Ok(());
+    }
+    let storage = lock_wallet_storage(state, req_id).await?;
+    let contact = storage.contact(to).ok_or_else(|| {
+        Response::error(
+            req_id.clone(),
+            error_codes::INVALID_PARAMS,
+            format!("'{}' is neither an address nor a saved contact", to),
+        )
+    })?;
+    if let Some(chain_id) = args.get("chain_id").and_then(Value::as_str).map(normalize_chain_id) {
+        if chain_id != contact.chain_id {
+            return Err(Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                format!("Contact '{}' is saved for {}, not {}", contact.name, contact.chain_id, chain_id),
+            ));
+        }
+    }
+    args["to_address"] = json!(contact.address);
+    Ok(())
+}
+
+/// Writes `storage` to disk; call while still holding the storage lock taken for the mutation.
+#[allow(clippy::result_large_err)]
+fn persist_storage(state: &AppState, storage: &wallet_storage::WalletStorage, req_id: &Value) -> Result<(), Response> {
+    wallet_storage::save_wallet_storage(&state.wallet_storage_path, storage).map_err(|e| {
+        error!("Failed to save wallet storage: {}", e);
+        Response::error(
+            req_id.clone(),
+            error_codes::INTERNAL_ERROR,
+            "Failed to save wallet to disk".into(),
+        )
+    })
+}
+
+// Master password for managed-wallet calls: from a live `session_token`, else the explicit argument.
+#[allow(clippy::result_large_err)]
+fn resolve_master_password(args: &Value, state: &AppState, req_id: &Value) -> Result<Zeroizing<String>, Response> {
+    match args.get("session_token").and_then(|v| v.as_str()) {
+        Some(token) => state.sessions.resolve(token).ok_or_else(|| {
+            Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                "Session token is invalid or expired; call unlock_storage again".into(),
+            )
+        }),
+        None => utils::get_required_arg::<String>(args, "master_password", req_id).map(Zeroizing::new),
+    }
+}
+
+// Maps a service-layer failure to a JSON-RPC error, keeping the category when it is a `SeiError`.
+fn service_error(req_id: &Value, e: anyhow::Error) -> Response {
+    let sei_error = e.downcast_ref::<SeiError>();
+    let code = match sei_error {
+        Some(SeiError::InvalidInput(_)) => error_codes::INVALID_PARAMS,
+        Some(SeiError::Rpc(_)) | Some(SeiError::Rejected { .. }) => error_codes::RPC_ERROR,
+        Some(SeiError::InsufficientFunds(_)) => error_codes::INSUFFICIENT_FUNDS,
+        Some(SeiError::NotFound(_)) => error_codes::NOT_FOUND,
+        Some(SeiError::Signing(_)) | None => error_codes::INTERNAL_ERROR,
+    };
+    // A refused broadcast also carries its category and what to do about it
+    match sei_error.and_then(SeiError::rejection) {
+        Some(category) => Response::error_with_data(
+            req_id.clone(),
+            code,
+            e.to_string(),
+            json!({ "rejection": { "category": category, "hint": category.hint() } }),
+        ),
+        None => Response::error(req_id.clone(), code, e.to_string()),
+    }
+}
+
+// Gas for a transfer: the caller's override, else the chain's configured default, else left unset for RPC estimation.
+// Unusable overrides are dropped so send_transaction estimates instead of sending gas 0.
+fn apply_gas_settings(mut tx: TransactionRequest, args: &Value, state: &AppState, chain_id: &str) -> TransactionRequest {
+    let gas_limit = utils::parse_gas_override(args, "gas_limit")
+        .or_else(|| state.config.default_gas_limit(chain_id).map(U256::from));
+    let gas_price = utils::parse_gas_override(args, "gas_price")
+        .or_else(|| state.config.default_gas_price(chain_id).map(U256::from));
+    if let Some(g) = gas_limit {
+        tx = tx.gas(g);
+    }
+    if let Some(gp) = gas_price {
+        tx = tx.gas_price(gp);
+    }
+    tx
+}
+
+// USD value of `amount` base units at the chain's USD_PRICES rate, rounded to cents; `None` without a
+// price. The price is whatever USD_PRICES says, not a live quote.
+fn usd_value(state: &AppState, chain_id: &str, amount: U256, decimals: u8) -> Option<f64> {
+    let price = state.config.usd_prices.get(chain_id)?;
+    let whole = erc20::format_units(amount, decimals).parse::<f64>().unwrap_or_default();
+    Some((whole * price * 100.0).round() / 100.0)
+}
+
+// Envelope, gas limit and fee caps behind an EVM fee estimate, in wei.
+fn gas_quote_details(quote: &transactions::GasQuote) -> Value {
+    json!({
+        "tx_type": if quote.max_priority_fee_per_gas.is_some() { "eip1559" } else { "legacy" },
+        "gas_limit": quote.gas_limit.to_string(),
+        "max_fee_per_gas": quote.max_fee_per_gas.to_string(),
+        "max_priority_fee_per_gas": quote.max_priority_fee_per_gas.map(|t| t.to_string()),
+    })
+}
+
+// Current primary balance of `address` as a number, with its denom.
+async fn fetch_balance(state: &AppState, chain_id: &str, address: &str, req_id: &Value) -> Result<(U256, String), Response> {
+    let b = state
+        .sei_client
+        .get_balance(chain_id, address)
+        .await
+        .map_err(|e| service_error(req_id, e))?;
+    let amount = U256::from_dec_str(&b.amount).map_err(|_| {
+        Response::error(
+            req_id.clone(),
+            error_codes::INTERNAL_ERROR,
+            format!("Unparseable balance '{}'", b.amount),
+        )
+    })?;
+    Ok((amount, b.denom))
+}
+
+// Enforces MAX_TRANSFER_AMOUNTS before any transfer leaves the server.
+#[allow(clippy::result_large_err)]
+fn enforce_transfer_cap(state: &AppState, chain_id: &str, amount: U256, req_id: &Value) -> Result<(), Response> {
+    utils::check_transfer_cap(&state.config.max_transfer_amounts, chain_id, amount)
+        .map_err(|msg| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg))
+}
+
+// Holds `amount` of the sender's SPENDING_BUDGETS allowance for a transfer about to be sent; `None` when
+// no budget covers the sender (looked up by wallet name, then address). Released on drop unless committed.
+#[allow(clippy::result_large_err)]
+fn reserve_spend(
+    state: &AppState,
+    chain_id: &str,
+    wallet_name: Option<&str>,
+    from: &str,
+    amount: U256,
+    req_id: &Value,
+) -> Result<Option<spending_ledger::Reservation>, Response> {
+    let keys: Vec<&str> = wallet_name.into_iter().chain([from]).collect();
+    let Some(budget) = state.config.spending_budget(chain_id, &keys) else {
+        return Ok(None);
+    };
+    state
+        .spending_ledger
+        .reserve(chain_id, from, budget, amount, chrono::Utc::now())
+        .map(Some)
+        .map_err(|e| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string()))
+}
+
+// Keeps the spend once the transfer has gone out.
+fn commit_spend(reservation: Option<spending_ledger::Reservation>) {
+    if let Some(reservation) = reservation {
+        reservation.commit();
+    }
+}
+
+// Guards base-unit transfer amounts against whole-token figures: decimals are always rejected with the
+// likely intended value, dust-sized integers are a warning, or an error under STRICT_AMOUNTS unless
+// the caller passes `confirm_amount: true`.
+#[allow(clippy::result_large_err)]
+fn amount_unit_warning(amount: &str, field: &str, decimals: u8, args: &Value, state: &AppState, req_id: &Value) -> Result<Option<String>, Response> {
+    let invalid = |msg: String| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg);
+    let warning = utils::check_base_unit_amount(amount, field, decimals).map_err(invalid)?;
+    let confirmed = args.get("confirm_amount").and_then(|v| v.as_bool()).unwrap_or(false);
+    match warning {
+        Some(w) if state.config.strict_amounts && !confirmed => {
+            Err(invalid(format!("{} Pass confirm_amount: true to send it as given.", w)))
+        }
+        Some(_) if confirmed => Ok(None),
+        other => Ok(other),
+    }
+}
+
+// Parses and caps an EVM value transfer; shared by `transfer_evm` and `preview_transfer` so both see the same request.
+#[allow(clippy::result_large_err)]
+fn evm_transfer_request(
+    to_address: &str,
+    amount_wei: &str,
+    args: &Value,
+    state: &AppState,
+    chain_id: &str,
+    req_id: &Value,
+) -> Result<(Address, U256, TransactionRequest), Response> {
+    let to = Address::from_str(to_address).map_err(|_| {
+        Response::error(
+            req_id.clone(),
+            error_codes::INVALID_PARAMS,
+            "Invalid 'to_address'".into(),
+        )
+    })?;
+    let value = U256::from_dec_str(amount_wei).map_err(|_| {
+        Response::error(
+            req_id.clone(),
+            error_codes::INVALID_PARAMS,
+            "Invalid 'amount_wei'".into(),
+        )
+    })?;
+    enforce_transfer_cap(state, chain_id, value, req_id)?;
+    let tx = apply_gas_settings(TransactionRequest::new().to(to).value(value), args, state, chain_id);
+    Ok((to, value, tx))
+}
+
+// Decrypted key of a managed wallet about to pay `to_address`, refusing multisig wallets and
+// recipients off the wallet's allowlist; shared by `transfer_from_wallet` and `sweep_wallet`.
+async fn managed_wallet_key(state: &AppState, wallet_name: &str, to_address: &str, args: &Value, req_id: &Value) -> Result<String, Response> {
+    let master_password = resolve_master_password(args, state, req_id)?;
+    let storage = lock_wallet_storage(state, req_id).await?;
+    if let Some(policy) = storage.multisig.get(wallet_name) {
+        return Err(Response::error(
+            req_id.clone(),
+            error_codes::INVALID_PARAMS,
+            format!(
+                "Wallet '{}' requires {} of {} approvals; use propose_transfer",
+                wallet_name,
+                policy.threshold,
+                policy.authorizers.len()
+            ),
+        ));
+    }
+    let allowed = storage.is_recipient_allowed(wallet_name, to_address).map_err(|e| {
+        Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
+    })?;
+    if !allowed {
+        return Err(Response::error(
+            req_id.clone(),
+            error_codes::INVALID_PARAMS,
+            format!("Recipient {} is not on the allowlist for wallet '{}'", to_address, wallet_name),
+        ));
+    }
+    storage
+        .get_decrypted_private_key(wallet_name, &master_password)
+        .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))
+}
+
+// Parses and caps a native bank send amount; shared by `transfer_sei` and `preview_transfer`.
+#[allow(clippy::result_large_err)]
+fn native_transfer_amount(amount_usei: &str, state: &AppState, chain_id: &str, req_id: &Value) -> Result<u64, Response> {
+    let amount = amount_usei.parse::<u64>().map_err(|_| {
+        Response::error(
+            req_id.clone(),
+            error_codes::INVALID_PARAMS,
+            "Invalid 'amount_usei'".into(),
+        )
+    })?;
+    enforce_transfer_cap(state, chain_id, U256::from(amount), req_id)?;
+    Ok(amount)
+}
+
+// Creation bytecode with the ABI-encoded constructor arguments appended; shared by
+// `deploy_contract` and `estimate_deployment_cost`.
+#[allow(clippy::result_large_err)]
+fn deploy_init_code(args: &Value, bytecode: &str, req_id: &Value) -> Result<Vec<u8>, Response> {
+    let invalid = |field: &str| {
+        Response::error(
+            req_id.clone(),
+            error_codes::INVALID_PARAMS,
+            format!("Invalid '{}'", field),
+        )
+    };
+    let code = hex::decode(bytecode.trim().trim_start_matches("0x")).map_err(|_| invalid("bytecode"))?;
+    if code.is_empty() {
+        return Err(invalid("bytecode"));
+    }
+    let constructor_args: Vec<Value> = match args.get("constructor_args") {
+        None | Some(Value::Null) => vec![],
+        Some(_) => utils::get_required_arg::<Vec<Value>>(args, "constructor_args", req_id)?,
+    };
+
+    match args.get("abi") {
+        Some(Value::Array(entries)) => {
+            let parsed = contract::parse_abi(entries)
+                .map_err(|e| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string()))?;
+            match parsed.constructor() {
+                Some(ctor) => {
+                    if ctor.inputs.len() != constructor_args.len() {
+                        return Err(Response::error(
+                            req_id.clone(),
+                            error_codes::INVALID_PARAMS,
+                            format!(
+                                "Constructor takes {} argument(s), got {}",
+                                ctor.inputs.len(),
+                                constructor_args.len()
+                            ),
+                        ));
+                    }
+                    let tokens = ctor
+                        .inputs
+                        .iter()
+                        .zip(&constructor_args)
+                        .map(|(p, v)| abi::json_to_token(&p.kind, v))
+                        .collect::<anyhow::Result<Vec<_>>>()
+                        .map_err(|e| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string()))?;
+                    ctor.encode_input(code, &tokens)
+                        .map_err(|e| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string()))
+                }
+                None if constructor_args.is_empty() => Ok(code),
+                None => Err(invalid("constructor_args")),
+            }
+        }
+        None | Some(Value::Null) if constructor_args.is_empty() => Ok(code),
+        _ => Err(Response::error(
+            req_id.clone(),
+            error_codes::INVALID_PARAMS,
+            "'constructor_args' requires the contract 'abi' (JSON array)".into(),
+        )),
+    }
+}
+
+// `{reason, decoded, data, message}` for an `eth_call` error; `abi` enables custom errors.
+fn describe_revert(err: &Value, abi: Option<&ethers_core::abi::Abi>) -> Value {
+    let data = rpc::revert_data(err);
+    let decoded = data.as_deref().and_then(|d| abi::decode_revert(d, abi));
+    let message = err["message"].as_str().unwrap_or_default();
+    let reason = decoded
+        .as_ref()
+        .and_then(|d| d["reason"].as_str())
+        .map(str::to_string)
+        .or_else(|| message.strip_prefix("execution reverted: ").map(str::to_string));
+    json!({
+        "reason": reason,
+        "decoded": decoded,
+        "data": data.map(|d| format!("0x{}", hex::encode(d))),
+        "message": message,
+    })
+}
+
+// Why a mined transaction reverted, found by replaying it at its block. Best-effort: a failed
+// replay is reported in the result rather than failing the tool. `abi` enables custom errors.
+async fn revert_details(transport: &dyn rpc::RpcTransport, rpc_url: &str, tx_hash: &str, abi: Option<&ethers_core::abi::Abi>) -> Value {
+    match rpc::replay_transaction(transport, rpc_url, tx_hash).await {
+        Ok(Some(err)) => describe_revert(&err, abi),
+        Ok(None) => json!({
+            "reason": null,
+            "message": "replaying the transaction at its block succeeded; the revert depended on state earlier in that block",
+        }),
+        Err(e) => json!({ "reason": null, "message": format!("could not replay transaction: {}", e) }),
+    }
+}
+
+// GAS_BUMP_PERCENT / MAX_GAS_BUMPS retry policy when the caller opts in with `auto_bump: true`.
+fn gas_bump_arg(args: &Value, state: &AppState) -> Option<transactions::GasBump> {
+    args.get("auto_bump").and_then(|v| v.as_bool()).unwrap_or(false).then_some(transactions::GasBump {
+        percent: state.config.gas_bump_percent,
+        max_bumps: state.config.max_gas_bumps,
+    })
+}
+
+// Polling cadence for tools that wait on the chain: per-call `poll_interval_ms` / `max_poll_attempts`
+// over POLL_INTERVAL_MS / MAX_POLL_ATTEMPTS, bounded by the tool's own `timeout`.
+fn poll_settings(args: &Value, state: &AppState, timeout: std::time::Duration) -> utils::PollSettings {
+    let interval_ms = args
+        .get("poll_interval_ms")
+        .and_then(|v| v.as_u64())
+        .filter(|ms| *ms > 0)
+        .or(Some(state.config.poll_interval_ms).filter(|ms| *ms > 0))
+        .unwrap_or(utils::DEFAULT_POLL_INTERVAL_MS);
+    let max_attempts = args
+        .get("max_poll_attempts")
+        .and_then(|v| v.as_u64())
+        .map(|n| n.min(u32::MAX as u64) as u32)
+        .unwrap_or(state.config.max_poll_attempts);
+    utils::PollSettings { interval: std::time::Duration::from_millis(interval_ms), max_attempts, timeout }
+}
+
+// Fee fields reported for a sent EVM transfer; `gas_price` is the (max) fee per gas of the accepted attempt.
+fn sent_fee_fields(payload: &mut Value, sent: &transactions::SentTransaction) {
+    payload["gas_price"] = json!(sent.fees.max_fee_per_gas.to_string());
+    payload["max_priority_fee_per_gas"] = json!(sent.fees.max_priority_fee_per_gas.map(|t| t.to_string()));
+    payload["gas_bumps"] = json!(sent.bumps);
+}
+
+// Best-effort audit line for a completed fund movement (see AUDIT_LOG_PATH).
+fn audit(state: &AppState, entry: audit::AuditEntry) {
+    audit::record(state.config.audit_log_path.as_deref(), &entry);
+}
+
+// Whether MCP_EXPOSED_CHAINS lets MCP tools reach `chain_id` (an empty list exposes every chain).
+fn chain_exposed(state: &AppState, chain_id: &str) -> bool {
+    let exposed = &state.config.mcp_exposed_chains;
+    exposed.is_empty() || exposed.iter().any(|c| normalize_chain_id(c) == chain_id)
+}
+
+#[allow(clippy::result_large_err)]
+fn require_exposed_chain(state: &AppState, chain_id: &str, req_id: &Value) -> Result<(), Response> {
+    if chain_exposed(state, chain_id) {
+        return Ok(());
+    }
+    Err(Response::error(
+        req_id.clone(),
+        error_codes::INVALID_PARAMS,
+        format!("Chain '{}' is not exposed over MCP", chain_id),
+    ))
+}
+
+// `total_available` for a clipped explorer listing: the explorer's own total when it reports one,
+// else the size of the page as it arrived before clipping.
+fn listing_total(pagination: &utils::Pagination, clipped: Option<usize>, count: usize) -> u64 {
+    pagination.total.unwrap_or(clipped.unwrap_or(count) as u64)
+}
+
+// Per-call `max_items`, clamped to the configured ceiling so one query can't flood the client.
+fn max_items_arg(args: &Value, state: &AppState) -> usize {
+    let cap = state.config.mcp_max_items.max(1);
+    args.get("max_items")
+        .and_then(|v| v.as_u64())
+        .map(|n| (n as usize).clamp(1, cap))
+        .unwrap_or(cap)
+}
+
+// Page size cap for lists kept in local storage: they come back whole unless the caller asks for
+// pages with `page_size`, which MCP_MAX_ITEMS then caps.
+fn local_page_cap(args: &Value, state: &AppState, len: usize) -> u64 {
+    match args.get("page_size") {
+        Some(v) if !v.is_null() => state.config.mcp_max_items as u64,
+        _ => len as u64,
+    }
+}
+
+// Block argument for eth_call: a named tag, 0x quantity, or decimal number (converted to hex). Defaults to latest.
+fn parse_block_tag(arg: Option<&Value>) -> Option<String> {
+    let raw = match arg {
+        None | Some(Value::Null) => return Some("latest".to_string()),
+        Some(Value::Number(n)) => return n.as_u64().map(|n| format!("0x{:x}", n)),
+        Some(Value::String(s)) => s.trim(),
+        Some(_) => return None,
+    };
+    match raw {
+        "latest" | "pending" | "earliest" | "safe" | "finalized" => Some(raw.to_string()),
+        hex if hex.starts_with("0x") => u64::from_str_radix(&hex[2..], 16).ok().map(|_| hex.to_string()),
+        dec => dec.parse::<u64>().ok().map(|n| format!("0x{:x}", n)),
+    }
+}
+
+// Shape check for an eth_call state override so typos fail here rather than as an opaque node error.
+fn validate_state_override(overrides: &Value) -> Result<(), String> {
+    let map = overrides.as_object().ok_or("'state_override' must be an object keyed by address")?;
+    for (address, account) in map {
+        Address::from_str(address).map_err(|_| format!("state_override key '{}' is not an address", address))?;
+        let fields = account
+            .as_object()
+            .ok_or_else(|| format!("state_override for {} must be an object", address))?;
+        for key in fields.keys() {
+            if !matches!(key.as_str(), "balance" | "nonce" | "code" | "state" | "stateDiff") {
+                return Err(format!(
+                    "Unknown state_override field '{}' for {} (expected balance, nonce, code, state or stateDiff)",
+                    key, address
+                ));
+            }
+        }
+        if fields.contains_key("state") && fields.contains_key("stateDiff") {
+            return Err(format!("state_override for {} cannot set both 'state' and 'stateDiff'", address));
+        }
+    }
+    Ok(())
+}
+
 /// This is the main dispatcher for all incoming MCP requests.
+/// Requests handled here belong to no connection, so they are never tracked for cancellation.
 pub async fn handle_mcp_request(req: Request, state: AppState) -> Option<Response> {
+    handle_connection_request("", req, state).await
+}
+
+/// `handle_mcp_request` for a request arriving on `connection` (a stdio loop or an SSE session);
+/// `notifications/cancelled` only reaches requests from the same connection.
+pub async fn handle_connection_request(connection: &str, req: Request, state: AppState) -> Option<Response> {
     info!("Handling MCP request for method: {}", req.method);
 
+    if req.method == "notifications/cancelled" {
+        handle_cancelled(connection, &req, &state);
+        return None;
+    }
     if req.is_notification() {
         return None;
     }
+    if req.jsonrpc != "2.0" {
+        return Some(Response::error(
+            req.response_id(),
+            error_codes::INVALID_REQUEST,
+            format!("Invalid Request: 'jsonrpc' must be \"2.0\", got {:?}", req.jsonrpc),
+        ));
+    }
+    if req.method.trim().is_empty() {
+        return Some(Response::error(
+            req.response_id(),
+            error_codes::INVALID_REQUEST,
+            "Invalid Request: 'method' must be a non-empty string".into(),
+        ));
+    }
+
+    if connection.is_empty() {
+        return Some(dispatch_request(req, state).await);
+    }
+
+    // Track the request so a later `notifications/cancelled` can drop it mid-flight; a second request
+    // reusing an id still in flight would make the first one impossible to cancel
+    let key = (connection.to_string(), req.response_id().to_string());
+    let token = match state.in_flight.entry(key.clone()) {
+        dashmap::mapref::entry::Entry::Occupied(_) => {
+            return Some(Response::error(
+                req.response_id(),
+                error_codes::INVALID_REQUEST,
+                format!("Invalid Request: id {} is already used by a request in flight", key.1),
+            ));
+        }
+        dashmap::mapref::entry::Entry::Vacant(slot) => slot.insert(CancellationToken::new()).clone(),
+    };
+    let cancellable = !requested_tool(&req).is_some_and(|tool| BROADCASTING_TOOLS.contains(&tool));
+    let response = tokio::select! {
+        response = dispatch_request(req, state.clone()) => Some(response),
+        _ = token.cancelled(), if cancellable => {
+            info!("Request {} cancelled by client", key.1);
+            None
+        }
+    };
+    state.in_flight.remove(&key);
+    response
+}
+
+// The tool a `tools/call` or direct tool-method request runs.
+fn requested_tool(req: &Request) -> Option<&str> {
+    match req.method.as_str() {
+        "tools/call" => req.params.as_ref()?.get("name")?.as_str(),
+        method => Some(method),
+    }
+}
+
+// Drops the connection's in-flight work for the `requestId` named in a cancellation notification.
+// The request clears its own entry once it ends, so its id stays taken until then.
+fn handle_cancelled(connection: &str, req: &Request, state: &AppState) {
+    let Some(request_id) = req.params.as_ref().and_then(|p| p.get("requestId")) else {
+        return;
+    };
+    if let Some(token) = state.in_flight.get(&(connection.to_string(), request_id.to_string())) {
+        token.cancel();
+    }
+}
 
-    let response = match req.method.as_str() {
-        "initialize" => handle_initialize(&req),
-        "tools/list" => handle_tools_list(&req),
+async fn dispatch_request(req: Request, state: AppState) -> Response {
+    match req.method.as_str() {
+        "initialize" => handle_initialize(&req, &state),
+        // Liveness probe: empty result echoing the id
+        "ping" => Response::success(req.response_id(), json!({})),
+        "tools/list" => handle_tools_list(&req, &state),
         "tools/call" => handle_tool_call(req, state).await,
         // Convenience aliases to support direct method calls from CLI
         // They are rewritten into tools/call internally to reuse the same logic
-        "get_balance" | "request_faucet" | "transfer_evm" | "transfer_sei" | "transfer_nft_evm"
-        | "search_events" | "get_contract" | "get_contract_code" | "get_contract_transactions"
-        | "redirect_to_seidocs" | "get_chain_info" | "get_transaction_info" | "get_transaction_history" | "get_nft_metadata" => {
+        method if is_direct_method(method) => {
             let name = req.method.clone();
             let wrapped = Request {
                 jsonrpc: req.jsonrpc.clone(),
@@ -144,22 +861,53 @@ pub async fn handle_mcp_request(req: Request, state: AppState) -> Option<Respons
             handle_tool_call(wrapped, state).await
         }
         _ => Response::error(
-            req.id,
+            req.response_id(),
             error_codes::METHOD_NOT_FOUND,
             format!("Method not found: {}", req.method),
         ),
-    };
-
-    Some(response)
+    }
 }
 
 /// Handles a 'tools/call' request by dispatching it to the correct tool logic.
 async fn handle_tool_call(req: Request, state: AppState) -> Response {
+    let tool = req.params.as_ref().and_then(|p| p.get("name")).and_then(Value::as_str).unwrap_or_default().to_string();
+    let correlation_id = state.config.correlation_ids.then(|| uuid::Uuid::new_v4().to_string());
+    let span = info_span!("tool_call", tool = %tool, correlation_id = tracing::field::Empty);
+    if let Some(id) = &correlation_id {
+        span.record("correlation_id", id.as_str());
+    }
+    let mut resp = async {
+        let resp = run_tool_call(req, state).await;
+        debug!(failed = resp.error.is_some(), "tool call finished");
+        resp
+    }
+    .instrument(span)
+    .await;
+    if let Some(id) = correlation_id {
+        attach_correlation_id(&mut resp, &id);
+    }
+    resp
+}
+
+// Puts `correlation_id` in the response's structured data (the result, or the error's data object)
+// but not in its content text.
+fn attach_correlation_id(resp: &mut Response, id: &str) {
+    if let Some(Value::Object(result)) = resp.result.as_mut() {
+        result.insert("correlation_id".into(), json!(id));
+    }
+    if let Some(error) = resp.error.as_mut() {
+        if let Value::Object(data) = error.data.get_or_insert_with(|| json!({})) {
+            data.insert("correlation_id".into(), json!(id));
+        }
+    }
+}
+
+async fn run_tool_call(req: Request, state: AppState) -> Response {
     let params = match req.params.as_ref() {
         Some(p) => p,
         None => {
             return Response::error(
-                req.id,
+                req.response_id(),
                 error_codes::INVALID_PARAMS,
                 "Missing 'params' object".into(),
             )
@@ -170,1130 +918,5893 @@ async fn handle_tool_call(req: Request, state: AppState) -> Response {
         Some(name) => name,
         None => {
             return Response::error(
-                req.id,
+                req.response_id(),
                 error_codes::INVALID_PARAMS,
                 "Missing 'name' field in params".into(),
             )
         }
     };
 
-    let empty_args = json!({});
-    let args = params.get("arguments").unwrap_or(&empty_args);
-    let req_id = &req.id;
-
-    // FIX: All tool logic is now wrapped in an async block for clean error handling
-    // and receives the shared application state.
-    match tool_name {
-        "redirect_to_seidocs" => {
-            // Return a simple payload with the docs URL and a text content for MCP clients
-            let url = crate::blockchain::services::docs::get_sei_docs_url();
-            // Best-effort: on Linux, try opening the default browser via xdg-open
-            #[cfg(target_os = "linux")]
-            {
-                match std::process::Command::new("xdg-open").arg(url).spawn() {
-                    Ok(_) => {
-                        // Include a link content item for clients that support it
-                        let payload = json!({
-                            "url": url,
-                            "opened": true,
-                            "content": [
-                                { "type": "text", "text": "Opened Sei documentation in your browser" },
-                                { "type": "link", "text": "Sei Docs", "url": url }
-                            ]
-                        });
-                        return Response::success(req_id.clone(), payload);
-                    }
-                    Err(_e) => {
-                        // Ignore and continue to return the link-only payload below
-                    }
-                }
+    if !state.config.tool_enabled(tool_name) {
+        return Response::error(
+            req.response_id(),
+            error_codes::METHOD_NOT_FOUND,
+            format!("Tool '{}' is not enabled on this server", tool_name),
+        );
+    }
+
+    let mut args = params.get("arguments").cloned().unwrap_or_else(|| json!({}));
+    let req_id = &req.response_id();
+    // `_timeout_ms` is a transport-level knob, so it is removed before any tool sees its arguments
+    let timeout_ms = match args.as_object_mut().and_then(|a| a.remove("_timeout_ms")) {
+        None => None,
+        Some(v) => match v.as_u64().filter(|ms| *ms > 0) {
+            Some(ms) => Some(ms),
+            None => {
+                return Response::error(
+                    req_id.clone(),
+                    error_codes::INVALID_PARAMS,
+                    "'_timeout_ms' must be a positive integer number of milliseconds".into(),
+                )
             }
-            let payload = json!({ "url": url });
-            let summary = "Open Sei documentation".to_string();
-            // Some MCP clients don't support a dedicated 'link' content item; keep it text-only
-            Response::success(
-                req_id.clone(),
-                json!({
+        },
+    };
+    // Tools that default or infer their chain check it themselves once it is resolved
+    if let Some(chain_id) = args.get("chain_id").and_then(|v| v.as_str()) {
+        if let Err(resp) = require_exposed_chain(&state, &normalize_chain_id(chain_id), req_id) {
+            return resp;
+        }
+    }
+
+    if CONTACT_RECIPIENT_TOOLS.contains(&tool_name) {
+        if let Err(resp) = resolve_contact_recipient(&mut args, &state, req_id).await {
+            return resp;
+        }
+    }
+
+    let Some(timeout_ms) = timeout_ms else {
+        return dispatch_tool(tool_name, &args, req_id, state).await;
+    };
+    let deadline = std::time::Duration::from_millis(timeout_ms);
+    if !BROADCASTING_TOOLS.contains(&tool_name) {
+        return match tokio::time::timeout(deadline, dispatch_tool(tool_name, &args, req_id, state)).await {
+            Ok(resp) => resp,
+            Err(_) => {
+                warn!(tool = tool_name, timeout_ms, "tool call timed out");
+                timeout_response(req_id, tool_name, timeout_ms, None)
+            }
+        };
+    }
+
+    // A send is never dropped at the deadline: it finishes in the background, so its spend reservation
+    // and nonce settle as usual, and the caller learns what it broadcast so far
+    let log = broadcasts::BroadcastLog::default();
+    let (tool, owned_args, owned_id) = (tool_name.to_string(), args.clone(), req_id.clone());
+    let mut call = tokio::spawn(
+        broadcasts::track(log.clone(), async move { dispatch_tool(&tool, &owned_args, &owned_id, state).await }).in_current_span(),
+    );
+    match tokio::time::timeout(deadline, &mut call).await {
+        Ok(Ok(resp)) => resp,
+        Ok(Err(e)) => Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, format!("Tool '{}' failed: {}", tool_name, e)),
+        Err(_) => {
+            let tx_hashes = log.lock().map(|h| h.clone()).unwrap_or_default();
+            warn!(tool = tool_name, timeout_ms, broadcast = tx_hashes.len(), "tool call passed its deadline, still running");
+            let tool = tool_name.to_string();
+            tokio::spawn(async move {
+                if let Ok(resp) = call.await {
+                    info!(tool, failed = resp.error.is_some(), "tool call finished after its deadline");
+                }
+            });
+            timeout_response(req_id, tool_name, timeout_ms, Some(tx_hashes))
+        }
+    }
+}
+
+/// Builds the error for a call that missed its `_timeout_ms` deadline. A call that may broadcast keeps
+/// running (`tx_hashes` is `Some`), so its outcome is unknown; whatever it already sent is listed.
+fn timeout_response(req_id: &Value, tool_name: &str, timeout_ms: u64, tx_hashes: Option<Vec<String>>) -> Response {
+    let mut message = format!("Operation timed out after {} ms ({})", timeout_ms, tool_name);
+    match tx_hashes.as_deref() {
+        None => {}
+        Some([]) => message.push_str("; it is still running and may yet send a transaction, so its outcome is unknown"),
+        Some(hashes) => message.push_str(&format!(
+            "; it is still running, so its outcome is unknown. Already broadcast: {}. Check their status before retrying",
+            hashes.join(", ")
+        )),
+    }
+    let outcome = if tx_hashes.is_some() { "unknown" } else { "abandoned" };
+    Response::error_with_data(
+        req_id.clone(),
+        error_codes::TIMEOUT,
+        message,
+        json!({ "timeout_ms": timeout_ms, "outcome": outcome, "broadcast_tx_hashes": tx_hashes.unwrap_or_default() }),
+    )
+}
+
+/// Runs a single tool by name against already-stripped arguments.
+async fn dispatch_tool(tool_name: &str, args: &Value, req_id: &Value, state: AppState) -> Response {
+    match tool_registry().iter().find(|tool| tool.name == tool_name) {
+        Some(tool) => (tool.handler)(args, req_id, state).await,
+        None => Response::error(
+            req_id.clone(),
+            error_codes::METHOD_NOT_FOUND,
+            format!("Tool not found: {}", tool_name),
+        ),
+    }
+}
+
+async fn redirect_to_seidocs(_args: &Value, req_id: &Value, state: AppState) -> Response {
+    // Return a simple payload with the docs URL and a text content for MCP clients
+    let url = crate::blockchain::services::docs::get_sei_docs_url();
+    // Best-effort and opt-in: on Linux, try opening the default browser via xdg-open.
+    // The URL is validated and passed as a single argv entry (no shell involved).
+    #[cfg(target_os = "linux")]
+    if state.config.open_docs_in_browser
+        && crate::blockchain::services::docs::is_safe_browser_url(url)
+    {
+        match std::process::Command::new("xdg-open").arg(url).spawn() {
+            Ok(_) => {
+                // Include a link content item for clients that support it
+                let payload = json!({
                     "url": url,
-                    "content": [ { "type": "text", "text": format!("{}: {}", summary, url) } ]
+                    "opened": true,
+                    "content": [
+                        { "type": "text", "text": "Opened Sei documentation in your browser" },
+                        { "type": "link", "text": "Sei Docs", "url": url }
+                    ]
+                });
+                return Response::success(req_id.clone(), payload);
+            }
+            Err(_e) => {
+                // Ignore and continue to return the link-only payload below
+            }
+        }
+    }
+    let summary = "Open Sei documentation".to_string();
+    // Some MCP clients don't support a dedicated 'link' content item; keep it text-only
+    Response::success(
+        req_id.clone(),
+        json!({
+            "url": url,
+            "content": [ { "type": "text", "text": format!("{}: {}", summary, url) } ]
+        })
+    )
+}
+
+async fn list_methods(_args: &Value, req_id: &Value, state: AppState) -> Response {
+    let protocol: Vec<Value> = PROTOCOL_METHODS
+        .iter()
+        .map(|(method, description)| json!({ "method": method, "description": description }))
+        .collect();
+    // A direct method takes the tool arguments as its params
+    let direct: Vec<Value> = DIRECT_METHOD_TOOLS
+        .iter()
+        .filter(|name| is_direct_method(name) && state.config.tool_enabled(name))
+        .map(|name| json!({ "method": name, "tool": name, "maps_to": "tools/call" }))
+        .collect();
+    let summary = format!(
+        "{} protocol methods and {} direct tool methods. Call a tool either as \
+         {{\"method\":\"tools/call\",\"params\":{{\"name\":\"get_balance\",\"arguments\":{{...}}}}}} \
+         or directly as {{\"method\":\"get_balance\",\"params\":{{...}}}}.",
+        protocol.len(),
+        direct.len()
+    );
+    Response::success(
+        req_id.clone(),
+        make_texty_result(summary, json!({ "protocol_methods": protocol, "direct_methods": direct })),
+    )
+}
+
+async fn resolve_chain_id(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let input = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
+        let chain_id = normalize_chain_id(&input);
+        let chain_type = ChainType::from_chain_id(&chain_id);
+        let rpc_configured = state.config.chain_rpc_urls.contains_key(&chain_id);
+        let aliases: serde_json::Map<String, Value> = CHAIN_ID_ALIASES
+            .iter()
+            .map(|(alias, canonical)| (alias.to_string(), json!(canonical)))
+            .collect();
+        let network_name = state.config.network_name(&chain_id);
+        let summary = format!(
+            "'{}' resolves to {} — {} ({:?}); RPC URL {}",
+            input,
+            chain_id,
+            network_name,
+            chain_type,
+            if rpc_configured { "configured" } else { "NOT configured" }
+        );
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, json!({
+                "input": input,
+                "chain_id": chain_id,
+                "network_name": network_name,
+                "chain_type": chain_type,
+                "rpc_configured": rpc_configured,
+                "aliases": aliases
+            })),
+        ))
+    }).await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn health_check_chains(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let probe = args.get("probe").and_then(|v| v.as_bool()).unwrap_or(true);
+        let chains = match args.get("chain_id").and_then(|v| v.as_str()) {
+            Some(c) => vec![normalize_chain_id(c)],
+            None => state.sei_client.chain_ids().into_iter().filter(|c| chain_exposed(&state, c)).collect(),
+        };
+        let mut report = Vec::with_capacity(chains.len());
+        let mut lines = Vec::with_capacity(chains.len());
+        for chain_id in chains {
+            // Only EVM endpoints speak JSON-RPC; native REST gateways are reported without probing
+            let is_evm = ChainType::from_chain_id(&chain_id) == ChainType::Evm;
+            let endpoints = state
+                .sei_client
+                .endpoint_health(&chain_id, probe && is_evm)
+                .await
+                .map_err(|e| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string()))?;
+            let active_url = state.sei_client.get_rpc_url(&chain_id).ok().cloned();
+            let healthy = endpoints.iter().filter(|(s, _)| s.healthy).count();
+            lines.push(format!(
+                "{}: {}/{} endpoint(s) healthy, using {}",
+                chain_id,
+                healthy,
+                endpoints.len(),
+                active_url.as_deref().unwrap_or("none")
+            ));
+            let endpoints: Vec<Value> = endpoints
+                .into_iter()
+                .enumerate()
+                .map(|(i, (status, latest_block))| {
+                    let mut entry = json!(status);
+                    entry["role"] = json!(if i == 0 { "primary" } else { "fallback" });
+                    entry["latest_block"] = json!(latest_block);
+                    entry
                 })
-            )
+                .collect();
+            report.push(json!({ "chain_id": chain_id, "active_url": active_url, "probed": probe && is_evm, "endpoints": endpoints }));
         }
-        ,
-        // --- SeiStream read-only tools ---
-        "get_chain_info" => {
-            let res: Result<Response, Response> = (async {
-                let client = Client::new();
-                let v = crate::blockchain::services::seistream::get_chain_info(&client)
-                    .await
-                    .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
-                // Provide both human-friendly text content and raw JSON for clients to parse
-                let latest = v.get("latestBlock").and_then(|b| b.get("height")).and_then(|h| h.as_u64());
-                let network = v.get("network").and_then(|n| n.as_str()).unwrap_or("unknown");
-                let summary = if let Some(h) = latest { format!("Chain info — {} (height {})", network, h) } else { format!("Chain info — {}", network) };
-                Ok(Response::success(
+        Ok(Response::success(req_id.clone(), make_texty_result(lines.join("\n"), json!({ "chains": report }))))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+// --- SeiStream read-only tools ---
+async fn websocket_status(_args: &Value, req_id: &Value, state: AppState) -> Response {
+    let status = state.ws.status();
+    let summary = if !status.configured {
+        "WebSocket not configured (set WEBSOCKET_URL)".to_string()
+    } else if status.connected {
+        format!("WebSocket connected to {}", status.url)
+    } else {
+        format!(
+            "WebSocket disconnected from {} after {} reconnect attempt(s){}",
+            status.url,
+            status.reconnect_attempts,
+            status.last_error.as_deref().map(|e| format!(": {}", e)).unwrap_or_default()
+        )
+    };
+    Response::success(req_id.clone(), make_texty_result(summary, json!(status)))
+}
+
+async fn get_chain_info(_args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let client = Client::new();
+        let v = crate::blockchain::services::seistream::get_chain_info(&client)
+            .await
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        // Provide both human-friendly text content and raw JSON for clients to parse
+        let latest = v.get("latestBlock").and_then(|b| b.get("height")).and_then(|h| h.as_u64());
+        let network = v.get("network").and_then(|n| n.as_str()).unwrap_or("unknown");
+        let network_name = state.config.network_name(network);
+        let summary = if let Some(h) = latest { format!("Chain info — {} (height {})", network_name, h) } else { format!("Chain info — {}", network_name) };
+        Ok(Response::success(
+            req_id.clone(),
+            json!({
+                // flatten key fields for clients that render top-level data
+                "network": v.get("network"),
+                "network_name": network_name,
+                "latestBlock": v.get("latestBlock"),
+                "validators": v.get("validators"),
+                "window": v.get("window"),
+                // full payload for programmatic consumers
+                "data": v,
+                // human summary
+                "content": [ { "type": "text", "text": summary } ]
+            }),
+        ))
+    })
+    .await;
+    match res { Ok(r) => r, Err(e) => e }
+}
+
+async fn get_explorer_url(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let arg = |key: &str| args.get(key).and_then(Value::as_str).map(str::trim).filter(|v| !v.is_empty());
+        let (kind, id, url) = match (arg("tx_hash"), arg("address")) {
+            (Some(hash), None) => ("tx", hash, state.config.explorer_tx_url(&chain_id, hash)),
+            (None, Some(address)) => ("address", address, state.config.explorer_address_url(&chain_id, address)),
+            _ => {
+                return Err(Response::error(
                     req_id.clone(),
-                    json!({
-                        // flatten key fields for clients that render top-level data
-                        "network": v.get("network"),
-                        "latestBlock": v.get("latestBlock"),
-                        "validators": v.get("validators"),
-                        "window": v.get("window"),
-                        // full payload for programmatic consumers
-                        "data": v,
-                        // human summary
-                        "content": [ { "type": "text", "text": summary } ]
-                    }),
+                    error_codes::INVALID_PARAMS,
+                    "Pass exactly one of 'tx_hash' or 'address'".into(),
                 ))
+            }
+        };
+        let url = url.ok_or_else(|| {
+            Response::error(
+                req_id.clone(),
+                error_codes::NOT_FOUND,
+                format!("No explorer configured for chain_id '{}'; set EXPLORER_URLS", chain_id),
+            )
+        })?;
+        let summary = format!("Explorer link for {} {}: {}", kind, id, url);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, json!({ "chain_id": chain_id, "kind": kind, "id": id, "explorer_url": url })),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_transaction_info(args: &Value, req_id: &Value, _state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let hash = utils::get_required_arg::<String>(args, "hash", req_id)?;
+        let client = Client::new();
+        let v = crate::blockchain::services::seistream::get_transaction_info(&client, &hash)
+            .await
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        let status = v.get("status").and_then(|s| s.as_str()).unwrap_or("");
+        let from = v.get("from").and_then(|s| s.as_str()).unwrap_or("");
+        let to = v.get("to").and_then(|s| s.as_str()).unwrap_or("");
+        let summary = if !status.is_empty() {
+            format!("Tx {} — {} ({} -> {})", &hash, status, from, to)
+        } else {
+            format!("Tx {}", &hash)
+        };
+        Ok(Response::success(
+            req_id.clone(),
+            json!({
+                // expose common tx fields at top-level when present
+                "hash": v.get("hash").cloned().unwrap_or_else(|| json!(hash)),
+                "from": v.get("from"),
+                "to": v.get("to"),
+                "status": v.get("status"),
+                "blockNumber": v.get("blockNumber"),
+                // full payload
+                "data": v,
+                // human summary
+                "content": [ { "type": "text", "text": summary } ]
             })
-            .await;
-            match res { Ok(r) => r, Err(e) => e }
+        ))
+    })
+    .await;
+    match res { Ok(r) => r, Err(e) => e }
+}
+
+async fn get_native_tx(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let hash = utils::get_required_arg::<String>(args, "hash", req_id)?;
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        if ChainType::from_chain_id(&chain_id) != ChainType::Native {
+            return Err(Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                format!("'{}' is an EVM chain; use get_transaction_receipt for EVM transactions", chain_id),
+            ));
         }
-        "get_transaction_info" => {
-            let res: Result<Response, Response> = (async {
-                let hash = utils::get_required_arg::<String>(args, "hash", req_id)?;
-                let client = Client::new();
-                let v = crate::blockchain::services::seistream::get_transaction_info(&client, &hash)
-                    .await
-                    .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
-                let status = v.get("status").and_then(|s| s.as_str()).unwrap_or("");
-                let from = v.get("from").and_then(|s| s.as_str()).unwrap_or("");
-                let to = v.get("to").and_then(|s| s.as_str()).unwrap_or("");
-                let summary = if !status.is_empty() {
-                    format!("Tx {} — {} ({} -> {})", &hash, status, from, to)
-                } else {
-                    format!("Tx {}", &hash)
-                };
-                Ok(Response::success(
-                    req_id.clone(),
-                    json!({
-                        // expose common tx fields at top-level when present
-                        "hash": v.get("hash").cloned().unwrap_or_else(|| json!(hash)),
-                        "from": v.get("from"),
-                        "to": v.get("to"),
-                        "status": v.get("status"),
-                        "blockNumber": v.get("blockNumber"),
-                        // full payload
-                        "data": v,
-                        // human summary
-                        "content": [ { "type": "text", "text": summary } ]
-                    })
-                ))
-            })
-            .await;
-            match res { Ok(r) => r, Err(e) => e }
-        }
-        "get_transaction_history" => {
-            let res: Result<Response, Response> = (async {
-                let address = utils::get_required_arg::<String>(args, "address", req_id)?;
-                let page = args.get("page").and_then(|v| v.as_u64());
-                let client = Client::new();
-                let v = crate::blockchain::services::seistream::get_transaction_history(&client, &address, page)
-                    .await
-                    .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
-                let count = v.get("items").and_then(|i| i.as_array()).map(|a| a.len()).unwrap_or(0);
-                let summary = match page {
-                    Some(p) => format!("History for {} — {} item(s) on page {}", &address, count, p),
-                    None => format!("History for {} — {} item(s)", &address, count),
-                };
-                Ok(Response::success(
-                    req_id.clone(),
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let tendermint_url = state.config.tendermint_rpc_urls.get(&chain_id).map(String::as_str);
+        let rest_url = state.sei_client.get_rpc_url(&chain_id).ok();
+        if tendermint_url.is_none() && rest_url.is_none() {
+            return Err(Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                format!("No Tendermint or REST URL configured for chain_id '{}'", chain_id),
+            ));
+        }
+        let tx = native_tx::get_native_tx(state.sei_client.http(), tendermint_url, rest_url.map_or("", String::as_str), &hash)
+            .await
+            .map_err(|e| service_error(req_id, e))?;
+        let summary = format!(
+            "Native tx {} on {}: {} (code {}), gas {}/{}, {} event(s)",
+            tx["hash"].as_str().unwrap_or_default(),
+            state.config.network_name(&chain_id),
+            if tx["success"] == json!(true) { "success" } else { "failed" },
+            tx["code"],
+            tx["gas_used"],
+            tx["gas_wanted"],
+            tx["events"].as_array().map_or(0, Vec::len)
+        );
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, tx)))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_transaction_trace(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let hash = utils::get_required_arg::<String>(args, "hash", req_id)?;
+        let include_raw = args.get("raw").and_then(Value::as_bool).unwrap_or(false);
+        state.sei_client.get_rpc_url(&chain_id).map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
+        })?;
+        let Some(traced) = state
+            .sei_client
+            .trace_transaction(&chain_id, &hash)
+            .await
+            .map_err(|e| service_error(req_id, e))?
+        else {
+            let summary = format!(
+                "Tracing unavailable on {}: the node exposes neither debug_traceTransaction nor trace_transaction",
+                chain_id
+            );
+            return Ok(Response::success(
+                req_id.clone(),
+                make_texty_result(summary, json!({ "hash": hash, "chain_id": chain_id, "supported": false, "trace": null })),
+            ));
+        };
+        let Some(root) = &traced.root else {
+            return Err(Response::error(
+                req_id.clone(),
+                error_codes::NOT_FOUND,
+                format!("No trace for {} on {} (unknown or pending transaction)", hash, chain_id),
+            ));
+        };
+        let (internal_calls, value_transfers) = root.internal_counts();
+        let summary = format!(
+            "Tx {}: {} {} -> {}, {} internal call(s), {} moving value",
+            hash,
+            root.call_type,
+            root.from,
+            root.to.as_deref().unwrap_or("?"),
+            internal_calls,
+            value_transfers
+        );
+        let mut payload = json!({
+            "hash": hash,
+            "chain_id": chain_id,
+            "supported": true,
+            "method": traced.method.rpc_method(),
+            "trace": root,
+            "internal_calls": internal_calls,
+            "internal_value_transfers": value_transfers,
+        });
+        if include_raw {
+            payload["raw_trace"] = traced.raw.clone();
+        }
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, payload)))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_transaction_receipt(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let hash = utils::get_required_arg::<String>(args, "hash", req_id)?;
+        let chain_id = args
+            .get("chain_id")
+            .and_then(|v| v.as_str())
+            .map(normalize_chain_id)
+            .unwrap_or_else(|| "sei-evm-testnet".to_string());
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
+        })?;
+        let receipt = rpc::rpc_call(state.sei_client.transport(), rpc_url, "eth_getTransactionReceipt", json!([hash]))
+            .await
+            .map_err(|e| service_error(req_id, e))?;
+        if receipt.is_null() {
+            return Ok(Response::success(
+                req_id.clone(),
+                make_texty_result(
+                    format!("No receipt for {} on {} (pending or unknown)", hash, chain_id),
+                    json!({ "hash": hash, "status": "pending", "receipt": null }),
+                ),
+            ));
+        }
+        if receipt["status"] != "0x0" {
+            return Ok(Response::success(
+                req_id.clone(),
+                make_texty_result(
+                    format!("Tx {} succeeded in block {}", hash, receipt["blockNumber"].as_str().unwrap_or("?")),
+                    json!({ "hash": hash, "status": "success", "receipt": receipt }),
+                ),
+            ));
+        }
+
+        // Custom errors need the ABI: explicit, else SeiStream's verified ABI for the callee
+        let abi_entries = match args.get("abi") {
+            Some(Value::Array(entries)) => Some(entries.clone()),
+            _ => match receipt["to"].as_str() {
+                Some(to) => state.sei_client.get_contract_abi(&chain_id, to).await.ok().flatten(),
+                None => None,
+            },
+        };
+        let parsed = abi_entries.and_then(|e| contract::parse_abi(&e).ok());
+        let revert = revert_details(state.sei_client.transport(), rpc_url, &hash, parsed.as_ref()).await;
+        let summary = match revert["reason"].as_str() {
+            Some(reason) => format!("Tx {} reverted: {}", hash, reason),
+            None => format!("Tx {} reverted (no reason recovered)", hash),
+        };
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, json!({ "hash": hash, "status": "reverted", "receipt": receipt, "revert": revert })),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn check_finality(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: &str| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg.to_string());
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let tx_hash = args.get("tx_hash").and_then(Value::as_str).map(str::trim).filter(|h| !h.is_empty());
+        let block_number = match args.get("block_number") {
+            None | Some(Value::Null) => None,
+            Some(v) => Some(v.as_u64().ok_or_else(|| invalid("'block_number' must be a non-negative integer"))?),
+        };
+        if tx_hash.is_some() == block_number.is_some() {
+            return Err(invalid("Pass exactly one of 'block_number' or 'tx_hash'"));
+        }
+        let required = args
+            .get("confirmations")
+            .and_then(Value::as_u64)
+            .unwrap_or_else(|| state.config.finality_confirmations(&chain_id))
+            .max(1);
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| invalid(&e.to_string()))?;
+        let client = state.sei_client.transport();
+        let latest = || async {
+            blocks::latest_block_number(client, rpc_url).await.map_err(|e| service_error(req_id, e))
+        };
+
+        let Some(tx_hash) = tx_hash else {
+            let number = block_number.unwrap_or_default();
+            let latest = latest().await?;
+            let block_hash = blocks::block_hash(client, rpc_url, number).await.map_err(|e| service_error(req_id, e))?;
+            let confirmations = finality::confirmations(latest, number);
+            let finalized = confirmations >= required;
+            let summary = format!(
+                "Block {} on {}: {} confirmation(s) of {} required{}",
+                number,
+                chain_id,
+                confirmations,
+                required,
+                if finalized { "; final" } else { "; not final yet" }
+            );
+            return Ok(Response::success(
+                req_id.clone(),
+                make_texty_result(
+                    summary,
                     json!({
-                        "data": v,
-                        "content": [ { "type": "text", "text": summary } ]
-                    })
-                ))
-            })
-            .await;
-            match res { Ok(r) => r, Err(e) => e }
-        }
-        "get_nft_metadata" => {
-            let res: Result<Response, Response> = (async {
-                // ERC-721 items for a contract
-                let contract = utils::get_required_arg::<String>(args, "contract_address", req_id)?;
-                let page = args.get("page").and_then(|v| v.as_u64());
-                let client = Client::new();
-                let v = crate::blockchain::services::seistream::get_nft_metadata_erc721_items(&client, &contract, page)
-                    .await
-                    .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
-                let count = v.get("items").and_then(|i| i.as_array()).map(|a| a.len()).unwrap_or(0);
-                let summary = match page {
-                    Some(p) => format!("ERC-721 items for {} — {} item(s) on page {}", &contract, count, p),
-                    None => format!("ERC-721 items for {} — {} item(s)", &contract, count),
-                };
-                // Optionally include the first item inline as text preview for Claude UX
-                let preview = v.get("items").and_then(|i| i.as_array()).and_then(|a| a.get(0)).cloned();
-                let mut content = vec![ json!({ "type": "text", "text": summary }) ];
-                if let Some(first) = preview {
-                    if let Ok(pretty) = serde_json::to_string_pretty(&first) {
-                        content.push(json!({ "type": "text", "text": format!("Preview (first item):\n{}", pretty) }));
+                        "chain_id": chain_id,
+                        "block_number": number,
+                        "block_hash": block_hash,
+                        "latest_block": latest,
+                        "confirmations": confirmations,
+                        "required_confirmations": required,
+                        "finalized": finalized,
+                    }),
+                ),
+            ));
+        };
+
+        let fetch_receipt = || async {
+            rpc::rpc_call(client, rpc_url, "eth_getTransactionReceipt", json!([tx_hash]))
+                .await
+                .map_err(|e| service_error(req_id, e))
+        };
+        let mined_at = |receipt: &Value| {
+            let number = receipt["blockNumber"].as_str().and_then(|n| u64::from_str_radix(n.trim_start_matches("0x"), 16).ok())?;
+            Some(finality::MinedAt { block_number: number, block_hash: receipt["blockHash"].as_str()?.to_string() })
+        };
+        let receipt = fetch_receipt().await?;
+        let latest = latest().await?;
+        // Re-fetched after reading the head: a receipt gone now (or seen before but missing
+        // altogether) means the transaction was mined and then reorged out
+        let (current, previous) = match mined_at(&receipt) {
+            Some(first) => {
+                let refetched = fetch_receipt().await?;
+                match mined_at(&refetched) {
+                    Some(now) => {
+                        let moved = state.finality.record(&chain_id, tx_hash, now.clone()).or((first != now).then_some(first));
+                        (Some((now, refetched)), moved)
                     }
+                    None => (None, Some(first)),
                 }
-                Ok(Response::success(
-                    req_id.clone(),
-                    json!({
-                        // top-level helpful fields for clients
-                        "contract_address": contract,
-                        "page": page,
-                        "count": count,
-                        // first item preview also as structured field
-                        "preview": v.get("items").and_then(|i| i.as_array()).and_then(|a| a.get(0)).cloned(),
-                        // full payload
-                        "data": v,
-                        // human summary and preview text
-                        "content": content
-                    })
-                ))
-            })
-            .await;
-            match res { Ok(r) => r, Err(e) => e }
-        }
-        "discord_post_message" => {
-            let res: Result<Response, Response> = (async {
-                let message = utils::get_required_arg::<String>(args, "message", req_id)?;
-                let username = args.get("username").and_then(|v| v.as_str());
-                let res = crate::api::discord::post_discord_message(&state, &message, username)
-                    .await
-                    .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
-                let summary = if let Some(u) = username {
-                    format!("Posted to Discord as '{}'", u)
-                } else {
-                    "Posted to Discord".to_string()
-                };
-                Ok(Response::success(
-                    req_id.clone(),
-                    make_texty_result(summary, res),
-                ))
-            })
-            .await;
-            res.unwrap_or_else(|err_resp| err_resp)
-        }
-        "get_balance" => {
-            let res: Result<Response, Response> = (async {
-                let address = utils::get_required_arg::<String>(args, "address", req_id)?;
-                let mut chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
-                chain_id = normalize_chain_id(&chain_id);
-                let rpc_url = match state.config.chain_rpc_urls.get(&chain_id) {
-                    Some(u) => u,
-                    None => {
-                        let keys: Vec<String> =
-                            state.config.chain_rpc_urls.keys().cloned().collect();
-                        return Err(Response::error(
-                            req_id.clone(),
-                            error_codes::INVALID_PARAMS,
-                            format!(
-                                "RPC URL not configured for chain_id '{}'. Available: {}",
-                                chain_id,
-                                keys.join(", ")
-                            ),
-                        ));
-                    }
-                };
-                let chain_type = ChainType::from_chain_id(&chain_id);
-                let client = Client::new();
-                let is_native = matches!(chain_type, ChainType::Native);
-                let balance = crate::blockchain::services::balance::get_balance(
-                    &client, rpc_url, &address, is_native,
-                )
-                .await
-                .map_err(|e| {
-                    Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
-                })?;
-                let debug_info = json!({
-                    "chain_id_normalized": chain_id,
-                    "rpc_url": rpc_url,
-                    "chain_type": if is_native { "native" } else { "evm" }
-                });
-                let balance_text = match serde_json::to_string(&balance) {
-                    Ok(s) => format!("Balance: {}", s),
-                    Err(_) => "Balance fetched".to_string(),
-                };
-                // Return plain JSON so MCP clients can parse result directly
-                Ok(Response::success(
-                    req_id.clone(),
+            }
+            None => (None, state.finality.last_seen(&chain_id, tx_hash)),
+        };
+        let previous_block = previous.as_ref().map(|p| json!({ "block_number": p.block_number, "block_hash": p.block_hash }));
+
+        let Some((at, receipt)) = current else {
+            let reorged_out = previous.is_some();
+            let summary = match &previous {
+                Some(p) => format!("Tx {} was mined in block {} but is no longer on {}: reorged out", tx_hash, p.block_number, chain_id),
+                None => format!("Tx {} has no receipt on {} (pending or unknown)", tx_hash, chain_id),
+            };
+            return Ok(Response::success(
+                req_id.clone(),
+                make_texty_result(
+                    summary,
                     json!({
-                        // Plain fields for Windsurf and generic JSON-RPC clients
-                        "balance": balance,
-                        "debug": debug_info,
-                        "message": balance_text,
-                        // Text content for clients that expect a content array
-                        "content": [
-                            { "type": "text", "text": balance_text }
-                        ]
+                        "chain_id": chain_id,
+                        "tx_hash": tx_hash,
+                        "status": if reorged_out { "reorged_out" } else { "pending" },
+                        "reorged_out": reorged_out,
+                        "previous_block": previous_block,
+                        "latest_block": latest,
+                        "confirmations": 0,
+                        "required_confirmations": required,
+                        "finalized": false,
                     }),
-                ))
-            })
-            .await;
-            res.unwrap_or_else(|err_resp| err_resp)
+                ),
+            ));
+        };
+        let confirmations = finality::confirmations(latest, at.block_number);
+        let finalized = confirmations >= required;
+        let summary = format!(
+            "Tx {} in block {} on {}: {} confirmation(s) of {} required{}{}",
+            tx_hash,
+            at.block_number,
+            chain_id,
+            confirmations,
+            required,
+            if finalized { "; final" } else { "; not final yet" },
+            if previous.is_some() { "; moved blocks since last seen (reorg)" } else { "" }
+        );
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "chain_id": chain_id,
+                    "tx_hash": tx_hash,
+                    "status": if finalized { "finalized" } else { "confirming" },
+                    "succeeded": receipt["status"] != "0x0",
+                    "block_number": at.block_number,
+                    "block_hash": at.block_hash,
+                    "reorged_out": false,
+                    "previous_block": previous_block,
+                    "latest_block": latest,
+                    "confirmations": confirmations,
+                    "required_confirmations": required,
+                    "finalized": finalized,
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_transaction_history(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        let max_items = max_items_arg(args, &state);
+        let (page, page_size) = utils::page_args(args, max_items as u64);
+        let client = Client::new();
+        let v = crate::blockchain::services::seistream::get_transaction_history(&client, &address, Some(page), Some(page_size))
+            .await
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        let pagination = crate::blockchain::services::seistream::listing_pagination(&v, page, page_size);
+        let mut v = v;
+        let filter_spam = args.get("filter_spam").and_then(|v| v.as_bool()).unwrap_or(false);
+        // Flag before truncating so filtering never leaves the page short of max_items
+        let mut spam_filtered = 0;
+        if let Some(items) = v.get_mut("items").and_then(|i| i.as_array_mut()) {
+            for item in items.iter_mut() {
+                let reasons = spam::spam_reasons(item, &address);
+                if let Some(obj) = item.as_object_mut() {
+                    obj.insert("is_likely_spam".into(), json!(!reasons.is_empty()));
+                    if !reasons.is_empty() {
+                        obj.insert("spam_reasons".into(), json!(reasons));
+                    }
+                }
+            }
+            if filter_spam {
+                let before = items.len();
+                items.retain(|item| item["is_likely_spam"] != json!(true));
+                spam_filtered = before - items.len();
+            }
         }
+        let clipped = v.get_mut("items").and_then(|i| utils::truncate_array(i, max_items));
+        let count = v.get("items").and_then(|i| i.as_array()).map(|a| a.len()).unwrap_or(0);
+        let mut summary = format!("History for {} — {} item(s) on page {}", &address, count, page);
+        if let Some(total) = clipped {
+            summary.push_str(&format!(" (truncated from {})", total));
+        }
+        if spam_filtered > 0 {
+            summary.push_str(&format!(", {} likely spam hidden", spam_filtered));
+        }
+        if let Some(n) = pagination.next_page {
+            summary.push_str(&format!("; more on page {}", n));
+        }
+        let total_available = listing_total(&pagination, clipped, count);
+        Ok(Response::success(
+            req_id.clone(),
+            json!({
+                "data": v,
+                "pagination": pagination,
+                "truncated": clipped.is_some(),
+                "total_available": total_available,
+                "spam_filtered": spam_filtered,
+                "content": [ { "type": "text", "text": summary } ]
+            })
+        ))
+    })
+    .await;
+    match res { Ok(r) => r, Err(e) => e }
+}
+
+async fn export_transaction_history(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        let limit = max_items_arg(args, &state) as u64;
+        let client = Client::new();
+        let history = crate::blockchain::services::history::get_transaction_history(&client, &address, limit)
+            .await
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        let csv = crate::blockchain::services::history::transactions_to_csv(&history.transactions);
+        // The CSV is the whole answer, so it is returned as the single text block
+        Ok(Response::success(
+            req_id.clone(),
+            json!({
+                "count": history.transactions.len(),
+                "partial": history.partial,
+                "skipped_items": history.skipped_items,
+                "content": [ { "type": "text", "text": csv } ]
+            }),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-        "create_wallet" => match state.sei_client.create_wallet().await {
-            Ok(wallet) => {
-                let summary = format!("Created wallet {}", wallet.address);
-                Response::success(req_id.clone(), make_texty_result(summary, json!(wallet)))
+async fn get_nft_metadata(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        // ERC-721 items for a contract
+        let contract = utils::get_required_arg::<String>(args, "contract_address", req_id)?;
+        let max_items = max_items_arg(args, &state);
+        let (page, page_size) = utils::page_args(args, max_items as u64);
+        let client = Client::new();
+        let v = crate::blockchain::services::seistream::get_nft_metadata_erc721_items(&client, &contract, Some(page), Some(page_size))
+            .await
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        let pagination = crate::blockchain::services::seistream::listing_pagination(&v, page, page_size);
+        let mut v = v;
+        let clipped = v.get_mut("items").and_then(|i| utils::truncate_array(i, max_items));
+        let count = v.get("items").and_then(|i| i.as_array()).map(|a| a.len()).unwrap_or(0);
+        let mut summary = format!("ERC-721 items for {} — {} item(s) on page {}", &contract, count, page);
+        if let Some(total) = clipped {
+            summary.push_str(&format!(" (truncated from {})", total));
+        }
+        // Optionally include the first item inline as text preview for Claude UX
+        let preview = v.get("items").and_then(|i| i.as_array()).and_then(|a| a.first()).cloned();
+        let mut content = vec![ json!({ "type": "text", "text": summary }) ];
+        if let Some(first) = preview {
+            if let Ok(pretty) = serde_json::to_string_pretty(&first) {
+                content.push(json!({ "type": "text", "text": format!("Preview (first item):\n{}", pretty) }));
             }
-            Err(e) => Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()),
-        },
+        }
+        Ok(Response::success(
+            req_id.clone(),
+            json!({
+                // top-level helpful fields for clients
+                "contract_address": contract,
+                "page": page,
+                "count": count,
+                "truncated": clipped.is_some(),
+                "total_available": listing_total(&pagination, clipped, count),
+                "pagination": pagination,
+                // first item preview also as structured field
+                "preview": v.get("items").and_then(|i| i.as_array()).and_then(|a| a.first()).cloned(),
+                // full payload
+                "data": v,
+                // human summary and preview text
+                "content": content
+            })
+        ))
+    })
+    .await;
+    match res { Ok(r) => r, Err(e) => e }
+}
 
-        "import_wallet" => {
-            let res: Result<Response, Response> = (async {
-                let key = utils::get_required_arg::<String>(args, "key", req_id)?;
-                let wallet = state.sei_client.import_wallet(&key).await.map_err(|e| {
-                    Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
-                })?;
-                let summary = format!("Imported wallet {}", wallet.address);
-                Ok(Response::success(
+async fn discord_post_message(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let message = utils::get_required_arg::<String>(args, "message", req_id)?;
+        let username = args.get("username").and_then(|v| v.as_str());
+        let res = crate::api::discord::post_discord_message(&state, &message, username)
+            .await
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        let summary = if let Some(u) = username {
+            format!("Posted to Discord as '{}'", u)
+        } else {
+            "Posted to Discord".to_string()
+        };
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, res),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn batch_query(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: String| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg);
+        let queries = utils::get_required_arg::<Vec<Value>>(args, "queries", req_id)?;
+        if queries.is_empty() || queries.len() > MAX_BATCH_QUERIES {
+            return Err(invalid(format!("'queries' must contain 1 to {} entries", MAX_BATCH_QUERIES)));
+        }
+        // The whole batch is refused up front if any entry is malformed or could change state
+        let mut calls = Vec::with_capacity(queries.len());
+        for (i, query) in queries.iter().enumerate() {
+            let tool = query
+                .get("tool")
+                .and_then(|t| t.as_str())
+                .ok_or_else(|| invalid(format!("queries[{}] is missing 'tool'", i)))?;
+            let sub_args = query.get("arguments").cloned().unwrap_or_else(|| json!({}));
+            if !sub_args.is_object() {
+                return Err(invalid(format!("queries[{}].arguments must be an object", i)));
+            }
+            if !state.config.tool_enabled(tool) {
+                return Err(Response::error(
                     req_id.clone(),
-                    make_texty_result(summary, json!(wallet)),
-                ))
-            })
-            .await;
-            res.unwrap_or_else(|err_resp| err_resp)
+                    error_codes::METHOD_NOT_FOUND,
+                    format!("queries[{}]: tool '{}' is not enabled on this server", i, tool),
+                ));
+            }
+            if !BATCHABLE_TOOLS.contains(&tool) {
+                return Err(invalid(format!("queries[{}]: '{}' is not a read-only tool and can't be batched", i, tool)));
+            }
+            if tool == "call_contract_by_abi" && sub_args.get("private_key").is_some_and(|k| !k.is_null()) {
+                return Err(invalid(format!("queries[{}]: call_contract_by_abi with a private_key sends a transaction and can't be batched", i)));
+            }
+            calls.push((tool.to_string(), sub_args));
         }
 
-        "request_faucet" => {
-            let res: Result<Response, Response> = (async {
-                let address = utils::get_required_arg::<String>(args, "address", req_id)?;
-                let mut chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
-                chain_id = normalize_chain_id(&chain_id);
-                let rpc_url = match state.config.chain_rpc_urls.get(&chain_id) {
-                    Some(u) => u,
-                    None => {
-                        let keys: Vec<String> =
-                            state.config.chain_rpc_urls.keys().cloned().collect();
-                        return Err(Response::error(
-                            req_id.clone(),
-                            error_codes::INVALID_PARAMS,
-                            format!(
-                                "RPC URL not configured for chain_id '{}'. Available: {}",
-                                chain_id,
-                                keys.join(", ")
-                            ),
-                        ));
+        // One failed query is reported in its entry; the rest of the batch still returns
+        let results: Vec<Value> = futures::stream::iter(calls)
+            .map(|(tool, sub_args)| {
+                let state = state.clone();
+                async move {
+                    let resp = match sub_args.get("chain_id").and_then(|v| v.as_str()) {
+                        Some(chain_id) => match require_exposed_chain(&state, &normalize_chain_id(chain_id), req_id) {
+                            Ok(()) => Box::pin(dispatch_tool(&tool, &sub_args, req_id, state)).await,
+                            Err(resp) => resp,
+                        },
+                        None => Box::pin(dispatch_tool(&tool, &sub_args, req_id, state)).await,
+                    };
+                    match (resp.result, resp.error) {
+                        (_, Some(error)) => json!({ "tool": tool, "ok": false, "error": error }),
+                        (result, None) => json!({ "tool": tool, "ok": true, "result": result }),
                     }
-                };
-                let tx_hash = crate::blockchain::services::faucet::send_faucet_tokens(
-                    &state.config,
-                    &address,
-                    &state.nonce_manager,
-                    rpc_url,
-                    &chain_id,
-                )
-                .await
-                .map_err(|e| {
-                    Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
-                })?;
-                let payload = json!({ "transaction_hash": tx_hash });
-                let summary = format!("Faucet sent tokens: tx {}", tx_hash);
-                Ok(Response::success(
-                    req_id.clone(),
-                    make_texty_result(summary, payload),
-                ))
+                }
             })
+            .buffered(BATCH_QUERY_CONCURRENCY)
+            .collect()
             .await;
-            res.unwrap_or_else(|err_resp| err_resp)
-        }
-
-        // --- Event tools ---
-        "search_events" => {
-            let res: Result<Response, Response> = (async {
-                let chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
-                match ChainType::from_chain_id(&chain_id) {
-                    ChainType::Evm => {
-                        let rpc_url =
-                            state.config.chain_rpc_urls.get(&chain_id).ok_or_else(|| {
-                                Response::error(
-                                    req_id.clone(),
-                                    error_codes::INVALID_PARAMS,
-                                    format!("RPC URL not configured for chain_id '{}'", chain_id),
-                                )
-                            })?;
-                        let address = args
-                            .get("contract_address")
-                            .and_then(|v| v.as_str())
-                            .ok_or_else(|| {
-                                Response::error(
-                                    req_id.clone(),
-                                    error_codes::INVALID_PARAMS,
-                                    "Missing 'contract_address'".into(),
-                                )
-                            })?;
-                        let from_block = args.get("from_block").and_then(|v| v.as_str());
-                        let to_block = args.get("to_block").and_then(|v| v.as_str());
-                        let topic0 = args.get("topic0").and_then(|v| v.as_str());
-
-                        // Helper to normalize block tags: accept hex tags (latest/earliest/pending) or decimal block numbers.
-                        fn normalize_block_tag(tag: &str) -> String {
-                            let t = tag.trim();
-                            if t == "latest"
-                                || t == "earliest"
-                                || t == "pending"
-                                || t.starts_with("0x")
-                            {
-                                return t.to_string();
-                            }
-                            // Try parse as decimal number
-                            if let Ok(n) = u64::from_str_radix(t, 10) {
-                                return format!("0x{:x}", n);
-                            }
-                            t.to_string()
-                        }
 
-                        let mut filter = serde_json::json!({ "address": address });
-                        if let Some(fb) = from_block {
-                            filter["fromBlock"] =
-                                serde_json::Value::String(normalize_block_tag(fb));
-                        }
-                        if let Some(tb) = to_block {
-                            filter["toBlock"] = serde_json::Value::String(normalize_block_tag(tb));
-                        }
-                        if let Some(t0) = topic0 {
-                            filter["topics"] = serde_json::json!([t0]);
-                        }
+        let failed = results.iter().filter(|r| r["ok"] == false).count();
+        let summary = format!(
+            "Ran {} quer{}: {} succeeded, {} failed",
+            results.len(),
+            if results.len() == 1 { "y" } else { "ies" },
+            results.len() - failed,
+            failed
+        );
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, json!({ "results": results, "failed": failed }))))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-                        let payload = serde_json::json!({
-                            "jsonrpc": "2.0",
-                            "method": "eth_getLogs",
-                            "params": [filter],
-                            "id": 1
-                        });
-                        let client = Client::new();
-                        let resp: serde_json::Value = client
-                            .post(rpc_url)
-                            .json(&payload)
-                            .send()
-                            .await
-                            .map_err(|e| {
-                                Response::error(
-                                    req_id.clone(),
-                                    error_codes::INTERNAL_ERROR,
-                                    format!("RPC error: {}", e),
-                                )
-                            })?
-                            .json()
-                            .await
-                            .map_err(|e| {
-                                Response::error(
-                                    req_id.clone(),
-                                    error_codes::INTERNAL_ERROR,
-                                    format!("Invalid RPC JSON: {}", e),
-                                )
-                            })?;
-                        if let Some(err) = resp.get("error") {
-                            return Err(Response::error(
-                                req_id.clone(),
-                                error_codes::INTERNAL_ERROR,
-                                format!("RPC error: {}", err),
-                            ));
-                        }
-                        // Wrap logs with a summary text
-                        let logs = resp["result"].clone();
-                        let count = logs.as_array().map(|a| a.len()).unwrap_or(0);
-                        let payload = json!({ "logs": logs });
-                        let summary = format!("Found {} log(s)", count);
-                        Ok(Response::success(
-                            req_id.clone(),
-                            make_texty_result(summary, payload),
-                        ))
-                    }
-                    ChainType::Native => Err(Response::error(
-                        req_id.clone(),
-                        error_codes::INTERNAL_ERROR,
-                        "Native event search not implemented yet".into(),
-                    )),
+async fn get_balances_multi(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let addresses = utils::get_required_arg::<Vec<String>>(args, "addresses", req_id)?;
+        if addresses.is_empty() || addresses.len() > MAX_MULTI_BALANCE_ADDRESSES {
+            return Err(Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                format!("'addresses' must contain 1 to {} entries", MAX_MULTI_BALANCE_ADDRESSES),
+            ));
+        }
+        if !state.config.chain_rpc_urls.contains_key(&chain_id) {
+            return Err(Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                format!("RPC URL not configured for chain_id '{}'", chain_id),
+            ));
+        }
+
+        // One failed address is reported in its entry; the rest of the batch still returns
+        let results: Vec<(String, Result<BalanceResponse, String>)> = futures::stream::iter(addresses)
+            .map(|address| {
+                let state = &state;
+                let chain_id = &chain_id;
+                async move {
+                    let result = state.sei_client.get_balance(chain_id, &address).await.map_err(|e| e.to_string());
+                    (address, result)
                 }
             })
+            .buffer_unordered(MULTI_BALANCE_CONCURRENCY)
+            .collect()
             .await;
-            res.unwrap_or_else(|err_resp| err_resp)
-        }
-
-        // --- Transfers ---
-        // EVM value transfer using a provided private key
-        "transfer_evm" => {
-            let res: Result<Response, Response> = (async {
-                let private_key = utils::get_required_arg::<String>(args, "private_key", req_id)?;
-                let chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
-                let to_address = utils::get_required_arg::<String>(args, "to_address", req_id)?;
-                let amount_wei = utils::get_required_arg::<String>(args, "amount_wei", req_id)?;
-
-                let to = Address::from_str(&to_address).map_err(|_| {
-                    Response::error(
-                        req_id.clone(),
-                        error_codes::INVALID_PARAMS,
-                        "Invalid 'to_address'".into(),
-                    )
-                })?;
-                let value = U256::from_dec_str(&amount_wei).map_err(|_| {
-                    Response::error(
-                        req_id.clone(),
-                        error_codes::INVALID_PARAMS,
-                        "Invalid 'amount_wei'".into(),
-                    )
-                })?;
 
-                let mut tx_request = TransactionRequest::new().to(to).value(value);
-                if let Some(g) = args.get("gas_limit").and_then(|v| v.as_str()) {
-                    tx_request =
-                        tx_request.gas(U256::from_dec_str(g).unwrap_or_else(|_| U256::from(0)));
-                }
-                if let Some(gp) = args.get("gas_price").and_then(|v| v.as_str()) {
-                    tx_request = tx_request
-                        .gas_price(U256::from_dec_str(gp).unwrap_or_else(|_| U256::from(0)));
+        let mut balances = serde_json::Map::new();
+        let mut failed = 0;
+        for (address, result) in results {
+            let entry = match result {
+                Ok(b) => json!({ "amount": b.amount, "denom": b.denom }),
+                Err(e) => {
+                    failed += 1;
+                    json!({ "error": e })
                 }
+            };
+            balances.insert(address, entry);
+        }
+        let network_name = state.config.network_name(&chain_id);
+        let summary = format!(
+            "Fetched {} of {} balance(s) on {}{}",
+            balances.len() - failed,
+            balances.len(),
+            network_name,
+            if failed > 0 { format!("; {} failed", failed) } else { String::new() }
+        );
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, json!({ "chain_id": chain_id, "network_name": network_name, "balances": balances, "failed": failed })),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-                let response = state
+async fn get_balance(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        let mut chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
+        chain_id = normalize_chain_id(&chain_id);
+        let rpc_url = match state.sei_client.get_rpc_url(&chain_id) {
+            Ok(u) => u,
+            Err(_) => {
+                let keys: Vec<String> = state
+                    .config
+                    .chain_rpc_urls
+                    .keys()
+                    .filter(|c| chain_exposed(&state, c))
+                    .cloned()
+                    .collect();
+                return Err(Response::error(
+                    req_id.clone(),
+                    error_codes::INVALID_PARAMS,
+                    format!(
+                        "RPC URL not configured for chain_id '{}'. Available: {}",
+                        chain_id,
+                        keys.join(", ")
+                    ),
+                ));
+            }
+        };
+        let chain_type = ChainType::from_chain_id(&chain_id);
+        let client = state.sei_client.http();
+        let is_native = matches!(chain_type, ChainType::Native);
+        let internal = |e: SeiError| service_error(req_id, e.into());
+        let denom = args.get("denom").and_then(|v| v.as_str()).filter(|d| !d.is_empty());
+        // Native chains report every bank denom unless one is requested explicitly
+        let (balance, balances) = match (is_native, denom) {
+            (true, Some(d)) => (
+                balance::get_native_balance_by_denom(client, rpc_url, &address, d)
+                    .await
+                    .map_err(internal)?,
+                None,
+            ),
+            (true, None) => {
+                let all = balance::get_native_balances(client, rpc_url, &address)
+                    .await
+                    .map_err(internal)?;
+                let primary = all
+                    .iter()
+                    .find(|b| b.denom == "usei")
+                    .or(all.first())
+                    .cloned()
+                    .unwrap_or(BalanceResponse { amount: "0".to_string(), denom: "usei".to_string() });
+                (primary, Some(all))
+            }
+            (false, _) => (
+                state
                     .sei_client
-                    .send_transaction(&chain_id, &private_key, tx_request, &state.nonce_manager)
+                    .get_balance(&chain_id, &address)
                     .await
-                    .map_err(|e| {
-                        Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
-                    })?;
-                let summary = match serde_json::to_string(&response) {
-                    Ok(s) => format!("EVM tx sent: {}", s),
-                    Err(_) => "EVM tx sent".to_string(),
-                };
-                Ok(Response::success(
-                    req_id.clone(),
-                    make_texty_result(summary, json!(response)),
-                ))
-            })
-            .await;
-            res.unwrap_or_else(|err_resp| err_resp)
+                    .map_err(|e| service_error(req_id, e))?,
+                None,
+            ),
+        };
+        let debug_info = json!({
+            "chain_id_normalized": chain_id,
+            "rpc_url": rpc_url,
+            "chain_type": if is_native { "native" } else { "evm" }
+        });
+        let network_name = state.config.network_name(&chain_id);
+        let balance_text = match serde_json::to_string(&balance) {
+            Ok(s) => format!("Balance on {}: {}", network_name, s),
+            Err(_) => "Balance fetched".to_string(),
+        };
+        // Return plain JSON so MCP clients can parse result directly
+        Ok(Response::success(
+            req_id.clone(),
+            json!({
+                // Plain fields for Windsurf and generic JSON-RPC clients
+                "balance": balance,
+                "balances": balances,
+                "network_name": network_name,
+                "explorer_url": state.config.explorer_address_url(&chain_id, &address),
+                "debug": debug_info,
+                "message": balance_text,
+                // Text content for clients that expect a content array
+                "content": [
+                    { "type": "text", "text": balance_text }
+                ]
+            }),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_balance_at_block(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: &str| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg.to_string());
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        Address::from_str(&address).map_err(|_| invalid("Invalid 'address' (expected a 0x EVM address)"))?;
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        if ChainType::from_chain_id(&chain_id) == ChainType::Native {
+            return Err(invalid("get_balance_at_block queries eth_getBalance; use an EVM chain_id"));
         }
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let rpc_url = state
+            .sei_client
+            .get_rpc_url(&chain_id)
+            .map_err(|e| invalid(&e.to_string()))?;
+        // A 32-byte hex value is a block hash (EIP-1898); anything else is a tag or number
+        let block = match args.get("block").and_then(Value::as_str).map(str::trim) {
+            Some(h) if h.len() == 66 && h.starts_with("0x") && h[2..].chars().all(|c| c.is_ascii_hexdigit()) => {
+                json!({ "blockHash": h })
+            }
+            _ => Value::String(parse_block_tag(args.get("block")).ok_or_else(|| {
+                invalid("'block' must be a tag (latest, earliest, ...), a block number or a block hash")
+            })?),
+        };
+        let balance = balance::get_evm_balance_at(state.sei_client.transport(), rpc_url, &address, block.clone())
+            .await
+            .map_err(|e| service_error(req_id, e.into()))?;
+        let wei = U256::from_dec_str(&balance.amount).unwrap_or_default();
+        let formatted = erc20::format_units(wei, 18);
+        let network_name = state.config.network_name(&chain_id);
+        let block_label = block.get("blockHash").unwrap_or(&block).as_str().unwrap_or_default().to_string();
+        let summary = format!("Balance of {} on {} at block {}: {} SEI", address, network_name, block_label, formatted);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "address": address,
+                    "chain_id": chain_id,
+                    "network_name": network_name,
+                    "block": block,
+                    "balance": balance,
+                    "balance_formatted": formatted,
+                    "decimals": 18,
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-        // Native SEI bank transfer using a provided Cosmos private key (0x-hex secp256k1)
-        "transfer_sei" => {
-            let res: Result<Response, Response> = (async {
-                let private_key = utils::get_required_arg::<String>(args, "private_key", req_id)?;
-                let chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
-                let to_address = utils::get_required_arg::<String>(args, "to_address", req_id)?;
-                let amount_usei = utils::get_required_arg::<String>(args, "amount_usei", req_id)?;
+async fn wait_for_balance_change(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let invalid = |field: &str| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, format!("Invalid '{}'", field))
+        };
+        let min_delta = match args.get("min_delta") {
+            None | Some(Value::Null) => U256::one(),
+            Some(Value::Number(n)) => U256::from_dec_str(&n.to_string()).map_err(|_| invalid("min_delta"))?,
+            Some(Value::String(s)) => U256::from_dec_str(s.trim()).map_err(|_| invalid("min_delta"))?,
+            Some(_) => return Err(invalid("min_delta")),
+        }
+        .max(U256::one());
+        // Bounded so a single call can't hold the request open indefinitely
+        let timeout_secs = args
+            .get("timeout_secs")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(60)
+            .clamp(1, 600);
+        let poll = poll_settings(args, &state, std::time::Duration::from_secs(timeout_secs));
+        let fetch = || fetch_balance(&state, &chain_id, &address, req_id);
 
-                let amount = amount_usei.parse::<u64>().map_err(|_| {
-                    Response::error(
-                        req_id.clone(),
-                        error_codes::INVALID_PARAMS,
-                        "Invalid 'amount_usei'".into(),
-                    )
-                })?;
-                let rpc_url = state.config.chain_rpc_urls.get(&chain_id).ok_or_else(|| {
-                    Response::error(
+        let (initial, denom) = fetch().await?;
+        let network_name = state.config.network_name(&chain_id);
+        let changed = utils::poll_until(poll, || async {
+            let (current, _) = fetch().await?;
+            let delta = if current >= initial { current - initial } else { initial - current };
+            Ok((delta >= min_delta).then_some((current, delta)))
+        })
+        .await?;
+        if let Some((current, delta)) = changed {
+            let direction = if current > initial { "increased" } else { "decreased" };
+            let summary = format!(
+                "Balance of {} on {} {} from {} to {} {}",
+                address, network_name, direction, initial, current, denom
+            );
+            return Ok(Response::success(
+                req_id.clone(),
+                make_texty_result(
+                    summary,
+                    json!({
+                        "changed": true,
+                        "initial_balance": initial.to_string(),
+                        "current_balance": current.to_string(),
+                        "delta": delta.to_string(),
+                        "direction": direction,
+                        "denom": denom,
+                        "network_name": network_name,
+                    }),
+                ),
+            ));
+        }
+        let summary = format!(
+            "No balance change of at least {} {} for {} within {}s",
+            min_delta, denom, address, timeout_secs
+        );
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "changed": false,
+                    "initial_balance": initial.to_string(),
+                    "denom": denom,
+                    "network_name": network_name,
+                    "timeout_secs": timeout_secs,
+                    "max_poll_attempts": poll.max_attempts,
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn create_wallet(args: &Value, req_id: &Value, _state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let word_count = match args.get("word_count") {
+            None | Some(Value::Null) => wallet::DEFAULT_MNEMONIC_WORDS,
+            Some(v) => match v.as_u64() {
+                Some(n @ (12 | 24)) => n as usize,
+                _ => {
+                    return Err(Response::error(
                         req_id.clone(),
                         error_codes::INVALID_PARAMS,
-                        format!("RPC URL not configured for chain_id '{}'", chain_id),
-                    )
-                })?;
+                        "'word_count' must be 12 or 24".into(),
+                    ))
+                }
+            },
+        };
+        let passphrase = args.get("passphrase").and_then(|v| v.as_str()).unwrap_or("");
+        let wallet = wallet::create_wallet_with_options(word_count, passphrase)
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        let mut summary = format!("Created wallet {}", wallet.address);
+        let mut payload = json!(wallet);
+        if !passphrase.is_empty() {
+            let note = "This wallet uses a BIP-39 passphrase. The mnemonic alone restores a different address: \
+                        keep the passphrase, it is required for every future import and cannot be recovered.";
+            summary = format!("{}. {}", summary, note);
+            payload["note"] = json!(note);
+        }
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, payload)))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-                let tx_hash = transactions::send_native_transaction_signed(
-                    &state.config,
-                    rpc_url,
-                    &private_key,
-                    &to_address,
-                    amount,
-                )
-                .await
-                .map_err(|e| {
-                    Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
-                })?;
-                let payload = json!({ "transaction_hash": tx_hash });
-                let summary = format!("SEI bank tx: {}", tx_hash);
-                Ok(Response::success(
-                    req_id.clone(),
-                    make_texty_result(summary, payload),
-                ))
-            })
-            .await;
-            res.unwrap_or_else(|err_resp| err_resp)
+async fn generate_vanity_address(args: &Value, req_id: &Value, _state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let raw_prefix = utils::get_required_arg::<String>(args, "prefix", req_id)?;
+        let prefix = raw_prefix.trim().trim_start_matches("0x").to_string();
+        if prefix.is_empty()
+            || prefix.len() > MAX_VANITY_PREFIX_LEN
+            || !prefix.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return Err(Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                format!("'prefix' must be 1 to {} hex characters", MAX_VANITY_PREFIX_LEN),
+            ));
         }
+        let case_sensitive = args.get("case_sensitive").and_then(|v| v.as_bool()).unwrap_or(false);
+        let max_attempts = args
+            .get("max_attempts")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(MAX_VANITY_ATTEMPTS)
+            .clamp(1, MAX_VANITY_ATTEMPTS);
+        let max_seconds = args.get("max_seconds").and_then(|v| v.as_u64()).unwrap_or(30).clamp(1, MAX_VANITY_SECONDS);
+        // Each hex character is 1 in 16; checksum case halves the odds again for every letter
+        let letters = prefix.chars().filter(|c| c.is_ascii_alphabetic()).count() as i32;
+        let expected_attempts =
+            16f64.powi(prefix.len() as i32) * if case_sensitive { 2f64.powi(letters) } else { 1.0 };
 
-        // EVM ERC-721 transfer
-        "transfer_nft_evm" => {
-            let res: Result<Response, Response> = (async {
-                let private_key = utils::get_required_arg::<String>(args, "private_key", req_id)?;
-                let chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
-                let contract_address =
-                    utils::get_required_arg::<String>(args, "contract_address", req_id)?;
-                let to_address = utils::get_required_arg::<String>(args, "to_address", req_id)?;
-                let token_id = utils::get_required_arg::<String>(args, "token_id", req_id)?;
+        let search_prefix = prefix.clone();
+        let search = tokio::task::spawn_blocking(move || {
+            wallet::generate_vanity_address(
+                &search_prefix,
+                case_sensitive,
+                max_attempts,
+                std::time::Duration::from_secs(max_seconds),
+            )
+        })
+        .await
+        .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
 
-                let wallet = LocalWallet::from_str(&private_key).map_err(|_| {
-                    Response::error(
-                        req_id.clone(),
-                        error_codes::INVALID_PARAMS,
-                        "Invalid 'private_key'".into(),
-                    )
-                })?;
-                let from_addr = wallet.address();
-                let to = Address::from_str(&to_address).map_err(|_| {
-                    Response::error(
-                        req_id.clone(),
-                        error_codes::INVALID_PARAMS,
-                        "Invalid 'to_address'".into(),
-                    )
-                })?;
-                let contract = Address::from_str(&contract_address).map_err(|_| {
-                    Response::error(
-                        req_id.clone(),
-                        error_codes::INVALID_PARAMS,
-                        "Invalid 'contract_address'".into(),
-                    )
-                })?;
-                let token_u256 = U256::from_dec_str(&token_id).map_err(|_| {
-                    Response::error(
-                        req_id.clone(),
-                        error_codes::INVALID_PARAMS,
-                        "Invalid 'token_id'".into(),
-                    )
-                })?;
+        let (summary, payload) = match search.wallet {
+            Some(found) => (
+                format!("Found {} after {} attempt(s)", found.address, search.attempts),
+                json!({
+                    "found": true,
+                    "address": found.address,
+                    "private_key": found.private_key,
+                    "attempts": search.attempts,
+                    "expected_attempts": expected_attempts,
+                }),
+            ),
+            None => (
+                format!(
+                    "No address starting with 0x{} within {} attempt(s) (about {:.0} expected); try a shorter prefix",
+                    prefix, search.attempts, expected_attempts
+                ),
+                json!({
+                    "found": false,
+                    "attempts": search.attempts,
+                    "expected_attempts": expected_attempts,
+                }),
+            ),
+        };
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, payload)))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-                // Encode safeTransferFrom(address,address,uint256)
-                let selector =
-                    &keccak256("safeTransferFrom(address,address,uint256)".as_bytes())[0..4];
-                let data_bytes = {
-                    let mut encoded = selector.to_vec();
-                    let tokens = vec![
-                        Token::Address(from_addr.into()),
-                        Token::Address(to.into()),
-                        Token::Uint(token_u256.into()),
-                    ];
-                    let mut tail = encode(&tokens);
-                    encoded.append(&mut tail);
-                    Bytes::from(encoded)
-                };
+// Generates a key server-side and stores it encrypted; only the address is returned
+async fn create_and_register_wallet(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let wallet_name = utils::get_required_arg::<String>(args, "wallet_name", req_id)?;
+        let master_password =
+            utils::get_required_arg::<String>(args, "master_password", req_id)?;
+        let wallet = state.sei_client.create_wallet().await.map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
+        })?;
+        let address = wallet.address.clone();
+        store_wallet(&state, &wallet_name, &wallet.private_key, address.clone(), &master_password, req_id)
+            .await?;
 
-                let mut tx_request = TransactionRequest::new()
-                    .to(contract)
-                    .data(data_bytes)
-                    .value(U256::zero());
-                if let Some(g) = args.get("gas_limit").and_then(|v| v.as_str()) {
-                    tx_request =
-                        tx_request.gas(U256::from_dec_str(g).unwrap_or_else(|_| U256::from(0)));
-                }
-                if let Some(gp) = args.get("gas_price").and_then(|v| v.as_str()) {
-                    tx_request = tx_request
-                        .gas_price(U256::from_dec_str(gp).unwrap_or_else(|_| U256::from(0)));
-                }
+        let payload = json!({ "status": "success", "wallet_name": wallet_name, "address": address });
+        let summary = format!("Created and registered wallet {} ({})", wallet_name, address);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, payload),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-                let response = state
-                    .sei_client
-                    .send_transaction(&chain_id, &private_key, tx_request, &state.nonce_manager)
-                    .await
-                    .map_err(|e| {
-                        Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
-                    })?;
-                Ok(Response::success(req_id.clone(), json!(response)))
-            })
-            .await;
-            res.unwrap_or_else(|err_resp| err_resp)
-        }
+async fn import_wallet(args: &Value, req_id: &Value, _state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let key = utils::get_required_arg::<String>(args, "key", req_id)?;
+        let passphrase = args.get("passphrase").and_then(|v| v.as_str()).unwrap_or("");
+        let wallet = wallet::import_wallet_with_passphrase(&key, passphrase).map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
+        })?;
+        let summary = format!("Imported wallet {}", wallet.address);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, json!(wallet)),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-        // --- Secure Wallet Storage Tools ---
-        "register_wallet" => {
-            let res: Result<Response, Response> = (async {
-                let wallet_name = utils::get_required_arg::<String>(args, "wallet_name", req_id)?;
-                let private_key = utils::get_required_arg::<String>(args, "private_key", req_id)?;
-                let master_password =
-                    utils::get_required_arg::<String>(args, "master_password", req_id)?;
+async fn sign_typed_data(args: &Value, req_id: &Value, _state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: String| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg);
+        let private_key = utils::get_required_arg::<String>(args, "private_key", req_id)?;
+        let typed_data = args.get("typed_data").ok_or_else(|| invalid("Missing required argument: typed_data".into()))?;
+        let wallet = LocalWallet::from_str(&private_key).map_err(|_| invalid("Invalid 'private_key'".into()))?;
+        let signed = wallet::sign_typed_data(&wallet, typed_data).map_err(|e| invalid(e.to_string()))?;
+        let signer = ethers_core::utils::to_checksum(&wallet.address(), None);
+        let signature = format!("0x{}", signed.signature);
+        let summary = format!("Signed EIP-712 data as {}: {}", signer, signature);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "signature": signature,
+                    "signer": signer,
+                    "digest": format!("0x{}", hex::encode(signed.digest)),
+                    "r": format!("{:#066x}", signed.signature.r),
+                    "s": format!("{:#066x}", signed.signature.s),
+                    "v": signed.signature.v,
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-                let wallet_info: WalletResponse =
-                    wallet::import_wallet(&private_key).map_err(|e| {
-                        Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
-                    })?;
+async fn compute_tx_hash(args: &Value, req_id: &Value, _state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        use ethers_core::types::transaction::eip2718::TypedTransaction;
+        use ethers_core::types::NameOrAddress;
+        let invalid = |msg: String| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg);
+        let raw = utils::get_required_arg::<String>(args, "raw_transaction", req_id)?;
+        let expected_from = match args.get("expected_from").and_then(|v| v.as_str()) {
+            Some(a) => Some(Address::from_str(a).map_err(|_| invalid("Invalid 'expected_from'".into()))?),
+            None => None,
+        };
+        let decoded = transactions::decode_signed_transaction(&raw).map_err(|e| invalid(e.to_string()))?;
+        let tx = &decoded.tx;
 
-                let mut storage = state.wallet_storage.lock().await;
-                if !storage.verify_master_password(&master_password) {
-                    return Err(Response::error(
-                        req_id.clone(),
-                        error_codes::INTERNAL_ERROR,
-                        "Invalid master password".into(),
-                    ));
+        let (kind, fees) = match tx {
+            TypedTransaction::Legacy(t) => ("legacy", json!({ "gas_price": t.gas_price.map(|p| p.to_string()) })),
+            TypedTransaction::Eip2930(t) => ("eip2930", json!({ "gas_price": t.tx.gas_price.map(|p| p.to_string()) })),
+            TypedTransaction::Eip1559(t) => (
+                "eip1559",
+                json!({
+                    "max_fee_per_gas": t.max_fee_per_gas.map(|p| p.to_string()),
+                    "max_priority_fee_per_gas": t.max_priority_fee_per_gas.map(|p| p.to_string()),
+                }),
+            ),
+        };
+        let to = match tx.to() {
+            Some(NameOrAddress::Address(a)) => Some(ethers_core::utils::to_checksum(a, None)),
+            Some(NameOrAddress::Name(n)) => Some(n.clone()),
+            None => None,
+        };
+        let from = ethers_core::utils::to_checksum(&decoded.from, None);
+        let hash = format!("{:?}", decoded.hash);
+        let from_matches = expected_from.map(|a| a == decoded.from);
+
+        let mut summary = format!("Transaction {} is signed by {} ({} envelope, not broadcast)", hash, from, kind);
+        if from_matches == Some(false) {
+            summary.push_str(&format!("\n⚠️ Signer does not match expected_from {}", args["expected_from"].as_str().unwrap_or_default()));
+        }
+        let mut payload = json!({
+            "hash": hash,
+            "from": from,
+            "from_matches": from_matches,
+            "type": kind,
+            "chain_id": tx.chain_id().map(|c| c.as_u64()),
+            "nonce": tx.nonce().map(|n| n.to_string()),
+            "to": to,
+            "value": tx.value().map(|v| v.to_string()).unwrap_or_else(|| "0".into()),
+            "gas": tx.gas().map(|g| g.to_string()),
+            "data": format!("0x{}", hex::encode(tx.data().map(|d| d.to_vec()).unwrap_or_default())),
+        });
+        if let (Some(payload), Some(fees)) = (payload.as_object_mut(), fees.as_object()) {
+            payload.extend(fees.clone());
+        }
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, payload)))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn broadcast_raw_transaction(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let raw = utils::get_required_arg::<String>(args, "raw_transaction", req_id)?;
+        if ChainType::from_chain_id(&chain_id) == ChainType::Native {
+            return Err(Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                "broadcast_raw_transaction applies to EVM chains".into(),
+            ));
+        }
+        // Decoding first refuses garbage locally and names the signer in the result
+        let decoded = transactions::decode_signed_transaction(&raw)
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string()))?;
+        let bytes = hex::decode(raw.trim().trim_start_matches("0x"))
+            .map_err(|_| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, "Invalid 'raw_transaction'".into()))?;
+        let from = ethers_core::utils::to_checksum(&decoded.from, None);
+        let value = decoded.tx.value().copied().unwrap_or_default();
+        enforce_transfer_cap(&state, &chain_id, value, req_id)?;
+        let reserved = if value.is_zero() { None } else { reserve_spend(&state, &chain_id, None, &from, value, req_id)? };
+        let tx_hash = state
+            .sei_client
+            .broadcast_raw_transaction(&chain_id, &bytes)
+            .await
+            .map_err(|e| service_error(req_id, e))?;
+        commit_spend(reserved);
+        let summary = format!("Broadcast {} from {} on {}; accepted into the mempool", tx_hash, from, state.config.network_name(&chain_id));
+        let payload = json!({
+            "tx_hash": tx_hash,
+            "from": from,
+            "nonce": decoded.tx.nonce().map(|n| n.to_string()),
+            "chain_id": chain_id,
+            "accepted": true,
+            "explorer_url": state.config.explorer_tx_url(&chain_id, &tx_hash),
+        });
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, payload)))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn sign_permit(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: String| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg);
+        let private_key = utils::get_required_arg::<String>(args, "private_key", req_id)?;
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let token = utils::get_required_arg::<String>(args, "contract_address", req_id)?;
+        let spender = utils::get_required_arg::<String>(args, "spender", req_id)?;
+        let value = utils::get_required_arg::<String>(args, "value", req_id)?;
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        if ChainType::from_chain_id(&chain_id) != ChainType::Evm {
+            return Err(invalid("sign_permit requires an EVM chain_id".into()));
+        }
+        let wallet = LocalWallet::from_str(&private_key).map_err(|_| invalid("Invalid 'private_key'".into()))?;
+        let token_addr = Address::from_str(&token).map_err(|_| invalid("Invalid 'contract_address'".into()))?;
+        let spender = Address::from_str(&spender).map_err(|_| invalid("Invalid 'spender'".into()))?;
+        let value = U256::from_dec_str(&value).map_err(|_| invalid("'value' must be a decimal integer in base units".into()))?;
+        let deadline = match args.get("deadline").and_then(|v| v.as_u64()) {
+            Some(d) => d,
+            None => chrono::Utc::now().timestamp() as u64 + DEFAULT_PERMIT_TTL_SECS,
+        };
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| invalid(e.to_string()))?;
+
+        let client = state.sei_client.transport();
+        let on_chain_separator = erc20::domain_separator(client, rpc_url, &token)
+            .await
+            .ok_or_else(|| invalid(format!("Token {} does not support permit (no DOMAIN_SEPARATOR)", token)))?;
+        let (name, evm_chain_id, nonce) = tokio::try_join!(
+            erc20::name(client, rpc_url, &token),
+            rpc::chain_id(client, rpc_url),
+            erc20::nonces(client, rpc_url, &token, wallet.address()),
+        )
+        .map_err(|e| service_error(req_id, e))?;
+        let version = match args.get("version").and_then(|v| v.as_str()) {
+            Some(v) => v.to_string(),
+            None => erc20::version(client, rpc_url, &token).await.unwrap_or_else(|| "1".to_string()),
+        };
+        // A mismatch means the token's domain isn't (name, version, chainId, address) and the permit would be rejected
+        if erc20::permit_domain_separator(&name, &version, evm_chain_id, token_addr) != on_chain_separator {
+            return Err(invalid(format!(
+                "Token {} DOMAIN_SEPARATOR does not match name '{}' and version '{}'; pass the token's EIP-712 'version'",
+                token, name, version
+            )));
+        }
+
+        let permit = erc20::Permit { owner: wallet.address(), spender, value, nonce, deadline: deadline.into() };
+        let typed_data = erc20::permit_typed_data(&name, &version, evm_chain_id, token_addr, &permit);
+        let signed = wallet::sign_typed_data(&wallet, &typed_data).map_err(|e| service_error(req_id, e))?;
+        let owner = ethers_core::utils::to_checksum(&wallet.address(), None);
+        let summary = format!(
+            "Signed permit for {} to spend {} of {} from {} until {}",
+            ethers_core::utils::to_checksum(&spender, None),
+            value,
+            name,
+            owner,
+            deadline
+        );
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "owner": owner,
+                    "spender": ethers_core::utils::to_checksum(&spender, None),
+                    "value": value.to_string(),
+                    "nonce": nonce.to_string(),
+                    "deadline": deadline,
+                    "v": signed.signature.v,
+                    "r": format!("{:#066x}", signed.signature.r),
+                    "s": format!("{:#066x}", signed.signature.s),
+                    "signature": format!("0x{}", signed.signature),
+                    "typed_data": typed_data,
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_faucet_status(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let mut chains: Vec<String> = match args.get("chain_id").and_then(|v| v.as_str()) {
+            Some(c) => vec![normalize_chain_id(c)],
+            None => state
+                .config
+                .chain_rpc_urls
+                .keys()
+                .filter(|c| chain_exposed(&state, c))
+                .cloned()
+                .collect(),
+        };
+        chains.sort();
+
+        // One chain's RPC failure is reported in its entry rather than failing the whole status
+        let mut statuses = Vec::new();
+        let mut low_chains = Vec::new();
+        for chain_id in &chains {
+            let Some(address) = faucet::faucet_address(&state.config, chain_id) else {
+                statuses.push(json!({
+                    "chain_id": chain_id,
+                    "address": null,
+                    "error": "No faucet account configured for this chain"
+                }));
+                continue;
+            };
+            match fetch_balance(&state, chain_id, &address, req_id).await {
+                Ok((balance, denom)) => {
+                    let low = faucet::is_low_balance(&state.config, chain_id, balance);
+                    if low == Some(true) {
+                        warn!("Faucet {} on {} is low: {} {}", address, chain_id, balance, denom);
+                        low_chains.push(chain_id.clone());
+                    }
+                    statuses.push(json!({
+                        "chain_id": chain_id,
+                        "address": address,
+                        "balance": balance.to_string(),
+                        "denom": denom,
+                        "low_balance_threshold": state.config.faucet_low_balance.get(chain_id),
+                        "low": low,
+                    }));
                 }
+                Err(err_resp) => {
+                    let message = err_resp.error.map(|e| e.message).unwrap_or_default();
+                    statuses.push(json!({ "chain_id": chain_id, "address": address, "error": message }));
+                }
+            }
+        }
+        let summary = if low_chains.is_empty() {
+            format!("Faucet status for {} chain(s); none low", chains.len())
+        } else {
+            format!("Faucet balance is low on: {}", low_chains.join(", "))
+        };
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, json!({ "faucets": statuses }))))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-                storage
-                    .add_wallet(
-                        wallet_name.clone(),
-                        &private_key,
-                        wallet_info.address,
-                        &master_password,
-                    )
-                    .map_err(|e| {
-                        Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
-                    })?;
+async fn faucet_daily_remaining(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let mut chains: Vec<String> = match args.get("chain_id").and_then(|v| v.as_str()) {
+            Some(c) => vec![normalize_chain_id(c)],
+            None => state.config.faucet_daily_caps.keys().filter(|c| chain_exposed(&state, c)).cloned().collect(),
+        };
+        chains.sort();
+        let now = chrono::Utc::now();
+        let entries: Vec<Value> = chains
+            .iter()
+            .map(|chain_id| match state.config.faucet_daily_caps.get(chain_id) {
+                Some(cap) => {
+                    let mut usage = json!(state.faucet_ledger.usage(chain_id, *cap, now)?);
+                    usage["chain_id"] = json!(chain_id);
+                    Ok(usage)
+                }
+                None => Ok(json!({ "chain_id": chain_id, "cap": null, "remaining": null })),
+            })
+            .collect::<anyhow::Result<_>>()
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        let summary = match entries.as_slice() {
+            [] => "No FAUCET_DAILY_CAPS configured; the faucet has no daily limit".to_string(),
+            [one] if one["cap"].is_null() => format!("No daily faucet cap on {}", one["chain_id"].as_str().unwrap_or_default()),
+            _ => entries
+                .iter()
+                .filter(|e| !e["cap"].is_null())
+                .map(|e| format!("{}: {} of {} left today", e["chain_id"].as_str().unwrap_or_default(), e["remaining"], e["cap"]))
+                .collect::<Vec<_>>()
+                .join("; "),
+        };
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, json!({ "chains": entries }))))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-                wallet_storage::save_wallet_storage(&state.wallet_storage_path, &storage).map_err(
-                    |e| {
-                        error!("Failed to save wallet storage: {}", e);
+async fn get_spending_status(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: &str| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg.to_string());
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let wallet_name = args.get("wallet_name").and_then(Value::as_str).map(str::trim).filter(|n| !n.is_empty());
+        let address = match (args.get("address").and_then(Value::as_str), wallet_name) {
+            (Some(address), None) => address.trim().to_string(),
+            (None, Some(name)) => lock_wallet_storage(&state, req_id)
+                .await?
+                .wallets
+                .get(name)
+                .map(|w| w.public_address.clone())
+                .ok_or_else(|| Response::error(req_id.clone(), error_codes::NOT_FOUND, format!("Wallet '{}' not found", name)))?,
+            _ => return Err(invalid("Pass exactly one of 'address' or 'wallet_name'")),
+        };
+        let keys: Vec<&str> = wallet_name.into_iter().chain([address.as_str()]).collect();
+        let Some(budget) = state.config.spending_budget(&chain_id, &keys) else {
+            let summary = format!("No spending budget covers {} on {}; transfers are unlimited", address, chain_id);
+            return Ok(Response::success(
+                req_id.clone(),
+                make_texty_result(summary, json!({ "chain_id": chain_id, "address": address, "budget_wei": null, "remaining_wei": null })),
+            ));
+        };
+        let status = state
+            .spending_ledger
+            .status(&chain_id, &address, budget, chrono::Utc::now())
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        let resets_at = status.resets_at.map(|t| t.to_rfc3339());
+        let summary = format!(
+            "{} on {}: {} of {} wei left per {}s window{}",
+            address,
+            chain_id,
+            status.remaining,
+            status.budget,
+            status.window_secs,
+            resets_at.as_ref().map(|t| format!("; resets at {}", t)).unwrap_or_default()
+        );
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "chain_id": chain_id,
+                    "address": address,
+                    "budget_wei": status.budget.to_string(),
+                    "spent_wei": status.spent.to_string(),
+                    "remaining_wei": status.remaining.to_string(),
+                    "window_secs": status.window_secs,
+                    "resets_at": resets_at
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn request_faucet(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        let mut chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
+        chain_id = normalize_chain_id(&chain_id);
+        let rpc_url = match state.sei_client.get_rpc_url(&chain_id) {
+            Ok(u) => u,
+            Err(_) => {
+                let keys: Vec<String> = state
+                    .config
+                    .chain_rpc_urls
+                    .keys()
+                    .filter(|c| chain_exposed(&state, c))
+                    .cloned()
+                    .collect();
+                return Err(Response::error(
+                    req_id.clone(),
+                    error_codes::INVALID_PARAMS,
+                    format!(
+                        "RPC URL not configured for chain_id '{}'. Available: {}",
+                        chain_id,
+                        keys.join(", ")
+                    ),
+                ));
+            }
+        };
+        let amount = match args.get("amount") {
+            None | Some(Value::Null) => None,
+            Some(v) => Some(
+                v.as_u64()
+                    .or_else(|| v.as_str().and_then(|s| s.parse::<u64>().ok()))
+                    .filter(|a| *a > 0)
+                    .ok_or_else(|| {
                         Response::error(
                             req_id.clone(),
-                            error_codes::INTERNAL_ERROR,
-                            "Failed to save wallet to disk".into(),
+                            error_codes::INVALID_PARAMS,
+                            "Invalid 'amount': expected a whole number of usei above 0".into(),
                         )
-                    },
-                )?;
+                    })?,
+            ),
+        };
+        let verify = args.get("verify").and_then(|v| v.as_bool()).unwrap_or(false);
+        let verify_timeout = std::time::Duration::from_secs(
+            args.get("verify_timeout_secs").and_then(|v| v.as_u64()).unwrap_or(60).clamp(1, 600),
+        );
+        let before = if verify {
+            Some(fetch_balance(&state, &chain_id, &address, req_id).await?)
+        } else {
+            None
+        };
+        let resp = crate::blockchain::services::faucet::send_faucet_tokens(
+            state.sei_client.transport(),
+            &state.config,
+            &state.faucet_ledger,
+            &address,
+            &state.nonce_manager,
+            rpc_url,
+            &chain_id,
+            amount,
+        )
+        .await
+        .map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
+        })?;
+        let tx_hashes = resp.tx_hashes();
+        let audited_amount = resp.amount.clone().unwrap_or_else(|| "default".to_string());
+        for hash in &tx_hashes {
+            audit(&state, audit::AuditEntry::new("request_faucet", &chain_id, None, &address, &audited_amount, hash));
+        }
+        let mut payload = json!({
+            "transaction_hash": resp.tx_hash,
+            "token_transaction_hash": resp.token_tx_hash,
+            "transaction_hashes": tx_hashes,
+            "amount": resp.amount,
+            "partial": resp.token_error.is_some(),
+            "token_error": resp.token_error,
+        });
+        let mut summary = format!("Faucet sent tokens: tx {}", tx_hashes.join(", "));
+        if let Some(e) = &resp.token_error {
+            summary.push_str(&format!("; the test token transfer failed: {}", e));
+        }
 
-                let payload = json!({ "status": "success", "wallet_name": wallet_name });
-                let summary = format!("Registered wallet {}", wallet_name);
-                Ok(Response::success(
+        if let Some((initial, denom)) = before {
+            let poll = poll_settings(args, &state, verify_timeout);
+            let failed = |reason: String| {
+                Response::error(
                     req_id.clone(),
-                    make_texty_result(summary, payload),
-                ))
+                    error_codes::RPC_ERROR,
+                    format!("Faucet transaction {} not confirmed: {}", resp.tx_hash, reason),
+                )
+            };
+            // Only EVM faucet hashes have receipts; native drips are verified by balance alone
+            if ChainType::from_chain_id(&chain_id) == ChainType::Evm {
+                let client = state.sei_client.transport();
+                match rpc::wait_for_receipt(client, rpc_url, &resp.tx_hash, poll)
+                    .await
+                    .map_err(|e| failed(e.to_string()))?
+                {
+                    Some(receipt) if receipt["status"] == "0x0" => {
+                        let revert = revert_details(state.sei_client.transport(), rpc_url, &resp.tx_hash, None).await;
+                        return Err(match revert["reason"].as_str() {
+                            Some(reason) => failed(format!("transaction reverted: {}", reason)),
+                            None => failed("transaction reverted".into()),
+                        });
+                    }
+                    Some(_) => {}
+                    None => {
+                        return Err(failed(format!(
+                            "no receipt within {}s; it may still be pending",
+                            verify_timeout.as_secs()
+                        )))
+                    }
+                }
+            }
+            let credited = utils::poll_until(poll, || async {
+                let current = fetch_balance(&state, &chain_id, &address, req_id).await?.0;
+                Ok((current > initial).then_some(current))
             })
-            .await;
-            res.unwrap_or_else(|err_resp| err_resp)
+            .await?;
+            let Some(current) = credited else {
+                return Err(failed(format!(
+                    "balance of {} stayed at {} {} for {}s",
+                    address,
+                    initial,
+                    denom,
+                    verify_timeout.as_secs()
+                )));
+            };
+            let delta = current - initial;
+            payload["verification"] = json!({
+                "verified": true,
+                "balance_before": initial.to_string(),
+                "balance_after": current.to_string(),
+                "delta": delta.to_string(),
+                "denom": denom,
+            });
+            summary = format!("{}; verified +{} {}", summary, delta, denom);
         }
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, payload),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-        "list_wallets" => {
-            let res: Result<Response, Response> = (async {
-                let master_password =
-                    utils::get_required_arg::<String>(args, "master_password", req_id)?;
-                let storage = state.wallet_storage.lock().await;
-                if !storage.verify_master_password(&master_password) {
-                    return Err(Response::error(
-                        req_id.clone(),
-                        error_codes::INTERNAL_ERROR,
-                        "Invalid master password".into(),
-                    ));
-                }
-                let wallets = storage.list_wallets();
-                let count = wallets.len();
-                let payload = json!({ "wallets": wallets });
-                let summary = format!("{} wallet(s)", count);
-                Ok(Response::success(
-                    req_id.clone(),
-                    make_texty_result(summary, payload),
-                ))
-            })
-            .await;
-            res.unwrap_or_else(|err_resp| err_resp)
+async fn get_events_since(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let contract_address = utils::get_required_arg::<String>(args, "contract_address", req_id)?;
+        let last_seen_block = utils::get_required_arg::<u64>(args, "last_seen_block", req_id)?;
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
+        })?;
+        let transport = state.sei_client.transport();
+        let latest = rpc::rpc_call(transport, rpc_url, "eth_blockNumber", json!([]))
+            .await
+            .ok()
+            .and_then(|v| v.as_str().and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok()))
+            .ok_or_else(|| Response::error(req_id.clone(), error_codes::RPC_ERROR, "Could not read the latest block".into()))?;
+
+        let block_of = |log: &Value| {
+            log["blockNumber"].as_str().and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok())
+        };
+        // The cursor never moves backwards, even if this node lags the one used last time
+        let mut next_cursor = latest.max(last_seen_block);
+        let mut logs = Vec::new();
+        if latest > last_seen_block {
+            let mut filter = json!({
+                "address": contract_address,
+                "fromBlock": format!("0x{:x}", last_seen_block + 1),
+                "toBlock": format!("0x{:x}", latest),
+            });
+            if let Some(t0) = args.get("topic0").and_then(|v| v.as_str()) {
+                filter["topics"] = json!([t0]);
+            }
+            logs = rpc::get_logs_chunked(transport, rpc_url, filter, state.config.log_chunk_size(&chain_id))
+                .await
+                .map_err(|e| Response::error(req_id.clone(), error_codes::RPC_ERROR, e.to_string()))?;
+        }
+        // Over the item cap, stop at a block boundary so the next call resumes without gaps or repeats
+        let cap = max_items_arg(args, &state);
+        let has_more = logs.len() > cap;
+        if has_more {
+            if let Some(cut) = block_of(&logs[cap]) {
+                let before_cut = logs.iter().take_while(|l| block_of(l).is_some_and(|b| b < cut)).count();
+                if before_cut > 0 {
+                    logs.truncate(before_cut);
+                    next_cursor = cut - 1;
+                } else {
+                    // A single block holds more than the cap; return all of it
+                    logs.retain(|l| block_of(l) == Some(cut));
+                    next_cursor = cut;
+                }
+            }
         }
+        let summary = if logs.is_empty() {
+            format!("No new events from {} after block {}; next cursor {}", contract_address, last_seen_block, next_cursor)
+        } else {
+            format!(
+                "{} new event(s) from {} in blocks {}..={}{}; next cursor {}",
+                logs.len(),
+                contract_address,
+                last_seen_block + 1,
+                next_cursor,
+                if has_more { " (more pending, call again)" } else { "" },
+                next_cursor
+            )
+        };
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "events": logs,
+                    "count": logs.len(),
+                    "from_block": last_seen_block + 1,
+                    "latest_block": latest,
+                    "next_cursor": next_cursor,
+                    "has_more": has_more,
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-        "transfer_from_wallet" => {
-            let res: Result<Response, Response> = (async {
-                let wallet_name = utils::get_required_arg::<String>(args, "wallet_name", req_id)?;
-                let chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
-                let to_address = utils::get_required_arg::<String>(args, "to_address", req_id)?;
-                let amount = utils::get_required_arg::<String>(args, "amount", req_id)?;
-                let master_password =
-                    utils::get_required_arg::<String>(args, "master_password", req_id)?;
+async fn get_block_by_timestamp(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let timestamp = utils::get_required_arg::<u64>(args, "timestamp", req_id)?;
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
+        })?;
+        let transport = state.sei_client.transport();
+        let (number, block_ts) = blocks::find_block_by_timestamp(transport, rpc_url, timestamp)
+            .await
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        let summary = format!(
+            "Block {} (timestamp {}) is closest to {} on {}",
+            number, block_ts, timestamp, chain_id
+        );
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "block_number": number,
+                    "block_number_hex": format!("0x{:x}", number),
+                    "block_timestamp": block_ts,
+                    "requested_timestamp": timestamp,
+                    "offset_secs": block_ts as i64 - timestamp as i64,
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-                let private_key = {
-                    // Scoped lock
-                    let storage = state.wallet_storage.lock().await;
-                    storage
-                        .get_decrypted_private_key(&wallet_name, &master_password)
-                        .map_err(|e| {
-                            Response::error(
-                                req_id.clone(),
-                                error_codes::INTERNAL_ERROR,
-                                e.to_string(),
-                            )
-                        })?
-                };
+async fn get_recent_blocks(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        if ChainType::from_chain_id(&chain_id) != ChainType::Evm {
+            return Err(Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                "get_recent_blocks requires an EVM chain_id".into(),
+            ));
+        }
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
+        })?;
+        let requested = args.get("count").and_then(|v| v.as_u64()).unwrap_or(DEFAULT_RECENT_BLOCKS).max(1);
+        let count = requested.min(state.config.max_recent_blocks.max(1));
+        let recent = blocks::get_recent_blocks(state.sei_client.transport(), rpc_url, count, RECENT_BLOCKS_CONCURRENCY)
+            .await
+            .map_err(|e| service_error(req_id, e))?;
+        let stats = blocks::block_stats(&recent);
+        let summary = format!(
+            "Last {} blocks on {}: {} txs, {:.1} per block, {:.0}% average gas used",
+            recent.len(),
+            state.config.network_name(&chain_id),
+            stats["total_txs"],
+            stats["avg_tx_count"].as_f64().unwrap_or_default(),
+            stats["avg_gas_used_ratio"].as_f64().unwrap_or_default() * 100.0
+        );
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "blocks": recent,
+                    "stats": stats,
+                    "requested": requested,
+                    "capped": requested > count,
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-                let to = Address::from_str(&to_address).map_err(|_| {
-                    Response::error(
-                        req_id.clone(),
-                        error_codes::INVALID_PARAMS,
-                        "Invalid 'to_address'".into(),
-                    )
-                })?;
-                let value = U256::from_dec_str(&amount).map_err(|_| {
-                    Response::error(
-                        req_id.clone(),
-                        error_codes::INVALID_PARAMS,
-                        "Invalid 'amount'".into(),
-                    )
-                })?;
+async fn get_pending_transactions(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        if ChainType::from_chain_id(&chain_id) != ChainType::Evm {
+            return Err(Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                "get_pending_transactions requires an EVM chain_id".into(),
+            ));
+        }
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
+        })?;
+        let transport = state.sei_client.transport();
+        let report = mempool::get_pending_transactions(transport, rpc_url, &address)
+            .await
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        let summary = match report.source {
+            "txpool" => format!("{} pending/queued transaction(s) for {}", report.count, address),
+            _ => format!(
+                "{} pending transaction(s) for {} (txpool unavailable; nonce {} mined, {} pending)",
+                report.count, address, report.latest_nonce, report.pending_nonce
+            ),
+        };
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, json!(report))))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-                let tx_request = TransactionRequest::new().to(to).value(value);
+async fn get_base_fee(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        if ChainType::from_chain_id(&chain_id) != ChainType::Evm {
+            return Err(Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                "get_base_fee requires an EVM chain_id".into(),
+            ));
+        }
+        let (fee, cached) = state.sei_client.latest_base_fee(&chain_id).await.map_err(|e| service_error(req_id, e))?;
+        let Some(base_fee) = fee.base_fee_per_gas else {
+            let summary = format!(
+                "Base fee unavailable on {}: block {} has no baseFeePerGas (pre-EIP-1559 chain); use gas_price",
+                chain_id, fee.block_number
+            );
+            return Ok(Response::success(
+                req_id.clone(),
+                make_texty_result(
+                    summary,
+                    json!({
+                        "chain_id": chain_id,
+                        "block_number": fee.block_number,
+                        "eip1559": false,
+                        "base_fee_wei": Value::Null,
+                        "base_fee_gwei": Value::Null,
+                        "cached": cached
+                    }),
+                ),
+            ));
+        };
+        let gwei = erc20::format_units(base_fee, 9);
+        let summary = format!("Base fee on {} at block {}: {} gwei", chain_id, fee.block_number, gwei);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "chain_id": chain_id,
+                    "block_number": fee.block_number,
+                    "eip1559": true,
+                    "base_fee_wei": base_fee.to_string(),
+                    "base_fee_gwei": gwei,
+                    "cached": cached
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
 
-                let response = state
-                    .sei_client
-                    .send_transaction(&chain_id, &private_key, tx_request, &state.nonce_manager)
-                    .await
-                    .map_err(|e| {
-                        Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
+async fn estimate_inclusion_time(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let raw_price = utils::get_required_arg::<String>(args, "gas_price", req_id)?;
+        let gas_price = U256::from_dec_str(raw_price.trim()).map_err(|_| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, "Invalid 'gas_price'".into())
+        })?;
+        if ChainType::from_chain_id(&chain_id) != ChainType::Evm {
+            return Err(Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                "estimate_inclusion_time requires an EVM chain_id".into(),
+            ));
+        }
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
+        })?;
+        let transport = state.sei_client.transport();
+        let estimate = fees::estimate_inclusion_time(transport, rpc_url, gas_price)
+            .await
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        let summary = format!(
+            "{} wei: {} — ~{}-{} blocks ({:.1}-{:.1}s), {} confidence",
+            gas_price,
+            estimate.bucket,
+            estimate.blocks_min,
+            estimate.blocks_max,
+            estimate.seconds_min,
+            estimate.seconds_max,
+            estimate.confidence
+        );
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, json!(estimate))))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+// --- Event tools ---
+async fn search_events(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
+        match ChainType::from_chain_id(&chain_id) {
+            ChainType::Evm => {
+                let rpc_url =
+                    state.sei_client.get_rpc_url(&chain_id).map_err(|e| {
+                        Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
                     })?;
-                let summary = match serde_json::to_string(&response) {
-                    Ok(s) => format!("Transfer sent: {}", s),
-                    Err(_) => "Transfer sent".to_string(),
-                };
-                Ok(Response::success(
-                    req_id.clone(),
-                    make_texty_result(summary, json!(response)),
-                ))
-            })
-            .await;
-            res.unwrap_or_else(|err_resp| err_resp)
-        }
-        "get_contract" => {
-            let res: Result<Response, Response> = (async {
-                let address = utils::get_required_arg::<String>(args, "address", req_id)?;
-                // Prefer explicit chain_id, else infer from NL, default to testnet
-                let mut chain = args
-                    .get("chain_id")
+                let address = args
+                    .get("contract_address")
                     .and_then(|v| v.as_str())
-                    .map(normalize_chain_id);
-                if chain.is_none() {
-                    chain = infer_evm_chain_from_args(args);
-                }
-                let chain_id = chain.unwrap_or_else(|| "sei-evm-testnet".to_string());
-                let contract = state
-                    .sei_client
-                    .get_contract(&chain_id, &address)
-                    .await
-                    .map_err(|e| {
-                        Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
+                    .ok_or_else(|| {
+                        Response::error(
+                            req_id.clone(),
+                            error_codes::INVALID_PARAMS,
+                            "Missing 'contract_address'".into(),
+                        )
                     })?;
-                let summary = format!("Contract {} on {}", address, chain_id);
-                let pretty = serde_json::to_string_pretty(&contract).unwrap_or_else(|_| contract.to_string());
-                Ok(Response::success(
-                    req_id.clone(),
-                    json!({
-                        "content": [
-                            { "type": "text", "text": format!("{}\n\n{}", summary, pretty) }
-                        ]
-                    })
-                ))
-            })
-            .await;
-            res.unwrap_or_else(|err_resp| err_resp)
-        }
-        "get_contract_code" => {
-            let res: Result<Response, Response> = (async {
-                let address = utils::get_required_arg::<String>(args, "address", req_id)?;
-                let mut chain = args
-                    .get("chain_id")
-                    .and_then(|v| v.as_str())
-                    .map(normalize_chain_id);
-                if chain.is_none() {
-                    chain = infer_evm_chain_from_args(args);
+                let from_block = args.get("from_block").and_then(|v| v.as_str());
+                let to_block = args.get("to_block").and_then(|v| v.as_str());
+                let topic0 = args.get("topic0").and_then(|v| v.as_str());
+
+                // Helper to normalize block tags: accept hex tags (latest/earliest/pending) or decimal block numbers.
+                fn normalize_block_tag(tag: &str) -> String {
+                    let t = tag.trim();
+                    if t == "latest"
+                        || t == "earliest"
+                        || t == "pending"
+                        || t.starts_with("0x")
+                    {
+                        return t.to_string();
+                    }
+                    // Try parse as decimal number
+                    if let Ok(n) = t.parse::<u64>() {
+                        return format!("0x{:x}", n);
+                    }
+                    t.to_string()
                 }
-                let chain_id = chain.unwrap_or_else(|| "sei-evm-testnet".to_string());
-                let code = state
-                    .sei_client
-                    .get_contract_code(&chain_id, &address)
+
+                let mut filter = serde_json::json!({ "address": address });
+                if let Some(fb) = from_block {
+                    filter["fromBlock"] =
+                        serde_json::Value::String(normalize_block_tag(fb));
+                }
+                if let Some(tb) = to_block {
+                    filter["toBlock"] = serde_json::Value::String(normalize_block_tag(tb));
+                }
+                if let Some(t0) = topic0 {
+                    filter["topics"] = serde_json::json!([t0]);
+                }
+
+                let logs = rpc::get_logs_chunked(state.sei_client.transport(), rpc_url, filter, state.config.log_chunk_size(&chain_id))
                     .await
                     .map_err(|e| {
-                        Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
+                        Response::error(
+                            req_id.clone(),
+                            error_codes::INTERNAL_ERROR,
+                            format!("RPC error: {}", e),
+                        )
                     })?;
-                let summary = format!("Contract code for {} on {}", address, chain_id);
-                let pretty = serde_json::to_string_pretty(&code).unwrap_or_else(|_| code.to_string());
+                // Wrap logs with a summary text
+                let mut logs = Value::Array(logs);
+                let clipped = utils::truncate_array(&mut logs, max_items_arg(args, &state));
+                let count = logs.as_array().map(|a| a.len()).unwrap_or(0);
+                let total = clipped.unwrap_or(count);
+                let payload = json!({
+                    "logs": logs,
+                    "truncated": clipped.is_some(),
+                    "total_available": total,
+                });
+                let summary = match clipped {
+                    Some(_) => format!("Found {} log(s), showing first {}", total, count),
+                    None => format!("Found {} log(s)", count),
+                };
                 Ok(Response::success(
                     req_id.clone(),
-                    json!({
-                        "content": [
-                            { "type": "text", "text": format!("{}\n\n{}", summary, pretty) }
-                        ]
-                    })
+                    make_texty_result(summary, payload),
                 ))
-            })
-            .await;
-            res.unwrap_or_else(|err_resp| err_resp)
+            }
+            ChainType::Native => Err(Response::error(
+                req_id.clone(),
+                error_codes::INTERNAL_ERROR,
+                "Native event search not implemented yet".into(),
+            )),
         }
-        "get_contract_transactions" => {
-            let res: Result<Response, Response> = (async {
-                let address = utils::get_required_arg::<String>(args, "address", req_id)?;
-                let mut chain = args
-                    .get("chain_id")
-                    .and_then(|v| v.as_str())
-                    .map(normalize_chain_id);
-                if chain.is_none() {
-                    chain = infer_evm_chain_from_args(args);
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_decoded_events(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: String| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg);
+        let contract_address = utils::get_required_arg::<String>(args, "contract_address", req_id)?;
+        let mut chain = args
+            .get("chain_id")
+            .and_then(|v| v.as_str())
+            .map(normalize_chain_id);
+        if chain.is_none() {
+            chain = infer_evm_chain_from_args(args);
+        }
+        let chain_id = chain.unwrap_or_else(|| "sei-evm-testnet".to_string());
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| invalid(e.to_string()))?;
+        let from_block = parse_block_tag(args.get("from_block"))
+            .ok_or_else(|| invalid("Invalid 'from_block'".into()))?;
+        let to_block = parse_block_tag(args.get("to_block"))
+            .ok_or_else(|| invalid("Invalid 'to_block'".into()))?;
+
+        // A caller-supplied ABI covers contracts that aren't verified on SeiStream
+        let abi_entries = match args.get("abi") {
+            Some(Value::Array(entries)) => entries.clone(),
+            Some(Value::String(raw)) => serde_json::from_str(raw)
+                .map_err(|e| invalid(format!("Invalid 'abi' JSON: {}", e)))?,
+            _ => state
+                .sei_client
+                .get_contract_abi(&chain_id, &contract_address)
+                .await
+                .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?
+                .ok_or_else(|| {
+                    invalid(format!(
+                        "ABI not available for {} (contract is not verified on SeiStream); pass 'abi' explicitly",
+                        contract_address
+                    ))
+                })?,
+        };
+        let parsed = contract::parse_abi(&abi_entries).map_err(|e| invalid(e.to_string()))?;
+
+        let mut filter = json!({ "address": contract_address, "fromBlock": from_block, "toBlock": to_block });
+        if let Some(name) = args.get("event_name").and_then(|v| v.as_str()) {
+            let event = parsed
+                .events_by_name(name)
+                .ok()
+                .and_then(|events| events.first())
+                .ok_or_else(|| invalid(format!("Event '{}' not found in contract ABI", name)))?;
+            filter["topics"] = json!([format!("{:?}", event.signature())]);
+        }
+        let logs = rpc::get_logs_chunked(state.sei_client.transport(), rpc_url, filter, state.config.log_chunk_size(&chain_id))
+            .await
+            .map_err(|e| Response::error(req_id.clone(), error_codes::RPC_ERROR, e.to_string()))?;
+
+        let mut decoded_count = 0;
+        let events: Vec<Value> = logs
+            .iter()
+            .map(|log| {
+                let position = json!({
+                    "block_number": log.get("blockNumber"),
+                    "tx_hash": log.get("transactionHash"),
+                    "log_index": log.get("logIndex"),
+                });
+                match abi::decode_log(&parsed, log) {
+                    Some(mut event) => {
+                        decoded_count += 1;
+                        event["decoded"] = json!(true);
+                        event["position"] = position;
+                        event
+                    }
+                    None => json!({ "decoded": false, "position": position, "log": log }),
                 }
-                let chain_id = chain.unwrap_or_else(|| "sei-evm-testnet".to_string());
-                let txs = state
-                    .sei_client
-                    .get_contract_transactions(&chain_id, &address)
-                    .await
-                    .map_err(|e| {
-                        Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
-                    })?;
-                let count = txs.get("items").and_then(|v| v.as_array()).map(|a| a.len()).unwrap_or(0);
-                let summary = format!("{} tx(s) for {} on {}", count, address, chain_id);
-                let pretty = serde_json::to_string_pretty(&txs).unwrap_or_else(|_| txs.to_string());
-                Ok(Response::success(
-                    req_id.clone(),
-                    json!({
-                        "content": [
-                            { "type": "text", "text": format!("{}\n\n{}", summary, pretty) }
-                        ]
-                    })
-                ))
             })
-            .await;
-            res.unwrap_or_else(|err_resp| err_resp)
+            .collect();
+        let mut events = Value::Array(events);
+        let clipped = utils::truncate_array(&mut events, max_items_arg(args, &state));
+        let total = logs.len();
+        let summary = format!(
+            "{} log(s) from {} in blocks {}..{}, {} decoded{}",
+            total,
+            contract_address,
+            from_block,
+            to_block,
+            decoded_count,
+            if clipped.is_some() { format!(" (showing first {})", max_items_arg(args, &state)) } else { String::new() }
+        );
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "chain_id": chain_id,
+                    "events": events,
+                    "decoded_count": decoded_count,
+                    "truncated": clipped.is_some(),
+                    "total_available": total,
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+// --- Transfers ---
+// EVM value transfer using a provided private key
+async fn transfer_evm(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let private_key = utils::get_required_arg::<String>(args, "private_key", req_id)?;
+        let chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
+        let to_address = utils::get_required_arg::<String>(args, "to_address", req_id)?;
+        let amount_wei = utils::get_required_arg::<String>(args, "amount_wei", req_id)?;
+        let amount_warning = amount_unit_warning(&amount_wei, "amount_wei", 18, args, &state, req_id)?;
+        let (_, value, mut tx_request) = evm_transfer_request(&to_address, &amount_wei, args, &state, &chain_id, req_id)?;
+        // Optional calldata riding along with the value, e.g. a tag the recipient can read
+        let data = match args.get("data").and_then(|v| v.as_str()).filter(|d| !d.is_empty()) {
+            Some(d) => Some(hex::decode(d.trim_start_matches("0x")).map_err(|_| {
+                Response::error(req_id.clone(), error_codes::INVALID_PARAMS, "Invalid 'data': expected hex".into())
+            })?),
+            None => None,
+        };
+        if let Some(bytes) = &data {
+            tx_request = tx_request.data(Bytes::from(bytes.clone()));
         }
-        _ => Response::error(
-            req.id,
-            error_codes::METHOD_NOT_FOUND,
-            format!("Tool not found: {}", tool_name),
-        ),
-    }
+        let from = LocalWallet::from_str(&private_key)
+            .map(|w| format!("{:?}", w.address()))
+            .ok();
+        let reserved = match &from {
+            Some(from) => reserve_spend(&state, &chain_id, None, from, value, req_id)?,
+            None => None,
+        };
+
+        let sent = state
+            .sei_client
+            .send_transaction_bumped(
+                &chain_id,
+                &private_key,
+                tx_request,
+                &state.nonce_manager,
+                gas_bump_arg(args, &state),
+                utils::parse_gas_override(args, "max_priority_fee_per_gas"),
+            )
+            .await
+            .map_err(|e| service_error(req_id, e))?;
+        commit_spend(reserved);
+        let response = &sent.response;
+        audit(&state, audit::AuditEntry::new("transfer_evm", &chain_id, from, &to_address, &amount_wei, &response.tx_hash));
+        let network_name = state.config.network_name(&chain_id);
+        let summary = match serde_json::to_string(&response) {
+            Ok(s) => format!("EVM tx sent on {}: {}", network_name, s),
+            Err(_) => "EVM tx sent".to_string(),
+        };
+        let mut payload = json!(response);
+        payload["network_name"] = json!(network_name);
+        payload["explorer_url"] = json!(state.config.explorer_tx_url(&chain_id, &response.tx_hash));
+        payload["amount_warning"] = json!(amount_warning);
+        payload["data"] = json!(data.map(|d| format!("0x{}", hex::encode(d))));
+        sent_fee_fields(&mut payload, &sent);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, payload),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
 }
 
-/// Handles the 'initialize' request.
-fn handle_initialize(req: &Request) -> Response {
-    let server_info = json!({
-        "name": "sei-mcp-server-rs",
-        "version": "0.2.0-fixed"
-    });
-    let capabilities = json!({ "tools": { "listChanged": false } });
-    let instructions =
-        "Sei EVM blockchain MCP server for secure wallet operations, balance queries, and transaction management.";
+// Native SEI bank transfer using a provided Cosmos private key (0x-hex secp256k1)
+async fn transfer_sei(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let private_key = utils::get_required_arg::<String>(args, "private_key", req_id)?;
+        let chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
+        let to_address = utils::get_required_arg::<String>(args, "to_address", req_id)?;
+        let amount_usei = utils::get_required_arg::<String>(args, "amount_usei", req_id)?;
 
-    Response::success(
-        req.id.clone(),
-        json!({
-            "serverInfo": server_info,
-            "protocolVersion": "2025-06-18",
-            "capabilities": capabilities,
-            "instructions": instructions
-        }),
-    )
+        let amount_warning = amount_unit_warning(&amount_usei, "amount_usei", 6, args, &state, req_id)?;
+        let amount = native_transfer_amount(&amount_usei, &state, &chain_id, req_id)?;
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
+        })?;
+
+        let from = transactions::native_sender_address(&state.config, &private_key).ok();
+        let reserved = match &from {
+            Some(from) => reserve_spend(&state, &chain_id, None, from, U256::from(amount), req_id)?,
+            None => None,
+        };
+
+        let memo = args.get("memo").and_then(|v| v.as_str()).unwrap_or_default();
+        let tx_hash = transactions::send_native_transaction_signed(
+            state.sei_client.http(),
+            &state.config,
+            rpc_url,
+            &private_key,
+            &to_address,
+            amount,
+            memo,
+        )
+        .await
+        .map_err(|e| service_error(req_id, e.into()))?;
+        commit_spend(reserved);
+        audit(&state, audit::AuditEntry::new("transfer_sei", &chain_id, None, &to_address, &amount_usei, &tx_hash));
+        let network_name = state.config.network_name(&chain_id);
+        let payload = json!({
+            "transaction_hash": tx_hash,
+            "network_name": network_name,
+            "explorer_url": state.config.explorer_tx_url(&chain_id, &tx_hash),
+            "memo": memo,
+            "amount_warning": amount_warning,
+        });
+        let summary = format!("SEI bank tx on {}: {}", network_name, tx_hash);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, payload),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
 }
 
-/// Handles the 'tools/list' request by returning a JSON definition of all available tools.
-// FIX: The tool list is now updated, secure, and functional.
-fn handle_tools_list(req: &Request) -> Response {
-    let tools = json!([
-        {
-            "name": "redirect_to_seidocs",
-            "description": "Return the Sei documentation URL (https://docs.sei.io/).",
-            "inputSchema": { "type": "object", "properties": {}, "additionalProperties": false }
-        },
-        {
-            "name": "get_balance",
-            "description": "Get the EVM balance of an address on a specific Sei chain.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "chain_id": {"type": "string", "description": "The blockchain chain ID (e.g., 'sei-testnet')"},
-                    "address": {"type": "string", "description": "The 0x... EVM wallet address to check."}
-                },
-                "required": ["chain_id", "address"]
-            }
-        },
-        {
-            "name": "create_wallet",
-            "description": "Create a new EVM wallet. Returns address, private key, and mnemonic.",
-            "inputSchema": { "type": "object", "properties": {}, "additionalProperties": false }
-        },
-        {
-            "name": "import_wallet",
-            "description": "Import an EVM wallet from a mnemonic phrase or private key.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "mnemonic_or_private_key": {"type": "string", "description": "The mnemonic phrase or private key to import."}
-                },
-                "required": ["mnemonic_or_private_key"]
-            }
-        },
-        {
-            "name": "search_events",
-            "description": "Search EVM logs via eth_getLogs. For native events, not yet implemented.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "chain_id": {"type": "string"},
-                    "contract_address": {"type": "string"},
-                    "topic0": {"type": "string", "description": "Keccak topic0 (event signature hash)"},
-                    "from_block": {"type": "string", "description": "hex block tag like '0x1' or 'earliest'"},
-                    "to_block": {"type": "string", "description": "hex block tag like 'latest'"}
-                },
-                "required": ["chain_id", "contract_address"],
-                "additionalProperties": false
-            }
-        },
-        {
-            "name": "request_faucet",
-            "description": "Request testnet tokens from the faucet for an EVM address.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "chain_id": {"type": "string", "description": "Target chain id configured in CHAIN_RPC_URLS."},
-                    "address": {"type": "string", "description": "The EVM (0x...) address to receive tokens."}
-                },
-                "required": ["chain_id", "address"],
-                "additionalProperties": false
-            }
-        },
-        {
-            "name": "register_wallet",
-            "description": "Encrypt and securely store a private key under a wallet name.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "wallet_name": {"type": "string", "description": "A unique name for the wallet (e.g., 'my-primary-wallet')."},
-                    "private_key": {"type": "string", "description": "The private key to encrypt and store."},
-                    "master_password": {"type": "string", "description": "The master password to encrypt the wallet. This password will be required for any future actions with this wallet."}
-                },
-                "required": ["wallet_name", "private_key", "master_password"]
-            }
-        },
-        {
-            "name": "list_wallets",
-            "description": "List the names of all wallets currently stored in the secure storage.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                     "master_password": {"type": "string", "description": "The master password for the wallet storage."}
-                },
-                "required": ["master_password"]
+async fn transfer_sei_with_feegrant(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let private_key = utils::get_required_arg::<String>(args, "private_key", req_id)?;
+        let chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
+        let to_address = utils::get_required_arg::<String>(args, "to_address", req_id)?;
+        let amount_usei = utils::get_required_arg::<String>(args, "amount_usei", req_id)?;
+        let granter = utils::get_required_arg::<String>(args, "granter", req_id)?;
+
+        let amount_warning = amount_unit_warning(&amount_usei, "amount_usei", 6, args, &state, req_id)?;
+        let amount = native_transfer_amount(&amount_usei, &state, &chain_id, req_id)?;
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
+        })?;
+
+        let from = transactions::native_sender_address(&state.config, &private_key).ok();
+        let reserved = match &from {
+            Some(from) => reserve_spend(&state, &chain_id, None, from, U256::from(amount), req_id)?,
+            None => None,
+        };
+
+        let memo = args.get("memo").and_then(|v| v.as_str()).unwrap_or_default();
+        let tx_hash = transactions::send_native_transaction_with_feegrant(
+            state.sei_client.http(),
+            &state.config,
+            rpc_url,
+            &private_key,
+            &to_address,
+            amount,
+            &granter,
+            memo,
+        )
+        .await
+        .map_err(|e| service_error(req_id, e.into()))?;
+        commit_spend(reserved);
+        audit(
+            &state,
+            audit::AuditEntry::new("transfer_sei_with_feegrant", &chain_id, None, &to_address, &amount_usei, &tx_hash),
+        );
+        let network_name = state.config.network_name(&chain_id);
+        let payload = json!({
+            "transaction_hash": tx_hash,
+            "fee_granter": granter,
+            "memo": memo,
+            "network_name": network_name,
+            "explorer_url": state.config.explorer_tx_url(&chain_id, &tx_hash),
+            "amount_warning": amount_warning,
+        });
+        let summary = format!("SEI bank tx on {} (fee paid by {}): {}", network_name, granter, tx_hash);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, payload),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn grant_fee_allowance(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: &str| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg.to_string());
+        let private_key = utils::get_required_arg::<String>(args, "private_key", req_id)?;
+        let chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
+        let grantee = utils::get_required_arg::<String>(args, "grantee", req_id)?;
+        let spend_limit = match args.get("spend_limit_usei") {
+            None | Some(Value::Null) => None,
+            Some(Value::String(s)) => Some(s.trim().parse::<u64>().map_err(|_| invalid("Invalid 'spend_limit_usei'"))?),
+            Some(v) => Some(v.as_u64().ok_or_else(|| invalid("Invalid 'spend_limit_usei'"))?),
+        };
+        let expiration = match args.get("expiration").and_then(|v| v.as_str()) {
+            None => None,
+            Some(t) => Some(
+                chrono::DateTime::parse_from_rfc3339(t)
+                    .map_err(|_| invalid("'expiration' must be an RFC 3339 timestamp"))?
+                    .with_timezone(&chrono::Utc),
+            ),
+        };
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| invalid(&e.to_string()))?;
+
+        let tx_hash = transactions::grant_fee_allowance(state.sei_client.http(), &state.config, rpc_url, &private_key, &grantee, spend_limit, expiration)
+            .await
+            .map_err(|e| service_error(req_id, e.into()))?;
+        let granter = transactions::native_sender_address(&state.config, &private_key)
+            .map_err(|e| service_error(req_id, e.into()))?;
+        let payload = json!({
+            "transaction_hash": tx_hash,
+            "granter": granter,
+            "grantee": grantee,
+            "spend_limit_usei": spend_limit.map(|l| l.to_string()),
+            "expiration": expiration.map(|t| t.to_rfc3339()),
+        });
+        let summary = format!("Fee allowance from {} to {}: {}", granter, grantee, tx_hash);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, payload),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+// Dry run of transfer_evm / transfer_sei: what would be sent and what it would cost
+async fn preview_transfer(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let private_key = utils::get_required_arg::<String>(args, "private_key", req_id)?;
+        let chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
+        let to_address = utils::get_required_arg::<String>(args, "to_address", req_id)?;
+        let invalid = |msg: &str| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg.to_string());
+
+        let amount_warning;
+        let (kind, from, to, amount, fee, decimals, base_unit, fee_details) = match ChainType::from_chain_id(&chain_id) {
+            ChainType::Evm => {
+                let amount_wei = utils::get_required_arg::<String>(args, "amount_wei", req_id)?;
+                amount_warning = amount_unit_warning(&amount_wei, "amount_wei", 18, args, &state, req_id)?;
+                let (to, value, tx_request) =
+                    evm_transfer_request(&to_address, &amount_wei, args, &state, &chain_id, req_id)?;
+                let from = LocalWallet::from_str(&private_key)
+                    .map_err(|_| invalid("Invalid 'private_key'"))?
+                    .address();
+                let quote = state
+                    .sei_client
+                    .quote_gas(&chain_id, from, tx_request)
+                    .await
+                    .map_err(|e| service_error(req_id, e))?;
+                let details = gas_quote_details(&quote);
+                ("evm", format!("{:?}", from), ethers_core::utils::to_checksum(&to, None), value, quote.max_fee(), 18, "wei".to_string(), details)
             }
-        },
-        {
-            "name": "transfer_from_wallet",
-            "description": "Transfer tokens from a securely stored wallet.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "wallet_name": {"type": "string", "description": "The name of the stored wallet to transfer from."},
-                    "chain_id": {"type": "string", "description": "The blockchain chain ID (e.g., 'sei-testnet')."},
-                    "to_address": {"type": "string", "description": "The recipient's 0x... EVM address."},
-                    "amount": {"type": "string", "description": "The amount to transfer in the smallest unit (e.g., usei)."},
-                    "master_password": {"type": "string", "description": "The master password to unlock the wallet for this transaction."}
-                },
-                "required": ["wallet_name", "chain_id", "to_address", "amount", "master_password"]
+            ChainType::Native => {
+                let amount_usei = utils::get_required_arg::<String>(args, "amount_usei", req_id)?;
+                amount_warning = amount_unit_warning(&amount_usei, "amount_usei", 6, args, &state, req_id)?;
+                let amount = native_transfer_amount(&amount_usei, &state, &chain_id, req_id)?;
+                let recipient = cosmrs::AccountId::from_str(&to_address)
+                    .ok()
+                    .filter(|a| a.prefix() == state.config.native_bech32_hrp)
+                    .ok_or_else(|| invalid("Invalid 'to_address'"))?;
+                let from = transactions::native_sender_address(&state.config, &private_key)
+                    .map_err(|e| service_error(req_id, e.into()))?;
+                let (fee, gas_limit) = transactions::native_fee(&state.config);
+                let details = json!({ "gas_limit": gas_limit.to_string() });
+                ("native", from, recipient.to_string(), U256::from(amount), U256::from(fee), 6, state.config.native_denom.clone(), details)
             }
-        },
-        {
-            "name": "transfer_evm",
-            "description": "Send an EVM value transfer using a provided private key.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "private_key": {"type": "string"},
-                    "chain_id": {"type": "string"},
-                    "to_address": {"type": "string"},
-                    "amount_wei": {"type": "string"},
-                    "gas_limit": {"type": "string"},
-                    "gas_price": {"type": "string"}
-                },
-                "required": ["private_key", "chain_id", "to_address", "amount_wei"],
-                "additionalProperties": false
+        };
+
+        let total = amount + fee;
+        let human = |v: U256| erc20::format_units(v, decimals);
+        let usd = state.config.usd_prices.get(&chain_id).map(|price| {
+            let value = |v: U256| usd_value(&state, &chain_id, v, decimals);
+            json!({ "price": price, "amount": value(amount), "fee": value(fee), "total": value(total) })
+        });
+        let payload = json!({
+            "chain_id": chain_id,
+            "network_name": state.config.network_name(&chain_id),
+            "kind": kind,
+            "from": from,
+            "to": to,
+            "amount": { "base": amount.to_string(), "unit": base_unit, "sei": human(amount) },
+            "estimated_fee": { "base": fee.to_string(), "unit": base_unit, "sei": human(fee), "details": fee_details },
+            "total": { "base": total.to_string(), "unit": base_unit, "sei": human(total) },
+            "usd": usd,
+            "amount_warning": amount_warning,
+            "sent": false,
+        });
+        let mut summary = format!(
+            "Preview: send {} SEI from {} to {} on {} (fee up to {} SEI, total {} SEI)",
+            human(amount), from, to, state.config.network_name(&chain_id), human(fee), human(total)
+        );
+        if let Some(total_usd) = payload["usd"]["total"].as_f64() {
+            summary.push_str(&format!(", about ${:.2}", total_usd));
+        }
+        summary.push_str(". Nothing was sent.");
+        if let Some(w) = &amount_warning {
+            summary.push_str(&format!(" Warning: {}", w));
+        }
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, payload)))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn quote_fee(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: &str| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg.to_string());
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let (fee, decimals, base_unit, details) = match ChainType::from_chain_id(&chain_id) {
+            ChainType::Native => {
+                // Bank sends pay the configured flat fee whatever they carry
+                let (fee, gas_limit) = transactions::native_fee(&state.config);
+                (U256::from(fee), 6, state.config.native_denom.clone(), json!({ "gas_limit": gas_limit.to_string() }))
             }
-        },
-        {
-            "name": "transfer_sei",
-            "description": "Send a native SEI (Cosmos) bank transfer using a provided private key.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "private_key": {"type": "string", "description": "0x-hex Cosmos secp256k1 private key"},
-                    "chain_id": {"type": "string"},
-                    "to_address": {"type": "string", "description": "Bech32 address (sei...)"},
-                    "amount_usei": {"type": "string"}
-                },
-                "required": ["private_key", "chain_id", "to_address", "amount_usei"],
-                "additionalProperties": false
+            ChainType::Evm => {
+                let to_address = args.get("to_address").and_then(Value::as_str);
+                if to_address.is_none() && args.get("gas_limit").is_none() {
+                    return Err(invalid("Pass a transaction ('to_address', optionally 'value'/'data'/'from') or a 'gas_limit'"));
+                }
+                let mut tx = TransactionRequest::new();
+                if let Some(to) = to_address {
+                    tx = tx.to(Address::from_str(to).map_err(|_| invalid("Invalid 'to_address'"))?);
+                }
+                if let Some(value) = args.get("value").and_then(Value::as_str) {
+                    tx = tx.value(U256::from_dec_str(value.trim()).map_err(|_| invalid("Invalid 'value'"))?);
+                }
+                if let Some(data) = args.get("data").and_then(Value::as_str).filter(|d| !d.is_empty()) {
+                    let bytes = hex::decode(data.trim_start_matches("0x")).map_err(|_| invalid("Invalid 'data': expected hex"))?;
+                    tx = tx.data(Bytes::from(bytes));
+                }
+                let from = match args.get("from").and_then(Value::as_str) {
+                    Some(a) => Address::from_str(a).map_err(|_| invalid("Invalid 'from'"))?,
+                    None => Address::zero(),
+                };
+                let tx = apply_gas_settings(tx, args, &state, &chain_id);
+                let quote = state.sei_client.quote_gas(&chain_id, from, tx).await.map_err(|e| service_error(req_id, e))?;
+                let mut details = gas_quote_details(&quote);
+                details["max_fee_per_gas_gwei"] = json!(erc20::format_units(quote.max_fee_per_gas, 9));
+                (quote.max_fee(), 18, "wei".to_string(), details)
             }
-        },
-        {
-            "name": "transfer_nft_evm",
-            "description": "Transfer an ERC-721 token (placeholder).",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "private_key": {"type": "string"},
-                    "chain_id": {"type": "string"},
-                    "contract_address": {"type": "string"},
-                    "to_address": {"type": "string"},
-                    "token_id": {"type": "string"}
-                },
-                "required": ["private_key", "chain_id", "contract_address", "to_address", "token_id"],
-                "additionalProperties": false
+        };
+        let sei = erc20::format_units(fee, decimals);
+        let usd = usd_value(&state, &chain_id, fee, decimals);
+        let mut summary = format!("Fee on {}: up to {} SEI ({} {})", state.config.network_name(&chain_id), sei, fee, base_unit);
+        if let Some(usd) = usd {
+            summary.push_str(&format!(", about ${:.2}", usd));
+        }
+        let payload = json!({
+            "chain_id": chain_id,
+            "network_name": state.config.network_name(&chain_id),
+            "fee": { "base": fee.to_string(), "unit": base_unit, "sei": sei, "usd": usd },
+            "usd_price": state.config.usd_prices.get(&chain_id),
+            "details": details,
+        });
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, payload)))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+// EVM ERC-721 transfer
+async fn transfer_nft_evm(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let private_key = utils::get_required_arg::<String>(args, "private_key", req_id)?;
+        let chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
+        let contract_address =
+            utils::get_required_arg::<String>(args, "contract_address", req_id)?;
+        let to_address = utils::get_required_arg::<String>(args, "to_address", req_id)?;
+        let token_id = utils::get_required_arg::<String>(args, "token_id", req_id)?;
+
+        let wallet = LocalWallet::from_str(&private_key).map_err(|_| {
+            Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                "Invalid 'private_key'".into(),
+            )
+        })?;
+        let from_addr = wallet.address();
+        let to = Address::from_str(&to_address).map_err(|_| {
+            Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                "Invalid 'to_address'".into(),
+            )
+        })?;
+        let contract = Address::from_str(&contract_address).map_err(|_| {
+            Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                "Invalid 'contract_address'".into(),
+            )
+        })?;
+        let token_u256 = U256::from_dec_str(&token_id).map_err(|_| {
+            Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                "Invalid 'token_id'".into(),
+            )
+        })?;
+
+        // Encode safeTransferFrom(address,address,uint256)
+        let selector =
+            &keccak256("safeTransferFrom(address,address,uint256)".as_bytes())[0..4];
+        let data_bytes = {
+            let mut encoded = selector.to_vec();
+            let tokens = vec![
+                Token::Address(from_addr),
+                Token::Address(to),
+                Token::Uint(token_u256),
+            ];
+            let mut tail = encode(&tokens);
+            encoded.append(&mut tail);
+            Bytes::from(encoded)
+        };
+
+        let tx_request = TransactionRequest::new()
+            .to(contract)
+            .data(data_bytes)
+            .value(U256::zero());
+        let tx_request = apply_gas_settings(tx_request, args, &state, &chain_id);
+
+        let response = state
+            .sei_client
+            .send_transaction(&chain_id, &private_key, tx_request, &state.nonce_manager)
+            .await
+            .map_err(|e| service_error(req_id, e))?;
+        audit(
+            &state,
+            audit::AuditEntry::new(
+                "transfer_nft_evm",
+                &chain_id,
+                Some(format!("{:?}", from_addr)),
+                &to_address,
+                &format!("{} #{}", contract_address, token_id),
+                &response.tx_hash,
+            ),
+        );
+        let mut payload = json!(response);
+        payload["explorer_url"] = json!(state.config.explorer_tx_url(&chain_id, &response.tx_hash));
+        Ok(Response::success(req_id.clone(), payload))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+// --- Secure Wallet Storage Tools ---
+async fn register_wallet(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let wallet_name = utils::get_required_arg::<String>(args, "wallet_name", req_id)?;
+        let private_key = utils::get_required_arg::<String>(args, "private_key", req_id)?;
+        let master_password =
+            utils::get_required_arg::<String>(args, "master_password", req_id)?;
+
+        let wallet_info: WalletResponse =
+            wallet::import_wallet(&private_key).map_err(|e| {
+                Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
+            })?;
+
+        store_wallet(&state, &wallet_name, &private_key, wallet_info.address, &master_password, req_id)
+            .await?;
+
+        let payload = json!({ "status": "success", "wallet_name": wallet_name });
+        let summary = format!("Registered wallet {}", wallet_name);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, payload),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn remove_wallet(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let wallet_name = utils::get_required_arg::<String>(args, "wallet_name", req_id)?;
+        let master_password = resolve_master_password(args, &state, req_id)?;
+
+        let mut storage = lock_wallet_storage(&state, req_id).await?;
+        let removed = storage
+            .remove_wallet(&wallet_name, &master_password)
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        if removed {
+            persist_storage(&state, &storage, req_id)?;
+        }
+
+        let summary = if removed {
+            format!("Removed wallet {}", wallet_name)
+        } else {
+            format!("No wallet named {}", wallet_name)
+        };
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, json!({ "wallet_name": wallet_name, "removed": removed })),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn unlock_storage(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let master_password =
+            utils::get_required_arg::<String>(args, "master_password", req_id)?;
+        if !lock_wallet_storage(&state, req_id).await?.verify_master_password(&master_password) {
+            return Err(Response::error(
+                req_id.clone(),
+                error_codes::INTERNAL_ERROR,
+                "Invalid master password".into(),
+            ));
+        }
+        let token = state.sessions.create(&master_password);
+        let idle_secs = state.sessions.idle_timeout().as_secs();
+        let payload = json!({ "session_token": token, "idle_timeout_secs": idle_secs });
+        let summary = format!("Storage unlocked; session expires after {}s idle", idle_secs);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, payload),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn lock_storage(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let token = utils::get_required_arg::<String>(args, "session_token", req_id)?;
+        let revoked = state.sessions.revoke(&token);
+        let summary = if revoked { "Storage locked" } else { "Session was not active" };
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary.to_string(), json!({ "revoked": revoked })),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn list_wallets(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let master_password = resolve_master_password(args, &state, req_id)?;
+        let storage = lock_wallet_storage(&state, req_id).await?;
+        if !storage.verify_master_password(&master_password) {
+            return Err(Response::error(
+                req_id.clone(),
+                error_codes::INTERNAL_ERROR,
+                "Invalid master password".into(),
+            ));
+        }
+        let tag = args.get("filter_by_tag").and_then(|v| v.as_str()).filter(|t| !t.trim().is_empty());
+        let matching = storage.wallets_with_tag(tag);
+        let (page, page_size) = utils::page_args(args, local_page_cap(args, &state, matching.len()));
+        let (matching, pagination) = utils::paginate(matching, page, page_size);
+        let wallets: Vec<&str> = matching.iter().map(|w| w.wallet_name.as_str()).collect();
+        let details: Vec<Value> = matching
+            .iter()
+            .map(|w| {
+                json!({
+                    "wallet_name": w.wallet_name,
+                    "public_address": w.public_address,
+                    "label": w.label,
+                    "tags": w.tags,
+                    "note": w.note,
+                })
+            })
+            .collect();
+        let count = pagination.total.unwrap_or_default();
+        let payload = json!({ "wallets": wallets, "details": details, "pagination": pagination });
+        let mut summary = match tag {
+            Some(t) => format!("{} wallet(s) tagged '{}'", count, t.trim().to_lowercase()),
+            None => format!("{} wallet(s)", count),
+        };
+        if let Some(n) = pagination.next_page {
+            summary.push_str(&format!("; showing page {}, more on page {}", page, n));
+        }
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, payload),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn set_wallet_metadata(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: &str| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg.to_string());
+        let wallet_name = utils::get_required_arg::<String>(args, "wallet_name", req_id)?;
+        let master_password = resolve_master_password(args, &state, req_id)?;
+        #[allow(clippy::result_large_err)]
+        let text_arg = |key: &str| match args.get(key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::String(s)) => Ok(Some(s.clone())),
+            Some(_) => Err(invalid(&format!("'{}' must be a string", key))),
+        };
+        let tags = match args.get("tags") {
+            None | Some(Value::Null) => None,
+            Some(Value::Array(items)) => Some(
+                items
+                    .iter()
+                    .map(|t| t.as_str().map(str::to_string))
+                    .collect::<Option<Vec<String>>>()
+                    .ok_or_else(|| invalid("'tags' must be an array of strings"))?,
+            ),
+            Some(_) => return Err(invalid("'tags' must be an array of strings")),
+        };
+        let update = wallet_storage::WalletMetadata { label: text_arg("label")?, tags, note: text_arg("note")? };
+        if update.label.is_none() && update.tags.is_none() && update.note.is_none() {
+            return Err(invalid("Provide at least one of 'label', 'tags' or 'note'"));
+        }
+
+        let mut storage = lock_wallet_storage(&state, req_id).await?;
+        let wallet = storage
+            .set_wallet_metadata(&wallet_name, update, &master_password)
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        let payload = json!({
+            "wallet_name": wallet.wallet_name,
+            "label": wallet.label,
+            "tags": wallet.tags,
+            "note": wallet.note,
+        });
+        persist_storage(&state, &storage, req_id)?;
+        let summary = format!("Updated metadata for '{}'", wallet_name);
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, payload)))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn add_contact(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let name = utils::get_required_arg::<String>(args, "name", req_id)?;
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let note = args.get("note").and_then(Value::as_str).map(str::to_string).filter(|n| !n.is_empty());
+        let replace = args.get("replace").and_then(Value::as_bool).unwrap_or(false);
+        let invalid = |msg: String| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg);
+
+        // Stored in the form the chain's transfer tools take
+        let address = match ChainType::from_chain_id(&chain_id) {
+            ChainType::Evm => Address::from_str(address.trim())
+                .map(|a| ethers_core::utils::to_checksum(&a, None))
+                .map_err(|_| invalid(format!("Invalid 'address' for {} (expected a 0x EVM address)", chain_id)))?,
+            ChainType::Native => cosmrs::AccountId::from_str(address.trim())
+                .ok()
+                .filter(|a| a.prefix() == state.config.native_bech32_hrp)
+                .map(|a| a.to_string())
+                .ok_or_else(|| invalid(format!("Invalid 'address' for {} (expected a bech32 {}1... address)", chain_id, state.config.native_bech32_hrp)))?,
+        };
+        let contact = wallet_storage::Contact { name: name.clone(), address, chain_id, note, created_at: chrono::Utc::now() };
+
+        let mut storage = lock_wallet_storage(&state, req_id).await?;
+        let replaced = storage.add_contact(contact, replace).map_err(|e| invalid(e.to_string()))?;
+        persist_storage(&state, &storage, req_id)?;
+        let saved = storage.contact(&name).cloned();
+        let summary = match (&saved, &replaced) {
+            (Some(c), Some(old)) => format!("Updated contact '{}': {} on {} (was {})", c.name, c.address, c.chain_id, old.address),
+            (Some(c), None) => format!("Saved contact '{}': {} on {}", c.name, c.address, c.chain_id),
+            (None, _) => "Saved contact".to_string(),
+        };
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, json!({ "contact": saved, "replaced": replaced })),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn list_contacts(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let chain_id = args.get("chain_id").and_then(Value::as_str).map(normalize_chain_id);
+        let storage = lock_wallet_storage(&state, req_id).await?;
+        let contacts = storage.list_contacts(chain_id.as_deref());
+        let (page, page_size) = utils::page_args(args, local_page_cap(args, &state, contacts.len()));
+        let (contacts, pagination) = utils::paginate(contacts, page, page_size);
+        let mut summary = if pagination.total == Some(0) {
+            "No contacts saved".to_string()
+        } else {
+            let lines: Vec<String> = contacts.iter().map(|c| format!("- {}: {} ({})", c.name, c.address, c.chain_id)).collect();
+            format!("{} contact(s):\n{}", pagination.total.unwrap_or_default(), lines.join("\n"))
+        };
+        if let Some(n) = pagination.next_page {
+            summary.push_str(&format!("\nMore on page {}", n));
+        }
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, json!({ "contacts": contacts, "pagination": pagination })),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn remove_contact(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let name = utils::get_required_arg::<String>(args, "name", req_id)?;
+        let mut storage = lock_wallet_storage(&state, req_id).await?;
+        let removed = storage.remove_contact(&name);
+        if removed.is_some() {
+            persist_storage(&state, &storage, req_id)?;
+        }
+        let summary = match &removed {
+            Some(c) => format!("Removed contact '{}'", c.name),
+            None => format!("No contact named '{}'", name),
+        };
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, json!({ "removed": removed.is_some(), "contact": removed })),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+// Adds (`adding`) or removes a recipient on a wallet's allowlist.
+async fn set_allowed_recipient(adding: bool, args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let wallet_name = utils::get_required_arg::<String>(args, "wallet_name", req_id)?;
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        let master_password =
+            utils::get_required_arg::<String>(args, "master_password", req_id)?;
+
+        let mut storage = lock_wallet_storage(&state, req_id).await?;
+        let changed = if adding {
+            storage.add_allowed_recipient(&wallet_name, &address, &master_password)
+        } else {
+            storage.remove_allowed_recipient(&wallet_name, &address, &master_password)
+        }
+        .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        if changed {
+            persist_storage(&state, &storage, req_id)?;
+        }
+        let allowlist = storage
+            .wallets
+            .get(&wallet_name)
+            .and_then(|w| w.allowed_recipients.clone());
+
+        let summary = match (adding, changed) {
+            (true, true) => format!("Allowed {} as a recipient of '{}'", address, wallet_name),
+            (true, false) => format!("{} was already allowed for '{}'", address, wallet_name),
+            (false, true) => format!("Removed {} from the allowlist of '{}'", address, wallet_name),
+            (false, false) => format!("{} was not on the allowlist of '{}'", address, wallet_name),
+        };
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({ "wallet_name": wallet_name, "changed": changed, "allowed_recipients": allowlist }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn add_allowed_recipient(args: &Value, req_id: &Value, state: AppState) -> Response {
+    set_allowed_recipient(true, args, req_id, state).await
+}
+
+async fn remove_allowed_recipient(args: &Value, req_id: &Value, state: AppState) -> Response {
+    set_allowed_recipient(false, args, req_id, state).await
+}
+
+async fn transfer_from_wallet(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let wallet_name = utils::get_required_arg::<String>(args, "wallet_name", req_id)?;
+        let chain_id = utils::get_required_arg::<String>(args, "chain_id", req_id)?;
+        let to_address = utils::get_required_arg::<String>(args, "to_address", req_id)?;
+        let amount = utils::get_required_arg::<String>(args, "amount", req_id)?;
+        let amount_warning = amount_unit_warning(&amount, "amount", 18, args, &state, req_id)?;
+        let private_key = managed_wallet_key(&state, &wallet_name, &to_address, args, req_id).await?;
+
+        let to = Address::from_str(&to_address).map_err(|_| {
+            Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                "Invalid 'to_address'".into(),
+            )
+        })?;
+        let value = U256::from_dec_str(&amount).map_err(|_| {
+            Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                "Invalid 'amount'".into(),
+            )
+        })?;
+        enforce_transfer_cap(&state, &chain_id, value, req_id)?;
+        let from = state
+            .wallet_storage
+            .lock()
+            .await
+            .wallets
+            .get(&wallet_name)
+            .map(|w| w.public_address.clone());
+        let reserved = match &from {
+            Some(from) => reserve_spend(&state, &chain_id, Some(&wallet_name), from, value, req_id)?,
+            None => None,
+        };
+
+        let tx_request = apply_gas_settings(TransactionRequest::new().to(to).value(value), args, &state, &chain_id);
+
+        let sent = state
+            .sei_client
+            .send_transaction_bumped(
+                &chain_id,
+                &private_key,
+                tx_request,
+                &state.nonce_manager,
+                gas_bump_arg(args, &state),
+                utils::parse_gas_override(args, "max_priority_fee_per_gas"),
+            )
+            .await
+            .map_err(|e| service_error(req_id, e))?;
+        commit_spend(reserved);
+        let response = &sent.response;
+        audit(&state, audit::AuditEntry::new("transfer_from_wallet", &chain_id, from, &to_address, &amount, &response.tx_hash));
+        let network_name = state.config.network_name(&chain_id);
+        let summary = match serde_json::to_string(&response) {
+            Ok(s) => format!("Transfer sent on {}: {}", network_name, s),
+            Err(_) => "Transfer sent".to_string(),
+        };
+        let mut payload = json!(response);
+        payload["network_name"] = json!(network_name);
+        payload["explorer_url"] = json!(state.config.explorer_tx_url(&chain_id, &response.tx_hash));
+        payload["amount_warning"] = json!(amount_warning);
+        sent_fee_fields(&mut payload, &sent);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, payload),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn sweep_wallet(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: &str| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg.to_string());
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let to_address = utils::get_required_arg::<String>(args, "to_address", req_id)?;
+        if ChainType::from_chain_id(&chain_id) == ChainType::Native {
+            return Err(invalid("sweep_wallet applies to EVM chains"));
+        }
+        let wallet_name = args.get("wallet_name").and_then(Value::as_str);
+        let private_key = match (args.get("private_key").and_then(Value::as_str), wallet_name) {
+            (Some(key), None) => key.to_string(),
+            (None, Some(name)) => managed_wallet_key(&state, name, &to_address, args, req_id).await?,
+            _ => return Err(invalid("Pass either 'private_key' or 'wallet_name' (with master_password or session_token)")),
+        };
+        let from = wallet::import_wallet(&private_key).map_err(|e| invalid(&e.to_string()))?.address;
+        let from_addr = Address::from_str(&from).map_err(|_| invalid("Invalid 'private_key'"))?;
+        let to = Address::from_str(&to_address).map_err(|_| invalid("Invalid 'to_address'"))?;
+
+        let (balance, _) = fetch_balance(&state, &chain_id, &from, req_id).await?;
+        // Estimated without value: carrying the whole balance would leave nothing for gas
+        let quote = state
+            .sei_client
+            .quote_gas(&chain_id, from_addr, apply_gas_settings(TransactionRequest::new().to(to), args, &state, &chain_id))
+            .await
+            .map_err(|e| service_error(req_id, e))?;
+        let fee = quote.max_fee();
+        let value = transactions::sweep_value(balance, &quote).ok_or_else(|| {
+            Response::error(
+                req_id.clone(),
+                error_codes::INSUFFICIENT_FUNDS,
+                format!("Insufficient balance to cover gas: {} wei held, {} wei fee", balance, fee),
+            )
+        })?;
+        enforce_transfer_cap(&state, &chain_id, value, req_id)?;
+        let reserved = reserve_spend(&state, &chain_id, wallet_name, &from, value, req_id)?;
+
+        // One pinned price (both fee caps on an EIP-1559 envelope) makes the fee exactly gas × price
+        let tx_request = TransactionRequest::new().to(to).value(value).gas(quote.gas_limit).gas_price(quote.max_fee_per_gas);
+        let response = state
+            .sei_client
+            .send_transaction(&chain_id, &private_key, tx_request, &state.nonce_manager)
+            .await
+            .map_err(|e| service_error(req_id, e))?;
+        commit_spend(reserved);
+        audit(
+            &state,
+            audit::AuditEntry::new("sweep_wallet", &chain_id, Some(from.clone()), &to_address, &value.to_string(), &response.tx_hash),
+        );
+        let network_name = state.config.network_name(&chain_id);
+        let summary = format!(
+            "Swept {} SEI from {} to {} on {} ({} wei fee): {}",
+            erc20::format_units(value, 18),
+            from,
+            to_address,
+            network_name,
+            fee,
+            response.tx_hash
+        );
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "tx_hash": response.tx_hash,
+                    "from": from,
+                    "to": to_address,
+                    "wallet_name": wallet_name,
+                    "balance_wei": balance.to_string(),
+                    "fee_wei": fee.to_string(),
+                    "swept_wei": value.to_string(),
+                    "gas_limit": quote.gas_limit.to_string(),
+                    "gas_price": quote.max_fee_per_gas.to_string(),
+                    "network_name": network_name,
+                    "explorer_url": state.config.explorer_tx_url(&chain_id, &response.tx_hash),
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn set_wallet_multisig(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: &str| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg.to_string());
+        let wallet_name = utils::get_required_arg::<String>(args, "wallet_name", req_id)?;
+        let master_password = resolve_master_password(args, &state, req_id)?;
+        let authorizers = args
+            .get("authorizers")
+            .and_then(|v| v.as_array())
+            .ok_or_else(|| invalid("'authorizers' must be an array of {name, confirmation_code}"))?
+            .iter()
+            .map(|a| Some((a.get("name")?.as_str()?.to_string(), a.get("confirmation_code")?.as_str()?.to_string())))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| invalid("Each authorizer needs a string 'name' and 'confirmation_code'"))?;
+        let threshold = args.get("threshold").and_then(|v| v.as_u64()).unwrap_or(authorizers.len() as u64) as usize;
+
+        let mut storage = lock_wallet_storage(&state, req_id).await?;
+        let policy = storage
+            .set_multisig_policy(&wallet_name, threshold, &authorizers, &master_password)
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string()))?
+            .map(|p| {
+                let mut names: Vec<&String> = p.authorizers.keys().collect();
+                names.sort();
+                json!({ "threshold": p.threshold, "authorizers": names })
+            });
+        persist_storage(&state, &storage, req_id)?;
+        let summary = match &policy {
+            Some(p) => format!("'{}' now needs {} of {} approvals per transfer", wallet_name, p["threshold"], authorizers.len()),
+            None => format!("Removed the multisig requirement from '{}'", wallet_name),
+        };
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, json!({ "wallet_name": wallet_name, "multisig": policy }))))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn propose_transfer(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: &str| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg.to_string());
+        let wallet_name = utils::get_required_arg::<String>(args, "wallet_name", req_id)?;
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let to_address = utils::get_required_arg::<String>(args, "to_address", req_id)?;
+        let amount = utils::get_required_arg::<String>(args, "amount", req_id)?;
+        let amount_warning = amount_unit_warning(&amount, "amount", 18, args, &state, req_id)?;
+        let master_password = resolve_master_password(args, &state, req_id)?;
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        Address::from_str(&to_address).map_err(|_| invalid("Invalid 'to_address'"))?;
+        let value = U256::from_dec_str(&amount).map_err(|_| invalid("Invalid 'amount'"))?;
+        enforce_transfer_cap(&state, &chain_id, value, req_id)?;
+
+        let mut storage = lock_wallet_storage(&state, req_id).await?;
+        if !storage.verify_master_password(&master_password) {
+            return Err(Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, "Invalid master password".into()));
+        }
+        let ttl = chrono::Duration::seconds(state.config.proposal_ttl_secs as i64);
+        let proposal = storage
+            .propose_transfer(&wallet_name, &chain_id, &to_address, &amount, ttl, chrono::Utc::now())
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string()))?
+            .clone();
+        let threshold = storage.multisig[&wallet_name].threshold;
+        persist_storage(&state, &storage, req_id)?;
+
+        let summary = format!(
+            "Proposal {} to send {} from '{}' to {} needs {} approval(s) before {}",
+            proposal.proposal_id, amount, wallet_name, to_address, threshold, proposal.expires_at
+        );
+        let mut payload = json!(proposal);
+        payload["threshold"] = json!(threshold);
+        payload["amount_warning"] = json!(amount_warning);
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, payload)))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn approve_proposal(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let proposal_id = utils::get_required_arg::<String>(args, "proposal_id", req_id)?;
+        let authorizer = utils::get_required_arg::<String>(args, "authorizer", req_id)?;
+        let confirmation_code = utils::get_required_arg::<String>(args, "confirmation_code", req_id)?;
+        let master_password = resolve_master_password(args, &state, req_id)?;
+
+        let (proposal, threshold, private_key, from) = {
+            // Scoped lock; released while the transfer is sent
+            let mut storage = lock_wallet_storage(&state, req_id).await?;
+            // Checked first so a bad password can't leave an approved proposal unsent
+            if !storage.verify_master_password(&master_password) {
+                return Err(Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, "Invalid master password".into()));
             }
-        },
-         {
-            "name": "get_contract",
-            "description": "Get general details for a smart contract.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "address": {"type": "string", "description": "The address of the smart contract."},
-                    "chain_id": {"type": "string", "description": "Optional chain id (e.g., 'sei-evm-mainnet' or 'sei-evm-testnet')."}
-                },
-                "required": ["address"]
+            let approved = storage.approve_proposal(&proposal_id, &authorizer, &confirmation_code, chrono::Utc::now());
+            let proposal = match approved {
+                Ok(p) => p.clone(),
+                Err(e) => {
+                    // Persist an expiry the attempt just recorded
+                    persist_storage(&state, &storage, req_id)?;
+                    return Err(Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string()));
+                }
+            };
+            let threshold = storage.multisig.get(&proposal.wallet_name).map_or(0, |p| p.threshold);
+            let private_key = if proposal.status == wallet_storage::ProposalStatus::Approved {
+                Some(
+                    storage
+                        .get_decrypted_private_key(&proposal.wallet_name, &master_password)
+                        .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?,
+                )
+            } else {
+                None
+            };
+            persist_storage(&state, &storage, req_id)?;
+            let from = storage.wallets.get(&proposal.wallet_name).map(|w| w.public_address.clone());
+            (proposal, threshold, private_key, from)
+        };
+
+        let Some(private_key) = private_key else {
+            let summary = format!(
+                "{} approved proposal {} ({} of {})",
+                authorizer.trim(),
+                proposal_id,
+                proposal.approvals.len(),
+                threshold
+            );
+            let mut payload = json!(proposal);
+            payload["threshold"] = json!(threshold);
+            return Ok(Response::success(req_id.clone(), make_texty_result(summary, payload)));
+        };
+
+        // Validated when the proposal was made
+        let (Ok(to), Ok(value)) = (Address::from_str(&proposal.to_address), U256::from_dec_str(&proposal.amount)) else {
+            return Err(Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, "Stored proposal is malformed".into()));
+        };
+        let tx_request = apply_gas_settings(TransactionRequest::new().to(to).value(value), args, &state, &proposal.chain_id);
+        let reserved = match &from {
+            Some(from) => reserve_spend(&state, &proposal.chain_id, Some(&proposal.wallet_name), from, value, req_id),
+            None => Ok(None),
+        };
+        let reserved = match reserved {
+            Ok(reserved) => reserved,
+            Err(refused) => {
+                // Over budget: the proposal fails as if the transfer could not be sent
+                let reason = refused.error.as_ref().map(|e| e.message.clone()).unwrap_or_default();
+                let mut storage = lock_wallet_storage(&state, req_id).await?;
+                storage.finish_proposal(&proposal_id, Err(reason));
+                persist_storage(&state, &storage, req_id)?;
+                return Err(refused);
             }
-        },
-        {
-            "name": "get_contract_code",
-            "description": "Get the code of a smart contract.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "address": {"type": "string", "description": "The address of the smart contract."}
-                },
-                "required": ["address"]
+        };
+        let sent = state
+            .sei_client
+            .send_transaction_bumped(
+                &proposal.chain_id,
+                &private_key,
+                tx_request,
+                &state.nonce_manager,
+                gas_bump_arg(args, &state),
+                utils::parse_gas_override(args, "max_priority_fee_per_gas"),
+            )
+            .await;
+        // On failure the reservation is released as it drops
+        if sent.is_ok() {
+            commit_spend(reserved);
+        }
+
+        let mut storage = lock_wallet_storage(&state, req_id).await?;
+        storage.finish_proposal(&proposal_id, sent.as_ref().map(|s| s.response.tx_hash.clone()).map_err(|e| e.to_string()));
+        persist_storage(&state, &storage, req_id)?;
+        drop(storage);
+        let sent = sent.map_err(|e| service_error(req_id, e))?;
+
+        audit(&state, audit::AuditEntry::new("approve_proposal", &proposal.chain_id, from, &proposal.to_address, &proposal.amount, &sent.response.tx_hash));
+        let summary = format!(
+            "Proposal {} reached {} approval(s) and was sent: {}",
+            proposal_id, threshold, sent.response.tx_hash
+        );
+        let mut payload = json!(proposal);
+        payload["status"] = json!(wallet_storage::ProposalStatus::Executed);
+        payload["tx_hash"] = json!(sent.response.tx_hash);
+        payload["threshold"] = json!(threshold);
+        sent_fee_fields(&mut payload, &sent);
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, payload)))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_proposal_status(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let proposal_id = utils::get_required_arg::<String>(args, "proposal_id", req_id)?;
+        let mut storage = lock_wallet_storage(&state, req_id).await?;
+        let proposal = storage.proposal_status(&proposal_id, chrono::Utc::now()).cloned().ok_or_else(|| {
+            Response::error(req_id.clone(), error_codes::NOT_FOUND, format!("Proposal '{}' not found", proposal_id))
+        })?;
+        let threshold = storage.multisig.get(&proposal.wallet_name).map(|p| p.threshold);
+        let summary = format!(
+            "Proposal {} is {:?} with {} approval(s)",
+            proposal_id,
+            proposal.status,
+            proposal.approvals.len()
+        )
+        .to_lowercase();
+        let mut payload = json!(proposal);
+        payload["threshold"] = json!(threshold);
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, payload)))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_contract(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        // Prefer explicit chain_id, else infer from NL, default to testnet
+        let mut chain = args
+            .get("chain_id")
+            .and_then(|v| v.as_str())
+            .map(normalize_chain_id);
+        if chain.is_none() {
+            chain = infer_evm_chain_from_args(args);
+        }
+        let chain_id = chain.unwrap_or_else(|| "sei-evm-testnet".to_string());
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let contract = state
+            .sei_client
+            .get_contract(&chain_id, &address)
+            .await
+            .map_err(|e| {
+                Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
+            })?;
+        let status = contract::verification_status(&contract);
+        let badge = match status {
+            "fully_verified" => "✅ Fully verified",
+            "partially_verified" => "☑️ Partially verified",
+            "verified" => "✅ Verified",
+            _ => "⚠️ Unverified",
+        };
+        let proxy_type = contract.get("proxyType").filter(|v| !v.is_null()).cloned();
+        let implementations = contract.get("implementations").filter(|v| !v.is_null()).cloned();
+        let mut summary = format!("Contract {} on {} — {}", address, chain_id, badge);
+        if let Some(p) = proxy_type.as_ref().and_then(|v| v.as_str()) {
+            summary.push_str(&format!("\nProxy ({})", p));
+            if let Some(impls) = implementations.as_ref().and_then(|v| v.as_array()) {
+                let list: Vec<&str> = impls.iter().filter_map(|i| i.as_str()).collect();
+                summary.push_str(&format!(" → implementations: {}", list.join(", ")));
             }
-        },
-        {
-            "name": "discord_post_message",
-            "description": "Post a message to Discord via webhook or bot token (configured in server env).",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "message": {"type": "string", "description": "Text to post. Code or natural language supported."},
-                    "username": {"type": "string", "description": "Optional display username (webhook mode)."}
-                },
-                "required": ["message"],
-                "additionalProperties": false
+        }
+        let flag = |key: &str| contract.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+        let pretty = serde_json::to_string_pretty(&contract).unwrap_or_else(|_| contract.to_string());
+        Ok(Response::success(
+            req_id.clone(),
+            json!({
+                "verification_status": status,
+                "verified": flag("verified"),
+                "fully_verified": flag("fullyVerified"),
+                "partially_verified": flag("partiallyVerified"),
+                "proxy_type": proxy_type,
+                "implementations": implementations,
+                "contract": contract,
+                "content": [
+                    { "type": "text", "text": format!("{}\n\n{}", summary, pretty) }
+                ]
+            })
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_contract_creation(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        Address::from_str(&address)
+            .map_err(|_| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, "Invalid 'address'".into()))?;
+        let chain_id = args
+            .get("chain_id")
+            .and_then(|v| v.as_str())
+            .map(normalize_chain_id)
+            .or_else(|| infer_evm_chain_from_args(args))
+            .unwrap_or_else(|| "sei-evm-testnet".to_string());
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let creation = state
+            .sei_client
+            .get_contract_creation(&chain_id, &address)
+            .await
+            .map_err(|e| service_error(req_id, e))?
+            .ok_or_else(|| {
+                Response::error(req_id.clone(), error_codes::NOT_FOUND, format!("No contract code at {} on {}", address, chain_id))
+            })?;
+
+        let block = creation.block_number.map(|b| format!(" in block {}", b)).unwrap_or_default();
+        let summary = match (&creation.creator, &creation.tx_hash) {
+            (Some(creator), Some(tx_hash)) => format!("{} was deployed by {}{} (tx {})", address, creator, block, tx_hash),
+            _ => format!("{} was created{} by an internal call from another contract; no top-level deployment tx", address, block),
+        };
+        let mut payload = json!(creation);
+        payload["address"] = json!(address);
+        payload["chain_id"] = json!(chain_id);
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, payload)))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_proxy_implementation(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        let mut chain = args
+            .get("chain_id")
+            .and_then(|v| v.as_str())
+            .map(normalize_chain_id);
+        if chain.is_none() {
+            chain = infer_evm_chain_from_args(args);
+        }
+        let chain_id = chain.unwrap_or_else(|| "sei-evm-testnet".to_string());
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let implementation = state
+            .sei_client
+            .get_proxy_implementation(&chain_id, &address)
+            .await
+            .map_err(|e| {
+                Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
+            })?
+            .map(|a| format!("{:?}", a));
+
+        // Cross-check against SeiStream's indexed view; best-effort since the explorer may lag or be down
+        let indexed: Option<Vec<String>> = state
+            .sei_client
+            .get_contract(&chain_id, &address)
+            .await
+            .ok()
+            .and_then(|c| c.get("implementations").and_then(|v| v.as_array()).cloned())
+            .map(|a| a.iter().filter_map(|i| i.as_str().map(|s| s.to_lowercase())).collect());
+        let matches_seistream = match (&implementation, &indexed) {
+            (Some(imp), Some(list)) if !list.is_empty() => Some(list.contains(imp)),
+            _ => None,
+        };
+
+        let summary = match (&implementation, matches_seistream) {
+            (None, _) => format!("{} has an empty EIP-1967 implementation slot (not an EIP-1967 proxy)", address),
+            (Some(imp), Some(false)) => format!(
+                "{} delegates to {} (⚠️ SeiStream lists {})",
+                address,
+                imp,
+                indexed.as_ref().map(|l| l.join(", ")).unwrap_or_default()
+            ),
+            (Some(imp), _) => format!("{} delegates to {}", address, imp),
+        };
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "address": address,
+                    "chain_id": chain_id,
+                    "is_proxy": implementation.is_some(),
+                    "implementation": implementation,
+                    "seistream_implementations": indexed,
+                    "matches_seistream": matches_seistream,
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_storage_at(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: String| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg);
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        Address::from_str(&address).map_err(|_| invalid("Invalid 'address'".into()))?;
+        // Decimal slots may arrive as JSON numbers
+        let slot_arg = match args.get("slot") {
+            Some(Value::String(s)) => s.clone(),
+            Some(Value::Number(n)) => n.to_string(),
+            _ => return Err(invalid("Missing or invalid 'slot' (0x-hex or decimal)".into())),
+        };
+        let base_slot = contract::parse_storage_word(&slot_arg).map_err(|e| invalid(e.to_string()))?;
+        let mapping_keys: Vec<Value> = match args.get("mapping_keys") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Array(keys)) => keys.clone(),
+            Some(_) => return Err(invalid("'mapping_keys' must be an array".into())),
+        };
+        // Nested mappings apply their keys outermost first: m[k1][k2] = slot(k2, slot(k1, base))
+        let mut slot = base_slot;
+        for key in &mapping_keys {
+            let key = match key {
+                Value::String(s) => s.clone(),
+                Value::Number(n) => n.to_string(),
+                _ => return Err(invalid("'mapping_keys' entries must be 0x-hex or decimal".into())),
+            };
+            let key = contract::parse_storage_word(&key).map_err(|e| invalid(format!("mapping key: {}", e)))?;
+            slot = contract::mapping_slot(key, slot);
+        }
+        let block = parse_block_tag(args.get("block"))
+            .ok_or_else(|| invalid("'block' must be a tag (latest, pending, ...) or a block number".into()))?;
+
+        let mut chain = args
+            .get("chain_id")
+            .and_then(|v| v.as_str())
+            .map(normalize_chain_id);
+        if chain.is_none() {
+            chain = infer_evm_chain_from_args(args);
+        }
+        let chain_id = chain.unwrap_or_else(|| "sei-evm-testnet".to_string());
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let rpc_url = state
+            .sei_client
+            .get_rpc_url(&chain_id)
+            .map_err(|e| invalid(e.to_string()))?;
+        let slot_hex = format!("{:?}", slot);
+        let value = rpc::get_storage_at(state.sei_client.transport(), rpc_url, &address, &slot_hex, &block)
+            .await
+            .map_err(|e| service_error(req_id, e))?;
+
+        let as_uint = U256::from_str_radix(value.trim_start_matches("0x"), 16).ok();
+        let as_address = contract::address_from_storage_word(&value)
+            .filter(|_| as_uint.is_some_and(|v| v.bits() <= 160))
+            .map(|a| format!("{:?}", a));
+        let summary = format!("Storage of {} at slot {} ({}): {}", address, slot_hex, block, value);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "address": address,
+                    "chain_id": chain_id,
+                    "block": block,
+                    "base_slot": format!("{:?}", base_slot),
+                    "mapping_keys": mapping_keys,
+                    "slot": slot_hex,
+                    "value": value,
+                    "value_uint": as_uint.map(|v| v.to_string()),
+                    "value_address": as_address,
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn compute_contract_address(args: &Value, req_id: &Value, _state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: &str| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg.to_string());
+        let deployer_str = utils::get_required_arg::<String>(args, "deployer", req_id)?;
+        let deployer = Address::from_str(&deployer_str).map_err(|_| invalid("Invalid 'deployer' address"))?;
+        #[allow(clippy::result_large_err)]
+        let bytes32 = |key: &str| -> Result<Option<[u8; 32]>, Response> {
+            let Some(raw) = args.get(key).and_then(|v| v.as_str()) else {
+                return Ok(None);
+            };
+            let bytes = hex::decode(raw.trim_start_matches("0x"))
+                .map_err(|_| invalid(&format!("'{}' must be hex", key)))?;
+            if bytes.len() != 32 {
+                return Err(invalid(&format!("'{}' must be 32 bytes", key)));
             }
-        },
-        { 
-            "name": "get_contract_transactions",
-            "description": "Get the transactions of a smart contract.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "address": {"type": "string", "description": "The address of the smart contract."}
-                },
-                "required": ["address"]
+            let mut out = [0u8; 32];
+            out.copy_from_slice(&bytes);
+            Ok(Some(out))
+        };
+
+        let (scheme, predicted) = match (bytes32("salt")?, bytes32("init_code_hash")?) {
+            (Some(salt), Some(hash)) => ("CREATE2", contract::compute_create2_address(deployer, salt, hash)),
+            (None, None) => {
+                let nonce = args
+                    .get("nonce")
+                    .and_then(|v| v.as_u64().or_else(|| v.as_str().and_then(|s| s.parse().ok())))
+                    .ok_or_else(|| invalid("Provide 'nonce' for CREATE, or 'salt' and 'init_code_hash' for CREATE2"))?;
+                ("CREATE", contract::compute_create_address(deployer, nonce))
             }
-        },
-        {
-            "name": "get_chain_info",
-            "description": "Get general chain info from SeiStream (network, latest block, validators, etc).",
-            "inputSchema": {"type": "object", "properties": {}, "additionalProperties": false}
-        },
-        {
-            "name": "get_transaction_info",
-            "description": "Get transaction info by EVM hash from SeiStream.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {"hash": {"type": "string"}},
-                "required": ["hash"],
-                "additionalProperties": false
+            _ => return Err(invalid("CREATE2 requires both 'salt' and 'init_code_hash'")),
+        };
+        let address = ethers_core::utils::to_checksum(&predicted, None);
+        let summary = format!("{} deployment from {} lands at {}", scheme, deployer_str, address);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, json!({ "scheme": scheme, "deployer": deployer_str, "address": address })),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn compute_selector(args: &Value, req_id: &Value, _state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let signature = utils::get_required_arg::<String>(args, "signature", req_id)?;
+        let (canonical, kind) = abi::canonical_signature(&signature)
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string()))?;
+        let hash = keccak256(canonical.as_bytes());
+        let mut payload = json!({ "signature": signature, "canonical_signature": canonical });
+        let mut parts = Vec::new();
+        // Without a `function`/`event` keyword the string could be either, so return both
+        if kind != Some(abi::SignatureKind::Event) {
+            let selector = format!("0x{}", hex::encode(&hash[..4]));
+            parts.push(format!("selector {}", selector));
+            payload["selector"] = json!(selector);
+        }
+        if kind != Some(abi::SignatureKind::Function) {
+            let topic = format!("0x{}", hex::encode(hash));
+            parts.push(format!("topic {}", topic));
+            payload["topic"] = json!(topic);
+        }
+        let summary = format!("{}: {}", canonical, parts.join(", "));
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, payload)))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn decode_abi(args: &Value, req_id: &Value, _state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: String| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg);
+        let data = utils::get_required_arg::<String>(args, "data", req_id)?;
+        let types = utils::get_required_arg::<Vec<String>>(args, "types", req_id)?;
+        let bytes = hex::decode(data.trim().trim_start_matches("0x")).map_err(|_| invalid("'data' must be hex".into()))?;
+        let values = abi::decode_values(&types, &bytes).map_err(|e| invalid(e.to_string()))?;
+        let decoded: Vec<Value> = types
+            .iter()
+            .zip(&values)
+            .map(|(t, v)| json!({ "type": t.trim(), "value": v }))
+            .collect();
+        let summary = format!(
+            "Decoded {} value(s): {}",
+            values.len(),
+            values.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(", ")
+        );
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, json!({ "values": values, "decoded": decoded }))))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn eth_call_raw(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: String| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg);
+        let to = utils::get_required_arg::<String>(args, "to", req_id)?;
+        let data = args.get("data").and_then(|v| v.as_str()).unwrap_or("0x");
+        if hex::decode(data.trim_start_matches("0x")).is_err() {
+            return Err(invalid("'data' must be 0x-prefixed hex".into()));
+        }
+        let block = parse_block_tag(args.get("block"))
+            .ok_or_else(|| invalid("'block' must be a tag (latest, pending, ...) or a block number".into()))?;
+        let overrides = args.get("state_override").filter(|v| !v.is_null());
+        if let Some(o) = overrides {
+            validate_state_override(o).map_err(invalid)?;
+        }
+        let mut chain = args
+            .get("chain_id")
+            .and_then(|v| v.as_str())
+            .map(normalize_chain_id);
+        if chain.is_none() {
+            chain = infer_evm_chain_from_args(args);
+        }
+        let chain_id = chain.unwrap_or_else(|| "sei-evm-testnet".to_string());
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| invalid(e.to_string()))?;
+
+        let mut call_obj = json!({ "to": to, "data": data });
+        if let Some(from) = args.get("from").and_then(|v| v.as_str()) {
+            call_obj["from"] = json!(from);
+        }
+        if let Some(v) = args.get("value").and_then(|v| v.as_str()) {
+            let wei = U256::from_dec_str(v).map_err(|_| invalid("'value' must be a decimal wei amount".into()))?;
+            call_obj["value"] = json!(format!("0x{:x}", wei));
+        }
+        if let Some(g) = args.get("gas").and_then(|v| v.as_str()) {
+            let gas = U256::from_dec_str(g).map_err(|_| invalid("'gas' must be a decimal number".into()))?;
+            call_obj["gas"] = json!(format!("0x{:x}", gas));
+        }
+
+        let output = rpc::eth_call_with_overrides(state.sei_client.transport(), rpc_url, call_obj, &block, overrides)
+            .await
+            .map_err(|e| {
+                let msg = e.to_string();
+                if overrides.is_some() && rpc::is_override_unsupported(&msg) {
+                    Response::error(
+                        req_id.clone(),
+                        error_codes::INVALID_PARAMS,
+                        format!(
+                            "The {} RPC node does not appear to support eth_call state overrides; retry without 'state_override'. Node said: {}",
+                            chain_id, msg
+                        ),
+                    )
+                } else {
+                    Response::error(req_id.clone(), error_codes::RPC_ERROR, msg)
+                }
+            })?;
+        let summary = format!(
+            "eth_call to {} at {}{} returned {}",
+            to,
+            block,
+            if overrides.is_some() { " (with state override)" } else { "" },
+            output
+        );
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "chain_id": chain_id,
+                    "block": block,
+                    "state_override_applied": overrides.is_some(),
+                    "result": output
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn resync_nonce(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: String| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg);
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        let parsed = Address::from_str(&address).map_err(|_| invalid("Invalid 'address'".into()))?;
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        if ChainType::from_chain_id(&chain_id) == ChainType::Native {
+            return Err(invalid("resync_nonce applies to EVM chains".into()));
+        }
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| invalid(e.to_string()))?;
+        let resync = state
+            .nonce_manager
+            .resync(parsed, rpc_url)
+            .await
+            .map_err(|e| Response::error(req_id.clone(), error_codes::RPC_ERROR, e.to_string()))?;
+        let summary = match resync.previous {
+            Some(prev) if prev == resync.chain => format!("Nonce for {} already in sync at {}", address, prev),
+            Some(prev) => format!("Nonce for {} resynced from {} to {}", address, prev, resync.chain),
+            None => format!("Nonce for {} set to {}", address, resync.chain),
+        };
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "address": address,
+                    "chain_id": chain_id,
+                    "previous_nonce": resync.previous.map(|n| n.as_u64()),
+                    "nonce": resync.chain.as_u64(),
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn rpc_passthrough(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: String| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg);
+        if !state.config.allow_rpc_passthrough {
+            return Err(invalid(
+                "rpc_passthrough is disabled; set ALLOW_RPC_PASSTHROUGH=true to enable it".into(),
+            ));
+        }
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let method = utils::get_required_arg::<String>(args, "method", req_id)?;
+        if method.trim().is_empty() {
+            return Err(invalid("'method' must not be empty".into()));
+        }
+        let params = match args.get("params") {
+            None | Some(Value::Null) => json!([]),
+            Some(p @ Value::Array(_)) => p.clone(),
+            Some(_) => return Err(invalid("'params' must be an array".into())),
+        };
+        if ChainType::from_chain_id(&chain_id) == ChainType::Native {
+            return Err(invalid(format!("'{}' is a native chain; rpc_passthrough forwards EVM JSON-RPC only", chain_id)));
+        }
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| invalid(e.to_string()))?;
+
+        let outcome = rpc::rpc_call(state.sei_client.transport(), rpc_url, &method, params).await;
+        // Raw broadcasts bypass every other check, so they are the calls the audit trail is for
+        let tx_hash = match (&outcome, method.as_str()) {
+            (Ok(Value::String(hash)), "eth_sendRawTransaction") => {
+                broadcasts::record(hash);
+                hash.clone()
             }
-        },
-        {
-            "name": "get_transaction_history",
-            "description": "Get transaction history for an EVM address from SeiStream.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "address": {"type": "string"},
-                    "page": {"type": "number", "description": "Optional page number"}
-                },
-                "required": ["address"],
-                "additionalProperties": false
+            _ => String::new(),
+        };
+        audit(&state, audit::AuditEntry::passthrough(&chain_id, &method, &tx_hash));
+        let result = outcome.map_err(|e| Response::error(req_id.clone(), error_codes::RPC_ERROR, e.to_string()))?;
+        let summary = format!("{} on {} returned {}", method, chain_id, result);
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(summary, json!({ "chain_id": chain_id, "method": method, "result": result })),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_nft_token_uri(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let contract_address = utils::get_required_arg::<String>(args, "contract_address", req_id)?;
+        let raw_id = match args.get("token_id") {
+            Some(Value::Number(n)) => n.to_string(),
+            _ => utils::get_required_arg::<String>(args, "token_id", req_id)?,
+        };
+        let token_id = U256::from_dec_str(raw_id.trim()).map_err(|_| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, "Invalid 'token_id'".into())
+        })?;
+        let want_metadata = args.get("fetch_metadata").and_then(|v| v.as_bool()).unwrap_or(true);
+        let mut chain = args
+            .get("chain_id")
+            .and_then(|v| v.as_str())
+            .map(normalize_chain_id);
+        if chain.is_none() {
+            chain = infer_evm_chain_from_args(args);
+        }
+        let chain_id = chain.unwrap_or_else(|| "sei-evm-testnet".to_string());
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
+        })?;
+        let gateway = &state.config.ipfs_gateway;
+        let client = Client::new();
+        let (uri, standard) = nft::token_uri(state.sei_client.transport(), rpc_url, &contract_address, token_id)
+            .await
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        let on_chain = uri.starts_with("data:");
+
+        // A metadata fetch failure still returns the URI; the error rides along in the payload
+        let (metadata, metadata_error) = if want_metadata {
+            match nft::fetch_metadata(&client, &uri, gateway).await {
+                Ok(m) => (Some(m), None),
+                Err(e) => (None, Some(e.to_string())),
             }
-        },
-        {
-            "name": "get_nft_metadata",
-            "description": "Get ERC-721 NFT metadata items for a contract from SeiStream.",
-            "inputSchema": {
-                "type": "object",
-                "properties": {
-                    "contract_address": {"type": "string"},
-                    "page": {"type": "number", "description": "Optional page number"}
-                },
-                "required": ["contract_address"],
-                "additionalProperties": false
+        } else {
+            (None, None)
+        };
+        let image = metadata.as_ref().and_then(|m| nft::image_url(m, gateway));
+        let summary = match (&image, &metadata_error) {
+            (Some(img), _) => format!("{} token {} image: {}", standard, token_id, img),
+            (None, Some(e)) => format!("{} token {} URI resolved; metadata unavailable: {}", standard, token_id, e),
+            (None, None) => format!("{} token {} URI: {}", standard, token_id, if on_chain { "on-chain data URI" } else { uri.as_str() }),
+        };
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "standard": standard,
+                    "token_id": token_id.to_string(),
+                    "token_uri": uri,
+                    "resolved_uri": if on_chain { None } else { Some(nft::resolve_uri(&uri, gateway)) },
+                    "on_chain_metadata": on_chain,
+                    "metadata": metadata,
+                    "metadata_error": metadata_error,
+                    "image": image,
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_token_balances(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let invalid = |msg: String| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, msg);
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        let contracts = utils::get_required_arg::<Vec<String>>(args, "token_contracts", req_id)?;
+        if contracts.is_empty() || contracts.len() > MAX_TOKEN_BALANCE_CONTRACTS {
+            return Err(invalid(format!("'token_contracts' must contain 1 to {} entries", MAX_TOKEN_BALANCE_CONTRACTS)));
+        }
+        let holder = Address::from_str(address.trim()).map_err(|_| invalid("Invalid 'address'".into()))?;
+        #[allow(clippy::result_large_err)]
+        let tokens = contracts
+            .iter()
+            .map(|c| Address::from_str(c.trim()).map_err(|_| invalid(format!("Invalid token contract '{}'", c))))
+            .collect::<Result<Vec<_>, _>>()?;
+        let rpc_url = state
+            .config
+            .chain_rpc_urls
+            .get(&chain_id)
+            .ok_or_else(|| invalid(format!("RPC URL not configured for chain_id '{}'", chain_id)))?;
+
+        let multicall = state.config.multicall3_address(&chain_id);
+        let (fetched, batched) = erc20::token_balances(state.sei_client.transport(), rpc_url, multicall, holder, &tokens).await;
+        let mut failed = 0;
+        let balances: Vec<Value> = contracts
+            .iter()
+            .zip(fetched)
+            .map(|(contract, fetched)| {
+                // KNOWN_TOKENS pins symbol and decimals over whatever the contract reports
+                let known = state.config.known_token(&chain_id, contract);
+                let decimals = known.map(|t| t.decimals).or(fetched.decimals);
+                let error = match (fetched.balance, decimals) {
+                    (None, _) => Some("balanceOf() failed; is it an ERC-20?"),
+                    (Some(_), None) => Some("decimals() failed; add the token to KNOWN_TOKENS to format it"),
+                    _ => None,
+                };
+                failed += error.is_some() as usize;
+                json!({
+                    "contract": contract,
+                    "symbol": known.map(|t| t.symbol.clone()).or(fetched.symbol),
+                    "decimals": decimals,
+                    "raw": fetched.balance.map(|b| b.to_string()),
+                    "formatted": fetched.balance.zip(decimals).map(|(b, d)| erc20::format_units(b, d)),
+                    "error": error,
+                })
+            })
+            .collect();
+        let summary = format!(
+            "Fetched {} of {} token balance(s) for {} on {} ({}){}",
+            balances.len() - failed,
+            balances.len(),
+            address,
+            state.config.network_name(&chain_id),
+            if batched { "one multicall" } else { "one call per token" },
+            if failed > 0 { format!("; {} failed", failed) } else { String::new() }
+        );
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "chain_id": chain_id,
+                    "address": address,
+                    "balances": balances,
+                    "failed": failed,
+                    "method": if batched { "multicall3" } else { "sequential" },
+                    "multicall3_address": multicall.filter(|_| batched),
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_token_supply(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let token = utils::get_required_arg::<String>(args, "contract_address", req_id)?;
+        let mut chain = args
+            .get("chain_id")
+            .and_then(|v| v.as_str())
+            .map(normalize_chain_id);
+        if chain.is_none() {
+            chain = infer_evm_chain_from_args(args);
+        }
+        let chain_id = chain.unwrap_or_else(|| "sei-evm-testnet".to_string());
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
+        })?;
+        let excluded: Vec<String> = match args.get("excluded_addresses") {
+            None | Some(Value::Null) => Vec::new(),
+            Some(_) => utils::get_required_arg(args, "excluded_addresses", req_id)?,
+        };
+        #[allow(clippy::result_large_err)]
+        let excluded_addrs = excluded
+            .iter()
+            .map(|a| {
+                Address::from_str(a).map_err(|_| {
+                    Response::error(
+                        req_id.clone(),
+                        error_codes::INVALID_PARAMS,
+                        format!("Invalid excluded address '{}'", a),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let internal = |e: anyhow::Error| {
+            Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
+        };
+        let transport = state.sei_client.transport();
+        let total = erc20::total_supply(transport, rpc_url, &token).await.map_err(internal)?;
+        let known = state.config.known_token(&chain_id, &token);
+        let decimals = match known {
+            Some(t) => t.decimals,
+            None => erc20::decimals(transport, rpc_url, &token).await.map_err(internal)?,
+        };
+
+        let mut excluded_total = U256::zero();
+        let mut excluded_balances = Vec::new();
+        for (raw, addr) in excluded.iter().zip(excluded_addrs) {
+            let bal = erc20::balance_of(transport, rpc_url, &token, addr).await.map_err(internal)?;
+            excluded_total = excluded_total.saturating_add(bal);
+            excluded_balances.push(json!({
+                "address": raw,
+                "balance_raw": bal.to_string(),
+                "balance": erc20::format_units(bal, decimals),
+            }));
+        }
+        let circulating = total.saturating_sub(excluded_total);
+
+        let mut summary = format!(
+            "Total supply of {}: {}",
+            known.map_or(token.as_str(), |t| t.symbol.as_str()),
+            erc20::format_units(total, decimals)
+        );
+        if !excluded.is_empty() {
+            summary.push_str(&format!(
+                "; circulating ≈ {} excluding {} address(es)",
+                erc20::format_units(circulating, decimals),
+                excluded.len()
+            ));
+        }
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "contract_address": token,
+                    "chain_id": chain_id,
+                    "symbol": known.map(|t| &t.symbol),
+                    "decimals": decimals,
+                    "decimals_source": if known.is_some() { "known_tokens" } else { "contract" },
+                    "decimals_warning": known.is_none().then_some("decimals() was reported by the contract itself; add the token to KNOWN_TOKENS to pin it"),
+                    "total_supply_raw": total.to_string(),
+                    "total_supply": erc20::format_units(total, decimals),
+                    "circulating_supply_raw": circulating.to_string(),
+                    "circulating_supply": erc20::format_units(circulating, decimals),
+                    "excluded": excluded_balances,
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_token_stats(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let token = utils::get_required_arg::<String>(args, "contract_address", req_id)?;
+        let chain_id = args
+            .get("chain_id")
+            .and_then(|v| v.as_str())
+            .map(normalize_chain_id)
+            .or_else(|| infer_evm_chain_from_args(args))
+            .unwrap_or_else(|| "sei-evm-testnet".to_string());
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
+        })?;
+        let transport = state.sei_client.transport();
+        let total = erc20::total_supply(transport, rpc_url, &token)
+            .await
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+
+        // Holder and transfer counts only exist in SeiStream's index
+        let indexed = if contract::seistream_indexes(&chain_id) {
+            let listing = state.sei_client.get_contract(&chain_id, &token).await.ok();
+            listing.as_ref().and_then(contract::token_details)
+        } else {
+            None
+        };
+        let known = state.config.known_token(&chain_id, &token);
+        let decimals = match (known, indexed.as_ref().and_then(|t| t.decimals.parse::<u8>().ok())) {
+            (Some(t), _) => Some(t.decimals),
+            (None, Some(d)) => Some(d),
+            (None, None) => erc20::decimals(transport, rpc_url, &token).await.ok(),
+        };
+        let symbol = known.map(|t| t.symbol.clone()).or_else(|| indexed.as_ref().map(|t| t.symbol.clone()));
+        let format = |v: U256| decimals.map(|d| erc20::format_units(v, d));
+        let holders = indexed.as_ref().map(|t| t.holders);
+        let transfers = indexed.as_ref().and_then(|t| t.transfers.parse::<u64>().ok());
+        // An index whose supply disagrees with the chain's is behind; its counts lag too
+        let indexed_supply = indexed.as_ref().and_then(|t| U256::from_dec_str(&t.total_supply).ok());
+        let stale = indexed_supply.is_some_and(|s| s != total);
+        let note = match (&indexed, stale) {
+            (None, _) if !contract::seistream_indexes(&chain_id) => {
+                Some(format!("SeiStream does not index {}; holder and transfer counts are unavailable", chain_id))
             }
-        },
-    ]);
-    Response::success(req.id.clone(), json!({ "tools": tools }))
+            (None, _) => Some("SeiStream has no token record for this contract".to_string()),
+            (Some(_), true) => Some("SeiStream's total supply differs from the chain's; its holder and transfer counts may be stale".to_string()),
+            (Some(_), false) => None,
+        };
+
+        let name = symbol.clone().unwrap_or_else(|| token.clone());
+        let mut summary = format!("{}: supply {}", name, format(total).unwrap_or_else(|| total.to_string()));
+        if let (Some(h), Some(t)) = (holders, transfers) {
+            summary.push_str(&format!(", {} holders, {} transfers", h, t));
+        }
+        if let Some(n) = &note {
+            summary.push_str(&format!(" ({})", n));
+        }
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "contract_address": token,
+                    "chain_id": chain_id,
+                    "name": indexed.as_ref().map(|t| &t.name),
+                    "symbol": symbol,
+                    "decimals": decimals,
+                    "total_supply_raw": total.to_string(),
+                    "total_supply": format(total),
+                    "holders": holders,
+                    "transfers": transfers,
+                    "indexed_total_supply_raw": indexed_supply.map(|s| s.to_string()),
+                    "sources": {
+                        "total_supply": "rpc",
+                        "holders": holders.map(|_| "seistream"),
+                        "transfers": transfers.map(|_| "seistream"),
+                    },
+                    "stale": stale,
+                    "note": note,
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_contract_code(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        let mut chain = args
+            .get("chain_id")
+            .and_then(|v| v.as_str())
+            .map(normalize_chain_id);
+        if chain.is_none() {
+            chain = infer_evm_chain_from_args(args);
+        }
+        let chain_id = chain.unwrap_or_else(|| "sei-evm-testnet".to_string());
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let code = state
+            .sei_client
+            .get_contract_code(&chain_id, &address)
+            .await
+            .map_err(|e| {
+                Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
+            })?;
+        let summary = format!("Contract code for {} on {}", address, chain_id);
+        let pretty = serde_json::to_string_pretty(&code).unwrap_or_else(|_| code.to_string());
+        Ok(Response::success(
+            req_id.clone(),
+            json!({
+                "content": [
+                    { "type": "text", "text": format!("{}\n\n{}", summary, pretty) }
+                ]
+            })
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_contract_abi(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        let mut chain = args
+            .get("chain_id")
+            .and_then(|v| v.as_str())
+            .map(normalize_chain_id);
+        if chain.is_none() {
+            chain = infer_evm_chain_from_args(args);
+        }
+        let chain_id = chain.unwrap_or_else(|| "sei-evm-testnet".to_string());
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let abi = state
+            .sei_client
+            .get_contract_abi(&chain_id, &address)
+            .await
+            .map_err(|e| {
+                Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
+            })?;
+        let Some(abi) = abi else {
+            let summary = format!(
+                "ABI not available for {} on {} (contract is not verified on SeiStream)",
+                address, chain_id
+            );
+            return Ok(Response::success(
+                req_id.clone(),
+                make_texty_result(summary, json!({ "address": address, "chain_id": chain_id, "available": false })),
+            ));
+        };
+        // Human-readable signatures help agents pick a function without reading raw JSON
+        let (functions, events): (Vec<String>, Vec<String>) = match contract::parse_abi(&abi) {
+            Ok(parsed) => (
+                parsed.functions().map(|f| f.signature()).collect(),
+                parsed.events().map(|e| e.abi_signature()).collect(),
+            ),
+            Err(_) => (vec![], vec![]),
+        };
+        let summary = format!(
+            "ABI for {} on {} — {} function(s), {} event(s)\n{}",
+            address,
+            chain_id,
+            functions.len(),
+            events.len(),
+            functions.join("\n")
+        );
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "address": address,
+                    "chain_id": chain_id,
+                    "available": true,
+                    "abi": abi,
+                    "functions": functions,
+                    "events": events
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn call_contract_by_abi(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let contract_address =
+            utils::get_required_arg::<String>(args, "contract_address", req_id)?;
+        let function_name = utils::get_required_arg::<String>(args, "function_name", req_id)?;
+        let call_args: Vec<Value> = match args.get("args") {
+            None | Some(Value::Null) => vec![],
+            Some(_) => utils::get_required_arg::<Vec<Value>>(args, "args", req_id)?,
+        };
+        let private_key = args.get("private_key").and_then(|v| v.as_str());
+        let invalid = |field: &str| {
+            Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                format!("Invalid '{}'", field),
+            )
+        };
+        let contract_addr = Address::from_str(&contract_address).map_err(|_| invalid("contract_address"))?;
+        let value = match args.get("value").and_then(|v| v.as_str()) {
+            Some(v) => Some(U256::from_dec_str(v).map_err(|_| invalid("value"))?),
+            None => None,
+        };
+        // A payable write moves native SEI like any transfer
+        if let (Some(_), Some(v)) = (private_key, value) {
+            enforce_transfer_cap(&state, &chain_id, v, req_id)?;
+        }
+
+        let abi_entries = state
+            .sei_client
+            .get_contract_abi(&chain_id, &contract_address)
+            .await
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?
+            .ok_or_else(|| {
+                Response::error(
+                    req_id.clone(),
+                    error_codes::INVALID_PARAMS,
+                    format!("ABI not available for {} (contract is not verified on SeiStream)", contract_address),
+                )
+            })?;
+        let parsed = contract::parse_abi(&abi_entries)
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+        let function = abi::find_function(&parsed, &function_name, call_args.len())
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string()))?;
+        let data = abi::encode_call(function, &call_args)
+            .map_err(|e| Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string()))?;
+        let signature = function.signature();
+
+        // Reads (and writes without a key) go through eth_call; writes with a key are signed and sent
+        let read_only = abi::is_read_only(function);
+        match private_key {
+            Some(pk) if !read_only => {
+                let value = value.unwrap_or_default();
+                let tx_request = apply_gas_settings(
+                    TransactionRequest::new().to(contract_addr).data(Bytes::from(data)).value(value),
+                    args,
+                    &state,
+                    &chain_id,
+                );
+                let from = LocalWallet::from_str(pk).map(|w| format!("{:?}", w.address())).ok();
+                let reserved = match &from {
+                    Some(from) if !value.is_zero() => reserve_spend(&state, &chain_id, None, from, value, req_id)?,
+                    _ => None,
+                };
+                let response = state
+                    .sei_client
+                    .send_transaction(&chain_id, pk, tx_request, &state.nonce_manager)
+                    .await
+                    .map_err(|e| service_error(req_id, e))?;
+                commit_spend(reserved);
+                let summary = format!("Sent {} to {}: tx {}", signature, contract_address, response.tx_hash);
+                Ok(Response::success(
+                    req_id.clone(),
+                    make_texty_result(
+                        summary,
+                        json!({ "mode": "write", "function": signature, "tx_hash": response.tx_hash }),
+                    ),
+                ))
+            }
+            _ => {
+                let mut call_obj = json!({
+                    "to": contract_address,
+                    "data": format!("0x{}", hex::encode(&data))
+                });
+                if let Some(v) = value {
+                    call_obj["value"] = json!(format!("0x{:x}", v));
+                }
+                let output = state
+                    .sei_client
+                    .eth_call(&chain_id, call_obj, "latest")
+                    .await
+                    .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+                let raw = hex::decode(output.trim_start_matches("0x")).map_err(|_| {
+                    Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, format!("eth_call returned non-hex output '{}'", output))
+                })?;
+                let decoded = abi::decode_output(function, &raw)
+                    .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+                let mode = if read_only { "read" } else { "simulated" };
+                let summary = if read_only {
+                    format!("{} on {} returned {}", signature, contract_address, decoded)
+                } else {
+                    format!(
+                        "Simulated {} on {} (no private_key supplied, nothing sent): {}",
+                        signature, contract_address, decoded
+                    )
+                };
+                Ok(Response::success(
+                    req_id.clone(),
+                    make_texty_result(
+                        summary,
+                        json!({ "mode": mode, "function": signature, "result": decoded, "raw": output }),
+                    ),
+                ))
+            }
+        }
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn deploy_contract(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let private_key = utils::get_required_arg::<String>(args, "private_key", req_id)?;
+        let bytecode = utils::get_required_arg::<String>(args, "bytecode", req_id)?;
+        let invalid = |field: &str| {
+            Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                format!("Invalid '{}'", field),
+            )
+        };
+        let init_code = deploy_init_code(args, &bytecode, req_id)?;
+
+        let value = match args.get("value").and_then(|v| v.as_str()) {
+            Some(v) => U256::from_dec_str(v).map_err(|_| invalid("value"))?,
+            None => U256::zero(),
+        };
+        enforce_transfer_cap(&state, &chain_id, value, req_id)?;
+        let mut tx_request = TransactionRequest::new().data(Bytes::from(init_code)).value(value);
+        // Explicit gas is recommended for large contracts where estimation is unreliable. The
+        // chain's DEFAULT_GAS_LIMITS are sized for transfers, so only the caller's override applies.
+        if let Some(g) = utils::parse_gas_override(args, "gas_limit") {
+            tx_request = tx_request.gas(g);
+        }
+        if let Some(gp) = utils::parse_gas_override(args, "gas_price") {
+            tx_request = tx_request.gas_price(gp);
+        }
+        let from = LocalWallet::from_str(&private_key).map(|w| format!("{:?}", w.address())).ok();
+        let reserved = match &from {
+            Some(from) if !value.is_zero() => reserve_spend(&state, &chain_id, None, from, value, req_id)?,
+            _ => None,
+        };
+
+        let response = state
+            .sei_client
+            .send_transaction(&chain_id, &private_key, tx_request, &state.nonce_manager)
+            .await
+            .map_err(|e| service_error(req_id, e))?;
+        commit_spend(reserved);
+
+        let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| {
+            Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
+        })?;
+        let timeout = std::time::Duration::from_secs(
+            args.get("timeout_secs").and_then(|v| v.as_u64()).unwrap_or(60).clamp(1, 600),
+        );
+        let receipt = rpc::wait_for_receipt(
+            state.sei_client.transport(),
+            rpc_url,
+            &response.tx_hash,
+            poll_settings(args, &state, timeout),
+        )
+        .await
+        .map_err(|e| Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string()))?;
+
+        let Some(receipt) = receipt else {
+            let summary = format!(
+                "Deployment tx {} sent; no receipt within {}s (still pending)",
+                response.tx_hash,
+                timeout.as_secs()
+            );
+            return Ok(Response::success(
+                req_id.clone(),
+                make_texty_result(
+                    summary,
+                    json!({
+                        "tx_hash": response.tx_hash,
+                        "status": "pending",
+                        "explorer_url": state.config.explorer_tx_url(&chain_id, &response.tx_hash),
+                    }),
+                ),
+            ));
+        };
+        let hex_u64 = |v: &Value| {
+            v.as_str().and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok())
+        };
+        let succeeded = hex_u64(&receipt["status"]) == Some(1);
+        let gas_used = hex_u64(&receipt["gasUsed"]);
+        let address = receipt["contractAddress"].as_str().map(str::to_string);
+        let revert = if succeeded {
+            None
+        } else {
+            let parsed = args.get("abi").and_then(Value::as_array).and_then(|e| contract::parse_abi(e).ok());
+            Some(revert_details(state.sei_client.transport(), rpc_url, &response.tx_hash, parsed.as_ref()).await)
+        };
+        let summary = match (&address, succeeded) {
+            (Some(a), true) => format!("Deployed contract at {} (tx {})", a, response.tx_hash),
+            _ => match revert.as_ref().and_then(|r| r["reason"].as_str()) {
+                Some(reason) => format!("Deployment tx {} reverted: {}", response.tx_hash, reason),
+                None => format!("Deployment tx {} reverted", response.tx_hash),
+            },
+        };
+        Ok(Response::success(
+            req_id.clone(),
+            make_texty_result(
+                summary,
+                json!({
+                    "tx_hash": response.tx_hash,
+                    "status": if succeeded { "success" } else { "reverted" },
+                    "contract_address": address,
+                    "gas_used": gas_used,
+                    "value": value.to_string(),
+                    "revert": revert,
+                    "explorer_url": state.config.explorer_tx_url(&chain_id, &response.tx_hash),
+                    "contract_explorer_url": address.as_deref().and_then(|a| state.config.explorer_address_url(&chain_id, a)),
+                }),
+            ),
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn estimate_deployment_cost(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let chain_id = normalize_chain_id(&utils::get_required_arg::<String>(args, "chain_id", req_id)?);
+        let bytecode = utils::get_required_arg::<String>(args, "bytecode", req_id)?;
+        let invalid = |field: &str| {
+            Response::error(
+                req_id.clone(),
+                error_codes::INVALID_PARAMS,
+                format!("Invalid '{}'", field),
+            )
+        };
+        let init_code = deploy_init_code(args, &bytecode, req_id)?;
+        // Constructors that check msg.sender estimate differently per deployer
+        let from = match args.get("from").and_then(|v| v.as_str()) {
+            Some(a) => Address::from_str(a).map_err(|_| invalid("from"))?,
+            None => Address::zero(),
+        };
+        let value = match args.get("value").and_then(|v| v.as_str()) {
+            Some(v) => U256::from_dec_str(v).map_err(|_| invalid("value"))?,
+            None => U256::zero(),
+        };
+        let tx_request = TransactionRequest::new().data(Bytes::from(init_code.clone())).value(value);
+
+        let quote = match state.sei_client.quote_gas(&chain_id, from, tx_request).await {
+            Ok(quote) => quote,
+            Err(e) => {
+                // Estimation errors rarely carry a readable reason; replaying the creation
+                // as an eth_call gets the revert data to decode
+                let rpc_url = state.sei_client.get_rpc_url(&chain_id).map_err(|e| {
+                    Response::error(req_id.clone(), error_codes::INVALID_PARAMS, e.to_string())
+                })?;
+                let call = json!({
+                    "from": format!("{:?}", from),
+                    "data": format!("0x{}", hex::encode(&init_code)),
+                    "value": format!("0x{:x}", value),
+                });
+                let Ok(Some(err)) = rpc::call_error(state.sei_client.transport(), rpc_url, call, "latest").await else {
+                    return Err(service_error(req_id, e));
+                };
+                let parsed = args.get("abi").and_then(Value::as_array).and_then(|e| contract::parse_abi(e).ok());
+                let revert = describe_revert(&err, parsed.as_ref());
+                let reason = revert["reason"].as_str().unwrap_or("no reason given").to_string();
+                return Err(Response::error_with_data(
+                    req_id.clone(),
+                    error_codes::RPC_ERROR,
+                    format!("Deployment would revert: {}", reason),
+                    json!({ "revert": revert }),
+                ));
+            }
+        };
+
+        let fee = quote.max_fee();
+        let fee_sei = erc20::format_units(fee, 18);
+        let summary = format!(
+            "Deploying on {} costs about {} SEI ({} gas at {} wei)",
+            state.config.network_name(&chain_id),
+            fee_sei,
+            quote.gas_limit,
+            quote.max_fee_per_gas
+        );
+        let payload = json!({
+            "chain_id": chain_id,
+            "gas_limit": quote.gas_limit.to_string(),
+            "gas_price": quote.max_fee_per_gas.to_string(),
+            "max_priority_fee_per_gas": quote.max_priority_fee_per_gas.map(|t| t.to_string()),
+            "fee": { "base": fee.to_string(), "unit": "wei", "sei": fee_sei },
+            "init_code_bytes": init_code.len(),
+        });
+        Ok(Response::success(req_id.clone(), make_texty_result(summary, payload)))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+async fn get_contract_transactions(args: &Value, req_id: &Value, state: AppState) -> Response {
+    let res: Result<Response, Response> = (async {
+        let address = utils::get_required_arg::<String>(args, "address", req_id)?;
+        let mut chain = args
+            .get("chain_id")
+            .and_then(|v| v.as_str())
+            .map(normalize_chain_id);
+        if chain.is_none() {
+            chain = infer_evm_chain_from_args(args);
+        }
+        let chain_id = chain.unwrap_or_else(|| "sei-evm-testnet".to_string());
+        require_exposed_chain(&state, &chain_id, req_id)?;
+        let max_items = max_items_arg(args, &state);
+        let (page, page_size) = utils::page_args(args, max_items as u64);
+        let txs = state
+            .sei_client
+            .get_contract_transactions(&chain_id, &address, Some(page), Some(page_size))
+            .await
+            .map_err(|e| {
+                Response::error(req_id.clone(), error_codes::INTERNAL_ERROR, e.to_string())
+            })?;
+        let pagination = crate::blockchain::services::seistream::listing_pagination(&txs, page, page_size);
+        let next_page = pagination.next_page;
+        let mut txs = txs;
+        let clipped = txs.get_mut("items").and_then(|v| utils::truncate_array(v, max_items));
+        let count = txs.get("items").and_then(|v| v.as_array()).map(|a| a.len()).unwrap_or(0);
+        let mut summary = format!("{} tx(s) for {} on {} (page {})", count, address, chain_id, page);
+        if let Some(total) = clipped {
+            summary.push_str(&format!(" (truncated from {})", total));
+        }
+        if let Some(n) = next_page {
+            summary.push_str(&format!("; more on page {}", n));
+        }
+        let pretty = serde_json::to_string_pretty(&txs).unwrap_or_else(|_| txs.to_string());
+        Ok(Response::success(
+            req_id.clone(),
+            json!({
+                "page": page,
+                "page_size": page_size,
+                "next_page": next_page,
+                "pagination": pagination,
+                "truncated": clipped.is_some(),
+                "total_available": listing_total(&pagination, clipped, count),
+                "content": [
+                    { "type": "text", "text": format!("{}\n\n{}", summary, pretty) }
+                ]
+            })
+        ))
+    })
+    .await;
+    res.unwrap_or_else(|err_resp| err_resp)
+}
+
+/// Handles the 'initialize' request.
+/// Server metadata for `initialize`. MCP_INSTRUCTIONS and MCP_CAPABILITIES override the defaults;
+/// the chains reachable over MCP are appended to the instructions either way.
+fn handle_initialize(req: &Request, state: &AppState) -> Response {
+    let server_info = json!({
+        "name": "sei-mcp-server-rs",
+        "version": "0.2.0-fixed"
+    });
+    let mut capabilities = json!({ "tools": { "listChanged": false } });
+    for (key, value) in &state.config.mcp_capabilities {
+        capabilities[key] = value.clone();
+    }
+    let mut instructions = state.config.mcp_instructions.clone().unwrap_or_else(|| {
+        "Sei EVM blockchain MCP server for secure wallet operations, balance queries, and transaction management. \
+         Some tools can also be called as a JSON-RPC method of the same name; see the list_methods tool."
+            .to_string()
+    });
+    let mut chains: Vec<&String> = state.config.chain_rpc_urls.keys().filter(|c| chain_exposed(state, c)).collect();
+    chains.sort();
+    if !chains.is_empty() {
+        let listed: Vec<String> = chains.iter().map(|c| format!("{} ({})", c, state.config.network_name(c))).collect();
+        instructions.push_str(&format!("\n\nConfigured chains: {}.", listed.join(", ")));
+    }
+
+    Response::success(
+        req.response_id(),
+        json!({
+            "serverInfo": server_info,
+            "protocolVersion": "2025-06-18",
+            "capabilities": capabilities,
+            "instructions": instructions
+        }),
+    )
+}
+
+/// Handles the 'tools/list' request by returning a JSON definition of every enabled tool
+/// (ENABLED_TOOLS/DISABLED_TOOLS). With MCP_EXPOSED_CHAINS set, every `chain_id` parameter is
+/// narrowed to an enum of the exposed chains.
+fn handle_tools_list(req: &Request, state: &AppState) -> Response {
+    let exposed = &state.config.mcp_exposed_chains;
+    let chains: Vec<String> = exposed.iter().map(|c| normalize_chain_id(c)).collect();
+    let tools: Vec<Value> = tool_registry()
+        .iter()
+        .filter(|tool| state.config.tool_enabled(tool.name))
+        .map(|tool| {
+            let mut tool = tool.definition.clone();
+            if let Some(chain_id) = tool.pointer_mut("/inputSchema/properties/chain_id").filter(|_| !chains.is_empty()) {
+                chain_id["enum"] = json!(chains);
+            }
+            tool
+        })
+        .collect();
+    Response::success(req.response_id(), json!({ "tools": tools }))
+}
+
+fn is_direct_method(name: &str) -> bool {
+    DIRECT_METHOD_TOOLS.contains(&name) && tool_registry().iter().any(|tool| tool.name == name)
+}
+
+/// Runs one tool against its arguments, answering the request `req_id`.
+pub type ToolHandler = for<'a> fn(&'a Value, &'a Value, AppState) -> BoxFuture<'a, Response>;
+
+/// A tool: the entry `tools/list` shows for it and the function `tools/call` runs.
+pub struct ToolDef {
+    pub name: &'static str,
+    /// Name, description and input schema
+    pub definition: Value,
+    pub handler: ToolHandler,
+}
+
+// Registers the async fn `$handler` as the tool of the same name, described by `$definition`.
+macro_rules! tool {
+    ($handler:ident, $definition:tt) => {{
+        let mut definition = json!($definition);
+        definition["name"] = json!(stringify!($handler));
+        ToolDef { name: stringify!($handler), definition, handler: |args, req_id, state| Box::pin($handler(args, req_id, state)) }
+    }};
+}
+
+/// Every tool the server offers; the single source of truth for `tools/list`, `tools/call`, direct
+/// method calls and ENABLED_TOOLS/DISABLED_TOOLS.
+pub fn tool_registry() -> &'static [ToolDef] {
+    static TOOLS: OnceLock<Vec<ToolDef>> = OnceLock::new();
+    TOOLS.get_or_init(|| {
+        let mut tools = vec![
+            tool!(redirect_to_seidocs, {
+                "description": "Return the Sei documentation URL (https://docs.sei.io/).",
+                "inputSchema": { "type": "object", "properties": {}, "additionalProperties": false }
+            }),
+            tool!(get_balance, {
+                "description": "Get the balance of an address on a specific Sei chain. Native chains return every bank denom held.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "The blockchain chain ID (e.g., 'sei-testnet')"},
+                        "address": {"type": "string", "description": "The 0x... EVM or sei1... native address to check."},
+                        "denom": {"type": "string", "description": "Native chains only: a single bank denom to query; omit to list all denoms."}
+                    },
+                    "required": ["chain_id", "address"]
+                }
+            }),
+            tool!(wait_for_balance_change, {
+                "description": "Poll an address balance until it changes by at least min_delta or the timeout elapses. Useful while waiting on a faucet drip or bridge deposit.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string"},
+                        "address": {"type": "string"},
+                        "min_delta": {"type": "string", "description": "Minimum change in the smallest unit (wei/usei). Defaults to any change."},
+                        "timeout_secs": {"type": "number", "description": "Maximum wait in seconds (default 60, max 600)."},
+                        "poll_interval_ms": {"type": "integer", "description": "Delay between checks in milliseconds (default POLL_INTERVAL_MS, 2000)."},
+                        "max_poll_attempts": {"type": "integer", "description": "Stop after this many checks even before the timeout (default MAX_POLL_ATTEMPTS; 0 = no limit)."}
+                    },
+                    "required": ["chain_id", "address"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(create_wallet, {
+                "description": "Create a new EVM wallet. Returns address, private key, and mnemonic.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "word_count": {"type": "integer", "enum": [12, 24], "description": "Mnemonic length (default 24)."},
+                        "passphrase": {"type": "string", "description": "Optional BIP-39 passphrase (\"25th word\"). Needed for every future import; it cannot be recovered."}
+                    },
+                    "additionalProperties": false
+                }
+            }),
+            tool!(generate_vanity_address, {
+                "description": "Generate a new EVM wallet whose address starts with a hex prefix (up to 8 characters; each extra character is ~16x slower). The search is capped by attempts and time and reports when nothing matched. Returns the private key.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "prefix": {"type": "string", "description": "Hex prefix to match after 0x, e.g. 'cafe'."},
+                        "case_sensitive": {"type": "boolean", "description": "Match the EIP-55 checksum capitalization (default false)."},
+                        "max_attempts": {"type": "number", "description": "Keys to try before giving up (default and max 5000000)."},
+                        "max_seconds": {"type": "number", "description": "Time limit in seconds (default 30, max 60)."}
+                    },
+                    "required": ["prefix"]
+                }
+            }),
+            tool!(create_and_register_wallet, {
+                "description": "Create a new EVM wallet and store it encrypted under wallet_name. Returns only the address; the private key never leaves the server.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "wallet_name": {"type": "string", "description": "A unique name for the new wallet."},
+                        "master_password": {"type": "string", "description": "The master password for the wallet storage."}
+                    },
+                    "required": ["wallet_name", "master_password"]
+                }
+            }),
+            tool!(sign_typed_data, {
+                "description": "Sign EIP-712 typed data (e.g. an ERC-2612 Permit or an exchange order) with a private key. Nothing is sent on-chain. Returns the 65-byte signature, its r/s/v parts and the signed digest.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "private_key": {"type": "string", "description": "The signer's private key (hex)."},
+                        "typed_data": {"type": ["object", "string"], "description": "EIP-712 payload with domain, types, primaryType and message, as an object or a JSON string."}
+                    },
+                    "required": ["private_key", "typed_data"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(broadcast_raw_transaction, {
+                "description": "Broadcast an already-signed EVM transaction (eth_sendRawTransaction). A rejection comes back as an error whose data.rejection gives a category (nonce_too_low, insufficient_funds, already_known, underpriced, intrinsic_gas_too_low, other) and a remediation hint. The value sent is checked against the transfer cap and spending budget before broadcasting.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string"},
+                        "raw_transaction": {"type": "string", "description": "0x-prefixed signed transaction bytes (legacy RLP or typed envelope)."}
+                    },
+                    "required": ["chain_id", "raw_transaction"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(compute_tx_hash, {
+                "description": "Decode a signed raw EVM transaction (legacy, EIP-2930 or EIP-1559) offline: returns its hash, the recovered sender and its fields, without broadcasting. Pass expected_from to check the signer.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "raw_transaction": {"type": "string", "description": "0x-prefixed signed transaction bytes, as passed to eth_sendRawTransaction."},
+                        "expected_from": {"type": "string", "description": "Optional address the transaction should be signed by; reported as from_matches."}
+                    },
+                    "required": ["raw_transaction"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(sign_permit, {
+                "description": "Sign an ERC-2612 permit letting 'spender' pull 'value' of a token from the signer, for a relayer to submit (gasless approval). Reads the token's name, version, nonces(owner) and the chain id on-chain, and fails if the token has no DOMAIN_SEPARATOR. Nothing is sent on-chain.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "private_key": {"type": "string", "description": "The token owner's private key (hex)."},
+                        "chain_id": {"type": "string", "description": "EVM chain id."},
+                        "contract_address": {"type": "string", "description": "The ERC-20 token."},
+                        "spender": {"type": "string", "description": "Address allowed to spend."},
+                        "value": {"type": "string", "description": "Allowance in the token's base units."},
+                        "deadline": {"type": "number", "description": "Unix timestamp after which the permit is invalid (default: one hour from now)."},
+                        "version": {"type": "string", "description": "EIP-712 domain version, if the token has no version() getter and it isn't \"1\"."}
+                    },
+                    "required": ["private_key", "chain_id", "contract_address", "spender", "value"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(import_wallet, {
+                "description": "Import an EVM wallet from a mnemonic phrase or private key.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "mnemonic_or_private_key": {"type": "string", "description": "The mnemonic phrase or private key to import."},
+                        "passphrase": {"type": "string", "description": "BIP-39 passphrase the mnemonic was created with, if any."}
+                    },
+                    "required": ["mnemonic_or_private_key"]
+                }
+            }),
+            tool!(search_events, {
+                "description": "Search EVM logs via eth_getLogs. For native events, not yet implemented.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string"},
+                        "contract_address": {"type": "string"},
+                        "topic0": {"type": "string", "description": "Keccak topic0 (event signature hash)"},
+                        "from_block": {"type": "string", "description": "hex block tag like '0x1' or 'earliest'"},
+                        "to_block": {"type": "string", "description": "hex block tag like 'latest'"},
+                        "max_items": {"type": "number", "description": "Maximum items to return (capped by MCP_MAX_ITEMS)"}
+                    },
+                    "required": ["chain_id", "contract_address"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_decoded_events, {
+                "description": "Fetch a contract's logs over a block range and decode them with its ABI into {event_name, signature, args}. The ABI comes from SeiStream unless passed in; logs that match no ABI event are returned raw.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "contract_address": {"type": "string"},
+                        "chain_id": {"type": "string", "description": "EVM chain id (defaults to sei-evm-testnet)."},
+                        "from_block": {"type": "string", "description": "Block tag or number (decimal or 0x hex). Default latest."},
+                        "to_block": {"type": "string", "description": "Block tag or number (decimal or 0x hex). Default latest."},
+                        "event_name": {"type": "string", "description": "Only fetch this event (filters on its topic0)."},
+                        "abi": {"type": ["array", "string"], "description": "ABI JSON to use instead of fetching it (for unverified contracts)."},
+                        "max_items": {"type": "number", "description": "Maximum items to return (capped by MCP_MAX_ITEMS)"}
+                    },
+                    "required": ["contract_address"]
+                }
+            }),
+            tool!(get_events_since, {
+                "description": "Follow a contract's events by polling: returns logs after last_seen_block up to the latest block, plus next_cursor to pass as last_seen_block next time. Empty when nothing is new. If has_more is true, call again straight away.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string"},
+                        "contract_address": {"type": "string"},
+                        "last_seen_block": {"type": "integer", "description": "Last block already processed; events from the block after it are returned."},
+                        "topic0": {"type": "string", "description": "Only events with this topic0 (event signature hash)."},
+                        "max_items": {"type": "number", "description": "Maximum events per call (capped by MCP_MAX_ITEMS); the cursor stops at a block boundary."}
+                    },
+                    "required": ["chain_id", "contract_address", "last_seen_block"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_block_by_timestamp, {
+                "description": "Find the EVM block closest to a Unix timestamp (binary search over eth_getBlockByNumber). Use the result as from_block/to_block in search_events.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string"},
+                        "timestamp": {"type": "number", "description": "Unix time in seconds."}
+                    },
+                    "required": ["chain_id", "timestamp"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_recent_blocks, {
+                "description": "Summarize the latest EVM blocks, newest first: number, timestamp, tx count and gas used / gas limit, plus averages (block time, txs per block, fullness). A quick view of chain activity.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string"},
+                        "count": {"type": "integer", "description": "How many blocks (default 10, capped by MAX_RECENT_BLOCKS)."}
+                    },
+                    "required": ["chain_id"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(faucet_daily_remaining, {
+                "description": "How much the faucet can still dispense today (UTC) under FAUCET_DAILY_CAPS, per chain, with the time the counter resets.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "Limit to one chain (default: every chain with a cap)."}
+                    },
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_spending_status, {
+                "description": "How much of its SPENDING_BUDGETS allowance a sender has left on a chain, and when the current window resets. transfer_evm, transfer_from_wallet and sweep_wallet refuse transfers beyond it.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "EVM chain id."},
+                        "address": {"type": "string", "description": "Sending 0x address; pass this or wallet_name"},
+                        "wallet_name": {"type": "string", "description": "Stored wallet; pass this or address"}
+                    },
+                    "required": ["chain_id"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_faucet_status, {
+                "description": "Show the faucet's sending address and current balance per chain, and whether it is below the FAUCET_LOW_BALANCE threshold. Check this before requesting if drips are failing. Never returns keys.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "Limit to one chain (default: every configured chain)."}
+                    }
+                }
+            }),
+            tool!(batch_query, {
+                "description": "Run up to 25 read-only tool calls concurrently in one request and get their results back in order. Only read tools (balances, token info, contract reads, blocks, transactions, events) are accepted; a batch naming any state-changing tool is refused. Each entry reports its own ok/result or error, so one failure doesn't abort the rest.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "queries": {
+                            "type": "array",
+                            "minItems": 1,
+                            "maxItems": 25,
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "tool": {"type": "string", "description": "Read-only tool name, e.g. get_balance or get_token_supply."},
+                                    "arguments": {"type": "object", "description": "That tool's arguments."}
+                                },
+                                "required": ["tool"]
+                            }
+                        }
+                    },
+                    "required": ["queries"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_balances_multi, {
+                "description": "Fetch balances for up to 100 addresses on one chain concurrently. Returns address -> {amount, denom}; an address that fails gets {error} without failing the batch.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string"},
+                        "addresses": {"type": "array", "items": {"type": "string"}, "minItems": 1, "maxItems": 100}
+                    },
+                    "required": ["chain_id", "addresses"]
+                }
+            }),
+            tool!(request_faucet, {
+                "description": "Request testnet tokens from the faucet for an EVM address. Refused once the chain's FAUCET_DAILY_CAPS total is dispensed for the UTC day. If FAUCET_TOKENS is set and the token transfer fails after the native drip, the result has partial: true, the native transaction_hash and token_error.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "Target chain id configured in CHAIN_RPC_URLS."},
+                        "address": {"type": "string", "description": "The EVM (0x...) address to receive tokens."},
+                        "amount": {"type": "string", "description": "Optional amount in usei; capped at the configured per-chain maximum."},
+                        "verify": {"type": "boolean", "description": "Wait for the receipt and confirm the recipient's balance increased (default false)."},
+                        "verify_timeout_secs": {"type": "integer", "description": "How long verification may wait (default 60, max 600)."},
+                        "poll_interval_ms": {"type": "integer", "description": "Delay between checks in milliseconds (default POLL_INTERVAL_MS, 2000)."},
+                        "max_poll_attempts": {"type": "integer", "description": "Stop after this many checks even before the timeout (default MAX_POLL_ATTEMPTS; 0 = no limit)."}
+                    },
+                    "required": ["chain_id", "address"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(register_wallet, {
+                "description": "Encrypt and securely store a private key under a wallet name.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "wallet_name": {"type": "string", "description": "A unique name for the wallet (e.g., 'my-primary-wallet')."},
+                        "private_key": {"type": "string", "description": "The private key to encrypt and store."},
+                        "master_password": {"type": "string", "description": "The master password to encrypt the wallet. This password will be required for any future actions with this wallet."}
+                    },
+                    "required": ["wallet_name", "private_key", "master_password"]
+                }
+            }),
+            tool!(list_wallets, {
+                "description": "List the wallets in the secure storage: their names, plus details with each wallet's address, label, tags and note.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                         "master_password": {"type": "string", "description": "The master password for the wallet storage."},
+                         "session_token": {"type": "string", "description": "Token from unlock_storage, used instead of master_password."},
+                         "filter_by_tag": {"type": "string", "description": "Only list wallets carrying this tag (case-insensitive)."},
+                         "page": {"type": "number", "description": "1-based page number (default 1)"},
+                         "page_size": {"type": "number", "description": "Wallets per page (default all; capped by MCP_MAX_ITEMS)"}
+                    }
+                }
+            }),
+            tool!(set_wallet_metadata, {
+                "description": "Set a stored wallet's label, tags (e.g. hot, cold, testing) and note. Omitted fields are kept, an empty label or note clears it, and tags replaces the whole set. Metadata is stored unencrypted; don't put secrets in it.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "wallet_name": {"type": "string"},
+                        "label": {"type": "string"},
+                        "tags": {"type": "array", "items": {"type": "string"}},
+                        "note": {"type": "string"},
+                        "master_password": {"type": "string", "description": "The master password for the wallet storage."},
+                        "session_token": {"type": "string", "description": "Token from unlock_storage, used instead of master_password."}
+                    },
+                    "required": ["wallet_name"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(remove_wallet, {
+                "description": "Delete a wallet from the secure storage.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "wallet_name": {"type": "string", "description": "The name of the wallet to remove."},
+                        "master_password": {"type": "string", "description": "The master password for the wallet storage."},
+                        "session_token": {"type": "string", "description": "Token from unlock_storage, used instead of master_password."}
+                    },
+                    "required": ["wallet_name"]
+                }
+            }),
+            tool!(transfer_from_wallet, {
+                "description": "Transfer tokens from a securely stored wallet.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "wallet_name": {"type": "string", "description": "The name of the stored wallet to transfer from."},
+                        "chain_id": {"type": "string", "description": "The blockchain chain ID (e.g., 'sei-testnet')."},
+                        "to_address": {"type": "string", "description": "The recipient's 0x... EVM address, or a contact name from add_contact."},
+                        "amount": {"type": "string", "description": "The amount to transfer in the smallest unit (e.g., usei)."},
+                        "confirm_amount": {"type": "boolean", "description": "Send an amount under 1000 base units even when STRICT_AMOUNTS would reject it as a likely whole-SEI figure."},
+                        "master_password": {"type": "string", "description": "The master password to unlock the wallet for this transaction."},
+                        "session_token": {"type": "string", "description": "Token from unlock_storage, used instead of master_password."},
+                        "gas_limit": {"type": "string"},
+                        "gas_price": {"type": "string"},
+                        "auto_bump": {"type": "boolean", "description": "On an 'underpriced' rejection, retry with fees raised by GAS_BUMP_PERCENT, up to MAX_GAS_BUMPS times (default false)."},
+                        "max_priority_fee_per_gas": {"type": "string", "description": "EIP-1559 tip in wei; the max fee is then twice the latest base fee plus this tip. Ignored for legacy sends and with gas_price."}
+                    },
+                    "required": ["wallet_name", "chain_id", "to_address", "amount"]
+                }
+            }),
+            tool!(sweep_wallet, {
+                "description": "Send an EVM account's entire native balance to to_address, less the exact gas fee, leaving it at zero. Takes a private_key or a stored wallet_name (with master_password or session_token; multisig wallets and allowlists apply). Gas is estimated and the price pinned so the fee is exactly gas_limit × gas_price; a balance that cannot cover it is refused.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "EVM chain id."},
+                        "to_address": {"type": "string", "description": "Destination 0x address, or a contact name from add_contact."},
+                        "private_key": {"type": "string", "description": "Key of the account to empty; pass this or wallet_name."},
+                        "wallet_name": {"type": "string", "description": "Stored wallet to empty; pass this or private_key."},
+                        "master_password": {"type": "string"},
+                        "session_token": {"type": "string", "description": "Token from unlock_storage, used instead of master_password."},
+                        "gas_limit": {"type": "string"},
+                        "gas_price": {"type": "string"}
+                    },
+                    "required": ["chain_id", "to_address"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(set_wallet_multisig, {
+                "description": "Put a stored wallet under M-of-N control. Each authorizer has a name and their own confirmation code; transfers then go through propose_transfer/approve_proposal and transfer_from_wallet is refused. An empty authorizers list removes the requirement.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "wallet_name": {"type": "string"},
+                        "threshold": {"type": "number", "description": "Approvals needed per transfer (default: all authorizers)."},
+                        "authorizers": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "name": {"type": "string"},
+                                    "confirmation_code": {"type": "string", "description": "Secret this authorizer gives to approve_proposal. Stored hashed."}
+                                },
+                                "required": ["name", "confirmation_code"]
+                            }
+                        },
+                        "master_password": {"type": "string"},
+                        "session_token": {"type": "string", "description": "Token from unlock_storage, used instead of master_password."}
+                    },
+                    "required": ["wallet_name", "authorizers"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(propose_transfer, {
+                "description": "Open a transfer from a multisig wallet. Nothing is sent until enough authorizers approve it with approve_proposal; the proposal expires after PROPOSAL_TTL_SECS.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "wallet_name": {"type": "string"},
+                        "chain_id": {"type": "string", "description": "EVM chain id."},
+                        "to_address": {"type": "string", "description": "The recipient's 0x... EVM address, or a contact name from add_contact."},
+                        "amount": {"type": "string", "description": "Amount in wei."},
+                        "confirm_amount": {"type": "boolean", "description": "Accept an amount under 1000 base units even when STRICT_AMOUNTS would reject it."},
+                        "master_password": {"type": "string"},
+                        "session_token": {"type": "string", "description": "Token from unlock_storage, used instead of master_password."}
+                    },
+                    "required": ["wallet_name", "chain_id", "to_address", "amount"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(approve_proposal, {
+                "description": "Approve a pending transfer proposal as one authorizer. The approval that meets the threshold sends the transfer and returns its tx_hash.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "proposal_id": {"type": "string"},
+                        "authorizer": {"type": "string", "description": "Authorizer name from set_wallet_multisig."},
+                        "confirmation_code": {"type": "string", "description": "That authorizer's confirmation code."},
+                        "master_password": {"type": "string", "description": "Unlocks the wallet key if this approval sends the transfer."},
+                        "session_token": {"type": "string", "description": "Token from unlock_storage, used instead of master_password."},
+                        "gas_limit": {"type": "string"},
+                        "gas_price": {"type": "string"},
+                        "auto_bump": {"type": "boolean", "description": "On an 'underpriced' rejection, retry with fees raised by GAS_BUMP_PERCENT, up to MAX_GAS_BUMPS times (default false)."},
+                        "max_priority_fee_per_gas": {"type": "string", "description": "EIP-1559 tip in wei; the max fee is then twice the latest base fee plus this tip. Ignored for legacy sends and with gas_price."}
+                    },
+                    "required": ["proposal_id", "authorizer", "confirmation_code"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_proposal_status, {
+                "description": "Show a transfer proposal: status (pending, approved, executed, failed, expired), who has approved, the threshold, expiry and the tx_hash once sent.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "proposal_id": {"type": "string"}
+                    },
+                    "required": ["proposal_id"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(add_contact, {
+                "description": "Save a named recipient in the address book (stored unencrypted beside the wallet storage; no master password needed). Transfer tools then accept the name as 'to_address'. Names are unique regardless of case and may not look like an address.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"},
+                        "address": {"type": "string", "description": "0x address on EVM chains, bech32 (sei...) on native chains."},
+                        "chain_id": {"type": "string", "description": "Chain the contact is paid on; transfers on other chains refuse the name."},
+                        "note": {"type": "string"},
+                        "replace": {"type": "boolean", "description": "Overwrite an existing contact with this name (default false)."}
+                    },
+                    "required": ["name", "address", "chain_id"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(list_contacts, {
+                "description": "List the address book, sorted by name.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "Only contacts saved for this chain."},
+                        "page": {"type": "number", "description": "1-based page number (default 1)"},
+                        "page_size": {"type": "number", "description": "Contacts per page (default all; capped by MCP_MAX_ITEMS)"}
+                    },
+                    "additionalProperties": false
+                }
+            }),
+            tool!(remove_contact, {
+                "description": "Delete a contact from the address book.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"}
+                    },
+                    "required": ["name"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(add_allowed_recipient, {
+                "description": "Restrict a stored wallet to paying only allowlisted recipients. The first entry turns the allowlist on.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "wallet_name": {"type": "string"},
+                        "address": {"type": "string", "description": "Recipient address to allow."},
+                        "master_password": {"type": "string"}
+                    },
+                    "required": ["wallet_name", "address", "master_password"]
+                }
+            }),
+            tool!(remove_allowed_recipient, {
+                "description": "Remove a recipient from a stored wallet's allowlist. An empty allowlist blocks all transfers.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "wallet_name": {"type": "string"},
+                        "address": {"type": "string"},
+                        "master_password": {"type": "string"}
+                    },
+                    "required": ["wallet_name", "address", "master_password"]
+                }
+            }),
+            tool!(unlock_storage, {
+                "description": "Verify the master password once and return a session token accepted by list_wallets and transfer_from_wallet until it idles out.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "master_password": {"type": "string", "description": "The master password for the wallet storage."}
+                    },
+                    "required": ["master_password"]
+                }
+            }),
+            tool!(lock_storage, {
+                "description": "Revoke a session token issued by unlock_storage.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "session_token": {"type": "string"}
+                    },
+                    "required": ["session_token"]
+                }
+            }),
+            tool!(transfer_evm, {
+                "description": "Send an EVM value transfer using a provided private key.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "private_key": {"type": "string"},
+                        "chain_id": {"type": "string"},
+                        "to_address": {"type": "string", "description": "Recipient address, or a contact name from add_contact."},
+                        "amount_wei": {"type": "string"},
+                        "confirm_amount": {"type": "boolean", "description": "Send an amount under 1000 base units even when STRICT_AMOUNTS would reject it as a likely whole-SEI figure."},
+                        "data": {"type": "string", "description": "Optional hex calldata to attach to the value transfer, e.g. a tag."},
+                        "gas_limit": {"type": "string"},
+                        "gas_price": {"type": "string"},
+                        "auto_bump": {"type": "boolean", "description": "On an 'underpriced' rejection, retry with fees raised by GAS_BUMP_PERCENT, up to MAX_GAS_BUMPS times (default false)."},
+                        "max_priority_fee_per_gas": {"type": "string", "description": "EIP-1559 tip in wei; the max fee is then twice the latest base fee plus this tip. Ignored for legacy sends and with gas_price."}
+                    },
+                    "required": ["private_key", "chain_id", "to_address", "amount_wei"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(transfer_sei, {
+                "description": "Send a native SEI (Cosmos) bank transfer using a provided private key.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "private_key": {"type": "string", "description": "0x-hex Cosmos secp256k1 private key"},
+                        "chain_id": {"type": "string"},
+                        "to_address": {"type": "string", "description": "Bech32 address (sei...), or a contact name from add_contact"},
+                        "amount_usei": {"type": "string"},
+                        "confirm_amount": {"type": "boolean", "description": "Send an amount under 1000 base units even when STRICT_AMOUNTS would reject it as a likely whole-SEI figure."},
+                        "memo": {"type": "string", "description": "Optional transaction memo (up to the chain's max_memo_characters, 256 bytes by default); echoed back in the result."}
+                    },
+                    "required": ["private_key", "chain_id", "to_address", "amount_usei"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(transfer_sei_with_feegrant, {
+                "description": "Send a native SEI (Cosmos) bank transfer whose fee is paid by another account through an existing fee grant (fee.granter). The sender signs; the granter must have granted the sender a fee allowance, e.g. with grant_fee_allowance.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "private_key": {"type": "string", "description": "0x-hex Cosmos secp256k1 private key of the sender"},
+                        "chain_id": {"type": "string"},
+                        "to_address": {"type": "string", "description": "Bech32 address (sei...), or a contact name from add_contact"},
+                        "amount_usei": {"type": "string"},
+                        "confirm_amount": {"type": "boolean", "description": "Send an amount under 1000 base units even when STRICT_AMOUNTS would reject it as a likely whole-SEI figure."},
+                        "memo": {"type": "string", "description": "Optional transaction memo (up to the chain's max_memo_characters, 256 bytes by default); echoed back in the result."},
+                        "granter": {"type": "string", "description": "Bech32 address of the account paying the fee"}
+                    },
+                    "required": ["private_key", "chain_id", "to_address", "amount_usei", "granter"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(grant_fee_allowance, {
+                "description": "Let another native account pay transaction fees from this key's balance (a feegrant BasicAllowance), optionally capped and with an expiry. The grantee then sends with transfer_sei_with_feegrant.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "private_key": {"type": "string", "description": "0x-hex Cosmos secp256k1 private key of the granter (who pays the fees)"},
+                        "chain_id": {"type": "string"},
+                        "grantee": {"type": "string", "description": "Bech32 address allowed to spend the allowance on fees"},
+                        "spend_limit_usei": {"type": "string", "description": "Total fees the grantee may spend, in usei (default unlimited)"},
+                        "expiration": {"type": "string", "description": "RFC 3339 time the allowance ends (default never)"}
+                    },
+                    "required": ["private_key", "chain_id", "grantee"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(preview_transfer, {
+                "description": "Dry run of transfer_evm / transfer_sei with the same arguments. Returns the resolved sender and recipient, the amount in SEI and base units, the estimated fee (an upper bound) and, when USD_PRICES covers the chain, USD figures. Nothing is signed or sent.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "private_key": {"type": "string", "description": "Sender key; only used to derive the from address"},
+                        "chain_id": {"type": "string"},
+                        "to_address": {"type": "string", "description": "0x address on EVM chains, bech32 (sei...) on native chains, or a contact name from add_contact"},
+                        "amount_wei": {"type": "string", "description": "Amount for EVM chains"},
+                        "amount_usei": {"type": "string", "description": "Amount for native chains"},
+                        "confirm_amount": {"type": "boolean", "description": "Send an amount under 1000 base units even when STRICT_AMOUNTS would reject it as a likely whole-SEI figure."},
+                        "gas_limit": {"type": "string"},
+                        "gas_price": {"type": "string"}
+                    },
+                    "required": ["private_key", "chain_id", "to_address"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(quote_fee, {
+                "description": "How much a transaction will cost before sending it: the fee upper bound in base units, SEI and, when USD_PRICES covers the chain, USD. On EVM chains pass the transaction (to_address with optional value, data and from) to have gas estimated, or just a gas_limit; native chains quote the configured flat fee.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string"},
+                        "to_address": {"type": "string", "description": "Recipient or contract, a 0x address or a contact name"},
+                        "value": {"type": "string", "description": "Wei sent along (default 0)"},
+                        "data": {"type": "string", "description": "Hex calldata"},
+                        "from": {"type": "string", "description": "Sender to estimate as (default the zero address); matters for calls that check balances or msg.sender"},
+                        "gas_limit": {"type": "string", "description": "Known gas use; skips estimation"},
+                        "gas_price": {"type": "string", "description": "Price in wei to quote at instead of the node's current one"}
+                    },
+                    "required": ["chain_id"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(transfer_nft_evm, {
+                "description": "Transfer an ERC-721 token (placeholder).",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "private_key": {"type": "string"},
+                        "chain_id": {"type": "string"},
+                        "contract_address": {"type": "string"},
+                        "to_address": {"type": "string", "description": "Recipient address, or a contact name from add_contact."},
+                        "token_id": {"type": "string"}
+                    },
+                    "required": ["private_key", "chain_id", "contract_address", "to_address", "token_id"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_contract, {
+                "description": "Get general details for a smart contract.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {"type": "string", "description": "The address of the smart contract."},
+                        "chain_id": {"type": "string", "description": "Optional chain id (e.g., 'sei-evm-mainnet' or 'sei-evm-testnet')."}
+                    },
+                    "required": ["address"]
+                }
+            }),
+            tool!(get_contract_code, {
+                "description": "Get the code of a smart contract.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {"type": "string", "description": "The address of the smart contract."},
+                        "chain_id": {"type": "string", "description": "EVM chain id (defaults to sei-evm-testnet)."}
+                    },
+                    "required": ["address"]
+                }
+            }),
+            tool!(discord_post_message, {
+                "description": "Post a message to Discord via webhook or bot token (configured in server env).",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "message": {"type": "string", "description": "Text to post. Code or natural language supported."},
+                        "username": {"type": "string", "description": "Optional display username (webhook mode)."}
+                    },
+                    "required": ["message"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_contract_abi, {
+                "description": "Get the parsed ABI of a verified smart contract from SeiStream, ready for encoding/decoding contract calls.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {"type": "string", "description": "The address of the smart contract."},
+                        "chain_id": {"type": "string", "description": "Optional chain id (e.g., 'sei-evm-mainnet' or 'sei-evm-testnet')."}
+                    },
+                    "required": ["address"]
+                }
+            }),
+            tool!(call_contract_by_abi, {
+                "description": "Call any function of a verified contract by name. The ABI is fetched automatically; view/pure functions are read via eth_call, state-changing functions are signed and sent when private_key is supplied (otherwise simulated).",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string"},
+                        "contract_address": {"type": "string"},
+                        "function_name": {"type": "string", "description": "Function name, e.g. 'balanceOf'. Overloads are matched by argument count."},
+                        "args": {"type": "array", "description": "Positional arguments. Numbers may be strings; tuples/arrays are JSON arrays."},
+                        "private_key": {"type": "string", "description": "Optional signer for state-changing functions."},
+                        "value": {"type": "string", "description": "Optional wei value for payable functions."},
+                        "gas_limit": {"type": "string"},
+                        "gas_price": {"type": "string"}
+                    },
+                    "required": ["chain_id", "contract_address", "function_name"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(deploy_contract, {
+                "description": "Deploy a contract from bytecode. Supports constructor arguments (with abi), a value for payable constructors, and an explicit gas_limit. Waits for the receipt and returns the address, gas used and tx hash.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string"},
+                        "private_key": {"type": "string"},
+                        "bytecode": {"type": "string", "description": "0x-prefixed creation bytecode."},
+                        "abi": {"type": "array", "description": "Contract ABI; needed only for constructor_args."},
+                        "constructor_args": {"type": "array"},
+                        "value": {"type": "string", "description": "Wei sent to a payable constructor."},
+                        "gas_limit": {"type": "string"},
+                        "gas_price": {"type": "string"},
+                        "timeout_secs": {"type": "number", "description": "How long to wait for the receipt (default 60)."},
+                        "poll_interval_ms": {"type": "integer", "description": "Delay between checks in milliseconds (default POLL_INTERVAL_MS, 2000)."},
+                        "max_poll_attempts": {"type": "integer", "description": "Stop after this many checks even before the timeout (default MAX_POLL_ATTEMPTS; 0 = no limit)."}
+                    },
+                    "required": ["chain_id", "private_key", "bytecode"]
+                }
+            }),
+            tool!(estimate_deployment_cost, {
+                "description": "Estimate the fee for deploying a contract without sending anything: eth_estimateGas on the creation call times the current gas price, in wei and SEI. If the constructor would revert, the error carries the decoded revert reason.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string"},
+                        "bytecode": {"type": "string", "description": "0x-prefixed creation bytecode."},
+                        "abi": {"type": "array", "description": "Contract ABI; needed for constructor_args and to decode custom errors."},
+                        "constructor_args": {"type": "array"},
+                        "value": {"type": "string", "description": "Wei sent to a payable constructor."},
+                        "from": {"type": "string", "description": "Deployer address to estimate as (default the zero address)."}
+                    },
+                    "required": ["chain_id", "bytecode"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_contract_transactions, {
+                "description": "Get the transactions of a smart contract, one page at a time; the result's pagination object gives the next page.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {"type": "string", "description": "The address of the smart contract."},
+                        "chain_id": {"type": "string", "description": "EVM chain id (defaults to sei-evm-testnet)."},
+                        "page": {"type": "number", "description": "1-based page number (default 1)"},
+                        "page_size": {"type": "number", "description": "Transactions per page (capped by MCP_MAX_ITEMS)"},
+                        "max_items": {"type": "number", "description": "Maximum items to return (capped by MCP_MAX_ITEMS)"}
+                    },
+                    "required": ["address"]
+                }
+            }),
+            tool!(compute_contract_address, {
+                "description": "Predict a contract's deployment address. CREATE uses deployer + nonce; CREATE2 uses deployer + salt + init_code_hash.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "deployer": {"type": "string", "description": "0x... address of the deploying account or factory."},
+                        "nonce": {"type": "number", "description": "CREATE: deployer's nonce at deployment."},
+                        "salt": {"type": "string", "description": "CREATE2: 32-byte hex salt."},
+                        "init_code_hash": {"type": "string", "description": "CREATE2: keccak256 of the init code (32-byte hex)."}
+                    },
+                    "required": ["deployer"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(compute_selector, {
+                "description": "Compute the 4-byte function selector and/or 32-byte event topic (keccak256) for a signature like 'transfer(address,uint256)'. Parameter names, 'indexed' and uint/int aliases are normalized away. A 'function'/'event' prefix limits the output to that kind; otherwise both are returned. Use the topic as topic0 in search_events.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "signature": {"type": "string", "description": "Function or event signature, e.g. 'transfer(address,uint256)' or 'event Transfer(address indexed from, address indexed to, uint256 value)'."}
+                    },
+                    "required": ["signature"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(decode_abi, {
+                "description": "Decode raw ABI-encoded data (e.g. eth_call_raw output) as a list of Solidity types. Handles dynamic types (string, bytes, T[]) and tuples like '(uint256,address)'. Integers come back as decimal strings. Errors if the data is not exactly the encoding of those types.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "data": {"type": "string", "description": "0x-prefixed hex, without a function selector."},
+                        "types": {"type": "array", "items": {"type": "string"}, "description": "Solidity types in order, e.g. [\"uint256\", \"address\"]."}
+                    },
+                    "required": ["data", "types"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(resync_nonce, {
+                "description": "Reset the server's cached next nonce for an address to the chain's latest transaction count. Use after sending from the same key outside this server, or after a transaction was dropped. The server also advances stale caches in the background.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {"type": "string", "description": "0x sender address."},
+                        "chain_id": {"type": "string", "description": "EVM chain id."}
+                    },
+                    "required": ["address", "chain_id"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(rpc_passthrough, {
+                "description": "Escape hatch: forward any JSON-RPC method to a chain's EVM RPC and return the raw result. Disabled unless the server sets ALLOW_RPC_PASSTHROUGH=true, because methods like eth_sendRawTransaction skip the server's transfer caps and checks. Every call is written to the audit log.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "EVM chain id (e.g. sei-evm-testnet)."},
+                        "method": {"type": "string", "description": "JSON-RPC method name, e.g. debug_traceTransaction."},
+                        "params": {"type": "array", "description": "Positional params passed through unchanged (default [])."}
+                    },
+                    "required": ["chain_id", "method"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(eth_call_raw, {
+                "description": "Power-user eth_call: send raw calldata at an optional block, optionally with a state override (per-address balance/nonce/code/state/stateDiff) to simulate against modified state. Returns the raw hex output. State overrides are a geth extension; nodes without it return an error rather than silently ignoring the override.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "to": {"type": "string", "description": "Contract address to call."},
+                        "data": {"type": "string", "description": "0x-prefixed calldata (default 0x)."},
+                        "from": {"type": "string", "description": "Optional caller address."},
+                        "value": {"type": "string", "description": "Optional value in wei (decimal string)."},
+                        "gas": {"type": "string", "description": "Optional gas cap (decimal string)."},
+                        "block": {"type": "string", "description": "Block tag (latest, pending, earliest, safe, finalized) or number (decimal or 0x hex). Default latest."},
+                        "state_override": {"type": "object", "description": "Map of address -> {balance, nonce, code, state, stateDiff}; balance/nonce as 0x quantities, state/stateDiff as slot -> 32-byte value."},
+                        "chain_id": {"type": "string", "description": "EVM chain id (defaults to sei-evm-testnet)."}
+                    },
+                    "required": ["to"]
+                }
+            }),
+            tool!(get_nft_token_uri, {
+                "description": "Resolve an NFT's metadata URI via tokenURI (ERC-721) or uri (ERC-1155), map ipfs:// to an HTTP gateway, decode on-chain data URIs, and optionally fetch the metadata JSON and its image URL.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "contract_address": {"type": "string", "description": "The NFT contract address."},
+                        "token_id": {"type": "string", "description": "Token id as a decimal string."},
+                        "chain_id": {"type": "string", "description": "EVM chain id (defaults to sei-evm-testnet)."},
+                        "fetch_metadata": {"type": "boolean", "description": "Fetch and return the metadata JSON (default true)."}
+                    },
+                    "required": ["contract_address", "token_id"]
+                }
+            }),
+            tool!(get_token_balances, {
+                "description": "ERC-20 balances of one address across up to 100 token contracts, each as {contract, symbol, raw, formatted}. balanceOf, decimals and symbol for every token go out in a single Multicall3 eth_call where MULTICALL3_ADDRESSES (or the built-in default) provides one, otherwise one token at a time. KNOWN_TOKENS symbol and decimals take precedence.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "EVM chain id."},
+                        "address": {"type": "string", "description": "Holder address (0x)."},
+                        "token_contracts": {"type": "array", "items": {"type": "string"}, "description": "ERC-20 contract addresses."}
+                    },
+                    "required": ["chain_id", "address", "token_contracts"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_token_stats, {
+                "description": "Token health snapshot for an ERC-20: live totalSupply from the chain plus holder and transfer counts from SeiStream (which indexes sei-evm-mainnet only). Each figure names its source, and stale is set when SeiStream's supply disagrees with the chain's.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "EVM chain id (defaults to sei-evm-testnet)."},
+                        "contract_address": {"type": "string"}
+                    },
+                    "required": ["contract_address"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_token_supply, {
+                "description": "Read an ERC-20's totalSupply() with decimals applied (from KNOWN_TOKENS when listed, else the contract's decimals()). Balances of excluded_addresses (burn/treasury) are subtracted for a circulating estimate.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "EVM chain id (defaults to sei-evm-testnet)."},
+                        "contract_address": {"type": "string"},
+                        "excluded_addresses": {"type": "array", "items": {"type": "string"}}
+                    },
+                    "required": ["contract_address"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_contract_creation, {
+                "description": "Find who deployed a contract and in which transaction and block. Uses SeiStream where it indexes the chain, otherwise bisects eth_getCode across blocks (needs a node with historical state).",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {"type": "string", "description": "The contract address."},
+                        "chain_id": {"type": "string", "description": "EVM chain id (defaults to sei-evm-testnet)."}
+                    },
+                    "required": ["address"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_proxy_implementation, {
+                "description": "Resolve the current implementation behind an EIP-1967 (transparent/UUPS) proxy by reading its storage slot, cross-checked against SeiStream.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {"type": "string", "description": "The proxy contract address."},
+                        "chain_id": {"type": "string", "description": "EVM chain id (defaults to sei-evm-testnet)."}
+                    },
+                    "required": ["address"]
+                }
+            }),
+            tool!(get_balance_at_block, {
+                "description": "Get an EVM address's native balance at a past block via eth_getBalance. Blocks older than the node's retained state need an archive node; pruned nodes return an error saying so.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "EVM chain id (e.g. sei-evm-mainnet)."},
+                        "address": {"type": "string", "description": "0x EVM address."},
+                        "block": {"type": ["string", "integer"], "description": "Block number, block hash, or tag (default latest)."}
+                    },
+                    "required": ["chain_id", "address", "block"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_storage_at, {
+                "description": "Read a raw 32-byte storage slot with eth_getStorageAt. The slot may be 0x-hex or decimal; pass mapping_keys to read mapping entries (slot = keccak256(key . slot), applied per key for nested mappings).",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {"type": "string", "description": "Contract address."},
+                        "slot": {"type": ["string", "integer"], "description": "Storage slot (0x-hex or decimal). For mappings, the slot the mapping is declared at."},
+                        "mapping_keys": {"type": "array", "items": {"type": ["string", "integer"]}, "description": "Value-type mapping keys (address, uint, bytes32) as 0x-hex or decimal, outermost first."},
+                        "block": {"type": ["string", "integer"], "description": "Block tag or number (default latest)."},
+                        "chain_id": {"type": "string", "description": "EVM chain id (defaults to sei-evm-testnet)."}
+                    },
+                    "required": ["address", "slot"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(list_methods, {
+                "description": "List every JSON-RPC method the server answers: the MCP protocol methods plus the tools that can also be called directly as a method, which takes the tool's arguments as params instead of going through tools/call.",
+                "inputSchema": { "type": "object", "properties": {}, "additionalProperties": false }
+            }),
+            tool!(resolve_chain_id, {
+                "description": "Show what a chain_id or alias resolves to: canonical id, chain type, whether an RPC URL is configured, and every recognized alias.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "Chain id or alias to resolve (e.g., 'sei testnet', 'pacific-1')."}
+                    },
+                    "required": ["chain_id"]
+                }
+            }),
+            tool!(get_pending_transactions, {
+                "description": "List an address's transactions that are in the mempool but not yet mined (nonce, gas price, value). Falls back to a count from the pending nonce when the node has no txpool API.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "EVM chain id (e.g., 'sei-evm-testnet')."},
+                        "address": {"type": "string", "description": "The sender address (0x...)."}
+                    },
+                    "required": ["chain_id", "address"]
+                }
+            }),
+            tool!(estimate_inclusion_time, {
+                "description": "Estimate how soon a transaction at a given gas price is likely to be mined. Returns a fast/medium/slow bucket with block and second ranges, based on mempool prices when available and recent fee history otherwise.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "EVM chain id (e.g., 'sei-evm-testnet')."},
+                        "gas_price": {"type": "string", "description": "Gas price (or max fee per gas) in wei, as a decimal string."}
+                    },
+                    "required": ["chain_id", "gas_price"]
+                }
+            }),
+            tool!(health_check_chains, {
+                "description": "Report the health of every RPC endpoint (primary and RPC_FALLBACK_URLS) per chain: healthy or skipped after repeated failures, consecutive failures, seconds until retry, last error, and which endpoint calls currently use. EVM endpoints are probed with eth_blockNumber first unless probe is false.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "Limit to one chain (default: every configured chain)."},
+                        "probe": {"type": "boolean", "description": "Send eth_blockNumber to each EVM endpoint before reporting (default true)."}
+                    },
+                    "additionalProperties": false
+                }
+            }),
+            tool!(websocket_status, {
+                "description": "Report the shared WebSocket connection's health: connected/disconnected, reconnect attempts, and last error.",
+                "inputSchema": {"type": "object", "properties": {}, "additionalProperties": false}
+            }),
+            tool!(get_chain_info, {
+                "description": "Get general chain info from SeiStream (network, latest block, validators, etc).",
+                "inputSchema": {"type": "object", "properties": {}, "additionalProperties": false}
+            }),
+            tool!(get_explorer_url, {
+                "description": "Block explorer link for a transaction hash or an address on a chain. Templates come from EXPLORER_URLS; the built-in Sei chains default to Seitrace.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string"},
+                        "tx_hash": {"type": "string", "description": "Transaction hash; pass this or address"},
+                        "address": {"type": "string", "description": "Account or contract address; pass this or tx_hash"}
+                    },
+                    "required": ["chain_id"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_transaction_info, {
+                "description": "Get transaction info by EVM hash from SeiStream.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {"hash": {"type": "string"}},
+                    "required": ["hash"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_native_tx, {
+                "description": "Look up a native (Cosmos) transaction by hash: success or failure code, gas used and wanted, the log, and its events with attributes decoded from base64 where the node encodes them. Uses TENDERMINT_RPC_URLS when configured for the chain, otherwise the REST gateway.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "Native chain id (e.g. atlantic-2, pacific-1)."},
+                        "hash": {"type": "string", "description": "64-hex-character transaction hash (0x optional)."}
+                    },
+                    "required": ["chain_id", "hash"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_transaction_receipt, {
+                "description": "Fetch an EVM transaction receipt from the chain's RPC. For a reverted transaction, replays it at its block to recover the revert reason: Error(string), Panic codes, or custom errors when an ABI is available.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "hash": {"type": "string"},
+                        "chain_id": {"type": "string", "description": "EVM chain (default sei-evm-testnet)"},
+                        "abi": {"type": "array", "description": "ABI used to decode custom errors; defaults to the callee's verified ABI on SeiStream"}
+                    },
+                    "required": ["hash"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_base_fee, {
+                "description": "Base fee per gas of the latest block on an EVM chain (briefly cached per chain). EIP-1559 transfers are priced from it, so passing just max_priority_fee_per_gas is enough. Pre-EIP-1559 chains report eip1559 false with no base fee.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "EVM chain id."}
+                    },
+                    "required": ["chain_id"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_transaction_trace, {
+                "description": "Internal call tree of a mined EVM transaction (from, to, value, type per call), showing contract-to-contract calls and internal value transfers the top-level transaction hides. Uses debug_traceTransaction's callTracer, or trace_transaction on parity-style nodes; reports supported false when the node offers neither.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "EVM chain id."},
+                        "hash": {"type": "string", "description": "Transaction hash."},
+                        "raw": {"type": "boolean", "description": "Also return the node's unsimplified trace output (default false)."}
+                    },
+                    "required": ["chain_id", "hash"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(check_finality, {
+                "description": "How many confirmations deep a block or transaction is and whether that meets the chain's finality threshold (FINALITY_CONFIRMATIONS, default 1 since Sei finalizes on commit). For a transaction the receipt is re-fetched to catch one that was mined and then reorged out, including since an earlier check_finality call saw it.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "chain_id": {"type": "string", "description": "EVM chain id."},
+                        "tx_hash": {"type": "string", "description": "Transaction to check; pass this or block_number"},
+                        "block_number": {"type": "integer", "minimum": 0, "description": "Block to check; pass this or tx_hash"},
+                        "confirmations": {"type": "integer", "minimum": 1, "description": "Threshold for this call, overriding FINALITY_CONFIRMATIONS"}
+                    },
+                    "required": ["chain_id"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_transaction_history, {
+                "description": "Get transaction history for an EVM address from SeiStream. Each item carries is_likely_spam (with spam_reasons when set) from local heuristics: zero-value transfers of unverified tokens, lookalike counterparty addresses, and URLs or calls to action in token names.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {"type": "string"},
+                        "page": {"type": "number", "description": "1-based page number (default 1)"},
+                        "page_size": {"type": "number", "description": "Items per page (capped by max_items / MCP_MAX_ITEMS)"},
+                        "filter_spam": {"type": "boolean", "description": "Drop items flagged is_likely_spam (default false)."},
+                        "max_items": {"type": "number", "description": "Maximum items to return (capped by MCP_MAX_ITEMS)"}
+                    },
+                    "required": ["address"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(export_transaction_history, {
+                "description": "Export an EVM address's transaction history as CSV (hash, from, to, amount, denom, timestamp, type) for spreadsheets.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "address": {"type": "string"},
+                        "max_items": {"type": "number", "description": "Maximum rows to export (capped by MCP_MAX_ITEMS)"}
+                    },
+                    "required": ["address"],
+                    "additionalProperties": false
+                }
+            }),
+            tool!(get_nft_metadata, {
+                "description": "Get ERC-721 NFT metadata items for a contract from SeiStream.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "contract_address": {"type": "string"},
+                        "page": {"type": "number", "description": "1-based page number (default 1)"},
+                        "page_size": {"type": "number", "description": "Items per page (capped by max_items / MCP_MAX_ITEMS)"},
+                        "max_items": {"type": "number", "description": "Maximum items to return (capped by MCP_MAX_ITEMS)"}
+                    },
+                    "required": ["contract_address"],
+                    "additionalProperties": false
+                }
+            }),
+        ];
+        // Every tool accepts the deadline handled in `handle_tool_call`
+        for tool in &mut tools {
+            if let Some(props) = tool.definition.pointer_mut("/inputSchema/properties").and_then(Value::as_object_mut) {
+                props.insert(
+                    "_timeout_ms".into(),
+                    json!({"type": "integer", "minimum": 1, "description": "Abandon the call after this many milliseconds. Transactions already broadcast are listed in the timeout error."}),
+                );
+            }
+        }
+        tools
+    })
 }
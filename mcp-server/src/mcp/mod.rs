@@ -1,5 +1,8 @@
 // Simple mod.rs to expose wallet storage and encryption modules
+pub mod audit;
 pub mod encryption;
 pub mod handler;
 pub mod protocol;
+pub mod session;
+pub mod transport;
 pub mod wallet_storage;
\ No newline at end of file
@@ -1,14 +1,16 @@
 // src/mcp/protocol.rs
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Request {
-    #[serde(default = "default_jsonrpc")]
-    pub jsonrpc: String,
+    // Left empty when absent so handle_mcp_request can reject non-2.0 requests
     #[serde(default)]
-    pub id: Value,
+    pub jsonrpc: String,
+    // `None` only when the member is absent (a notification); an explicit `null` is `Some(Value::Null)`
+    #[serde(default, deserialize_with = "present_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
     pub method: String,
     #[serde(default)]
     pub params: Option<Value>,
@@ -32,16 +34,43 @@ pub struct ErrorObject {
     pub data: Option<Value>,
 }
 
-fn default_jsonrpc() -> String {
-    "2.0".to_string()
+// Only called when `id` is present, so `null` becomes `Some(Value::Null)` rather than `None`.
+fn present_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
 }
 
 impl Request {
     pub fn is_notification(&self) -> bool {
-        self.id.is_null()
+        self.id.is_none()
+    }
+
+    /// The id to echo back: exactly what the client sent, `null` included.
+    pub fn response_id(&self) -> Value {
+        self.id.clone().unwrap_or(Value::Null)
     }
 }
 
+/// Parses one JSON-RPC request. Malformed JSON is a parse error with a `null` id; well-formed JSON
+/// that isn't a valid request is an invalid-request error that still echoes the client's id when it has one.
+#[allow(clippy::result_large_err)]
+pub fn parse_request(raw: &str) -> Result<Request, Response> {
+    let value: Value = serde_json::from_str(raw)
+        .map_err(|e| Response::error(Value::Null, error_codes::PARSE_ERROR, format!("Parse error: {}", e)))?;
+    let id = match value.get("id") {
+        Some(id @ (Value::String(_) | Value::Number(_) | Value::Null)) => id.clone(),
+        Some(_) => {
+            return Err(Response::error(
+                Value::Null,
+                error_codes::INVALID_REQUEST,
+                "Invalid Request: 'id' must be a string, number or null".into(),
+            ))
+        }
+        None => Value::Null,
+    };
+    serde_json::from_value(value)
+        .map_err(|e| Response::error(id, error_codes::INVALID_REQUEST, format!("Invalid Request: {}", e)))
+}
+
 impl Response {
     pub fn success(id: Value, result: Value) -> Self {
         Self {
@@ -88,4 +117,9 @@ pub mod error_codes {
     pub const INTERNAL_ERROR: i32 = -32603;
     pub const SERVER_ERROR_START: i32 = -32099;
     pub const SERVER_ERROR_END: i32 = -32000;
+    // Implementation-defined codes within the server error range
+    pub const RPC_ERROR: i32 = -32000;
+    pub const INSUFFICIENT_FUNDS: i32 = -32001;
+    pub const NOT_FOUND: i32 = -32002;
+    pub const TIMEOUT: i32 = -32003;
 }
\ No newline at end of file
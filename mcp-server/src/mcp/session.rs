@@ -0,0 +1,87 @@
+// src/mcp/session.rs
+
+use dashmap::DashMap;
+use rand::RngCore;
+use std::time::{Duration, Instant};
+use zeroize::Zeroizing;
+
+struct Session {
+    master_password: Zeroizing<String>,
+    last_used: Instant,
+}
+
+/// Short-lived unlock sessions for the wallet storage.
+/// A token stands in for the master password until it is revoked or sits idle past the timeout.
+pub struct SessionStore {
+    sessions: DashMap<String, Session>,
+    idle_timeout: Duration,
+}
+
+impl SessionStore {
+    pub fn new(idle_timeout: Duration) -> Self {
+        Self { sessions: DashMap::new(), idle_timeout }
+    }
+
+    pub fn idle_timeout(&self) -> Duration {
+        self.idle_timeout
+    }
+
+    /// Opens a session for an already-verified master password and returns its opaque token.
+    pub fn create(&self, master_password: &str) -> String {
+        self.evict_expired();
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let token = hex::encode(bytes);
+        self.sessions.insert(
+            token.clone(),
+            Session { master_password: Zeroizing::new(master_password.to_string()), last_used: Instant::now() },
+        );
+        token
+    }
+
+    /// Returns the master password behind a live token and refreshes its idle timer.
+    pub fn resolve(&self, token: &str) -> Option<Zeroizing<String>> {
+        let mut session = self.sessions.get_mut(token)?;
+        if session.last_used.elapsed() > self.idle_timeout {
+            drop(session);
+            self.sessions.remove(token);
+            return None;
+        }
+        session.last_used = Instant::now();
+        Some(session.master_password.clone())
+    }
+
+    /// Revokes a token; returns whether it was live.
+    pub fn revoke(&self, token: &str) -> bool {
+        self.sessions.remove(token).is_some()
+    }
+
+    fn evict_expired(&self) {
+        let timeout = self.idle_timeout;
+        self.sessions.retain(|_, s| s.last_used.elapsed() <= timeout);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_lifecycle() {
+        let store = SessionStore::new(Duration::from_secs(60));
+        let token = store.create("hunter2");
+        assert_eq!(store.resolve(&token).as_deref().map(String::as_str), Some("hunter2"));
+        assert!(store.resolve("bogus").is_none());
+        assert!(store.revoke(&token));
+        assert!(store.resolve(&token).is_none());
+        assert!(!store.revoke(&token));
+    }
+
+    #[test]
+    fn test_session_expires_when_idle() {
+        let store = SessionStore::new(Duration::ZERO);
+        let token = store.create("hunter2");
+        std::thread::sleep(Duration::from_millis(5));
+        assert!(store.resolve(&token).is_none());
+    }
+}
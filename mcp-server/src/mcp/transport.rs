@@ -0,0 +1,360 @@
+// src/mcp/transport.rs
+
+use crate::mcp::{
+    handler::handle_connection_request,
+    protocol::{error_codes, parse_request, Response},
+};
+use crate::AppState;
+use axum::{
+    extract::{DefaultBodyLimit, Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse,
+    },
+    routing::{get, post},
+    Json, Router,
+};
+use dashmap::DashMap;
+use futures::Stream;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::io::{self, AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::sync::{mpsc, Semaphore};
+use tracing::{debug, error, info, warn};
+
+/// Most requests one stdio connection runs at once; the loop stops reading until one of them finishes.
+const MAX_CONCURRENT_REQUESTS: usize = 32;
+
+/// Outcome of reading one newline-delimited message with a length bound.
+#[derive(Debug, PartialEq, Eq)]
+pub enum BoundedLine {
+    Line(String),
+    /// The line exceeded the limit; it was discarded up to and including its newline.
+    TooLong,
+    Eof,
+}
+
+/// Reads a single line of at most `max_len` bytes (excluding the newline).
+/// Oversized lines are drained without buffering so the reader resynchronizes on the next line.
+pub async fn read_bounded_line<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+    max_len: usize,
+) -> io::Result<BoundedLine> {
+    let mut buf: Vec<u8> = Vec::new();
+    let mut overflowed = false;
+    let mut saw_any = false;
+
+    loop {
+        let available = reader.fill_buf().await?;
+        if available.is_empty() {
+            break;
+        }
+        saw_any = true;
+        let (content_len, consumed, found_newline) = match available.iter().position(|&b| b == b'\n') {
+            Some(i) => (i, i + 1, true),
+            None => (available.len(), available.len(), false),
+        };
+        if !overflowed {
+            if buf.len() + content_len > max_len {
+                overflowed = true;
+                buf = Vec::new();
+            } else {
+                buf.extend_from_slice(&available[..content_len]);
+            }
+        }
+        reader.consume(consumed);
+        if found_newline {
+            break;
+        }
+    }
+
+    if overflowed {
+        Ok(BoundedLine::TooLong)
+    } else if !saw_any {
+        Ok(BoundedLine::Eof)
+    } else {
+        Ok(BoundedLine::Line(String::from_utf8_lossy(&buf).into_owned()))
+    }
+}
+
+/// Runs the newline-delimited JSON-RPC loop until EOF or a write failure.
+/// Each request is handled on its own task so long-running tools don't block `ping` or cancellation,
+/// up to `MAX_CONCURRENT_REQUESTS` at a time.
+pub async fn run_stdio_loop<R, W>(mut reader: R, mut writer: W, state: AppState, max_line_len: usize)
+where
+    R: AsyncBufRead + Unpin,
+    W: AsyncWrite + Unpin,
+{
+    let (tx, mut rx) = mpsc::channel::<Response>(MAX_CONCURRENT_REQUESTS);
+    let permits = Arc::new(Semaphore::new(MAX_CONCURRENT_REQUESTS));
+    // Cancellations only reach requests read by this loop
+    let connection = format!("stdio-{}", uuid::Uuid::new_v4());
+
+    let read_loop = async move {
+        loop {
+            let line = match read_bounded_line(&mut reader, max_line_len).await {
+                Ok(BoundedLine::Eof) => {
+                    info!("EOF received, shutting down MCP server");
+                    break;
+                }
+                Ok(BoundedLine::TooLong) => {
+                    warn!("Rejected MCP request line longer than {} bytes", max_line_len);
+                    let _ = tx
+                        .send(Response::error(
+                            serde_json::Value::Null,
+                            error_codes::PARSE_ERROR,
+                            format!("Parse error: request line exceeds maximum of {} bytes", max_line_len),
+                        ))
+                        .await;
+                    continue;
+                }
+                Ok(BoundedLine::Line(line)) => line,
+                Err(e) => {
+                    error!("Failed to read from stdin: {}", e);
+                    break;
+                }
+            };
+
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            debug!("Received: {}", line);
+
+            match parse_request(line) {
+                // Notifications (cancellations among them) are quick and must not wait for a free slot
+                Ok(request) if request.is_notification() => {
+                    if let Some(response) = handle_connection_request(&connection, request, state.clone()).await {
+                        let _ = tx.send(response).await;
+                    }
+                }
+                Ok(request) => {
+                    let Ok(permit) = permits.clone().acquire_owned().await else {
+                        break;
+                    };
+                    let state = state.clone();
+                    let tx = tx.clone();
+                    let connection = connection.clone();
+                    tokio::spawn(async move {
+                        if let Some(response) = handle_connection_request(&connection, request, state).await {
+                            let _ = tx.send(response).await;
+                        }
+                        drop(permit);
+                    });
+                }
+                Err(error_response) => {
+                    error!("Rejected MCP request: {:?}", error_response.error);
+                    let _ = tx.send(error_response).await;
+                }
+            }
+        }
+        // Dropping the sender lets the writer finish once in-flight requests have answered
+    };
+
+    let write_loop = async {
+        while let Some(response) = rx.recv().await {
+            if write_response(&mut writer, &response).await.is_err() {
+                break;
+            }
+        }
+    };
+
+    tokio::join!(read_loop, write_loop);
+}
+
+async fn write_response<W: AsyncWrite + Unpin>(writer: &mut W, response: &Response) -> io::Result<()> {
+    let Ok(response_json) = serde_json::to_string(response) else {
+        return Ok(());
+    };
+    debug!("Sending: {}", response_json);
+    let result = async {
+        writer.write_all(format!("{}\n", response_json).as_bytes()).await?;
+        writer.flush().await
+    }
+    .await;
+    if let Err(e) = &result {
+        error!("Failed to write response: {}", e);
+    }
+    result
+}
+
+// --- HTTP + SSE transport ---
+
+#[derive(Clone)]
+struct HttpTransport {
+    app: AppState,
+    // Open SSE streams by session id; POSTs tagged with a session answer over its stream
+    sessions: Arc<DashMap<String, mpsc::UnboundedSender<Response>>>,
+}
+
+#[derive(Deserialize)]
+struct SessionQuery {
+    session_id: Option<String>,
+}
+
+// Removes the session when its SSE stream is dropped by the client.
+struct SessionGuard {
+    id: String,
+    sessions: Arc<DashMap<String, mpsc::UnboundedSender<Response>>>,
+}
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        self.sessions.remove(&self.id);
+        debug!("SSE session {} closed", self.id);
+    }
+}
+
+/// Routes for MCP over HTTP: `POST /mcp` takes a JSON-RPC request and `GET /mcp/sse` opens a
+/// server-to-client event stream. A POST without `session_id` gets its response in the body;
+/// with `?session_id=` (announced in the stream's first `endpoint` event) it gets 202 and the
+/// response arrives on that stream. Both routes require `MCP_HTTP_TOKEN` as a bearer token; without one
+/// every request is refused unless `MCP_HTTP_ALLOW_UNAUTHENTICATED` is set.
+pub fn http_router(state: AppState) -> Router {
+    let body_limit = state.config.mcp_max_line_bytes;
+    Router::new()
+        .route("/mcp", post(http_post))
+        .route("/mcp/sse", get(http_sse))
+        .layer(DefaultBodyLimit::max(body_limit))
+        .with_state(HttpTransport { app: state, sessions: Arc::new(DashMap::new()) })
+}
+
+fn authorized(state: &AppState, headers: &HeaderMap) -> bool {
+    let Some(expected) = state.config.mcp_http_token.as_deref() else {
+        return state.config.mcp_http_allow_unauthenticated;
+    };
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|token| tokens_match(token, expected))
+}
+
+// Compares digests byte by byte without stopping early, so response timing reveals neither how much of
+// the token was right nor its length
+fn tokens_match(given: &str, expected: &str) -> bool {
+    let (given, expected) = (Sha256::digest(given.as_bytes()), Sha256::digest(expected.as_bytes()));
+    given.iter().zip(expected.iter()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+async fn http_post(
+    State(transport): State<HttpTransport>,
+    Query(query): Query<SessionQuery>,
+    headers: HeaderMap,
+    body: String,
+) -> axum::response::Response {
+    if !authorized(&transport.app, &headers) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    let request = match parse_request(&body) {
+        Ok(request) => request,
+        Err(response) => return (StatusCode::BAD_REQUEST, Json(response)).into_response(),
+    };
+    debug!("Received over HTTP: {}", request.method);
+
+    match query.session_id {
+        Some(session_id) => {
+            let Some(stream) = transport.sessions.get(&session_id).map(|s| s.clone()) else {
+                return (StatusCode::NOT_FOUND, "Unknown or closed SSE session").into_response();
+            };
+            let app = transport.app.clone();
+            tokio::spawn(async move {
+                if let Some(response) = handle_connection_request(&session_id, request, app).await {
+                    let _ = stream.send(response);
+                }
+            });
+            StatusCode::ACCEPTED.into_response()
+        }
+        // A bare POST is its own connection: nothing else can cancel it
+        None => match handle_connection_request(&uuid::Uuid::new_v4().to_string(), request, transport.app.clone()).await {
+            Some(response) => Json(response).into_response(),
+            None => StatusCode::ACCEPTED.into_response(),
+        },
+    }
+}
+
+async fn http_sse(
+    State(transport): State<HttpTransport>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, StatusCode> {
+    if !authorized(&transport.app, &headers) {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    let session_id = uuid::Uuid::new_v4().to_string();
+    let (tx, rx) = mpsc::unbounded_channel::<Response>();
+    transport.sessions.insert(session_id.clone(), tx);
+    info!("SSE session {} opened", session_id);
+
+    let endpoint = Event::default().event("endpoint").data(format!("/mcp?session_id={}", session_id));
+    let guard = SessionGuard { id: session_id, sessions: transport.sessions.clone() };
+    let responses = futures::stream::unfold((rx, guard), |(mut rx, guard)| async move {
+        let response = rx.recv().await?;
+        let data = serde_json::to_string(&response).unwrap_or_default();
+        Some((Ok(Event::default().event("message").data(data)), (rx, guard)))
+    });
+    let stream = futures::StreamExt::chain(futures::stream::once(async { Ok(endpoint) }), responses);
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use serde_json::Value;
+
+    #[tokio::test]
+    async fn test_read_bounded_line_discards_oversized_line() {
+        let input = format!("{}\nshort\n", "x".repeat(100));
+        let mut reader = io::BufReader::with_capacity(8, input.as_bytes());
+        assert_eq!(read_bounded_line(&mut reader, 16).await.unwrap(), BoundedLine::TooLong);
+        assert_eq!(read_bounded_line(&mut reader, 16).await.unwrap(), BoundedLine::Line("short".into()));
+        assert_eq!(read_bounded_line(&mut reader, 16).await.unwrap(), BoundedLine::Eof);
+    }
+
+    #[tokio::test]
+    async fn test_loop_recovers_after_oversized_line() {
+        let state = AppState::new(Config::default(), std::env::temp_dir().join("sei-mcp-transport-test.json"));
+        let oversized = format!(r#"{{"jsonrpc":"2.0","id":1,"method":"{}"}}"#, "a".repeat(4096));
+        let valid = r#"{"jsonrpc":"2.0","id":2,"method":"initialize"}"#;
+        let input = format!("{}\n{}\n", oversized, valid);
+        let mut output: Vec<u8> = Vec::new();
+
+        run_stdio_loop(io::BufReader::new(input.as_bytes()), &mut output, state, 1024).await;
+
+        let responses = parse_output(output);
+        assert_eq!(responses.len(), 2);
+        let rejected = responses.iter().find(|r| r["id"].is_null()).unwrap();
+        assert_eq!(rejected["error"]["code"], error_codes::PARSE_ERROR);
+        let initialized = responses.iter().find(|r| r["id"] == 2).unwrap();
+        assert!(initialized["result"]["serverInfo"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_ping_and_cancel_notification() {
+        let state = AppState::new(Config::default(), std::env::temp_dir().join("sei-mcp-transport-test.json"));
+        let input = concat!(
+            r#"{"jsonrpc":"2.0","id":"p1","method":"ping"}"#, "\n",
+            r#"{"jsonrpc":"2.0","method":"notifications/cancelled","params":{"requestId":99}}"#, "\n",
+        );
+        let mut output: Vec<u8> = Vec::new();
+
+        run_stdio_loop(io::BufReader::new(input.as_bytes()), &mut output, state, 1024).await;
+
+        let responses = parse_output(output);
+        assert_eq!(responses.len(), 1, "notifications must not be answered");
+        assert_eq!(responses[0]["id"], "p1");
+        assert_eq!(responses[0]["result"], serde_json::json!({}));
+    }
+
+    fn parse_output(output: Vec<u8>) -> Vec<Value> {
+        String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect()
+    }
+}
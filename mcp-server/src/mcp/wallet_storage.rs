@@ -8,6 +8,7 @@ use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoredWallet {
@@ -16,11 +17,96 @@ pub struct StoredWallet {
     pub encrypted_private_key: String,
     pub public_address: String,
     pub created_at: DateTime<Utc>,
+    // None = may pay anyone; Some(list) = only these (lowercased) addresses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_recipients: Option<Vec<String>>,
+    // Organizational metadata; not secret, so stored in the clear beside the encrypted key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+/// A partial update for `set_wallet_metadata`: `None` fields are left alone,
+/// an empty label or note clears it, and `tags` replaces the whole set.
+#[derive(Debug, Clone, Default)]
+pub struct WalletMetadata {
+    pub label: Option<String>,
+    pub tags: Option<Vec<String>>,
+    pub note: Option<String>,
+}
+
+/// M-of-N control over a stored wallet: transfers need `threshold` distinct authorizers to approve
+/// a proposal with their confirmation codes. Codes are kept as SHA-256 hashes, keyed by authorizer name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultisigPolicy {
+    pub threshold: usize,
+    pub authorizers: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Pending,
+    // Threshold met; the transfer is being (or was) sent
+    Approved,
+    Executed,
+    Failed,
+    Expired,
+}
+
+/// A transfer from a multisig wallet waiting for approvals.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransferProposal {
+    pub proposal_id: String,
+    pub wallet_name: String,
+    pub chain_id: String,
+    pub to_address: String,
+    pub amount: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub approvals: Vec<String>,
+    pub status: ProposalStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl TransferProposal {
+    /// Marks a pending proposal expired once `now` is past its deadline; returns the current status.
+    fn refresh(&mut self, now: DateTime<Utc>) -> ProposalStatus {
+        if self.status == ProposalStatus::Pending && now > self.expires_at {
+            self.status = ProposalStatus::Expired;
+        }
+        self.status
+    }
+}
+
+/// An address book entry. Nothing here is secret, so contacts are stored in the clear and
+/// managed without the master password.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Contact {
+    pub name: String,
+    pub address: String,
+    pub chain_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct WalletStorage {
     pub wallets: HashMap<String, StoredWallet>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub multisig: HashMap<String, MultisigPolicy>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub proposals: HashMap<String, TransferProposal>,
+    // Keyed by lowercased name, so names are unique regardless of case
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub contacts: HashMap<String, Contact>,
     pub master_password_hash: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -35,6 +121,9 @@ impl WalletStorage {
         let master_password_hash = Self::hash_password(master_password);
         Self {
             wallets: HashMap::new(),
+            multisig: HashMap::new(),
+            proposals: HashMap::new(),
+            contacts: HashMap::new(),
             master_password_hash,
             created_at: Utc::now(),
             updated_at: Utc::now(),
@@ -47,6 +136,11 @@ impl WalletStorage {
         format!("{:x}", hasher.finalize())
     }
 
+    /// False until a master password has been set (no storage file existed yet).
+    pub fn is_initialized(&self) -> bool {
+        !self.master_password_hash.is_empty()
+    }
+
     pub fn verify_master_password(&self, master_password: &str) -> bool {
         self.master_password_hash == Self::hash_password(master_password)
     }
@@ -73,6 +167,10 @@ impl WalletStorage {
             encrypted_private_key,
             public_address,
             created_at: Utc::now(),
+            allowed_recipients: None,
+            label: None,
+            tags: Vec::new(),
+            note: None,
         };
 
         self.wallets.insert(wallet_name, stored_wallet);
@@ -97,6 +195,273 @@ impl WalletStorage {
         decrypt_private_key(&wallet.encrypted_private_key, master_password)
     }
 
+    /// Adds `address` to the wallet's recipient allowlist, creating the list on first use.
+    /// Returns false if it was already present.
+    pub fn add_allowed_recipient(&mut self, wallet_name: &str, address: &str, master_password: &str) -> Result<bool> {
+        let wallet = self.wallet_mut_verified(wallet_name, master_password)?;
+        let address = address.to_lowercase();
+        let list = wallet.allowed_recipients.get_or_insert_with(Vec::new);
+        if list.contains(&address) {
+            return Ok(false);
+        }
+        list.push(address);
+        self.updated_at = Utc::now();
+        Ok(true)
+    }
+
+    /// Removes `address` from the allowlist. An emptied list stays in place, blocking all transfers,
+    /// rather than silently lifting the restriction.
+    pub fn remove_allowed_recipient(&mut self, wallet_name: &str, address: &str, master_password: &str) -> Result<bool> {
+        let wallet = self.wallet_mut_verified(wallet_name, master_password)?;
+        let address = address.to_lowercase();
+        let Some(list) = wallet.allowed_recipients.as_mut() else {
+            return Ok(false);
+        };
+        let before = list.len();
+        list.retain(|a| *a != address);
+        let removed = list.len() != before;
+        if removed {
+            self.updated_at = Utc::now();
+        }
+        Ok(removed)
+    }
+
+    /// Whether `wallet_name` may pay `address` under its allowlist (wallets without one may pay anyone).
+    pub fn is_recipient_allowed(&self, wallet_name: &str, address: &str) -> Result<bool> {
+        let wallet = self
+            .wallets
+            .get(wallet_name)
+            .ok_or_else(|| anyhow!("Wallet '{}' not found", wallet_name))?;
+        Ok(match &wallet.allowed_recipients {
+            None => true,
+            Some(list) => list.contains(&address.to_lowercase()),
+        })
+    }
+
+    /// Applies `update` to the wallet's label, tags and note. Tags are trimmed, lowercased and deduplicated.
+    pub fn set_wallet_metadata(&mut self, wallet_name: &str, update: WalletMetadata, master_password: &str) -> Result<&StoredWallet> {
+        let wallet = self.wallet_mut_verified(wallet_name, master_password)?;
+        let non_empty = |s: String| Some(s.trim().to_string()).filter(|s| !s.is_empty());
+        if let Some(label) = update.label {
+            wallet.label = non_empty(label);
+        }
+        if let Some(note) = update.note {
+            wallet.note = non_empty(note);
+        }
+        if let Some(tags) = update.tags {
+            let mut tags: Vec<String> = tags.iter().map(|t| t.trim().to_lowercase()).filter(|t| !t.is_empty()).collect();
+            tags.sort();
+            tags.dedup();
+            wallet.tags = tags;
+        }
+        self.updated_at = Utc::now();
+        Ok(&self.wallets[wallet_name])
+    }
+
+    /// Stored wallets ordered by name, limited to those tagged `tag` (case-insensitive) when given.
+    pub fn wallets_with_tag(&self, tag: Option<&str>) -> Vec<&StoredWallet> {
+        let tag = tag.map(|t| t.trim().to_lowercase());
+        let mut wallets: Vec<&StoredWallet> = self
+            .wallets
+            .values()
+            .filter(|w| tag.as_ref().is_none_or(|t| w.tags.contains(t)))
+            .collect();
+        wallets.sort_by(|a, b| a.wallet_name.cmp(&b.wallet_name));
+        wallets
+    }
+
+    /// Puts `wallet_name` under M-of-N control (`authorizers` are name/code pairs), replacing any
+    /// earlier policy. An empty `authorizers` list lifts the requirement.
+    pub fn set_multisig_policy(
+        &mut self,
+        wallet_name: &str,
+        threshold: usize,
+        authorizers: &[(String, String)],
+        master_password: &str,
+    ) -> Result<Option<&MultisigPolicy>> {
+        self.wallet_mut_verified(wallet_name, master_password)?;
+        if authorizers.is_empty() {
+            self.multisig.remove(wallet_name);
+            self.updated_at = Utc::now();
+            return Ok(None);
+        }
+        let mut hashed = HashMap::new();
+        for (name, code) in authorizers {
+            let name = name.trim();
+            if name.is_empty() || code.is_empty() {
+                return Err(anyhow!("Authorizer names and confirmation codes must not be empty"));
+            }
+            if hashed.insert(name.to_string(), Self::hash_password(code)).is_some() {
+                return Err(anyhow!("Authorizer '{}' is listed twice", name));
+            }
+        }
+        if threshold == 0 || threshold > hashed.len() {
+            return Err(anyhow!("Threshold must be between 1 and {} (the number of authorizers)", hashed.len()));
+        }
+        self.multisig.insert(wallet_name.to_string(), MultisigPolicy { threshold, authorizers: hashed });
+        self.updated_at = Utc::now();
+        Ok(self.multisig.get(wallet_name))
+    }
+
+    /// Opens a transfer proposal for a multisig wallet, valid for `ttl` from `now`.
+    pub fn propose_transfer(
+        &mut self,
+        wallet_name: &str,
+        chain_id: &str,
+        to_address: &str,
+        amount: &str,
+        ttl: chrono::Duration,
+        now: DateTime<Utc>,
+    ) -> Result<&TransferProposal> {
+        if !self.multisig.contains_key(wallet_name) {
+            return Err(anyhow!("Wallet '{}' has no multisig policy; use transfer_from_wallet", wallet_name));
+        }
+        if !self.is_recipient_allowed(wallet_name, to_address)? {
+            return Err(anyhow!("Recipient {} is not on the allowlist for wallet '{}'", to_address, wallet_name));
+        }
+        let mut id = [0u8; 8];
+        rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut id);
+        let proposal_id = hex::encode(id);
+        let proposal = TransferProposal {
+            proposal_id: proposal_id.clone(),
+            wallet_name: wallet_name.to_string(),
+            chain_id: chain_id.to_string(),
+            to_address: to_address.to_string(),
+            amount: amount.to_string(),
+            created_at: now,
+            expires_at: now + ttl,
+            approvals: Vec::new(),
+            status: ProposalStatus::Pending,
+            tx_hash: None,
+            error: None,
+        };
+        self.proposals.insert(proposal_id.clone(), proposal);
+        self.updated_at = Utc::now();
+        Ok(&self.proposals[&proposal_id])
+    }
+
+    /// Records `authorizer`'s approval after checking their confirmation code. The proposal moves to
+    /// `Approved` on the approval that meets the threshold; the caller then sends the transfer and
+    /// reports the outcome with `finish_proposal`. Repeat approvals from one authorizer count once.
+    pub fn approve_proposal(
+        &mut self,
+        proposal_id: &str,
+        authorizer: &str,
+        confirmation_code: &str,
+        now: DateTime<Utc>,
+    ) -> Result<&TransferProposal> {
+        let proposal = self
+            .proposals
+            .get_mut(proposal_id)
+            .ok_or_else(|| anyhow!("Proposal '{}' not found", proposal_id))?;
+        match proposal.refresh(now) {
+            ProposalStatus::Pending => {}
+            ProposalStatus::Expired => {
+                self.updated_at = Utc::now();
+                return Err(anyhow!("Proposal '{}' expired at {}", proposal_id, proposal.expires_at));
+            }
+            status => return Err(anyhow!("Proposal '{}' is no longer pending ({:?})", proposal_id, status)),
+        }
+        let policy = self
+            .multisig
+            .get(&proposal.wallet_name)
+            .ok_or_else(|| anyhow!("Wallet '{}' no longer has a multisig policy", proposal.wallet_name))?;
+        let authorizer = authorizer.trim();
+        match policy.authorizers.get(authorizer) {
+            Some(hash) if *hash == Self::hash_password(confirmation_code) => {}
+            _ => return Err(anyhow!("Unknown authorizer or wrong confirmation code")),
+        }
+        if !proposal.approvals.iter().any(|a| a == authorizer) {
+            proposal.approvals.push(authorizer.to_string());
+        }
+        if proposal.approvals.len() >= policy.threshold {
+            proposal.status = ProposalStatus::Approved;
+        }
+        self.updated_at = Utc::now();
+        Ok(&self.proposals[proposal_id])
+    }
+
+    /// Records the result of sending an approved proposal's transfer.
+    pub fn finish_proposal(&mut self, proposal_id: &str, outcome: std::result::Result<String, String>) {
+        if let Some(proposal) = self.proposals.get_mut(proposal_id) {
+            match outcome {
+                Ok(tx_hash) => {
+                    proposal.status = ProposalStatus::Executed;
+                    proposal.tx_hash = Some(tx_hash);
+                }
+                Err(error) => {
+                    proposal.status = ProposalStatus::Failed;
+                    proposal.error = Some(error);
+                }
+            }
+            self.updated_at = Utc::now();
+        }
+    }
+
+    /// The proposal as of `now`, with a passed deadline reflected as `Expired`.
+    pub fn proposal_status(&mut self, proposal_id: &str, now: DateTime<Utc>) -> Option<&TransferProposal> {
+        let proposal = self.proposals.get_mut(proposal_id)?;
+        proposal.refresh(now);
+        Some(proposal)
+    }
+
+    fn wallet_mut_verified(&mut self, wallet_name: &str, master_password: &str) -> Result<&mut StoredWallet> {
+        if !self.verify_master_password(master_password) {
+            return Err(anyhow!("Invalid master password"));
+        }
+        self.wallets
+            .get_mut(wallet_name)
+            .ok_or_else(|| anyhow!("Wallet '{}' not found", wallet_name))
+    }
+
+    /// Adds a contact, or with `replace` overwrites the one with the same name (compared
+    /// case-insensitively). Names that look like addresses are refused so that a recipient
+    /// always means the same thing: anything address-shaped is an address, never a name.
+    pub fn add_contact(&mut self, contact: Contact, replace: bool) -> Result<Option<Contact>> {
+        let name = contact.name.trim();
+        if name.is_empty() {
+            return Err(anyhow!("Contact name must not be empty"));
+        }
+        if looks_like_address(name) {
+            return Err(anyhow!("Contact name '{}' looks like an address; pick a name that does not", name));
+        }
+        let key = name.to_lowercase();
+        if !replace {
+            if let Some(existing) = self.contacts.get(&key) {
+                return Err(anyhow!(
+                    "A contact named '{}' already exists ({} on {}); pass replace: true to overwrite it",
+                    existing.name, existing.address, existing.chain_id
+                ));
+            }
+        }
+        let contact = Contact { name: name.to_string(), ..contact };
+        self.updated_at = Utc::now();
+        Ok(self.contacts.insert(key, contact))
+    }
+
+    pub fn remove_contact(&mut self, name: &str) -> Option<Contact> {
+        let removed = self.contacts.remove(&name.trim().to_lowercase());
+        if removed.is_some() {
+            self.updated_at = Utc::now();
+        }
+        removed
+    }
+
+    pub fn contact(&self, name: &str) -> Option<&Contact> {
+        self.contacts.get(&name.trim().to_lowercase())
+    }
+
+    /// Contacts sorted by name, optionally only those for `chain_id`.
+    pub fn list_contacts(&self, chain_id: Option<&str>) -> Vec<&Contact> {
+        let mut contacts: Vec<&Contact> = self
+            .contacts
+            .values()
+            .filter(|c| chain_id.is_none_or(|id| c.chain_id == id))
+            .collect();
+        contacts.sort_by_key(|c| c.name.to_lowercase());
+        contacts
+    }
+
     pub fn list_wallets(&self) -> Vec<String> {
         self.wallets.keys().cloned().collect()
     }
@@ -106,6 +471,7 @@ impl WalletStorage {
             return Err(anyhow!("Invalid master password"));
         }
         if self.wallets.remove(wallet_name).is_some() {
+            self.multisig.remove(wallet_name);
             self.updated_at = Utc::now();
             Ok(true)
         } else {
@@ -115,6 +481,13 @@ impl WalletStorage {
 }
 
 
+/// True for a 0x-prefixed string or a bech32 account (`sei1...`): the shapes a recipient
+/// address can take, which contact names must avoid.
+pub fn looks_like_address(value: &str) -> bool {
+    let value = value.trim();
+    value.get(..2).is_some_and(|p| p.eq_ignore_ascii_case("0x")) || cosmrs::AccountId::from_str(value).is_ok()
+}
+
 /// Helper function to get the default path for the wallet storage file.
 pub fn get_wallet_storage_path() -> Result<PathBuf> {
     let mut path = dirs::home_dir().ok_or_else(|| anyhow!("Could not find home directory"))?;
@@ -123,6 +496,33 @@ pub fn get_wallet_storage_path() -> Result<PathBuf> {
     Ok(path)
 }
 
+/// Reads the wallet storage file. A missing file is an uninitialized (default) storage.
+/// A file that doesn't parse is moved aside to `<file>.corrupt.<timestamp>` so the wallets in it can be
+/// recovered by hand, and the returned error names the backup.
+pub fn load_wallet_storage(file_path: &Path) -> Result<WalletStorage> {
+    if !file_path.exists() {
+        return Ok(WalletStorage::default());
+    }
+    let json = fs::read_to_string(file_path).context("Failed to read wallet storage file")?;
+    match serde_json::from_str(&json) {
+        Ok(storage) => Ok(storage),
+        Err(parse_error) => {
+            let mut backup_name = file_path.file_name().ok_or_else(|| anyhow!("Invalid wallet storage path"))?.to_os_string();
+            backup_name.push(format!(".corrupt.{}", Utc::now().format("%Y%m%dT%H%M%S%.3fZ")));
+            let backup = file_path.with_file_name(backup_name);
+            fs::rename(file_path, &backup).with_context(|| {
+                format!("Wallet storage {} is corrupt ({}) and could not be backed up", file_path.display(), parse_error)
+            })?;
+            Err(anyhow!(
+                "Wallet storage {} was corrupt ({}); it was moved to {}. Restore it from there or from a backup; new wallets will start a fresh storage file",
+                file_path.display(),
+                parse_error,
+                backup.display()
+            ))
+        }
+    }
+}
+
 /// Loads a wallet storage from a file. If the file does not exist, it creates a new one.
 pub fn load_or_create_wallet_storage(file_path: &Path, master_password: &str) -> Result<WalletStorage> {
     if !file_path.exists() {
@@ -130,13 +530,11 @@ pub fn load_or_create_wallet_storage(file_path: &Path, master_password: &str) ->
             fs::create_dir_all(parent)?;
         }
         let new_storage = WalletStorage::new(master_password);
-        let json = serde_json::to_string_pretty(&new_storage)?;
-        fs::write(file_path, json)?;
+        save_wallet_storage(file_path, &new_storage)?;
         return Ok(new_storage);
     }
 
-    let json = fs::read_to_string(file_path).context("Failed to read wallet storage file")?;
-    let storage: WalletStorage = serde_json::from_str(&json).context("Failed to parse wallet storage JSON")?;
+    let storage = load_wallet_storage(file_path)?;
 
     if !storage.verify_master_password(master_password) {
         return Err(anyhow!("Invalid master password for existing wallet storage"));
@@ -146,8 +544,152 @@ pub fn load_or_create_wallet_storage(file_path: &Path, master_password: &str) ->
 }
 
 /// Saves the wallet storage to a file.
+/// Writes a sibling temp file and renames it over the target, so readers never see a partial file.
+/// Callers must hold the storage `Mutex` across the whole read-modify-save sequence.
 pub fn save_wallet_storage(file_path: &Path, storage: &WalletStorage) -> Result<()> {
     let json = serde_json::to_string_pretty(storage)?;
-    fs::write(file_path, json)?;
+    crate::utils::write_atomic(file_path, json.as_bytes())?;
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recipient_allowlist() {
+        let mut storage = WalletStorage::new("pw");
+        storage
+            .add_wallet("hot".into(), "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318", "0xabc".into(), "pw")
+            .unwrap();
+        let alice = "0x00000000000000000000000000000000000000Aa";
+
+        assert!(storage.is_recipient_allowed("hot", alice).unwrap());
+        assert!(storage.add_allowed_recipient("hot", "0xf00", "wrong").is_err());
+        assert!(storage.add_allowed_recipient("hot", alice, "pw").unwrap());
+        assert!(!storage.add_allowed_recipient("hot", &alice.to_lowercase(), "pw").unwrap());
+        assert!(storage.is_recipient_allowed("hot", &alice.to_uppercase().replace("0X", "0x")).unwrap());
+        assert!(!storage.is_recipient_allowed("hot", "0xf00").unwrap());
+
+        assert!(storage.remove_allowed_recipient("hot", alice, "pw").unwrap());
+        assert!(!storage.is_recipient_allowed("hot", alice).unwrap(), "emptied allowlist must block");
+    }
+
+    fn multisig_storage() -> WalletStorage {
+        let mut storage = WalletStorage::new("pw");
+        storage
+            .add_wallet("team".into(), "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318", "0xabc".into(), "pw")
+            .unwrap();
+        let authorizers = [("alice", "a-code"), ("bob", "b-code"), ("carol", "c-code")].map(|(n, c)| (n.to_string(), c.to_string()));
+        assert!(storage.set_multisig_policy("team", 4, &authorizers, "pw").is_err());
+        assert!(storage.set_multisig_policy("team", 2, &authorizers, "wrong").is_err());
+        storage.set_multisig_policy("team", 2, &authorizers, "pw").unwrap();
+        storage
+    }
+
+    #[test]
+    fn test_proposal_executes_only_at_threshold() {
+        let mut storage = multisig_storage();
+        let now = Utc::now();
+        let id = storage
+            .propose_transfer("team", "sei-evm-testnet", "0xdead", "1000", chrono::Duration::hours(1), now)
+            .unwrap()
+            .proposal_id
+            .clone();
+
+        assert!(storage.approve_proposal(&id, "alice", "b-code", now).is_err(), "codes are per authorizer");
+        assert!(storage.approve_proposal(&id, "mallory", "a-code", now).is_err());
+        assert_eq!(storage.approve_proposal(&id, "alice", "a-code", now).unwrap().status, ProposalStatus::Pending);
+        // A repeat approval does not count twice
+        let repeat = storage.approve_proposal(&id, "alice", "a-code", now).unwrap();
+        assert_eq!((repeat.approvals.len(), repeat.status), (1, ProposalStatus::Pending));
+
+        assert_eq!(storage.approve_proposal(&id, "bob", "b-code", now).unwrap().status, ProposalStatus::Approved);
+        assert!(storage.approve_proposal(&id, "carol", "c-code", now).is_err(), "no approvals after the threshold");
+
+        storage.finish_proposal(&id, Ok("0xhash".into()));
+        let done = storage.proposal_status(&id, now).unwrap();
+        assert_eq!((done.status, done.tx_hash.as_deref()), (ProposalStatus::Executed, Some("0xhash")));
+    }
+
+    #[test]
+    fn test_proposal_expires() {
+        let mut storage = multisig_storage();
+        let now = Utc::now();
+        let id = storage
+            .propose_transfer("team", "sei-evm-testnet", "0xdead", "1000", chrono::Duration::minutes(5), now)
+            .unwrap()
+            .proposal_id
+            .clone();
+        storage.approve_proposal(&id, "alice", "a-code", now).unwrap();
+
+        let later = now + chrono::Duration::minutes(6);
+        let err = storage.approve_proposal(&id, "bob", "b-code", later).unwrap_err();
+        assert!(err.to_string().contains("expired"), "{}", err);
+        assert_eq!(storage.proposal_status(&id, later).unwrap().status, ProposalStatus::Expired);
+        assert!(storage.propose_transfer("nobody", "sei-evm-testnet", "0xdead", "1", chrono::Duration::minutes(5), now).is_err());
+    }
+
+    #[test]
+    fn test_wallet_metadata_and_tag_filter() {
+        let mut storage = WalletStorage::new("pw");
+        let key = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+        storage.add_wallet("ops".into(), key, "0xabc".into(), "pw").unwrap();
+        storage.add_wallet("vault".into(), key, "0xdef".into(), "pw").unwrap();
+
+        let update = WalletMetadata {
+            label: Some("Ops hot wallet".into()),
+            tags: Some(vec!["Hot".into(), " testing ".into(), "hot".into(), "".into()]),
+            note: None,
+        };
+        assert!(storage.set_wallet_metadata("ops", update.clone(), "wrong").is_err());
+        let ops = storage.set_wallet_metadata("ops", update, "pw").unwrap();
+        assert_eq!(ops.tags, ["hot", "testing"]);
+        assert_eq!(ops.label.as_deref(), Some("Ops hot wallet"));
+
+        let cold = WalletMetadata { tags: Some(vec!["cold".into()]), note: Some("offline signer".into()), ..Default::default() };
+        storage.set_wallet_metadata("vault", cold, "pw").unwrap();
+
+        let names = |tag| storage.wallets_with_tag(tag).iter().map(|w| w.wallet_name.clone()).collect::<Vec<_>>();
+        assert_eq!(names(None), ["ops", "vault"]);
+        assert_eq!(names(Some("HOT")), ["ops"]);
+        assert_eq!(names(Some("cold")), ["vault"]);
+        assert!(names(Some("missing")).is_empty());
+
+        // Omitted fields are kept; an empty label clears it
+        let clear = WalletMetadata { label: Some(String::new()), ..Default::default() };
+        let ops = storage.set_wallet_metadata("ops", clear, "pw").unwrap();
+        assert_eq!((ops.label.clone(), ops.tags.len()), (None, 2));
+    }
+
+    #[test]
+    fn test_contact_names_are_unique_and_never_address_shaped() {
+        let mut storage = WalletStorage::default();
+        let contact = |name: &str, address: &str| Contact {
+            name: name.into(),
+            address: address.into(),
+            chain_id: "sei-evm-testnet".into(),
+            note: None,
+            created_at: Utc::now(),
+        };
+        storage.add_contact(contact(" Alice ", "0x00000000000000000000000000000000000000Aa"), false).unwrap();
+        assert_eq!(storage.contact("ALICE").unwrap().name, "Alice");
+
+        let err = storage.add_contact(contact("alice", "0x00000000000000000000000000000000000000Bb"), false).unwrap_err();
+        assert!(err.to_string().contains("already exists"), "{}", err);
+        let replaced = storage.add_contact(contact("alice", "0x00000000000000000000000000000000000000Bb"), true).unwrap();
+        assert_eq!(replaced.unwrap().name, "Alice");
+        assert_eq!(storage.contact("Alice").unwrap().address, "0x00000000000000000000000000000000000000Bb");
+
+        assert!(storage.add_contact(contact("0xbob", "0x00000000000000000000000000000000000000Cc"), false).is_err());
+        assert!(storage.add_contact(contact("sei1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq703fpu", "0x00"), false).is_err());
+        assert!(storage.add_contact(contact("  ", "0x00"), false).is_err());
+
+        storage.add_contact(contact("bob", "0x00000000000000000000000000000000000000Cc"), false).unwrap();
+        let names: Vec<_> = storage.list_contacts(None).iter().map(|c| c.name.clone()).collect();
+        assert_eq!(names, ["alice", "bob"]);
+        assert!(storage.list_contacts(Some("sei-evm-mainnet")).is_empty());
+        assert!(storage.remove_contact("BOB").is_some());
+        assert!(storage.remove_contact("bob").is_none());
+    }
+}
@@ -1,10 +1,20 @@
 //! Utility functions for the SEI MCP server
 
+use ethers_core::types::U256;
 use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::future::Future;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Duration;
 use serde_json::{Value, from_value};
+use tracing::warn;
 use crate::mcp::protocol::{Response, error_codes};
 
 /// Helper function to extract a required argument from a JSON object
+#[allow(clippy::result_large_err)]
 pub fn get_required_arg<T: DeserializeOwned>(
     args: &Value,
     key: &str,
@@ -23,3 +33,275 @@ pub fn get_required_arg<T: DeserializeOwned>(
 pub fn to_string<T: std::fmt::Display>(value: T) -> String {
     value.to_string()
 }
+
+/// Clips a JSON array in place to at most `max_items` entries.
+/// Returns the original length when entries were dropped, `None` otherwise.
+pub fn truncate_array(value: &mut Value, max_items: usize) -> Option<usize> {
+    let items = value.as_array_mut()?;
+    let total = items.len();
+    if total <= max_items {
+        return None;
+    }
+    items.truncate(max_items);
+    Some(total)
+}
+
+/// Reads an optional gas override (`gas_limit`/`gas_price`) as a decimal string or number.
+/// Empty, zero, or malformed values yield `None` so the caller falls back to estimation.
+pub fn parse_gas_override(args: &Value, key: &str) -> Option<U256> {
+    let raw = match args.get(key)? {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::Null => return None,
+        other => other.to_string(),
+    };
+    if raw.is_empty() {
+        return None;
+    }
+    match U256::from_dec_str(&raw) {
+        Ok(v) if !v.is_zero() => Some(v),
+        Ok(_) => {
+            warn!("Ignoring zero '{}'; falling back to RPC estimate", key);
+            None
+        }
+        Err(_) => {
+            warn!("Ignoring malformed '{}' value '{}'; falling back to RPC estimate", key, raw);
+            None
+        }
+    }
+}
+
+/// Rejects `amount` when it exceeds the configured cap for `chain_id`; chains without a cap are unlimited.
+/// A malformed cap fails closed so a typo in config never silently lifts the limit.
+pub fn check_transfer_cap(caps: &HashMap<String, String>, chain_id: &str, amount: U256) -> Result<(), String> {
+    let Some(raw) = caps.get(chain_id) else {
+        return Ok(());
+    };
+    let cap = U256::from_dec_str(raw.trim())
+        .map_err(|_| format!("Configured transfer cap for '{}' is not a valid amount", chain_id))?;
+    if amount > cap {
+        return Err(format!(
+            "Transfer amount {} exceeds the configured maximum of {} for '{}'",
+            amount, cap, chain_id
+        ));
+    }
+    Ok(())
+}
+
+/// The `pagination` object every list-returning tool includes, so callers page the same way
+/// everywhere. Pages are 1-based; `total` is `None` when the source doesn't report one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Pagination {
+    pub page: u64,
+    pub page_size: u64,
+    pub total: Option<u64>,
+    pub has_more: bool,
+    pub next_page: Option<u64>,
+}
+
+impl Pagination {
+    /// Pagination for page `page` of a list of `total` items.
+    pub fn of_total(page: u64, page_size: u64, total: u64) -> Self {
+        let has_more = page.saturating_mul(page_size) < total;
+        Pagination { page, page_size, total: Some(total), has_more, next_page: has_more.then_some(page + 1) }
+    }
+}
+
+/// Reads `page` (1-based, default 1) and `page_size` (default and cap `max_page_size`) arguments.
+pub fn page_args(args: &Value, max_page_size: u64) -> (u64, u64) {
+    let max_page_size = max_page_size.max(1);
+    let page = args.get("page").and_then(|v| v.as_u64()).unwrap_or(1).max(1);
+    let page_size = args
+        .get("page_size")
+        .and_then(|v| v.as_u64())
+        .map(|n| n.clamp(1, max_page_size))
+        .unwrap_or(max_page_size);
+    (page, page_size)
+}
+
+/// Cuts page `page` out of a list held in full.
+pub fn paginate<T>(items: Vec<T>, page: u64, page_size: u64) -> (Vec<T>, Pagination) {
+    let pagination = Pagination::of_total(page, page_size, items.len() as u64);
+    let start = (page - 1).saturating_mul(page_size).min(items.len() as u64) as usize;
+    let items = items.into_iter().skip(start).take(page_size as usize).collect();
+    (items, pagination)
+}
+
+/// Integer base-unit amounts below this are almost always a whole-token figure typed into a
+/// base-unit field ("100" meaning 100 SEI, not 100 wei).
+pub const SUSPICIOUS_BASE_UNITS: u64 = 1000;
+
+/// Catches whole-token figures passed where `field` expects base units (`decimals` places, e.g. 18 for wei).
+/// A decimal point can never be a base-unit amount, so it is an `Err` naming the likely intended value;
+/// an integer below `SUSPICIOUS_BASE_UNITS` comes back as a warning. Other input is left to the caller's parser.
+pub fn check_base_unit_amount(amount: &str, field: &str, decimals: u8) -> Result<Option<String>, String> {
+    let amount = amount.trim();
+    if amount.contains('.') && amount.parse::<f64>().is_ok() {
+        let suggestion = ethers_core::utils::parse_units(amount, decimals as u32)
+            .map(|v| format!(" If you meant {} SEI, pass {}.", amount, U256::from(v)))
+            .unwrap_or_default();
+        return Err(format!(
+            "'{}' takes an integer amount in base units (10^-{} SEI), but got '{}'.{}",
+            field, decimals, amount, suggestion
+        ));
+    }
+    match amount.parse::<u64>() {
+        Ok(v) if v > 0 && v < SUSPICIOUS_BASE_UNITS => {
+            let intended = U256::from(v) * U256::exp10(decimals as usize);
+            Ok(Some(format!(
+                "'{}' is {} base units, a dust amount (10^-{} SEI each). If you meant {} SEI, pass {}.",
+                field, v, decimals, v, intended
+            )))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Poll interval used when neither the call nor POLL_INTERVAL_MS sets one.
+pub const DEFAULT_POLL_INTERVAL_MS: u64 = 2000;
+
+/// How a polling tool re-checks: every `interval`, for at most `max_attempts` checks
+/// (0 = no limit), and never past `timeout` overall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollSettings {
+    pub interval: Duration,
+    pub max_attempts: u32,
+    pub timeout: Duration,
+}
+
+/// Runs `check` until it yields `Some`, then returns that value. Returns `Ok(None)` once
+/// `settings.max_attempts` checks or `settings.timeout` have passed without a result;
+/// the first error from `check` ends polling immediately.
+pub async fn poll_until<T, E, F, Fut>(settings: PollSettings, mut check: F) -> Result<Option<T>, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<T>, E>>,
+{
+    let deadline = tokio::time::Instant::now() + settings.timeout;
+    let mut attempts = 0;
+    loop {
+        if let Some(value) = check().await? {
+            return Ok(Some(value));
+        }
+        attempts += 1;
+        let now = tokio::time::Instant::now();
+        if now >= deadline || (settings.max_attempts > 0 && attempts >= settings.max_attempts) {
+            return Ok(None);
+        }
+        tokio::time::sleep(settings.interval.min(deadline - now)).await;
+    }
+}
+
+/// Writes `contents` to a sibling temp file and renames it over `path`, so a crash mid-write never
+/// leaves a truncated file behind.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut tmp_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("no file name in {}", path.display())))?
+        .to_os_string();
+    tmp_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let write = || -> io::Result<()> {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+    };
+    write().inspect_err(|_| {
+        let _ = fs::remove_file(&tmp_path);
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_poll_until_stops_after_max_attempts() {
+        let settings = PollSettings { interval: Duration::from_millis(1), max_attempts: 3, timeout: Duration::from_secs(60) };
+        let mut calls = 0;
+        let result: Result<Option<()>, String> = poll_until(settings, || {
+            calls += 1;
+            async { Ok(None) }
+        })
+        .await;
+        assert_eq!(result, Ok(None));
+        assert_eq!(calls, 3);
+    }
+
+    #[tokio::test]
+    async fn test_poll_until_returns_first_ready_value() {
+        let settings = PollSettings { interval: Duration::from_millis(1), max_attempts: 0, timeout: Duration::from_secs(60) };
+        let mut calls = 0;
+        let result: Result<Option<u32>, String> = poll_until(settings, || {
+            calls += 1;
+            let ready = (calls == 2).then_some(calls);
+            async move { Ok(ready) }
+        })
+        .await;
+        assert_eq!(result, Ok(Some(2)));
+    }
+
+    #[test]
+    fn test_truncate_array() {
+        let mut v = json!([1, 2, 3, 4]);
+        assert_eq!(truncate_array(&mut v, 2), Some(4));
+        assert_eq!(v, json!([1, 2]));
+        assert_eq!(truncate_array(&mut v, 5), None);
+        assert_eq!(truncate_array(&mut json!({"a": 1}), 1), None);
+    }
+
+    #[test]
+    fn test_check_base_unit_amount() {
+        let err = check_base_unit_amount("1.5", "amount_wei", 18).unwrap_err();
+        assert!(err.contains("pass 1500000000000000000"), "{}", err);
+        assert!(check_base_unit_amount("0.25", "amount_usei", 6).unwrap_err().contains("pass 250000"));
+
+        let warning = check_base_unit_amount("100", "amount_wei", 18).unwrap().unwrap();
+        assert!(warning.contains("pass 100000000000000000000"), "{}", warning);
+
+        assert_eq!(check_base_unit_amount("1000000000000000000", "amount_wei", 18), Ok(None));
+        assert_eq!(check_base_unit_amount("abc", "amount_wei", 18), Ok(None));
+    }
+
+    #[test]
+    fn test_check_transfer_cap() {
+        let caps = HashMap::from([
+            ("sei-evm-testnet".to_string(), "1000".to_string()),
+            ("broken".to_string(), "lots".to_string()),
+        ]);
+        assert!(check_transfer_cap(&caps, "sei-evm-testnet", U256::from(1000)).is_ok());
+        assert!(check_transfer_cap(&caps, "sei-evm-testnet", U256::from(1001)).is_err());
+        assert!(check_transfer_cap(&caps, "pacific-1", U256::MAX).is_ok());
+        assert!(check_transfer_cap(&caps, "broken", U256::one()).is_err());
+    }
+
+    #[test]
+    fn test_paginate() {
+        let (items, p) = paginate((1..=5).collect::<Vec<_>>(), 2, 2);
+        assert_eq!(items, [3, 4]);
+        assert_eq!(p, Pagination { page: 2, page_size: 2, total: Some(5), has_more: true, next_page: Some(3) });
+        let (items, p) = paginate((1..=5).collect::<Vec<_>>(), 3, 2);
+        assert_eq!((items, p.has_more, p.next_page), (vec![5], false, None));
+        let (items, p) = paginate((1..=5).collect::<Vec<_>>(), 9, 2);
+        assert!(items.is_empty() && !p.has_more);
+
+        assert_eq!(page_args(&json!({}), 100), (1, 100));
+        assert_eq!(page_args(&json!({"page": 0, "page_size": 500}), 100), (1, 100));
+        assert_eq!(page_args(&json!({"page": 3, "page_size": 10}), 100), (3, 10));
+    }
+
+    #[test]
+    fn test_parse_gas_override_falls_back() {
+        let args = json!({"empty": "", "zero": "0", "bad": "12abc", "neg": "-5", "num": 21000, "ok": " 50000 "});
+        assert_eq!(parse_gas_override(&args, "missing"), None);
+        assert_eq!(parse_gas_override(&args, "empty"), None);
+        assert_eq!(parse_gas_override(&args, "zero"), None);
+        assert_eq!(parse_gas_override(&args, "bad"), None);
+        assert_eq!(parse_gas_override(&args, "neg"), None);
+        assert_eq!(parse_gas_override(&args, "num"), Some(U256::from(21000)));
+        assert_eq!(parse_gas_override(&args, "ok"), Some(U256::from(50000)));
+    }
+}
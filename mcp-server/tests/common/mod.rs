@@ -0,0 +1,85 @@
+// Shared helpers for integration tests: MCP tool calls and a mock JSON-RPC node.
+// Each test binary uses a different subset of these.
+#![allow(dead_code)]
+
+use axum::{routing::post, Json, Router};
+use sei_mcp_server_rs::config::Config;
+use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+use sei_mcp_server_rs::mcp::protocol::Request;
+use sei_mcp_server_rs::AppState;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+pub const TX_HASH: &str = "0x9fc76417374aa880d4449a1f7f31ec597f00b1f6f3dd2d66f4c9c6c445836d8b";
+pub const DEPLOYED_ADDRESS: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+
+/// Sends a JSON-RPC request for `method` and returns the whole response.
+pub async fn send(state: &AppState, method: &str, params: Value) -> Value {
+    let req: Request = serde_json::from_value(json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params })).unwrap();
+    serde_json::to_value(handle_mcp_request(req, state.clone()).await.unwrap()).unwrap()
+}
+
+/// Calls `tool` through `tools/call` and returns the whole JSON-RPC response.
+pub async fn call(state: &AppState, tool: &str, arguments: Value) -> Value {
+    send(state, "tools/call", json!({ "name": tool, "arguments": arguments })).await
+}
+
+/// Default config with `sei-evm-testnet` served by `rpc_url`.
+pub fn testnet_config(rpc_url: String) -> Config {
+    Config { chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(), ..Config::default() }
+}
+
+/// State for `config` with its wallet file named `wallet_file` in the temp dir.
+pub fn app_state(config: Config, wallet_file: &str) -> AppState {
+    AppState::new(config, std::env::temp_dir().join(wallet_file))
+}
+
+/// Every request the mock node received, in order.
+pub type Calls = Arc<Mutex<Vec<Value>>>;
+
+/// JSON-RPC reply for `req` from a node that accepts everything; tests can wrap this to inject failures.
+pub fn default_reply(req: &Value) -> Value {
+    let result = match req["method"].as_str().unwrap_or_default() {
+        "eth_getTransactionCount" => json!("0x0"),
+        "eth_chainId" => json!("0x530"),
+        "eth_estimateGas" => json!("0x5208"),
+        "eth_gasPrice" => json!("0x3b9aca00"),
+        "eth_call" => json!("0x"),
+        "eth_sendRawTransaction" => json!(TX_HASH),
+        "eth_getTransactionReceipt" => json!({
+            "transactionHash": TX_HASH,
+            "status": "0x1",
+            "gasUsed": "0x1e8480",
+            "contractAddress": DEPLOYED_ADDRESS
+        }),
+        _ => Value::Null,
+    };
+    json!({ "jsonrpc": "2.0", "id": req["id"], "result": result })
+}
+
+/// Starts the default mock node (see `default_reply`).
+pub async fn spawn_mock_rpc() -> (String, Calls) {
+    spawn_rpc(default_reply).await
+}
+
+/// Starts a mock node on an ephemeral port answering with `reply`; returns its URL plus the call recorder.
+pub async fn spawn_rpc<F>(reply: F) -> (String, Calls)
+where
+    F: Fn(&Value) -> Value + Clone + Send + Sync + 'static,
+{
+    let calls: Calls = Arc::default();
+    let recorder = calls.clone();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let app = Router::new().route(
+        "/",
+        post(move |Json(req): Json<Value>| async move {
+            recorder.lock().unwrap().push(req.clone());
+            Json(reply(&req))
+        }),
+    );
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (url, calls)
+}
@@ -0,0 +1,863 @@
+mod common;
+
+mod call_contract_by_abi {
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::Request;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_payable_write_respects_transfer_cap() {
+        let config = Config {
+            max_transfer_amounts: [("sei-evm-testnet".to_string(), "100".to_string())].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-call-contract-cap-test.json"));
+
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "call_contract_by_abi",
+                "arguments": {
+                    "chain_id": "sei-evm-testnet",
+                    "contract_address": "0x000000000000000000000000000000000000dEaD",
+                    "function_name": "deposit",
+                    "private_key": "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+                    "value": "101"
+                }
+            }
+        }))
+        .unwrap();
+        let resp = serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap();
+        assert_eq!(resp["error"]["code"], -32602, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("exceeds"), "{}", resp);
+    }
+}
+
+mod compute_selector {
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::{error_codes, Request};
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    async fn compute_selector(signature: &str) -> Value {
+        let state = AppState::new(Config::default(), std::env::temp_dir().join("sei-mcp-selector-test.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "compute_selector", "arguments": { "signature": signature } }
+        }))
+        .unwrap();
+        serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_erc20_transfer_selector() {
+        let resp = compute_selector("function transfer(address to, uint amount) returns (bool)").await;
+        assert_eq!(resp["result"]["canonical_signature"], "transfer(address,uint256)", "{}", resp);
+        assert_eq!(resp["result"]["selector"], "0xa9059cbb");
+        assert!(resp["result"]["topic"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_event_topic() {
+        let resp = compute_selector("event Transfer(address indexed from, address indexed to, uint256 value)").await;
+        assert_eq!(resp["result"]["topic"], "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef", "{}", resp);
+        assert!(resp["result"]["selector"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_bare_signature_returns_both_and_bad_input_is_rejected() {
+        let resp = compute_selector("balanceOf(address)").await;
+        assert_eq!(resp["result"]["selector"], "0x70a08231", "{}", resp);
+        assert!(resp["result"]["topic"].as_str().unwrap().starts_with("0x70a08231"));
+
+        let resp = compute_selector("balanceOf(address").await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+    }
+}
+
+mod contract_creation {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::{error_codes, Request};
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const CONTRACT: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+    const DEPLOYER: &str = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+    const LATEST: u64 = 1000;
+    const CREATED_AT: u64 = 613;
+
+    fn block_number(param: &Value) -> u64 {
+        u64::from_str_radix(param.as_str().unwrap().trim_start_matches("0x"), 16).unwrap()
+    }
+
+    // A node where CONTRACT has code from CREATED_AT on; `top_level` says whether a plain
+    // transaction (rather than a factory's internal call) deployed it.
+    async fn get_contract_creation(top_level: bool, address: &str) -> (Value, common::Calls) {
+        let (rpc_url, calls) = common::spawn_rpc(move |req: &Value| {
+            let result = match req["method"].as_str() {
+                Some("eth_blockNumber") => json!(format!("0x{:x}", LATEST)),
+                Some("eth_getCode") => {
+                    let deployed = req["params"][0].as_str() == Some(CONTRACT) && block_number(&req["params"][1]) >= CREATED_AT;
+                    json!(if deployed { "0x6080604052" } else { "0x" })
+                }
+                Some("eth_getBlockByNumber") => json!({
+                    "number": req["params"][0],
+                    "transactions": [
+                        { "hash": "0x01", "from": DEPLOYER, "to": "0x000000000000000000000000000000000000dead" },
+                        { "hash": common::TX_HASH, "from": DEPLOYER, "to": null }
+                    ]
+                }),
+                Some("eth_getTransactionReceipt") => json!({
+                    "transactionHash": common::TX_HASH,
+                    "status": "0x1",
+                    "contractAddress": if top_level { CONTRACT } else { "0x0000000000000000000000000000000000000001" }
+                }),
+                _ => return common::default_reply(req),
+            };
+            json!({ "jsonrpc": "2.0", "id": req["id"], "result": result })
+        })
+        .await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-contract-creation-test.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "get_contract_creation", "arguments": { "address": address, "chain_id": "sei-evm-testnet" } }
+        }))
+        .unwrap();
+        (serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap(), calls)
+    }
+
+    #[tokio::test]
+    async fn test_bisects_eth_get_code_to_the_creation_block() {
+        let (resp, calls) = get_contract_creation(true, CONTRACT).await;
+        let result = &resp["result"];
+        assert_eq!(result["block_number"], CREATED_AT, "{}", resp);
+        assert_eq!(result["creator"], DEPLOYER);
+        assert_eq!(result["tx_hash"], common::TX_HASH);
+        assert_eq!(result["source"], "rpc");
+
+        // Logarithmic in chain height, not a linear scan
+        let probes = calls.lock().unwrap().iter().filter(|c| c["method"] == "eth_getCode").count();
+        assert!(probes <= 12, "{} eth_getCode calls", probes);
+    }
+
+    #[tokio::test]
+    async fn test_factory_deployment_reports_block_without_creator() {
+        let (resp, _) = get_contract_creation(false, CONTRACT).await;
+        let result = &resp["result"];
+        assert_eq!(result["block_number"], CREATED_AT, "{}", resp);
+        assert!(result["creator"].is_null());
+        assert!(result["tx_hash"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_address_without_code_is_not_found() {
+        let (resp, _) = get_contract_creation(true, "0x000000000000000000000000000000000000dEaD").await;
+        assert_eq!(resp["error"]["code"], error_codes::NOT_FOUND, "{}", resp);
+    }
+}
+
+mod decode_abi {
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::{error_codes, Request};
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    async fn decode_abi(data: &str, types: Value) -> Value {
+        let state = AppState::new(Config::default(), std::env::temp_dir().join("sei-mcp-decode-abi-test.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "decode_abi", "arguments": { "data": data, "types": types } }
+        }))
+        .unwrap();
+        serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_decodes_uint_and_string() {
+        // abi.encode(uint256(42), "Sei")
+        let data = concat!(
+            "0x000000000000000000000000000000000000000000000000000000000000002a",
+            "0000000000000000000000000000000000000000000000000000000000000040",
+            "0000000000000000000000000000000000000000000000000000000000000003",
+            "5365690000000000000000000000000000000000000000000000000000000000"
+        );
+        let resp = decode_abi(data, json!(["uint256", "string"])).await;
+        assert_eq!(resp["result"]["values"], json!(["42", "Sei"]), "{}", resp);
+        assert_eq!(resp["result"]["decoded"][1], json!({ "type": "string", "value": "Sei" }));
+    }
+
+    #[tokio::test]
+    async fn test_mismatched_types_are_rejected() {
+        let word = "0x000000000000000000000000000000000000000000000000000000000000002a";
+        let resp = decode_abi(word, json!(["uint256", "address"])).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("does not match"), "{}", resp);
+    }
+}
+
+mod decoded_events {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+    fn transfer_abi() -> Value {
+        json!([{ "type": "event", "name": "Transfer", "anonymous": false, "inputs": [
+            { "name": "from", "type": "address", "indexed": true },
+            { "name": "to", "type": "address", "indexed": true },
+            { "name": "value", "type": "uint256", "indexed": false }
+        ]}])
+    }
+
+    fn logs() -> Value {
+        json!([
+            {
+                "topics": [
+                    TRANSFER_TOPIC,
+                    "0x0000000000000000000000000000000000000000000000000000000000000000",
+                    "0x000000000000000000000000000000000000000000000000000000000000dead"
+                ],
+                "data": "0x0000000000000000000000000000000000000000000000000000000000000005",
+                "blockNumber": "0x10",
+                "transactionHash": common::TX_HASH,
+                "logIndex": "0x0"
+            },
+            {
+                "topics": ["0x1111111111111111111111111111111111111111111111111111111111111111"],
+                "data": "0x",
+                "blockNumber": "0x11",
+                "transactionHash": common::TX_HASH,
+                "logIndex": "0x1"
+            }
+        ])
+    }
+
+    async fn decoded_events(rpc_url: String, arguments: Value) -> Value {
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            mcp_max_items: 100,
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-decoded-events.json"));
+        common::call(&state, "get_decoded_events", arguments).await
+    }
+
+    #[tokio::test]
+    async fn test_decodes_known_events_and_passes_through_unknown() {
+        let (rpc_url, calls) = common::spawn_rpc(|req| json!({ "jsonrpc": "2.0", "id": req["id"], "result": logs() })).await;
+        let resp = decoded_events(
+            rpc_url,
+            json!({ "contract_address": common::DEPLOYED_ADDRESS, "abi": transfer_abi(), "from_block": "16", "to_block": "0x11" }),
+        )
+        .await;
+
+        let events = resp["result"]["events"].as_array().unwrap_or_else(|| panic!("{}", resp));
+        assert_eq!(resp["result"]["decoded_count"], 1);
+        assert_eq!(events[0]["event_name"], "Transfer");
+        assert_eq!(events[0]["args"]["value"], "5");
+        assert_eq!(events[0]["position"]["block_number"], "0x10");
+        assert_eq!(events[1]["decoded"], false);
+        assert_eq!(events[1]["log"]["logIndex"], "0x1");
+
+        let filter = &calls.lock().unwrap()[0]["params"][0];
+        assert_eq!(filter["fromBlock"], "0x10");
+        assert!(filter.get("topics").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_event_name_filters_on_topic0() {
+        let (rpc_url, calls) = common::spawn_rpc(|req| json!({ "jsonrpc": "2.0", "id": req["id"], "result": [] })).await;
+        let resp = decoded_events(
+            rpc_url,
+            json!({ "contract_address": common::DEPLOYED_ADDRESS, "abi": transfer_abi(), "event_name": "Transfer" }),
+        )
+        .await;
+
+        assert_eq!(resp["result"]["total_available"], 0, "{}", resp);
+        assert_eq!(calls.lock().unwrap()[0]["params"][0]["topics"], json!([TRANSFER_TOPIC]));
+    }
+}
+
+mod deploy_contract {
+    use super::common;
+    use ethers_core::types::{Transaction, U256};
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::Request;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn test_deploy_carries_constructor_value_and_gas_limit() {
+        let (rpc_url, calls) = common::spawn_mock_rpc().await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-deploy-test.json"));
+
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "deploy_contract",
+                "arguments": {
+                    "chain_id": "sei-evm-testnet",
+                    "private_key": "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+                    "bytecode": "0x6080604052",
+                    "abi": [{"type": "constructor", "stateMutability": "payable", "inputs": [{"name": "owner", "type": "address"}]}],
+                    "constructor_args": ["0x000000000000000000000000000000000000dEaD"],
+                    "value": "5",
+                    "gas_limit": "3000000"
+                }
+            }
+        }))
+        .unwrap();
+        let resp = serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap();
+        let result = &resp["result"];
+        assert_eq!(result["contract_address"], common::DEPLOYED_ADDRESS, "{}", resp);
+        assert_eq!(result["tx_hash"], common::TX_HASH);
+        assert_eq!(result["gas_used"], 2_000_000);
+        assert_eq!(result["status"], "success");
+
+        let calls = calls.lock().unwrap();
+        assert!(calls.iter().all(|c| c["method"] != "eth_estimateGas"), "explicit gas_limit must skip estimation");
+        let raw = calls
+            .iter()
+            .find(|c| c["method"] == "eth_sendRawTransaction")
+            .and_then(|c| c["params"][0].as_str())
+            .unwrap();
+        let tx: Transaction = ethers_core::utils::rlp::decode(&hex::decode(&raw[2..]).unwrap()).unwrap();
+        assert!(tx.to.is_none());
+        assert_eq!(tx.value, U256::from(5));
+        assert_eq!(tx.gas, U256::from(3_000_000));
+        // init code = bytecode ++ abi-encoded owner
+        let input: Value = json!(format!("0x{}", hex::encode(&tx.input)));
+        assert_eq!(input, json!("0x6080604052000000000000000000000000000000000000000000000000000000000000dead"));
+    }
+
+    #[tokio::test]
+    async fn test_receipt_polling_stops_after_max_attempts() {
+        let (rpc_url, calls) = common::spawn_rpc(|req: &Value| match req["method"].as_str() {
+            Some("eth_getTransactionReceipt") => json!({ "jsonrpc": "2.0", "id": req["id"], "result": null }),
+            _ => common::default_reply(req),
+        })
+        .await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            max_poll_attempts: 10,
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-deploy-poll-test.json"));
+
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "deploy_contract",
+                "arguments": {
+                    "chain_id": "sei-evm-testnet",
+                    "private_key": "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+                    "bytecode": "0x6080604052",
+                    "gas_limit": "3000000",
+                    "timeout_secs": 600,
+                    "poll_interval_ms": 10,
+                    "max_poll_attempts": 3
+                }
+            }
+        }))
+        .unwrap();
+        let started = std::time::Instant::now();
+        let resp = serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap();
+        assert!(started.elapsed().as_secs() < 10, "polling ran past max_poll_attempts");
+        assert_eq!(resp["result"]["status"], "pending", "{}", resp);
+        assert_eq!(resp["result"]["tx_hash"], common::TX_HASH);
+
+        let receipt_polls = calls.lock().unwrap().iter().filter(|c| c["method"] == "eth_getTransactionReceipt").count();
+        assert_eq!(receipt_polls, 3, "the per-call override beats MAX_POLL_ATTEMPTS");
+    }
+
+    #[tokio::test]
+    async fn test_zero_gas_limit_falls_back_to_estimation() {
+        let (rpc_url, calls) = common::spawn_mock_rpc().await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-deploy-zero-gas-test.json"));
+
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "deploy_contract",
+                "arguments": {
+                    "chain_id": "sei-evm-testnet",
+                    "private_key": "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+                    "bytecode": "0x6080604052",
+                    "gas_limit": "0"
+                }
+            }
+        }))
+        .unwrap();
+        let resp = serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap();
+        assert_eq!(resp["result"]["status"], "success", "{}", resp);
+        assert!(calls.lock().unwrap().iter().any(|c| c["method"] == "eth_estimateGas"), "a zero gas_limit is ignored");
+    }
+
+    #[tokio::test]
+    async fn test_constructor_value_respects_transfer_cap() {
+        let (rpc_url, calls) = common::spawn_mock_rpc().await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            max_transfer_amounts: [("sei-evm-testnet".to_string(), "100".to_string())].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-deploy-cap-test.json"));
+
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "deploy_contract",
+                "arguments": {
+                    "chain_id": "sei-evm-testnet",
+                    "private_key": "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+                    "bytecode": "0x6080604052",
+                    "value": "101"
+                }
+            }
+        }))
+        .unwrap();
+        let resp = serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap();
+        assert_eq!(resp["error"]["code"], -32602, "{}", resp);
+        assert!(calls.lock().unwrap().iter().all(|c| c["method"] != "eth_sendRawTransaction"));
+    }
+}
+
+mod estimate_deployment_cost {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const BYTECODE: &str = "0x6080604052";
+    // Error(string) with reason "owner required"
+    const REVERT_DATA: &str = "0x08c379a00000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000e6f776e6572207265717569726564000000000000000000000000000000000000";
+
+    // A node that estimates 21000 gas at 1 gwei, or rejects every creation when `reverts` is set.
+    async fn estimate(reverts: bool, arguments: Value) -> (Value, common::Calls) {
+        let (rpc_url, calls) = common::spawn_rpc(move |req: &Value| match req["method"].as_str() {
+            Some("eth_estimateGas" | "eth_call") if reverts => json!({
+                "jsonrpc": "2.0",
+                "id": req["id"],
+                "error": { "code": 3, "message": "execution reverted", "data": REVERT_DATA }
+            }),
+            _ => common::default_reply(req),
+        })
+        .await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-estimate-deployment-test.json"));
+        (common::call(&state, "estimate_deployment_cost", arguments).await, calls)
+    }
+
+    #[tokio::test]
+    async fn test_estimates_creation_call_with_constructor_args() {
+        let (resp, calls) = estimate(
+            false,
+            json!({
+                "chain_id": "sei-evm-testnet",
+                "bytecode": BYTECODE,
+                "abi": [{ "type": "constructor", "inputs": [{ "name": "supply", "type": "uint256" }], "stateMutability": "nonpayable" }],
+                "constructor_args": ["42"]
+            }),
+        )
+        .await;
+        let result = &resp["result"];
+        assert_eq!(result["gas_limit"], "21000", "{}", resp);
+        assert_eq!(result["gas_price"], "1000000000");
+        assert_eq!(result["fee"]["base"], "21000000000000");
+        assert_eq!(result["fee"]["sei"], "0.000021");
+
+        let calls = calls.lock().unwrap();
+        let estimate = calls.iter().find(|c| c["method"] == "eth_estimateGas").unwrap();
+        let tx = &estimate["params"][0];
+        assert!(tx.get("to").is_none(), "a creation call has no recipient: {}", tx);
+        let data = tx["data"].as_str().or(tx["input"].as_str()).unwrap();
+        assert!(data.starts_with(BYTECODE));
+        assert!(data.ends_with("2a"), "constructor argument appended: {}", data);
+    }
+
+    #[tokio::test]
+    async fn test_reverting_constructor_reports_reason() {
+        let (resp, _) = estimate(true, json!({ "chain_id": "sei-evm-testnet", "bytecode": BYTECODE })).await;
+        assert_eq!(resp["error"]["code"], error_codes::RPC_ERROR, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("owner required"), "{}", resp);
+        assert_eq!(resp["error"]["data"]["revert"]["reason"], "owner required");
+    }
+
+    #[tokio::test]
+    async fn test_constructor_args_without_abi_are_rejected() {
+        let (resp, calls) = estimate(false, json!({ "chain_id": "sei-evm-testnet", "bytecode": BYTECODE, "constructor_args": ["1"] })).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(calls.lock().unwrap().is_empty());
+    }
+}
+
+mod events_since {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const CONTRACT: &str = "0x5fbdb2315678afecb367f032d93f642f64180aa3";
+
+    // Head at block 40; the contract emitted two events in each of blocks 10, 20 and 30.
+    fn node(req: &Value) -> Value {
+        let hex = |v: &Value| u64::from_str_radix(v.as_str().unwrap().trim_start_matches("0x"), 16).unwrap();
+        let result = match req["method"].as_str().unwrap_or_default() {
+            "eth_blockNumber" => json!("0x28"),
+            "eth_getLogs" => {
+                let (from, to) = (hex(&req["params"][0]["fromBlock"]), hex(&req["params"][0]["toBlock"]));
+                let logs: Vec<Value> = [10u64, 20, 30]
+                    .iter()
+                    .filter(|b| (from..=to).contains(b))
+                    .flat_map(|b| (0..2).map(move |i| json!({ "address": CONTRACT, "blockNumber": format!("0x{:x}", b), "logIndex": format!("0x{:x}", i) })))
+                    .collect();
+                json!(logs)
+            }
+            _ => Value::Null,
+        };
+        json!({ "jsonrpc": "2.0", "id": req["id"], "result": result })
+    }
+
+    async fn events_since(last_seen_block: u64, max_items: Option<u64>) -> Value {
+        let (rpc_url, _) = common::spawn_rpc(node).await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            mcp_max_items: 100,
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-events-since-test.json"));
+        let mut arguments = json!({ "chain_id": "sei-evm-testnet", "contract_address": CONTRACT, "last_seen_block": last_seen_block });
+        if let Some(n) = max_items {
+            arguments["max_items"] = json!(n);
+        }
+        common::call(&state, "get_events_since", arguments).await["result"].clone()
+    }
+
+    #[tokio::test]
+    async fn test_returns_events_after_cursor() {
+        let result = events_since(15, None).await;
+        assert_eq!(result["count"], 4, "{}", result);
+        assert_eq!(result["events"][0]["blockNumber"], "0x14");
+        assert_eq!(result["next_cursor"], 40);
+        assert_eq!(result["has_more"], false);
+    }
+
+    #[tokio::test]
+    async fn test_nothing_new_returns_empty_with_cursor() {
+        let result = events_since(40, None).await;
+        assert_eq!(result["events"], json!([]), "{}", result);
+        assert_eq!(result["next_cursor"], 40);
+    }
+
+    #[tokio::test]
+    async fn test_capped_page_stops_at_block_boundary() {
+        // Three of six events fit, but block 20's pair must not be split across calls
+        let first = events_since(0, Some(3)).await;
+        assert_eq!(first["count"], 2, "{}", first);
+        assert_eq!(first["has_more"], true);
+        assert_eq!(first["next_cursor"], 19);
+
+        let second = events_since(19, Some(3)).await;
+        assert_eq!(second["events"][0]["blockNumber"], "0x14");
+        assert_eq!(second["next_cursor"], 29);
+    }
+}
+
+mod known_tokens {
+    use super::common;
+    use sei_mcp_server_rs::config::{Config, KnownToken};
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::Request;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const TOKEN: &str = "0x5FbDB2315678afecb367f032d93F642f64180aa3";
+    const DECIMALS_SELECTOR: &str = "0x313ce567";
+
+    // totalSupply() is 2_500_000 base units; decimals() claims 18.
+    fn token(req: &Value) -> Value {
+        let result = match req["params"][0]["data"].as_str() {
+            Some(DECIMALS_SELECTOR) => json!(format!("0x{:064x}", 18)),
+            Some(_) => json!(format!("0x{:064x}", 2_500_000)),
+            None => Value::Null,
+        };
+        json!({ "jsonrpc": "2.0", "id": req["id"], "result": result })
+    }
+
+    async fn token_supply(known_tokens: &[(&str, u8)]) -> (Value, common::Calls) {
+        let (rpc_url, calls) = common::spawn_rpc(token).await;
+        let known = known_tokens
+            .iter()
+            .map(|(address, decimals)| (address.to_string(), KnownToken { symbol: "USDC".into(), decimals: *decimals }))
+            .collect();
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            known_tokens: [("sei-evm-testnet".to_string(), known)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-known-tokens-test.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "get_token_supply", "arguments": { "chain_id": "sei-evm-testnet", "contract_address": TOKEN } }
+        }))
+        .unwrap();
+        (serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap(), calls)
+    }
+
+    fn decimals_calls(calls: &common::Calls) -> usize {
+        calls.lock().unwrap().iter().filter(|c| c["params"][0]["data"] == DECIMALS_SELECTOR).count()
+    }
+
+    #[tokio::test]
+    async fn test_known_token_skips_decimals_call() {
+        // Configured lowercase; looked up with the checksummed address
+        let (resp, calls) = token_supply(&[(&TOKEN.to_lowercase(), 6)]).await;
+        assert_eq!(resp["result"]["total_supply"], "2.5", "{}", resp);
+        assert_eq!(resp["result"]["symbol"], "USDC");
+        assert_eq!(resp["result"]["decimals_source"], "known_tokens");
+        assert_eq!(decimals_calls(&calls), 0);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_token_reads_decimals_and_warns() {
+        let (resp, calls) = token_supply(&[]).await;
+        assert_eq!(resp["result"]["decimals"], 18, "{}", resp);
+        assert_eq!(resp["result"]["decimals_source"], "contract");
+        assert!(resp["result"]["decimals_warning"].is_string());
+        assert_eq!(decimals_calls(&calls), 1);
+    }
+}
+
+mod token_balances {
+    use super::common;
+    use ethers_core::abi::{decode, encode, ParamType, Token};
+    use ethers_core::types::U256;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::{error_codes, Request};
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const MULTICALL: &str = "0xca11bde05977b3631167028862be2a173976ca11";
+    const HOLDER: &str = "0x1111111111111111111111111111111111111111";
+    const TOKEN_A: &str = "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    const TOKEN_B: &str = "0xbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+    const NOT_A_TOKEN: &str = "0xcccccccccccccccccccccccccccccccccccccccc";
+
+    // What each token answers to balanceOf, decimals() and symbol(); `None` is a revert.
+    fn token_reply(target: &str, data: &[u8]) -> Option<Vec<u8>> {
+        let (balance, decimals, symbol) = match target {
+            TOKEN_A => (U256::exp10(18) * 3 / 2, 18u8, "AAA"),
+            TOKEN_B => (U256::from(2_500_000), 6, "BBB"),
+            _ => return None,
+        };
+        match hex::encode(&data[..4]).as_str() {
+            "70a08231" => Some(encode(&[Token::Uint(balance)])),
+            "313ce567" => Some(encode(&[Token::Uint(decimals.into())])),
+            "95d89b41" => Some(encode(&[Token::String(symbol.into())])),
+            _ => None,
+        }
+    }
+
+    fn reply(req: &Value) -> Value {
+        if req["method"] != "eth_call" {
+            return common::default_reply(req);
+        }
+        let to = req["params"][0]["to"].as_str().unwrap().to_lowercase();
+        let data = hex::decode(req["params"][0]["data"].as_str().unwrap().trim_start_matches("0x")).unwrap();
+        let result = if to == MULTICALL {
+            let kind = ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::Address, ParamType::Bool, ParamType::Bytes])));
+            let calls = decode(&[kind], &data[4..]).unwrap().pop().unwrap().into_array().unwrap();
+            let results = calls
+                .into_iter()
+                .map(|call| {
+                    let call = call.into_tuple().unwrap();
+                    let target = format!("{:?}", call[0].clone().into_address().unwrap());
+                    let out = token_reply(&target, &call[2].clone().into_bytes().unwrap());
+                    Token::Tuple(vec![Token::Bool(out.is_some()), Token::Bytes(out.unwrap_or_default())])
+                })
+                .collect();
+            Some(encode(&[Token::Array(results)]))
+        } else {
+            token_reply(&to, &data)
+        };
+        match result {
+            Some(bytes) => json!({ "jsonrpc": "2.0", "id": req["id"], "result": format!("0x{}", hex::encode(bytes)) }),
+            None => json!({ "jsonrpc": "2.0", "id": req["id"], "error": { "code": 3, "message": "execution reverted" } }),
+        }
+    }
+
+    async fn token_balances(multicall3_addresses: &[(&str, &str)], tokens: &[&str]) -> (Value, common::Calls) {
+        let (rpc_url, calls) = common::spawn_rpc(reply).await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            multicall3_addresses: multicall3_addresses.iter().map(|(c, a)| (c.to_string(), a.to_string())).collect(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-token-balances-test.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "get_token_balances",
+                "arguments": { "chain_id": "sei-evm-testnet", "address": HOLDER, "token_contracts": tokens }
+            }
+        }))
+        .unwrap();
+        (serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap(), calls)
+    }
+
+    fn assert_balances(resp: &Value) {
+        let balances = &resp["result"]["balances"];
+        assert_eq!(balances[0], json!({ "contract": TOKEN_A, "symbol": "AAA", "decimals": 18, "raw": "1500000000000000000", "formatted": "1.5", "error": null }), "{}", resp);
+        assert_eq!(balances[1]["formatted"], "2.5");
+        assert_eq!(balances[1]["symbol"], "BBB");
+        assert_eq!(balances[2]["raw"], Value::Null);
+        assert!(balances[2]["error"].as_str().unwrap().contains("balanceOf"));
+        assert_eq!(resp["result"]["failed"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_default_multicall_reads_every_token_in_one_call() {
+        let (resp, calls) = token_balances(&[], &[TOKEN_A, TOKEN_B, NOT_A_TOKEN]).await;
+        assert_balances(&resp);
+        assert_eq!(resp["result"]["method"], "multicall3");
+        let calls = calls.lock().unwrap();
+        let eth_calls: Vec<_> = calls.iter().filter(|c| c["method"] == "eth_call").collect();
+        assert_eq!(eth_calls.len(), 1);
+        assert_eq!(eth_calls[0]["params"][0]["to"].as_str().unwrap().to_lowercase(), MULTICALL);
+    }
+
+    #[tokio::test]
+    async fn test_without_multicall_calls_each_token() {
+        let (resp, calls) = token_balances(&[("sei-evm-testnet", "")], &[TOKEN_A, TOKEN_B, NOT_A_TOKEN]).await;
+        assert_balances(&resp);
+        assert_eq!(resp["result"]["method"], "sequential");
+        assert_eq!(calls.lock().unwrap().iter().filter(|c| c["method"] == "eth_call").count(), 9);
+    }
+
+    #[tokio::test]
+    async fn test_failed_multicall_falls_back_to_sequential() {
+        // Nothing answers at this address, so the aggregate call reverts
+        let (resp, _calls) = token_balances(&[("sei-evm-testnet", NOT_A_TOKEN)], &[TOKEN_A, TOKEN_B, NOT_A_TOKEN]).await;
+        assert_balances(&resp);
+        assert_eq!(resp["result"]["method"], "sequential");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_token_contract_is_refused() {
+        let (resp, _calls) = token_balances(&[], &[TOKEN_A, "not-an-address"]).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        let (resp, _calls) = token_balances(&[], &[]).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS);
+    }
+}
+
+mod token_stats {
+    use super::common;
+    use ethers_core::abi::{encode, Token};
+    use ethers_core::types::U256;
+    use sei_mcp_server_rs::config::{Config, KnownToken};
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::Request;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const TOKEN: &str = "0xaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+
+    // A token with 2.5 million units of supply at 6 decimals.
+    async fn token_stats(known_tokens: Vec<(&str, KnownToken)>) -> Value {
+        let (rpc_url, _calls) = common::spawn_rpc(|req: &Value| {
+            if req["method"] != "eth_call" {
+                return common::default_reply(req);
+            }
+            let word = match &req["params"][0]["data"].as_str().unwrap()[..10] {
+                "0x18160ddd" => U256::from(2_500_000_000_000u64),
+                "0x313ce567" => U256::from(6),
+                _ => U256::zero(),
+            };
+            json!({ "jsonrpc": "2.0", "id": req["id"], "result": format!("0x{}", hex::encode(encode(&[Token::Uint(word)]))) })
+        })
+        .await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            known_tokens: [("sei-evm-testnet".to_string(), known_tokens.into_iter().map(|(a, t)| (a.to_string(), t)).collect())].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-token-stats-test.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "get_token_stats", "arguments": { "chain_id": "sei-evm-testnet", "contract_address": TOKEN } }
+        }))
+        .unwrap();
+        serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_unindexed_chain_reports_live_supply_and_notes_missing_counts() {
+        let resp = token_stats(Vec::new()).await;
+        let result = &resp["result"];
+        assert_eq!(result["total_supply_raw"], "2500000000000", "{}", resp);
+        assert_eq!(result["total_supply"], "2500000");
+        assert_eq!(result["decimals"], 6);
+        assert_eq!(result["holders"], Value::Null);
+        assert_eq!(result["sources"]["total_supply"], "rpc");
+        assert_eq!(result["stale"], false);
+        assert!(result["note"].as_str().unwrap().contains("does not index sei-evm-testnet"));
+    }
+
+    #[tokio::test]
+    async fn test_known_token_supplies_symbol_and_decimals() {
+        let resp = token_stats(vec![(TOKEN, KnownToken { symbol: "USDC".to_string(), decimals: 3 })]).await;
+        let result = &resp["result"];
+        assert_eq!(result["symbol"], "USDC", "{}", resp);
+        assert_eq!(result["total_supply"], "2500000000");
+        assert!(result["content"][0]["text"].as_str().unwrap().starts_with("USDC: supply 2500000000"));
+    }
+}
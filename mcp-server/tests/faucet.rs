@@ -0,0 +1,302 @@
+mod common;
+
+mod faucet_daily_cap {
+    use super::common;
+    use axum::{routing::post, Json, Router};
+    use sei_mcp_server_rs::config::{Config, FaucetToken, DEFAULT_FAUCET_AMOUNT};
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    const TX_HASH: &str = "0x9fc76417374aa880d4449a1f7f31ec597f00b1f6f3dd2d66f4c9c6c445836d8b";
+
+    // A faucet API that reports each drip's amount and counts how often it was called.
+    async fn spawn_faucet_api(drips: Arc<AtomicUsize>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let app = Router::new().route(
+            "/faucet/request",
+            post(move |Json(body): Json<Value>| async move {
+                drips.fetch_add(1, Ordering::SeqCst);
+                // Slow enough that concurrent requests overlap while the API is being called
+                tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                Json(json!({ "txHash": TX_HASH, "amount": body["amount"] }))
+            }),
+        );
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_daily_cap_stops_address_spraying() {
+        let drips = Arc::new(AtomicUsize::new(0));
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), "http://127.0.0.1:9".to_string())].into(),
+            faucet_api_url: spawn_faucet_api(drips.clone()).await,
+            faucet_amounts: [("sei-evm-testnet".to_string(), 400)].into(),
+            faucet_daily_caps: [("sei-evm-testnet".to_string(), 1000)].into(),
+            faucet_ledger_path: Some(dir.path().join("faucet_ledger.json")),
+            ..Config::default()
+        };
+        let state = AppState::new(config.clone(), dir.path().join("wallets.json"));
+
+        // Every drip goes to a fresh address, so only the chain-wide cap can stop it
+        for i in 0..2 {
+            let resp = common::call(&state, "request_faucet", json!({ "chain_id": "sei-evm-testnet", "address": format!("0x{:040x}", i + 1) })).await;
+            assert_eq!(resp["result"]["amount"], "400", "{}", resp);
+        }
+        let resp = common::call(&state, "request_faucet", json!({ "chain_id": "sei-evm-testnet", "address": format!("0x{:040x}", 3) })).await;
+        assert!(resp["error"]["message"].as_str().unwrap().contains("daily faucet limit reached"), "{}", resp);
+        assert_eq!(drips.load(Ordering::SeqCst), 2, "the refused request must not reach the faucet API");
+
+        let resp = common::call(&state, "faucet_daily_remaining", json!({})).await;
+        let today = &resp["result"]["chains"][0];
+        assert_eq!((today["dispensed"].as_u64(), today["remaining"].as_u64()), (Some(800), Some(200)), "{}", resp);
+
+        // A restarted server still sees today's total
+        let restarted = AppState::new(config, dir.path().join("wallets.json"));
+        let resp = common::call(&restarted, "faucet_daily_remaining", json!({ "chain_id": "sei-evm-testnet" })).await;
+        assert_eq!(resp["result"]["chains"][0]["remaining"], 200, "{}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_cannot_overshoot_the_cap() {
+        let drips = Arc::new(AtomicUsize::new(0));
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), "http://127.0.0.1:9".to_string())].into(),
+            faucet_api_url: spawn_faucet_api(drips.clone()).await,
+            faucet_amounts: [("sei-evm-testnet".to_string(), 400)].into(),
+            faucet_daily_caps: [("sei-evm-testnet".to_string(), 1000)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, dir.path().join("wallets.json"));
+
+        let requests = (0..6).map(|i| {
+            let state = state.clone();
+            async move { common::call(&state, "request_faucet", json!({ "chain_id": "sei-evm-testnet", "address": format!("0x{:040x}", i + 1) })).await }
+        });
+        let responses = futures::future::join_all(requests).await;
+        let granted = responses.iter().filter(|r| r["error"].is_null()).count();
+        assert_eq!(granted, 2, "{:?}", responses);
+        assert_eq!(drips.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_drip_gives_back_its_share_of_the_cap() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), "http://127.0.0.1:9".to_string())].into(),
+            // Nothing listens here, so every faucet call fails
+            faucet_api_url: "http://127.0.0.1:9".to_string(),
+            faucet_amounts: [("sei-evm-testnet".to_string(), 400)].into(),
+            faucet_daily_caps: [("sei-evm-testnet".to_string(), 1000)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, dir.path().join("wallets.json"));
+        let resp = common::call(&state, "request_faucet", json!({ "chain_id": "sei-evm-testnet", "address": format!("0x{:040x}", 1) })).await;
+        assert!(resp["error"].is_object(), "{}", resp);
+
+        let resp = common::call(&state, "faucet_daily_remaining", json!({ "chain_id": "sei-evm-testnet" })).await;
+        assert_eq!(resp["result"]["chains"][0]["remaining"], 1000, "{}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_requested_amounts_must_be_positive_and_are_capped_by_default() {
+        let drips = Arc::new(AtomicUsize::new(0));
+        let dir = tempfile::tempdir().unwrap();
+        // No FAUCET_AMOUNTS entry, so requests are held to DEFAULT_FAUCET_AMOUNT
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), "http://127.0.0.1:9".to_string())].into(),
+            faucet_api_url: spawn_faucet_api(drips.clone()).await,
+            ..Config::default()
+        };
+        let state = AppState::new(config, dir.path().join("wallets.json"));
+        let address = format!("0x{:040x}", 1);
+
+        for amount in [json!(0), json!("0"), json!(-5)] {
+            let resp = common::call(&state, "request_faucet", json!({ "chain_id": "sei-evm-testnet", "address": address, "amount": amount })).await;
+            assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        }
+        assert_eq!(drips.load(Ordering::SeqCst), 0);
+
+        let resp = common::call(&state, "request_faucet", json!({ "chain_id": "sei-evm-testnet", "address": address, "amount": "1000000000000" })).await;
+        assert_eq!(resp["result"]["amount"], DEFAULT_FAUCET_AMOUNT.to_string(), "{}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_failed_token_transfer_still_returns_the_native_drip() {
+        let drips = Arc::new(AtomicUsize::new(0));
+        let dir = tempfile::tempdir().unwrap();
+        // No TX_PRIVATE_KEY_EVM, so the token half of the drip cannot be sent
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), "http://127.0.0.1:9".to_string())].into(),
+            faucet_api_url: spawn_faucet_api(drips.clone()).await,
+            faucet_tokens: [(
+                "sei-evm-testnet".to_string(),
+                FaucetToken { contract: "0x000000000000000000000000000000000000dEaD".to_string(), amount: "1".to_string() },
+            )]
+            .into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, dir.path().join("wallets.json"));
+
+        let resp = common::call(&state, "request_faucet", json!({ "chain_id": "sei-evm-testnet", "address": format!("0x{:040x}", 1) })).await;
+        let result = &resp["result"];
+        assert_eq!(result["transaction_hash"], TX_HASH, "{}", resp);
+        assert_eq!(result["partial"], true);
+        assert!(result["token_transaction_hash"].is_null());
+        assert!(result["token_error"].as_str().unwrap().contains("TX_PRIVATE_KEY_EVM"), "{}", resp);
+        assert_eq!(result["transaction_hashes"], json!([TX_HASH]));
+        assert_eq!(drips.load(Ordering::SeqCst), 1);
+    }
+}
+
+mod faucet_status {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::Request;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    // Address of the well-known test key below
+    const FAUCET_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    const FAUCET_ADDRESS: &str = "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23";
+
+    async fn faucet_status(rpc_url: String, threshold: &str) -> Value {
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            tx_private_key_evm: FAUCET_KEY.to_string(),
+            faucet_low_balance: [("sei-evm-testnet".to_string(), threshold.to_string())].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-faucet-status.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "get_faucet_status", "arguments": {} }
+        }))
+        .unwrap();
+        serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap()
+    }
+
+    async fn node_with_balance(balance: &'static str) -> (String, common::Calls) {
+        common::spawn_rpc(move |req| {
+            if req["method"] == "eth_getBalance" {
+                return json!({ "jsonrpc": "2.0", "id": req["id"], "result": balance });
+            }
+            common::default_reply(req)
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_reports_low_faucet() {
+        let (rpc_url, calls) = node_with_balance("0x64").await;
+        let resp = faucet_status(rpc_url, "1000").await;
+
+        let status = &resp["result"]["faucets"][0];
+        assert_eq!(status["address"].as_str().unwrap().to_lowercase(), FAUCET_ADDRESS, "{}", resp);
+        assert_eq!(status["balance"], "100");
+        assert_eq!(status["low"], true);
+        assert_eq!(calls.lock().unwrap()[0]["params"][0].as_str().unwrap().to_lowercase(), FAUCET_ADDRESS);
+        assert!(!resp.to_string().contains(&FAUCET_KEY[2..]), "key must never be returned");
+    }
+
+    #[tokio::test]
+    async fn test_healthy_faucet_is_not_low() {
+        let (rpc_url, _) = node_with_balance("0x3e8").await;
+        let resp = faucet_status(rpc_url, "1000").await;
+        assert_eq!(resp["result"]["faucets"][0]["low"], false, "{}", resp);
+    }
+}
+
+mod faucet_verify {
+    use super::common;
+    use axum::{routing::post, Json, Router};
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::Request;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    async fn spawn_faucet_api() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let app = Router::new().route("/faucet/request", post(|| async { Json(json!({ "txHash": common::TX_HASH })) }));
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        url
+    }
+
+    async fn request_verified_faucet(rpc_url: String) -> Value {
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            faucet_api_url: spawn_faucet_api().await,
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-faucet-verify.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "request_faucet",
+                "arguments": {
+                    "chain_id": "sei-evm-testnet",
+                    "address": "0x000000000000000000000000000000000000dEaD",
+                    "verify": true,
+                    "verify_timeout_secs": 10
+                }
+            }
+        }))
+        .unwrap();
+        serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_verified_faucet_reports_balance_delta() {
+        // First balance read is before the drip; every later read sees the funds
+        let reads = Arc::new(AtomicUsize::new(0));
+        let (rpc_url, _) = common::spawn_rpc(move |req| {
+            if req["method"] == "eth_getBalance" {
+                let balance = if reads.fetch_add(1, Ordering::SeqCst) == 0 { "0x0" } else { "0x64" };
+                return json!({ "jsonrpc": "2.0", "id": req["id"], "result": balance });
+            }
+            common::default_reply(req)
+        })
+        .await;
+
+        let resp = request_verified_faucet(rpc_url).await;
+        let verification = &resp["result"]["verification"];
+        assert_eq!(verification["verified"], true, "{}", resp);
+        assert_eq!(verification["balance_before"], "0");
+        assert_eq!(verification["delta"], "100");
+    }
+
+    #[tokio::test]
+    async fn test_verified_faucet_reports_revert() {
+        let (rpc_url, _) = common::spawn_rpc(|req| match req["method"].as_str() {
+            Some("eth_getBalance") => json!({ "jsonrpc": "2.0", "id": req["id"], "result": "0x0" }),
+            Some("eth_getTransactionReceipt") => json!({
+                "jsonrpc": "2.0",
+                "id": req["id"],
+                "result": { "transactionHash": common::TX_HASH, "status": "0x0" }
+            }),
+            _ => common::default_reply(req),
+        })
+        .await;
+
+        let resp = request_verified_faucet(rpc_url).await;
+        let message = resp["error"]["message"].as_str().unwrap_or_default();
+        assert!(message.contains("reverted") && message.contains(common::TX_HASH), "{}", resp);
+    }
+}
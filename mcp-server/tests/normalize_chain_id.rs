@@ -1,4 +1,5 @@
-use sei_mcp_server_rs::mcp::handler::normalize_chain_id;
+use sei_mcp_server_rs::config::{Config, DEFAULT_NETWORK_NAMES};
+use sei_mcp_server_rs::mcp::handler::{normalize_chain_id, CHAIN_ID_ALIASES};
 
 #[test]
 fn test_normalize_chain_id_aliases() {
@@ -11,3 +12,31 @@ fn test_normalize_chain_id_aliases() {
 fn test_normalize_chain_id_trimming() {
     assert_eq!(normalize_chain_id("  sei-testnet  "), "sei-evm-testnet");
 }
+
+#[test]
+fn test_alias_table_resolves_to_canonical_ids() {
+    for (alias, canonical) in CHAIN_ID_ALIASES {
+        assert_eq!(normalize_chain_id(alias), *canonical);
+        assert_eq!(normalize_chain_id(canonical), *canonical, "canonical ids must be fixed points");
+    }
+}
+
+#[test]
+fn test_every_canonical_chain_has_a_default_network_name() {
+    let config = Config::default();
+    for (_, canonical) in CHAIN_ID_ALIASES {
+        assert!(DEFAULT_NETWORK_NAMES.iter().any(|(id, _)| id == canonical), "no name for {}", canonical);
+    }
+    assert_eq!(config.network_name("sei-evm-testnet"), "Sei Atlantic-2 Testnet (EVM)");
+    assert_eq!(config.network_name("my-devnet"), "my-devnet");
+}
+
+#[test]
+fn test_configured_network_name_overrides_default() {
+    let config = Config {
+        network_display_names: [("sei-evm-testnet".to_string(), "Team Testnet".to_string())].into(),
+        ..Config::default()
+    };
+    assert_eq!(config.network_name("sei-evm-testnet"), "Team Testnet");
+    assert_eq!(config.network_name("pacific-1"), "Sei Pacific-1 Mainnet (Cosmos)");
+}
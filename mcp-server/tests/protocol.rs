@@ -0,0 +1,1075 @@
+mod common;
+
+mod audit_log {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::Request;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const PRIVATE_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    #[tokio::test]
+    async fn test_transfer_writes_one_redacted_audit_entry() {
+        let (rpc_url, _) = common::spawn_mock_rpc().await;
+
+        let dir = tempfile::tempdir().unwrap();
+        let audit_path = dir.path().join("audit.jsonl");
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            audit_log_path: Some(audit_path.clone()),
+            ..Config::default()
+        };
+        let state = AppState::new(config, dir.path().join("wallets.json"));
+
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "transfer_evm",
+                "arguments": {
+                    "chain_id": "sei-evm-testnet",
+                    "private_key": PRIVATE_KEY,
+                    "to_address": "0x000000000000000000000000000000000000dEaD",
+                    "amount_wei": "1000"
+                }
+            }
+        }))
+        .unwrap();
+        let resp = serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap();
+        assert!(resp["error"].is_null(), "transfer failed: {}", resp);
+
+        let log = std::fs::read_to_string(&audit_path).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 1);
+        assert!(!log.contains(PRIVATE_KEY.trim_start_matches("0x")), "audit log leaked the private key");
+
+        let entry: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(entry["tool"], "transfer_evm");
+        assert_eq!(entry["chain_id"], "sei-evm-testnet");
+        assert_eq!(entry["amount"], "1000");
+        assert_eq!(entry["tx_hash"], common::TX_HASH);
+        assert_eq!(entry["from"], "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23");
+    }
+}
+
+mod batch_query {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::{error_codes, Request};
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const ADDRESS: &str = "0x000000000000000000000000000000000000dEaD";
+
+    async fn batch(queries: Value) -> (Value, common::Calls) {
+        let (rpc_url, calls) = common::spawn_rpc(|req: &Value| match req["method"].as_str() {
+            Some("eth_getBalance") => json!({ "jsonrpc": "2.0", "id": req["id"], "result": "0x2a" }),
+            _ => common::default_reply(req),
+        })
+        .await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-batch-query-test.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "batch_query", "arguments": { "queries": queries } }
+        }))
+        .unwrap();
+        (serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap(), calls)
+    }
+
+    #[tokio::test]
+    async fn test_results_keep_order_and_failures_stay_per_entry() {
+        let (resp, _) = batch(json!([
+            { "tool": "get_balance", "arguments": { "chain_id": "sei-evm-testnet", "address": ADDRESS } },
+            { "tool": "get_balance", "arguments": { "chain_id": "sei-evm-mainnet", "address": ADDRESS } },
+            { "tool": "compute_selector", "arguments": { "signature": "transfer(address,uint256)" } }
+        ]))
+        .await;
+        let result = &resp["result"];
+        assert_eq!(result["failed"], 1, "{}", resp);
+        let results = result["results"].as_array().unwrap();
+        assert_eq!(results.len(), 3);
+
+        assert_eq!(results[0]["tool"], "get_balance");
+        assert_eq!(results[0]["ok"], true);
+        assert_eq!(results[0]["result"]["balance"]["amount"], "42");
+
+        assert_eq!(results[1]["ok"], false, "no RPC configured for mainnet");
+        assert!(results[1]["error"]["message"].is_string());
+
+        assert_eq!(results[2]["ok"], true);
+        assert_eq!(results[2]["result"]["selector"], "0xa9059cbb");
+    }
+
+    #[tokio::test]
+    async fn test_state_changing_tool_refuses_the_whole_batch() {
+        let (resp, calls) = batch(json!([
+            { "tool": "get_balance", "arguments": { "chain_id": "sei-evm-testnet", "address": ADDRESS } },
+            { "tool": "transfer_evm", "arguments": { "chain_id": "sei-evm-testnet", "to_address": ADDRESS, "amount": "1" } }
+        ]))
+        .await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("transfer_evm"));
+        assert!(calls.lock().unwrap().is_empty(), "nothing in a refused batch runs");
+    }
+
+    #[tokio::test]
+    async fn test_contract_call_with_private_key_is_refused() {
+        let (resp, _) = batch(json!([{
+            "tool": "call_contract_by_abi",
+            "arguments": { "chain_id": "sei-evm-testnet", "contract_address": ADDRESS, "function_name": "approve", "private_key": "0x01" }
+        }]))
+        .await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_batches_cannot_nest() {
+        let (resp, _) = batch(json!([{ "tool": "batch_query", "arguments": { "queries": [] } }])).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+    }
+}
+
+mod cancellation {
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_connection_request;
+    use sei_mcp_server_rs::mcp::protocol::Request;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::json;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_cancellation_only_reaches_the_same_connection() {
+        // Accepts connections but never answers, so the balance lookup hangs until cancelled
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), format!("http://{}", listener.local_addr().unwrap()))].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-cancellation-test.json"));
+        let request = |value: serde_json::Value| serde_json::from_value::<Request>(value).unwrap();
+        let balance = request(json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "tools/call",
+            "params": { "name": "get_balance", "arguments": { "chain_id": "sei-evm-testnet", "address": "0x000000000000000000000000000000000000dEaD" } }
+        }));
+        let cancel = || request(json!({ "jsonrpc": "2.0", "method": "notifications/cancelled", "params": { "requestId": 7 } }));
+
+        let pending = tokio::spawn(handle_connection_request("client-a", balance, state.clone()));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert!(handle_connection_request("client-b", cancel(), state.clone()).await.is_none());
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!pending.is_finished(), "another connection's cancellation must not touch client-a's request");
+
+        handle_connection_request("client-a", cancel(), state.clone()).await;
+        let response = tokio::time::timeout(Duration::from_secs(5), pending).await.expect("request was not cancelled").unwrap();
+        assert!(response.is_none(), "a cancelled request is not answered");
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_in_flight_id_is_rejected() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), format!("http://{}", listener.local_addr().unwrap()))].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-cancellation-duplicate-test.json"));
+        let balance = || {
+            serde_json::from_value::<Request>(json!({
+                "jsonrpc": "2.0",
+                "id": 9,
+                "method": "tools/call",
+                "params": { "name": "get_balance", "arguments": { "chain_id": "sei-evm-testnet", "address": "0x000000000000000000000000000000000000dEaD" } }
+            }))
+            .unwrap()
+        };
+
+        let pending = tokio::spawn(handle_connection_request("client-a", balance(), state.clone()));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let duplicate = handle_connection_request("client-a", balance(), state.clone()).await.unwrap();
+        assert_eq!(duplicate.error.unwrap().code, sei_mcp_server_rs::mcp::protocol::error_codes::INVALID_REQUEST);
+        let other = tokio::spawn(handle_connection_request("client-b", balance(), state.clone()));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!other.is_finished(), "ids are per connection, so client-b's request runs");
+        other.abort();
+
+        let cancel = serde_json::from_value::<Request>(json!({ "jsonrpc": "2.0", "method": "notifications/cancelled", "params": { "requestId": 9 } })).unwrap();
+        handle_connection_request("client-a", cancel, state.clone()).await;
+        let response = tokio::time::timeout(Duration::from_secs(5), pending).await.expect("the first request stays cancellable").unwrap();
+        assert!(response.is_none());
+    }
+}
+
+mod correlation_id {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    fn app_state(correlation_ids: bool) -> AppState {
+        let config = Config { correlation_ids, ..Config::default() };
+        common::app_state(config, "sei-mcp-correlation-id-test.json")
+    }
+
+    fn selector_args() -> Value {
+        json!({ "signature": "transfer(address,uint256)" })
+    }
+
+    #[tokio::test]
+    async fn test_each_response_gets_its_own_correlation_id() {
+        let state = app_state(true);
+        let first = common::call(&state, "compute_selector", selector_args()).await;
+        let id = first["result"]["correlation_id"].as_str().unwrap();
+        assert!(uuid::Uuid::parse_str(id).is_ok(), "{}", id);
+        assert!(!first["result"]["content"][0]["text"].as_str().unwrap().contains(id), "kept out of the content text");
+
+        let second = common::call(&state, "compute_selector", selector_args()).await;
+        assert_ne!(second["result"]["correlation_id"], first["result"]["correlation_id"]);
+
+        let failed = common::call(&state, "compute_selector", json!({})).await;
+        assert_eq!(failed["error"]["code"], error_codes::INVALID_PARAMS, "{}", failed);
+        assert!(failed["error"]["data"]["correlation_id"].is_string(), "{}", failed);
+    }
+
+    #[tokio::test]
+    async fn test_correlation_ids_are_off_by_default() {
+        let resp = common::call(&app_state(false), "compute_selector", selector_args()).await;
+        assert_eq!(resp["result"]["selector"], "0xa9059cbb", "{}", resp);
+        assert!(resp["result"].get("correlation_id").is_none());
+    }
+}
+
+mod exposed_chains {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    async fn state_exposing(rpc_url: &str, exposed: &[&str]) -> AppState {
+        let config = Config {
+            chain_rpc_urls: [
+                ("sei-evm-testnet".to_string(), rpc_url.to_string()),
+                ("sei-evm-mainnet".to_string(), rpc_url.to_string()),
+            ]
+            .into(),
+            mcp_exposed_chains: exposed.iter().map(|c| c.to_string()).collect(),
+            ..Config::default()
+        };
+        AppState::new(config, std::env::temp_dir().join("sei-mcp-exposed-chains.json"))
+    }
+
+    const ADDRESS: &str = "0x000000000000000000000000000000000000dEaD";
+
+    #[tokio::test]
+    async fn test_hidden_chain_is_rejected_despite_rpc_url() {
+        let (rpc_url, calls) = common::spawn_mock_rpc().await;
+        let state = state_exposing(&rpc_url, &["sei-evm-testnet"]).await;
+
+        let args = json!({ "name": "get_balance", "arguments": { "chain_id": "sei-mainnet", "address": ADDRESS } });
+        let resp = common::send(&state, "tools/call", args).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("sei-evm-mainnet"));
+
+        // Tools that fall back to a default chain are checked too
+        let state = state_exposing(&rpc_url, &["sei-evm-mainnet"]).await;
+        let args = json!({ "name": "eth_call_raw", "arguments": { "to": ADDRESS } });
+        let resp = common::send(&state, "tools/call", args).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(calls.lock().unwrap().is_empty(), "no RPC traffic to a hidden chain");
+    }
+
+    #[tokio::test]
+    async fn test_exposed_chain_still_works() {
+        let (rpc_url, _) = common::spawn_mock_rpc().await;
+        let state = state_exposing(&rpc_url, &["sei-evm-testnet"]).await;
+        let args = json!({ "name": "eth_call_raw", "arguments": { "to": ADDRESS, "chain_id": "sei-testnet" } });
+        let resp = common::send(&state, "tools/call", args).await;
+        assert!(resp["error"].is_null(), "{}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_tools_list_enumerates_exposed_chains() {
+        let state = state_exposing("http://127.0.0.1:1", &["sei-evm-testnet"]).await;
+        let resp = common::send(&state, "tools/list", json!({})).await;
+        let tools = resp["result"]["tools"].as_array().unwrap();
+        let with_chain: Vec<&Value> = tools
+            .iter()
+            .filter_map(|t| t.pointer("/inputSchema/properties/chain_id"))
+            .collect();
+        assert!(!with_chain.is_empty());
+        for chain_id in with_chain {
+            assert_eq!(chain_id["enum"], json!(["sei-evm-testnet"]));
+        }
+
+        let state = state_exposing("http://127.0.0.1:1", &[]).await;
+        let resp = common::send(&state, "tools/list", json!({})).await;
+        assert!(resp["result"]["tools"][0].pointer("/inputSchema/properties/chain_id/enum").is_none());
+    }
+}
+
+mod http_transport {
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::transport::http_router;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    async fn serve(config: Config) -> String {
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-http-test.json"));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, http_router(state)).await.unwrap();
+        });
+        base
+    }
+
+    // Reads the SSE body until one complete event arrives; returns (event name, data).
+    async fn next_event(resp: &mut reqwest::Response, buf: &mut String) -> (String, String) {
+        loop {
+            if let Some(end) = buf.find("\n\n") {
+                let raw: String = buf.drain(..end + 2).collect();
+                let field = |name: &str| {
+                    raw.lines()
+                        .find_map(|l| l.strip_prefix(name))
+                        .map(|v| v.trim_start().to_string())
+                        .unwrap_or_default()
+                };
+                return (field("event:"), field("data:"));
+            }
+            let chunk = resp.chunk().await.unwrap().expect("stream ended");
+            buf.push_str(&String::from_utf8_lossy(&chunk));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_post_returns_response_in_body() {
+        let base = serve(Config { mcp_max_line_bytes: 1 << 20, mcp_http_allow_unauthenticated: true, ..Config::default() }).await;
+        let client = reqwest::Client::new();
+        let resp: Value = client
+            .post(format!("{}/mcp", base))
+            .json(&json!({"jsonrpc": "2.0", "id": 1, "method": "initialize"}))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(resp["id"], 1);
+        assert!(resp["result"]["serverInfo"].is_object());
+
+        let notification = client
+            .post(format!("{}/mcp", base))
+            .json(&json!({"jsonrpc": "2.0", "method": "notifications/initialized"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(notification.status(), 202);
+    }
+
+    #[tokio::test]
+    async fn test_token_is_required_when_configured() {
+        let base = serve(Config {
+            mcp_max_line_bytes: 1 << 20,
+            mcp_http_token: Some("s3cret".into()),
+            ..Config::default()
+        })
+        .await;
+        let client = reqwest::Client::new();
+        let body = json!({"jsonrpc": "2.0", "id": 1, "method": "ping"});
+        let denied = client.post(format!("{}/mcp", base)).json(&body).send().await.unwrap();
+        assert_eq!(denied.status(), 401);
+        let denied_sse = client.get(format!("{}/mcp/sse", base)).bearer_auth("wrong").send().await.unwrap();
+        assert_eq!(denied_sse.status(), 401);
+        let prefix = client.post(format!("{}/mcp", base)).bearer_auth("s3cre").json(&body).send().await.unwrap();
+        assert_eq!(prefix.status(), 401);
+        let allowed = client.post(format!("{}/mcp", base)).bearer_auth("s3cret").json(&body).send().await.unwrap();
+        assert_eq!(allowed.status(), 200);
+    }
+
+    #[tokio::test]
+    async fn test_no_token_refuses_everyone_unless_opted_out() {
+        let base = serve(Config { mcp_max_line_bytes: 1 << 20, ..Config::default() }).await;
+        let client = reqwest::Client::new();
+        let body = json!({"jsonrpc": "2.0", "id": 1, "method": "ping"});
+        let denied = client.post(format!("{}/mcp", base)).json(&body).send().await.unwrap();
+        assert_eq!(denied.status(), 401);
+        let denied_sse = client.get(format!("{}/mcp/sse", base)).send().await.unwrap();
+        assert_eq!(denied_sse.status(), 401);
+    }
+
+    #[tokio::test]
+    async fn test_sse_session_receives_responses() {
+        let base = serve(Config { mcp_max_line_bytes: 1 << 20, mcp_http_allow_unauthenticated: true, ..Config::default() }).await;
+        let client = reqwest::Client::new();
+        let mut stream = client.get(format!("{}/mcp/sse", base)).send().await.unwrap();
+        let mut buf = String::new();
+        let (event, endpoint) = next_event(&mut stream, &mut buf).await;
+        assert_eq!(event, "endpoint");
+        assert!(endpoint.starts_with("/mcp?session_id="));
+
+        let accepted = client
+            .post(format!("{}{}", base, endpoint))
+            .json(&json!({"jsonrpc": "2.0", "id": "p1", "method": "ping"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(accepted.status(), 202);
+
+        let (event, data) = next_event(&mut stream, &mut buf).await;
+        assert_eq!(event, "message");
+        let resp: Value = serde_json::from_str(&data).unwrap();
+        assert_eq!(resp["id"], "p1");
+        assert_eq!(resp["result"], json!({}));
+
+        let unknown = client
+            .post(format!("{}/mcp?session_id=nope", base))
+            .json(&json!({"jsonrpc": "2.0", "id": 2, "method": "ping"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(unknown.status(), 404);
+    }
+}
+
+mod initialize {
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::Request;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    fn chains() -> std::collections::HashMap<String, String> {
+        [
+            ("sei-evm-testnet".to_string(), "http://127.0.0.1:1".to_string()),
+            ("sei-evm-mainnet".to_string(), "http://127.0.0.1:2".to_string()),
+        ]
+        .into()
+    }
+
+    async fn initialize(config: Config) -> Value {
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-initialize-test.json"));
+        let req: Request = serde_json::from_value(json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} })).unwrap();
+        serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_default_instructions_list_configured_chains() {
+        let resp = initialize(Config { chain_rpc_urls: chains(), ..Config::default() }).await;
+        let result = &resp["result"];
+        let instructions = result["instructions"].as_str().unwrap();
+        assert!(instructions.starts_with("Sei EVM blockchain MCP server"), "{}", instructions);
+        assert!(instructions.contains("sei-evm-mainnet (Sei Pacific-1 Mainnet (EVM)), sei-evm-testnet (Sei Atlantic-2 Testnet (EVM))"));
+        assert_eq!(result["capabilities"], json!({ "tools": { "listChanged": false } }));
+    }
+
+    #[tokio::test]
+    async fn test_operator_overrides_instructions_and_capabilities() {
+        let config = Config {
+            chain_rpc_urls: chains(),
+            mcp_exposed_chains: vec!["sei-evm-testnet".to_string()],
+            mcp_instructions: Some("Testnet only; never move real funds.".to_string()),
+            mcp_capabilities: json!({ "tools": { "listChanged": true }, "logging": {} }).as_object().unwrap().clone(),
+            ..Config::default()
+        };
+        let resp = initialize(config).await;
+        let result = &resp["result"];
+        let instructions = result["instructions"].as_str().unwrap();
+        assert!(instructions.starts_with("Testnet only; never move real funds."), "{}", instructions);
+        // Only the chains exposed over MCP are advertised
+        assert!(instructions.contains("sei-evm-testnet"));
+        assert!(!instructions.contains("sei-evm-mainnet"));
+        assert_eq!(result["capabilities"], json!({ "tools": { "listChanged": true }, "logging": {} }));
+        assert_eq!(result["serverInfo"]["name"], "sei-mcp-server-rs");
+    }
+}
+
+mod request_id {
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::{error_codes, parse_request};
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    // Ids of every allowed type, including look-alikes that must not be coerced into each other
+    fn ids() -> Vec<Value> {
+        vec![json!(7), json!("7"), json!(0), json!(-3), json!(2.5), json!("req-abc"), json!(""), Value::Null]
+    }
+
+    // One request per response path: success, method/tool not found, bad params, tool-level error, bad jsonrpc
+    fn bodies() -> Vec<Value> {
+        vec![
+            json!({ "jsonrpc": "2.0", "method": "ping" }),
+            json!({ "jsonrpc": "2.0", "method": "tools/list" }),
+            json!({ "jsonrpc": "2.0", "method": "no_such_method" }),
+            json!({ "jsonrpc": "2.0", "method": "tools/call" }),
+            json!({ "jsonrpc": "2.0", "method": "tools/call", "params": {} }),
+            json!({ "jsonrpc": "2.0", "method": "tools/call", "params": { "name": "no_such_tool" } }),
+            json!({ "jsonrpc": "2.0", "method": "tools/call", "params": { "name": "get_balance", "arguments": {} } }),
+            json!({ "jsonrpc": "2.0", "method": "get_balance", "params": {} }),
+            json!({ "jsonrpc": "1.0", "method": "ping" }),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_response_id_matches_request_id_exactly() {
+        let state = AppState::new(Config::default(), std::env::temp_dir().join("sei-mcp-request-id.json"));
+        for id in ids() {
+            for mut body in bodies() {
+                body["id"] = id.clone();
+                let req = parse_request(&body.to_string()).unwrap();
+                let resp = handle_mcp_request(req, state.clone()).await.expect("requests with an id are answered");
+                let resp = serde_json::to_value(resp).unwrap();
+                assert_eq!(resp["id"], id, "request {} got response {}", body, resp);
+                assert!(resp.as_object().unwrap().contains_key("id"), "{}", resp);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_absent_id_is_a_notification() {
+        let state = AppState::new(Config::default(), std::env::temp_dir().join("sei-mcp-request-id.json"));
+        let req = parse_request(r#"{"jsonrpc":"2.0","method":"ping"}"#).unwrap();
+        assert!(req.is_notification());
+        assert!(handle_mcp_request(req, state).await.is_none());
+
+        let req = parse_request(r#"{"jsonrpc":"2.0","id":null,"method":"ping"}"#).unwrap();
+        assert!(!req.is_notification());
+    }
+
+    #[test]
+    fn test_invalid_request_keeps_id() {
+        let err = parse_request(r#"{"jsonrpc":"2.0","id":"abc"}"#).unwrap_err();
+        assert_eq!(err.id, json!("abc"));
+        assert_eq!(err.error.unwrap().code, error_codes::INVALID_REQUEST);
+
+        let err = parse_request(r#"{"jsonrpc":"2.0","id":9,"method":5}"#).unwrap_err();
+        assert_eq!(err.id, json!(9));
+    }
+
+    #[test]
+    fn test_unparseable_or_structured_id_gets_null() {
+        let err = parse_request(r#"{"jsonrpc":"2.0","id":1,"#).unwrap_err();
+        assert_eq!(err.id, Value::Null);
+        assert_eq!(err.error.unwrap().code, error_codes::PARSE_ERROR);
+
+        let err = parse_request(r#"{"jsonrpc":"2.0","id":{"n":1},"method":"ping"}"#).unwrap_err();
+        assert_eq!(err.id, Value::Null);
+        assert_eq!(err.error.unwrap().code, error_codes::INVALID_REQUEST);
+    }
+}
+
+mod request_validation {
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::{error_codes, Request};
+    use sei_mcp_server_rs::AppState;
+
+    async fn call(raw: &str) -> serde_json::Value {
+        let state = AppState::new(Config::default(), std::env::temp_dir().join("sei-mcp-validation-test.json"));
+        let req: Request = serde_json::from_str(raw).unwrap();
+        let resp = handle_mcp_request(req, state).await.expect("request should be answered");
+        serde_json::to_value(resp).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rejects_wrong_jsonrpc_version() {
+        let resp = call(r#"{"jsonrpc":"1.0","id":1,"method":"ping"}"#).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_REQUEST);
+        assert_eq!(resp["id"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_missing_jsonrpc() {
+        let resp = call(r#"{"id":2,"method":"ping"}"#).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_empty_method() {
+        let resp = call(r#"{"jsonrpc":"2.0","id":3,"method":""}"#).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_accepts_valid_request() {
+        let resp = call(r#"{"jsonrpc":"2.0","id":4,"method":"ping"}"#).await;
+        assert!(resp["error"].is_null());
+        assert_eq!(resp["result"], serde_json::json!({}));
+    }
+}
+
+mod service_errors {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::{error_codes, Request};
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    async fn transfer_with_node(reply: fn(&Value) -> Value) -> Value {
+        let (rpc_url, _) = common::spawn_rpc(reply).await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-service-errors.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "transfer_evm",
+                "arguments": {
+                    "chain_id": "sei-evm-testnet",
+                    "private_key": "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+                    "to_address": "0x000000000000000000000000000000000000dEaD",
+                    "amount_wei": "1000"
+                }
+            }
+        }))
+        .unwrap();
+        serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_insufficient_funds_has_its_own_code() {
+        let resp = transfer_with_node(|req| match req["method"].as_str() {
+            Some("eth_estimateGas") => json!({
+                "jsonrpc": "2.0",
+                "id": req["id"],
+                "error": { "code": -32000, "message": "insufficient funds for gas * price + value" }
+            }),
+            _ => common::default_reply(req),
+        })
+        .await;
+        assert_eq!(resp["error"]["code"], error_codes::INSUFFICIENT_FUNDS, "{}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_node_failure_is_reported_as_rpc_error() {
+        let resp = transfer_with_node(|req| match req["method"].as_str() {
+            Some("eth_sendRawTransaction") => json!({
+                "jsonrpc": "2.0",
+                "id": req["id"],
+                "error": { "code": -32000, "message": "nonce too low" }
+            }),
+            _ => common::default_reply(req),
+        })
+        .await;
+        assert_eq!(resp["error"]["code"], error_codes::RPC_ERROR, "{}", resp);
+        assert_eq!(resp["error"]["data"]["rejection"]["category"], "nonce_too_low");
+        assert!(resp["error"]["data"]["rejection"]["hint"].as_str().unwrap().contains("resync_nonce"));
+    }
+}
+
+mod stdio_loop {
+    use super::common;
+    use ethers_core::types::{TransactionRequest, U256};
+    use ethers_signers::LocalWallet;
+    use futures::future::BoxFuture;
+    use sei_mcp_server_rs::blockchain::client::{ChainBackend, SeiClient};
+    use sei_mcp_server_rs::blockchain::models::{BalanceResponse, TransactionResponse};
+    use sei_mcp_server_rs::blockchain::nonce_manager::NonceManager;
+    use sei_mcp_server_rs::blockchain::services::transactions::{FeePolicy, GasQuote, SentTransaction};
+    use sei_mcp_server_rs::config::{Config, TxType};
+    use sei_mcp_server_rs::mcp::transport::run_stdio_loop;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+
+    // Nothing listens here; any request that bypasses the mock backend fails the test.
+    const UNREACHABLE_RPC: &str = "http://127.0.0.1:9";
+    const SENDER_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    /// Canned chain: every address holds 5 SEI and every send is accepted with `common::TX_HASH`.
+    #[derive(Default)]
+    struct MockBackend {
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl ChainBackend for MockBackend {
+        fn get_balance<'a>(&'a self, _rpc_url: &'a str, address: &'a str, _is_native: bool) -> BoxFuture<'a, anyhow::Result<BalanceResponse>> {
+            self.calls.lock().unwrap().push(format!("get_balance {}", address));
+            Box::pin(async { Ok(BalanceResponse { amount: "5000000000000000000".to_string(), denom: "usei".to_string() }) })
+        }
+
+        fn send_evm_transaction<'a>(
+            &'a self,
+            _rpc_url: &'a str,
+            _wallet: LocalWallet,
+            tx_request: TransactionRequest,
+            _nonce_manager: &'a NonceManager,
+            _policy: FeePolicy,
+        ) -> BoxFuture<'a, anyhow::Result<SentTransaction>> {
+            self.calls.lock().unwrap().push(format!("send {}", tx_request.value.unwrap_or_default()));
+            Box::pin(async {
+                Ok(SentTransaction {
+                    response: TransactionResponse { tx_hash: common::TX_HASH.to_string() },
+                    fees: GasQuote { gas_limit: U256::from(21_000), max_fee_per_gas: U256::from(1_000_000_000u64), max_priority_fee_per_gas: None },
+                    bumps: 0,
+                })
+            })
+        }
+
+        fn quote_gas<'a>(&'a self, _rpc_url: &'a str, _tx_request: TransactionRequest, _tx_type: TxType) -> BoxFuture<'a, anyhow::Result<GasQuote>> {
+            Box::pin(async { Ok(GasQuote { gas_limit: U256::from(21_000), max_fee_per_gas: U256::from(1_000_000_000u64), max_priority_fee_per_gas: None }) })
+        }
+
+        fn eth_call<'a>(&'a self, _rpc_url: &'a str, _call_obj: Value, _block: &'a str) -> BoxFuture<'a, anyhow::Result<String>> {
+            Box::pin(async { Ok("0x".to_string()) })
+        }
+    }
+
+    // Feeds `lines` through the stdio loop and returns the responses keyed by request id order.
+    async fn run_session(lines: &[Value], backend: Arc<MockBackend>) -> Vec<Value> {
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), UNREACHABLE_RPC.to_string())].into(),
+            ..Config::default()
+        };
+        let mut state = AppState::new(config.clone(), std::env::temp_dir().join("sei-mcp-stdio-loop-test.json"));
+        state.sei_client = SeiClient::new(&config.chain_rpc_urls, &config.websocket_url).with_backend(backend);
+
+        let input: String = lines.iter().map(|l| format!("{}\n", l)).collect();
+        let mut output = Vec::new();
+        run_stdio_loop(tokio::io::BufReader::new(input.as_bytes()), &mut output, state, 64 * 1024).await;
+
+        let mut responses: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        responses.sort_by_key(|r| r["id"].as_u64());
+        responses
+    }
+
+    #[tokio::test]
+    async fn test_session_lists_tools_and_calls_them_against_mock_chain() {
+        let backend = Arc::new(MockBackend::default());
+        let lines = [
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+            json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "tools/call", "params": { "name": "get_balance", "arguments": {
+                "chain_id": "sei-evm-testnet",
+                "address": "0x000000000000000000000000000000000000dEaD"
+            }}}),
+            json!({ "jsonrpc": "2.0", "id": 4, "method": "tools/call", "params": { "name": "transfer_evm", "arguments": {
+                "private_key": SENDER_KEY,
+                "chain_id": "sei-evm-testnet",
+                "to_address": "0x000000000000000000000000000000000000dEaD",
+                "amount_wei": "1000000000000000",
+                "gas_price": "1000000000"
+            }}}),
+        ];
+        let responses = run_session(&lines, backend.clone()).await;
+
+        // The notification gets no reply
+        assert_eq!(responses.iter().map(|r| r["id"].as_u64().unwrap()).collect::<Vec<_>>(), [1, 2, 3, 4]);
+        assert!(responses[0]["result"]["serverInfo"].is_object(), "{}", responses[0]);
+
+        let tools = responses[1]["result"]["tools"].as_array().unwrap();
+        for name in ["get_balance", "transfer_evm"] {
+            assert!(tools.iter().any(|t| t["name"] == name), "{} missing from tools/list", name);
+        }
+
+        assert_eq!(responses[2]["result"]["balance"]["amount"], "5000000000000000000", "{}", responses[2]);
+        assert_eq!(responses[3]["result"]["tx_hash"], common::TX_HASH, "{}", responses[3]);
+
+        let calls = backend.calls.lock().unwrap().clone();
+        assert_eq!(calls, ["get_balance 0x000000000000000000000000000000000000dEaD", "send 1000000000000000"]);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_line_does_not_end_session() {
+        let backend = Arc::new(MockBackend::default());
+        let lines = [
+            json!("not a request"),
+            json!({ "jsonrpc": "2.0", "id": 7, "method": "tools/call", "params": { "name": "get_balance", "arguments": {
+                "chain_id": "sei-evm-testnet",
+                "address": "0x000000000000000000000000000000000000dEaD"
+            }}}),
+        ];
+        let responses = run_session(&lines, backend).await;
+        let answered = responses.iter().find(|r| r["id"] == 7).unwrap();
+        assert_eq!(answered["result"]["balance"]["denom"], "usei", "{}", answered);
+    }
+}
+
+mod tool_allowlist {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::json;
+
+    fn app_state(enabled: &[&str], disabled: &[&str]) -> AppState {
+        let config = Config {
+            enabled_tools: enabled.iter().map(|t| t.to_string()).collect(),
+            disabled_tools: disabled.iter().map(|t| t.to_string()).collect(),
+            ..Config::default()
+        };
+        common::app_state(config, "sei-mcp-tool-allowlist-test.json")
+    }
+
+    async fn listed_tools(state: &AppState) -> Vec<String> {
+        let resp = common::send(state, "tools/list", json!({})).await;
+        resp["result"]["tools"].as_array().unwrap().iter().map(|t| t["name"].as_str().unwrap().to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_disabled_tool_is_unlisted_and_rejected() {
+        let state = app_state(&[], &["compute_selector"]);
+        let tools = listed_tools(&state).await;
+        assert!(!tools.iter().any(|t| t == "compute_selector"));
+        assert!(tools.iter().any(|t| t == "decode_abi"), "other tools stay listed");
+
+        let resp = common::call(&state, "compute_selector", json!({ "signature": "transfer(address,uint256)" })).await;
+        assert_eq!(resp["error"]["code"], error_codes::METHOD_NOT_FOUND, "{}", resp);
+        // Neither the direct-method alias nor batch_query gets around it
+        let resp = common::send(&state, "compute_selector", json!({ "signature": "transfer(address,uint256)" })).await;
+        assert_eq!(resp["error"]["code"], error_codes::METHOD_NOT_FOUND, "{}", resp);
+        let batch = json!({ "queries": [{ "tool": "compute_selector", "arguments": { "signature": "transfer(address,uint256)" } }] });
+        let resp = common::call(&state, "batch_query", batch).await;
+        assert_eq!(resp["error"]["code"], error_codes::METHOD_NOT_FOUND, "{}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_allowlist_limits_surface_and_denylist_wins() {
+        let state = app_state(&["compute_selector", "list_methods", "decode_abi"], &["decode_abi"]);
+        let mut tools = listed_tools(&state).await;
+        tools.sort();
+        assert_eq!(tools, ["compute_selector", "list_methods"]);
+
+        let resp = common::call(&state, "compute_selector", json!({ "signature": "transfer(address,uint256)" })).await;
+        assert_eq!(resp["result"]["selector"], "0xa9059cbb", "{}", resp);
+        let resp = common::call(&state, "decode_abi", json!({})).await;
+        assert_eq!(resp["error"]["code"], error_codes::METHOD_NOT_FOUND);
+        let resp = common::call(&state, "get_balance", json!({ "chain_id": "sei-evm-testnet", "address": "0x0" })).await;
+        assert_eq!(resp["error"]["code"], error_codes::METHOD_NOT_FOUND);
+
+        let resp = common::call(&state, "list_methods", json!({})).await;
+        let direct: Vec<&str> = resp["result"]["direct_methods"].as_array().unwrap().iter().map(|m| m["method"].as_str().unwrap()).collect();
+        assert_eq!(direct, ["list_methods"]);
+    }
+}
+
+mod tool_registry {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::tool_registry;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::json;
+    use std::collections::HashSet;
+    use std::time::Duration;
+
+    fn app_state(dir: &tempfile::TempDir) -> AppState {
+        AppState::new(Config::default(), dir.path().join("wallets.json"))
+    }
+
+    #[test]
+    fn test_tool_names_are_unique_and_have_schemas() {
+        let mut seen = HashSet::new();
+        for tool in tool_registry() {
+            assert!(seen.insert(tool.name), "duplicate tool: {}", tool.name);
+            assert_eq!(tool.definition["name"], tool.name);
+            assert!(tool.definition["description"].is_string(), "{} has no description", tool.name);
+            assert_eq!(tool.definition["inputSchema"]["type"], "object", "{} has no object input schema", tool.name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_every_listed_tool_is_dispatched() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = app_state(&dir);
+        let listed = common::send(&state, "tools/list", json!({})).await;
+        let names: Vec<String> = listed["result"]["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["name"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(names.len(), tool_registry().len());
+
+        for name in names {
+            // Empty arguments make real tools fail validation fast; an unknown name fails as "Tool not found".
+            // A call that hangs proves nothing either way, so it fails the test too.
+            let resp = tokio::time::timeout(Duration::from_secs(5), common::call(&state, &name, json!({})))
+                .await
+                .unwrap_or_else(|_| panic!("{} did not answer empty arguments within 5s", name));
+            assert_ne!(resp["error"]["code"], error_codes::METHOD_NOT_FOUND, "{} is listed but not dispatched: {}", name, resp);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_only_allowlisted_tools_are_direct_methods() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = app_state(&dir);
+        let resp = common::send(&state, "redirect_to_seidocs", json!({})).await;
+        assert!(resp["result"].is_object(), "{}", resp);
+        // Registered tools outside the allowlist answer only through tools/call
+        let resp = common::send(&state, "transfer_from_wallet", json!({})).await;
+        assert_eq!(resp["error"]["code"], error_codes::METHOD_NOT_FOUND, "{}", resp);
+        let resp = common::send(&state, "not_a_tool", json!({})).await;
+        assert_eq!(resp["error"]["code"], error_codes::METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_list_methods_covers_protocol_and_direct_methods() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = app_state(&dir);
+        let resp = common::send(&state, "list_methods", json!({})).await;
+        let result = &resp["result"];
+        let protocol: Vec<&str> = result["protocol_methods"].as_array().unwrap().iter().map(|m| m["method"].as_str().unwrap()).collect();
+        for method in ["initialize", "ping", "tools/list", "tools/call"] {
+            assert!(protocol.contains(&method), "{} missing from {:?}", method, protocol);
+        }
+        let direct: HashSet<&str> = result["direct_methods"].as_array().unwrap().iter().map(|m| m["method"].as_str().unwrap()).collect();
+        assert!(direct.contains("get_balance") && direct.contains("list_methods"), "{:?}", direct);
+        assert!(!direct.contains("transfer_from_wallet"), "{:?}", direct);
+        for method in &direct {
+            assert!(tool_registry().iter().any(|tool| tool.name == *method), "{} is not a registered tool", method);
+        }
+    }
+}
+
+mod tool_timeout {
+    use super::common;
+    use sei_mcp_server_rs::config::{Config, SpendingBudget};
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn test_timeout_reports_already_broadcast_tx() {
+        // The receipt never arrives, so deploy_contract would poll for its full timeout_secs
+        let (rpc_url, _) = common::spawn_rpc(|req: &Value| match req["method"].as_str() {
+            Some("eth_getTransactionReceipt") => json!({ "jsonrpc": "2.0", "id": req["id"], "result": null }),
+            _ => common::default_reply(req),
+        })
+        .await;
+        let state = common::app_state(common::testnet_config(rpc_url), "sei-mcp-timeout-deploy-test.json");
+
+        let started = Instant::now();
+        let resp = common::call(
+            &state,
+            "deploy_contract",
+            json!({
+                "chain_id": "sei-evm-testnet",
+                "private_key": "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+                "bytecode": "0x6080604052",
+                "gas_limit": "3000000",
+                "timeout_secs": 60,
+                "_timeout_ms": 300
+            }),
+        )
+        .await;
+        assert!(started.elapsed().as_secs() < 10, "deadline was not enforced");
+        assert_eq!(resp["error"]["code"], error_codes::TIMEOUT, "{}", resp);
+        let message = resp["error"]["message"].as_str().unwrap();
+        assert!(message.contains("timed out after 300 ms"), "{}", message);
+        assert!(message.contains(common::TX_HASH), "{}", message);
+        assert_eq!(resp["error"]["data"]["broadcast_tx_hashes"], json!([common::TX_HASH]));
+        assert_eq!(resp["error"]["data"]["outcome"], "unknown");
+    }
+
+    #[tokio::test]
+    async fn test_send_past_its_deadline_keeps_its_spend_reserved() {
+        let (rpc_url, _) = common::spawn_rpc(|req: &Value| match req["method"].as_str() {
+            Some("eth_getTransactionReceipt") => json!({ "jsonrpc": "2.0", "id": req["id"], "result": null }),
+            _ => common::default_reply(req),
+        })
+        .await;
+        let budget = SpendingBudget { amount: "1000".to_string(), window_secs: 3600 };
+        let config = Config {
+            spending_budgets: [("sei-evm-testnet".to_string(), [("*".to_string(), budget)].into())].into(),
+            ..common::testnet_config(rpc_url)
+        };
+        let state = common::app_state(config, "sei-mcp-timeout-budget-test.json");
+
+        let resp = common::call(
+            &state,
+            "deploy_contract",
+            json!({
+                "chain_id": "sei-evm-testnet",
+                "private_key": "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+                "bytecode": "0x6080604052",
+                "gas_limit": "3000000",
+                "value": "600",
+                "timeout_secs": 60,
+                "_timeout_ms": 300
+            }),
+        )
+        .await;
+        assert_eq!(resp["error"]["code"], error_codes::TIMEOUT, "{}", resp);
+        let status = common::call(
+            &state,
+            "get_spending_status",
+            json!({ "chain_id": "sei-evm-testnet", "address": "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23" }),
+        )
+        .await;
+        assert_eq!(status["result"]["spent_wei"], "600", "the deploy was sent, so its spend must not be released: {}", status);
+    }
+
+    #[tokio::test]
+    async fn test_read_tool_timeout_is_abandoned() {
+        // Accepts connections but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let rpc_url = format!("http://{}", listener.local_addr().unwrap());
+        let state = common::app_state(common::testnet_config(rpc_url), "sei-mcp-timeout-read-test.json");
+
+        let resp = common::call(
+            &state,
+            "get_balance",
+            json!({ "chain_id": "sei-evm-testnet", "address": "0x000000000000000000000000000000000000dEaD", "_timeout_ms": 200 }),
+        )
+        .await;
+        assert_eq!(resp["error"]["code"], error_codes::TIMEOUT, "{}", resp);
+        assert_eq!(resp["error"]["data"]["outcome"], "abandoned");
+    }
+
+    #[tokio::test]
+    async fn test_timeout_field_is_stripped_before_the_tool_runs() {
+        let (rpc_url, calls) = common::spawn_mock_rpc().await;
+        let state = common::app_state(common::testnet_config(rpc_url), "sei-mcp-timeout-strip-test.json");
+
+        let resp = common::call(
+            &state,
+            "eth_call_raw",
+            json!({
+                "chain_id": "sei-evm-testnet",
+                "to": "0x000000000000000000000000000000000000dEaD",
+                "data": "0x",
+                "_timeout_ms": 5000
+            }),
+        )
+        .await;
+        assert!(resp["error"].is_null(), "{}", resp);
+        let calls = calls.lock().unwrap();
+        let eth_call = calls.iter().find(|c| c["method"] == "eth_call").unwrap();
+        assert!(!eth_call.to_string().contains("_timeout_ms"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_non_positive_timeout() {
+        let state = AppState::new(Config::default(), std::env::temp_dir().join("sei-mcp-timeout-invalid-test.json"));
+        for bad in [json!(0), json!(-5), json!("100")] {
+            let resp = common::call(&state, "create_wallet", json!({ "_timeout_ms": bad })).await;
+            assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        }
+    }
+}
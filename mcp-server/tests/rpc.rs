@@ -0,0 +1,1135 @@
+mod common;
+
+mod balance_at_block {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const ADDRESS: &str = "0x000000000000000000000000000000000000dEaD";
+
+    async fn balance_at(arguments: Value, pruned: bool) -> (Value, common::Calls) {
+        let (rpc_url, calls) = common::spawn_rpc(move |req: &Value| match req["method"].as_str() {
+            Some("eth_getBalance") if pruned => json!({
+                "jsonrpc": "2.0",
+                "id": req["id"],
+                "error": { "code": -32000, "message": "missing trie node 7f3a (path ) state is not available" }
+            }),
+            // 1.5 SEI
+            Some("eth_getBalance") => json!({ "jsonrpc": "2.0", "id": req["id"], "result": "0x14d1120d7b160000" }),
+            _ => common::default_reply(req),
+        })
+        .await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-balance-at-block-test.json"));
+        (common::call(&state, "get_balance_at_block", arguments).await, calls)
+    }
+
+    #[tokio::test]
+    async fn test_block_number_is_passed_to_get_balance() {
+        let (resp, calls) = balance_at(json!({ "chain_id": "sei-evm-testnet", "address": ADDRESS, "block": 1000 }), false).await;
+        let result = &resp["result"];
+        assert_eq!(result["balance"]["amount"], "1500000000000000000", "{}", resp);
+        assert_eq!(result["balance_formatted"], "1.5");
+        assert_eq!(result["block"], "0x3e8");
+
+        let calls = calls.lock().unwrap();
+        let call = calls.iter().find(|c| c["method"] == "eth_getBalance").unwrap();
+        assert_eq!(call["params"], json!([ADDRESS, "0x3e8"]));
+    }
+
+    #[tokio::test]
+    async fn test_block_hash_uses_eip1898_object() {
+        let hash = format!("0x{}", "ab".repeat(32));
+        let (resp, calls) = balance_at(json!({ "chain_id": "sei-evm-testnet", "address": ADDRESS, "block": hash }), false).await;
+        assert!(resp["error"].is_null(), "{}", resp);
+        let calls = calls.lock().unwrap();
+        let call = calls.iter().find(|c| c["method"] == "eth_getBalance").unwrap();
+        assert_eq!(call["params"][1], json!({ "blockHash": hash }));
+    }
+
+    #[tokio::test]
+    async fn test_pruned_node_explains_archive_requirement() {
+        let (resp, _) = balance_at(json!({ "chain_id": "sei-evm-testnet", "address": ADDRESS, "block": "0x1" }), true).await;
+        assert_eq!(resp["error"]["code"], error_codes::RPC_ERROR, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("archive node"), "{}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_native_chain() {
+        let (resp, _) = balance_at(json!({ "chain_id": "atlantic-2", "address": ADDRESS, "block": 1 }), false).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+    }
+}
+
+mod balances_multi {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::{error_codes, Request};
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const RICH: &str = "0x000000000000000000000000000000000000dEaD";
+    const BROKE: &str = "0x000000000000000000000000000000000000bEEF";
+
+    async fn balances(rpc_url: String, addresses: Value) -> Value {
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-balances-multi.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "get_balances_multi", "arguments": { "chain_id": "sei-evm-testnet", "addresses": addresses } }
+        }))
+        .unwrap();
+        serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_one_failure_does_not_fail_the_batch() {
+        let (rpc_url, _) = common::spawn_rpc(|req| {
+            if req["params"][0] == BROKE {
+                return json!({ "jsonrpc": "2.0", "id": req["id"], "error": { "code": -32000, "message": "boom" } });
+            }
+            json!({ "jsonrpc": "2.0", "id": req["id"], "result": "0x2a" })
+        })
+        .await;
+        let resp = balances(rpc_url, json!([RICH, BROKE])).await;
+
+        let result = &resp["result"];
+        assert_eq!(result["balances"][RICH]["amount"], "42", "{}", resp);
+        assert!(result["balances"][BROKE]["error"].as_str().unwrap().contains("boom"));
+        assert_eq!(result["failed"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_address_count_is_capped() {
+        let (rpc_url, calls) = common::spawn_mock_rpc().await;
+        let resp = balances(rpc_url, json!(vec![RICH; 101])).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS);
+        assert!(calls.lock().unwrap().is_empty());
+    }
+}
+
+mod base_fee {
+    use super::common;
+    use ethers_core::types::transaction::eip2718::TypedTransaction;
+    use ethers_core::types::U256;
+    use ethers_core::utils::rlp::Rlp;
+    use sei_mcp_server_rs::config::{Config, TxType};
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    // A node at block 0x20 whose latest block carries `base_fee` (hex), or no baseFeePerGas at all
+    async fn node(base_fee: Option<&'static str>) -> (String, common::Calls) {
+        common::spawn_rpc(move |req: &Value| {
+            if req["method"] != "eth_getBlockByNumber" {
+                return common::default_reply(req);
+            }
+            let mut block = json!({ "number": "0x20", "hash": format!("0x{:064x}", 0x20) });
+            if let Some(fee) = base_fee {
+                block["baseFeePerGas"] = json!(fee);
+            }
+            json!({ "jsonrpc": "2.0", "id": req["id"], "result": block })
+        })
+        .await
+    }
+
+    fn app_state(rpc_url: String, tx_type: Option<TxType>) -> AppState {
+        let config = Config {
+            tx_types: tx_type.map(|t| [("sei-evm-testnet".to_string(), t)].into()).unwrap_or_default(),
+            ..common::testnet_config(rpc_url)
+        };
+        common::app_state(config, "sei-mcp-base-fee-test.json")
+    }
+
+    fn block_lookups(calls: &common::Calls) -> usize {
+        calls.lock().unwrap().iter().filter(|c| c["method"] == "eth_getBlockByNumber").count()
+    }
+
+    #[tokio::test]
+    async fn test_reports_latest_base_fee_and_caches_it() {
+        let (rpc_url, calls) = node(Some("0x59682f00")).await;
+        let state = app_state(rpc_url, None);
+        let resp = common::call(&state, "get_base_fee", json!({ "chain_id": "sei-evm-testnet" })).await;
+        let result = &resp["result"];
+        assert_eq!(result["base_fee_wei"], "1500000000", "{}", resp);
+        assert_eq!(result["base_fee_gwei"], "1.5");
+        assert_eq!(result["block_number"], 32);
+        assert_eq!(result["eip1559"], true);
+        assert_eq!(result["cached"], false);
+
+        let resp = common::call(&state, "get_base_fee", json!({ "chain_id": "sei-evm-testnet" })).await;
+        assert_eq!(resp["result"]["cached"], true, "{}", resp);
+        assert_eq!(block_lookups(&calls), 1);
+    }
+
+    #[tokio::test]
+    async fn test_pre_1559_chain_reports_base_fee_unavailable() {
+        let (rpc_url, _calls) = node(None).await;
+        let resp = common::call(&app_state(rpc_url, None), "get_base_fee", json!({ "chain_id": "sei-evm-testnet" })).await;
+        let result = &resp["result"];
+        assert_eq!(result["eip1559"], false, "{}", resp);
+        assert_eq!(result["base_fee_wei"], Value::Null);
+        assert!(result["content"][0]["text"].as_str().unwrap().contains("unavailable"));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_with_only_a_priority_fee_builds_on_the_base_fee() {
+        let (rpc_url, calls) = node(Some("0x64")).await;
+        let state = app_state(rpc_url, Some(TxType::Eip1559));
+        let arguments = json!({
+            "chain_id": "sei-evm-testnet",
+            "private_key": "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+            "to_address": "0x000000000000000000000000000000000000dEaD",
+            "amount_wei": "7",
+            "max_priority_fee_per_gas": "5"
+        });
+        let resp = common::call(&state, "transfer_evm", arguments).await;
+        assert!(resp["error"].is_null(), "{}", resp);
+
+        let calls = calls.lock().unwrap();
+        assert!(calls.iter().all(|c| c["method"] != "eth_feeHistory"), "a tip alone needs no fee history");
+        let raw = calls.iter().find(|c| c["method"] == "eth_sendRawTransaction").and_then(|c| c["params"][0].as_str()).unwrap();
+        let bytes = hex::decode(&raw[2..]).unwrap();
+        let TypedTransaction::Eip1559(tx) = TypedTransaction::decode_signed(&Rlp::new(&bytes)).unwrap().0 else {
+            panic!("expected an EIP-1559 envelope")
+        };
+        assert_eq!(tx.max_priority_fee_per_gas, Some(U256::from(5)));
+        assert_eq!(tx.max_fee_per_gas, Some(U256::from(205)));
+    }
+}
+
+mod check_finality {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    const BLOCK_HASH: &str = "0x5d1ca1cf1b8e5a7f0ac32dd35d0c6c27e8d0a4ac7e7cfb6e60f2c6ea0b3f1d11";
+
+    // A node at height 100 that has `TX_HASH` mined in block 96 for the first `receipts` receipt lookups
+    // and no receipt after that, as if the block it was in had been reorged away.
+    async fn node(receipts: Arc<AtomicUsize>) -> String {
+        let (rpc_url, _calls) = common::spawn_rpc(move |req: &Value| {
+            let result = match req["method"].as_str().unwrap_or_default() {
+                "eth_blockNumber" => json!("0x64"),
+                "eth_getBlockByNumber" => json!({ "number": req["params"][0], "hash": BLOCK_HASH }),
+                "eth_getTransactionReceipt" if req["params"][0] == common::TX_HASH => {
+                    let remaining = receipts.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1));
+                    match remaining {
+                        Ok(_) => json!({ "transactionHash": common::TX_HASH, "status": "0x1", "blockNumber": "0x60", "blockHash": BLOCK_HASH }),
+                        Err(_) => Value::Null,
+                    }
+                }
+                "eth_getTransactionReceipt" => Value::Null,
+                _ => return common::default_reply(req),
+            };
+            json!({ "jsonrpc": "2.0", "id": req["id"], "result": result })
+        })
+        .await;
+        rpc_url
+    }
+
+    fn app_state(rpc_url: String, finality_confirmations: &[(&str, u64)]) -> AppState {
+        let config = Config {
+            finality_confirmations: finality_confirmations.iter().map(|(c, n)| (c.to_string(), *n)).collect(),
+            ..common::testnet_config(rpc_url)
+        };
+        common::app_state(config, "sei-mcp-check-finality-test.json")
+    }
+
+    fn tx_args() -> Value {
+        json!({ "chain_id": "sei-evm-testnet", "tx_hash": common::TX_HASH })
+    }
+
+    #[tokio::test]
+    async fn test_mined_tx_reports_confirmations_against_threshold() {
+        let state = app_state(node(Arc::new(AtomicUsize::new(usize::MAX))).await, &[]);
+        let resp = common::call(&state, "check_finality", tx_args()).await;
+        let result = &resp["result"];
+        assert_eq!(result["confirmations"], 5, "{}", resp);
+        assert_eq!(result["block_number"], 96);
+        assert_eq!(result["finalized"], true);
+        assert_eq!(result["status"], "finalized");
+
+        let state = app_state(node(Arc::new(AtomicUsize::new(usize::MAX))).await, &[("sei-evm-testnet", 10)]);
+        let resp = common::call(&state, "check_finality", tx_args()).await;
+        assert_eq!(resp["result"]["required_confirmations"], 10, "{}", resp);
+        assert_eq!(resp["result"]["finalized"], false);
+        assert_eq!(resp["result"]["status"], "confirming");
+    }
+
+    #[tokio::test]
+    async fn test_tx_that_disappears_after_being_seen_is_reorged_out() {
+        let receipts = Arc::new(AtomicUsize::new(usize::MAX));
+        let state = app_state(node(receipts.clone()).await, &[]);
+        let resp = common::call(&state, "check_finality", tx_args()).await;
+        assert_eq!(resp["result"]["finalized"], true, "{}", resp);
+
+        // The block is reorged away between calls
+        receipts.store(0, Ordering::SeqCst);
+        let resp = common::call(&state, "check_finality", tx_args()).await;
+        let result = &resp["result"];
+        assert_eq!(result["reorged_out"], true, "{}", resp);
+        assert_eq!(result["status"], "reorged_out");
+        assert_eq!(result["finalized"], false);
+        assert_eq!(result["previous_block"]["block_number"], 96);
+    }
+
+    #[tokio::test]
+    async fn test_tx_that_disappears_during_the_check_is_reorged_out() {
+        // The receipt is there for the first lookup only; the re-fetch finds it gone
+        let state = app_state(node(Arc::new(AtomicUsize::new(1))).await, &[]);
+        let resp = common::call(&state, "check_finality", tx_args()).await;
+        assert_eq!(resp["result"]["reorged_out"], true, "{}", resp);
+        assert_eq!(resp["result"]["finalized"], false);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_tx_is_pending_not_reorged() {
+        let state = app_state(node(Arc::new(AtomicUsize::new(0))).await, &[]);
+        let resp = common::call(&state, "check_finality", tx_args()).await;
+        assert_eq!(resp["result"]["status"], "pending", "{}", resp);
+        assert_eq!(resp["result"]["reorged_out"], false);
+        assert_eq!(resp["result"]["confirmations"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_block_number_with_explicit_threshold() {
+        let state = app_state(node(Arc::new(AtomicUsize::new(0))).await, &[]);
+        let resp = common::call(&state, "check_finality", json!({ "chain_id": "sei-evm-testnet", "block_number": 90, "confirmations": 20 })).await;
+        let result = &resp["result"];
+        assert_eq!(result["confirmations"], 11, "{}", resp);
+        assert_eq!(result["block_hash"], BLOCK_HASH);
+        assert_eq!(result["finalized"], false);
+
+        let resp = common::call(&state, "check_finality", json!({ "chain_id": "sei-evm-testnet", "block_number": 90, "tx_hash": common::TX_HASH })).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS);
+    }
+}
+
+mod eth_call_raw {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const HOLDER: &str = "0x000000000000000000000000000000000000dEaD";
+
+    async fn eth_call_raw(rpc_url: String, arguments: Value) -> Value {
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-eth-call-raw.json"));
+        common::call(&state, "eth_call_raw", arguments).await
+    }
+
+    #[tokio::test]
+    async fn test_state_override_is_forwarded() {
+        let (rpc_url, calls) = common::spawn_rpc(|req| json!({ "jsonrpc": "2.0", "id": req["id"], "result": "0x2a" })).await;
+        let overrides = json!({ HOLDER: { "balance": "0xde0b6b3a7640000" } });
+        let resp = eth_call_raw(
+            rpc_url,
+            json!({ "to": HOLDER, "data": "0x70a08231", "block": "1234", "state_override": overrides }),
+        )
+        .await;
+
+        let payload = &resp["result"];
+        assert_eq!(payload["result"], "0x2a", "{}", resp);
+        assert_eq!(payload["state_override_applied"], true);
+        let params = &calls.lock().unwrap()[0]["params"];
+        assert_eq!(params[1], "0x4d2");
+        assert_eq!(params[2], overrides);
+    }
+
+    #[tokio::test]
+    async fn test_plain_call_sends_two_params() {
+        let (rpc_url, calls) = common::spawn_rpc(|req| json!({ "jsonrpc": "2.0", "id": req["id"], "result": "0x" })).await;
+        let resp = eth_call_raw(rpc_url, json!({ "to": HOLDER })).await;
+
+        assert_eq!(resp["result"]["block"], "latest", "{}", resp);
+        assert_eq!(calls.lock().unwrap()[0]["params"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_override_explains_itself() {
+        let (rpc_url, _) = common::spawn_rpc(|req| {
+            json!({ "jsonrpc": "2.0", "id": req["id"], "error": { "code": -32602, "message": "too many arguments, want at most 2" } })
+        })
+        .await;
+        let resp = eth_call_raw(rpc_url, json!({ "to": HOLDER, "state_override": { HOLDER: { "balance": "0x1" } } })).await;
+
+        let message = resp["error"]["message"].as_str().unwrap();
+        assert!(message.contains("does not appear to support"), "{}", message);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_override_is_rejected_locally() {
+        let (rpc_url, calls) = common::spawn_mock_rpc().await;
+        let resp = eth_call_raw(rpc_url, json!({ "to": HOLDER, "state_override": { HOLDER: { "balanse": "0x1" } } })).await;
+
+        assert!(resp["error"]["message"].as_str().unwrap().contains("balanse"));
+        assert!(calls.lock().unwrap().is_empty());
+    }
+}
+
+mod explorer_url {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use serde_json::{json, Value};
+
+    const ADDRESS: &str = "0x000000000000000000000000000000000000dEaD";
+    const WALLETS: &str = "sei-mcp-explorer-url-test.json";
+
+    #[tokio::test]
+    async fn test_builtin_chains_link_to_seitrace() {
+        let resp = common::call(&common::app_state(Config::default(), WALLETS), "get_explorer_url", json!({ "chain_id": "sei-evm-testnet", "tx_hash": common::TX_HASH })).await;
+        assert_eq!(
+            resp["result"]["explorer_url"],
+            format!("https://seitrace.com/tx/{}?chain=atlantic-2", common::TX_HASH),
+            "{}",
+            resp
+        );
+
+        let resp = common::call(&common::app_state(Config::default(), WALLETS), "get_explorer_url", json!({ "chain_id": "pacific-1", "address": ADDRESS })).await;
+        assert_eq!(resp["result"]["explorer_url"], format!("https://seitrace.com/address/{}?chain=pacific-1", ADDRESS));
+        assert_eq!(resp["result"]["kind"], "address");
+    }
+
+    #[tokio::test]
+    async fn test_configured_template_overrides_and_extends() {
+        let config = Config {
+            explorer_urls: [
+                ("sei-evm-testnet".to_string(), "https://scan.example/{kind}/{id}".to_string()),
+                ("devnet".to_string(), "https://devnet.example/{kind}/{id}".to_string()),
+            ]
+            .into(),
+            ..Config::default()
+        };
+        let resp = common::call(&common::app_state(config.clone(), WALLETS), "get_explorer_url", json!({ "chain_id": "sei-evm-testnet", "address": ADDRESS })).await;
+        assert_eq!(resp["result"]["explorer_url"], format!("https://scan.example/address/{}", ADDRESS), "{}", resp);
+
+        let resp = common::call(&common::app_state(config, WALLETS), "get_explorer_url", json!({ "chain_id": "devnet", "tx_hash": common::TX_HASH })).await;
+        assert_eq!(resp["result"]["explorer_url"], format!("https://devnet.example/tx/{}", common::TX_HASH));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_chain_and_ambiguous_arguments_are_refused() {
+        let resp = common::call(&common::app_state(Config::default(), WALLETS), "get_explorer_url", json!({ "chain_id": "devnet", "tx_hash": common::TX_HASH })).await;
+        assert_eq!(resp["error"]["code"], error_codes::NOT_FOUND, "{}", resp);
+
+        let both = json!({ "chain_id": "sei-evm-testnet", "tx_hash": common::TX_HASH, "address": ADDRESS });
+        let resp = common::call(&common::app_state(Config::default(), WALLETS), "get_explorer_url", both).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+
+        let resp = common::call(&common::app_state(Config::default(), WALLETS), "get_explorer_url", json!({ "chain_id": "sei-evm-testnet" })).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS);
+    }
+
+    #[tokio::test]
+    async fn test_balance_response_links_the_address() {
+        let (rpc_url, _calls) = common::spawn_rpc(|req: &Value| match req["method"].as_str() {
+            Some("eth_getBalance") => json!({ "jsonrpc": "2.0", "id": req["id"], "result": "0xde0b6b3a7640000" }),
+            _ => common::default_reply(req),
+        })
+        .await;
+        let resp = common::call(&common::app_state(common::testnet_config(rpc_url), WALLETS), "get_balance", json!({ "chain_id": "sei-evm-testnet", "address": ADDRESS })).await;
+        assert_eq!(
+            resp["result"]["explorer_url"],
+            format!("https://seitrace.com/address/{}?chain=atlantic-2", ADDRESS),
+            "{}",
+            resp
+        );
+    }
+}
+
+mod native_tx {
+    use super::common;
+    use axum::{extract::Query, routing::get, Json, Router};
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+    use std::collections::HashMap;
+
+    const HASH: &str = "9FC76417374AA880D4449A1F7F31EC597F00B1F6F3DD2D66F4C9C6C445836D8B";
+
+    // Tendermint RPC answering /tx for HASH with base64-encoded event attributes, as pre-0.37 nodes do.
+    async fn spawn_tendermint() -> String {
+        let app = Router::new().route(
+            "/tx",
+            get(|Query(q): Query<HashMap<String, String>>| async move {
+                if q.get("hash").map(String::as_str) != Some(&format!("0x{}", HASH)) {
+                    return Json(json!({ "jsonrpc": "2.0", "id": -1, "error": { "code": -32603, "message": "Internal error", "data": "tx (X) not found" } }));
+                }
+                Json(json!({ "jsonrpc": "2.0", "id": -1, "result": {
+                    "hash": HASH,
+                    "height": "1234",
+                    "tx_result": {
+                        "code": 0,
+                        "log": "",
+                        "gas_wanted": "200000",
+                        "gas_used": "81234",
+                        "events": [{ "type": "transfer", "attributes": [
+                            { "key": "cmVjaXBpZW50", "value": "c2VpMXJlY2lwaWVudA==", "index": true },
+                            { "key": "YW1vdW50", "value": "MTAwdXNlaQ==", "index": true }
+                        ]}]
+                    }
+                }}))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        url
+    }
+
+    async fn native_tx(arguments: Value) -> Value {
+        let tendermint = spawn_tendermint().await;
+        let config = Config {
+            tendermint_rpc_urls: [("atlantic-2".to_string(), tendermint)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-native-tx-test.json"));
+        common::call(&state, "get_native_tx", arguments).await
+    }
+
+    #[tokio::test]
+    async fn test_decodes_tendermint_tx_result() {
+        let resp = native_tx(json!({ "chain_id": "atlantic-2", "hash": format!("0x{}", HASH.to_lowercase()) })).await;
+        let result = &resp["result"];
+        assert_eq!(result["success"], true, "{}", resp);
+        assert_eq!(result["height"], 1234);
+        assert_eq!(result["gas_used"], 81234);
+        assert_eq!(result["events"][0]["attributes"][0], json!({ "key": "recipient", "value": "sei1recipient" }));
+        assert_eq!(result["events"][0]["attributes"][1]["value"], "100usei");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_hash_is_not_found() {
+        let resp = native_tx(json!({ "chain_id": "atlantic-2", "hash": "ab".repeat(32) })).await;
+        assert_eq!(resp["error"]["code"], error_codes::NOT_FOUND, "{}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_evm_chain() {
+        let resp = native_tx(json!({ "chain_id": "sei-evm-testnet", "hash": HASH })).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+    }
+}
+
+mod pagination {
+    use super::common;
+    use sei_mcp_server_rs::blockchain::services::seistream;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const PRIVATE_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    fn keys(pagination: &Value) -> Vec<String> {
+        let mut keys: Vec<String> = pagination.as_object().expect("pagination object").keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    #[tokio::test]
+    async fn test_list_tools_share_the_pagination_envelope() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = Config { mcp_max_items: 100, ..Config::default() };
+        let state = AppState::new(config, dir.path().join("wallets.json"));
+        for name in ["a", "b", "c"] {
+            let resp = common::call(&state, "register_wallet", json!({ "wallet_name": name, "private_key": PRIVATE_KEY, "master_password": "pw" })).await;
+            assert!(resp["error"].is_null(), "{}", resp);
+            let address = "0x000000000000000000000000000000000000dEaD";
+            common::call(&state, "add_contact", json!({ "name": name, "address": address, "chain_id": "sei-evm-testnet" })).await;
+        }
+
+        let wallets = common::call(&state, "list_wallets", json!({ "master_password": "pw", "page": 2, "page_size": 2 })).await;
+        assert_eq!(wallets["result"]["wallets"], json!(["c"]), "{}", wallets);
+        let wallets = &wallets["result"]["pagination"];
+        assert_eq!(*wallets, json!({ "page": 2, "page_size": 2, "total": 3, "has_more": false, "next_page": null }));
+
+        let contacts = common::call(&state, "list_contacts", json!({ "page": 1, "page_size": 2 })).await;
+        assert_eq!(contacts["result"]["contacts"].as_array().unwrap().len(), 2, "{}", contacts);
+        let contacts = &contacts["result"]["pagination"];
+        assert_eq!(*contacts, json!({ "page": 1, "page_size": 2, "total": 3, "has_more": true, "next_page": 2 }));
+
+        // The explorer-backed tools build theirs from the listing the same way
+        let listing = json!({ "items": [1, 2], "pagination": { "pages": 2, "rows": 3, "currPage": 1, "nextPage": 2 } });
+        let remote = serde_json::to_value(seistream::listing_pagination(&listing, 1, 2)).unwrap();
+        assert_eq!(remote, *contacts);
+        assert_eq!(keys(&remote), keys(wallets));
+    }
+}
+
+mod recent_blocks {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::Request;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    // Head at block 100; block n has n % 3 transactions and is n% full.
+    fn chain(req: &Value) -> Value {
+        let result = match req["method"].as_str().unwrap_or_default() {
+            "eth_blockNumber" => json!("0x64"),
+            "eth_getBlockByNumber" => {
+                let n = u64::from_str_radix(req["params"][0].as_str().unwrap().trim_start_matches("0x"), 16).unwrap();
+                json!({
+                    "number": format!("0x{:x}", n),
+                    "hash": format!("0x{:064x}", n),
+                    "timestamp": format!("0x{:x}", 1_000 + n * 2),
+                    "transactions": vec![format!("0x{:064x}", n); (n % 3) as usize],
+                    "gasUsed": format!("0x{:x}", n * 1_000),
+                    "gasLimit": format!("0x{:x}", 100_000),
+                })
+            }
+            _ => Value::Null,
+        };
+        json!({ "jsonrpc": "2.0", "id": req["id"], "result": result })
+    }
+
+    async fn recent_blocks(max_recent_blocks: u64, count: u64) -> (Value, common::Calls) {
+        let (rpc_url, calls) = common::spawn_rpc(chain).await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            max_recent_blocks,
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-recent-blocks-test.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "get_recent_blocks", "arguments": { "chain_id": "sei-evm-testnet", "count": count } }
+        }))
+        .unwrap();
+        (serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap(), calls)
+    }
+
+    #[tokio::test]
+    async fn test_recent_blocks_newest_first_with_stats() {
+        let (resp, _) = recent_blocks(50, 3).await;
+        let result = &resp["result"];
+        let numbers: Vec<u64> = result["blocks"].as_array().unwrap().iter().map(|b| b["number"].as_u64().unwrap()).collect();
+        assert_eq!(numbers, [100, 99, 98], "{}", resp);
+        assert_eq!(result["blocks"][0]["tx_count"], 1);
+        assert_eq!(result["blocks"][0]["gas_used_ratio"], 1.0);
+        assert_eq!(result["stats"]["avg_block_time_secs"], 2.0);
+        assert_eq!(result["stats"]["total_txs"], 3);
+        assert_eq!(result["capped"], false);
+    }
+
+    #[tokio::test]
+    async fn test_count_is_capped_by_config() {
+        let (resp, calls) = recent_blocks(5, 500).await;
+        assert_eq!(resp["result"]["blocks"].as_array().unwrap().len(), 5, "{}", resp);
+        assert_eq!(resp["result"]["requested"], 500);
+        assert_eq!(resp["result"]["capped"], true);
+        let fetched = calls.lock().unwrap().iter().filter(|c| c["method"] == "eth_getBlockByNumber").count();
+        assert_eq!(fetched, 5);
+    }
+}
+
+mod revert_reason {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::Request;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    // Error(string) "insufficient funds"
+    const REVERT_DATA: &str = concat!(
+        "0x08c379a0",
+        "0000000000000000000000000000000000000000000000000000000000000020",
+        "0000000000000000000000000000000000000000000000000000000000000012",
+        "696e73756666696369656e742066756e64730000000000000000000000000000"
+    );
+
+    // A node where TX_HASH was mined in block 0x10 and reverted; replaying it reverts again.
+    fn reverted_node(req: &Value) -> Value {
+        let reply = |result: Value| json!({ "jsonrpc": "2.0", "id": req["id"], "result": result });
+        match req["method"].as_str().unwrap_or_default() {
+            "eth_getTransactionReceipt" => reply(json!({
+                "transactionHash": common::TX_HASH,
+                "status": "0x0",
+                "blockNumber": "0x10",
+                "to": "0x000000000000000000000000000000000000dead"
+            })),
+            "eth_getTransactionByHash" => reply(json!({
+                "hash": common::TX_HASH,
+                "from": "0x1111111111111111111111111111111111111111",
+                "to": "0x000000000000000000000000000000000000dead",
+                "input": "0xa9059cbb",
+                "value": "0x0",
+                "gas": "0x5208",
+                "blockNumber": "0x10"
+            })),
+            "eth_call" => json!({
+                "jsonrpc": "2.0",
+                "id": req["id"],
+                "error": { "code": 3, "message": "execution reverted: insufficient funds", "data": REVERT_DATA }
+            }),
+            _ => common::default_reply(req),
+        }
+    }
+
+    async fn receipt(state: AppState) -> Value {
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "get_transaction_receipt",
+                "arguments": { "hash": common::TX_HASH, "chain_id": "sei-evm-testnet", "abi": [] }
+            }
+        }))
+        .unwrap();
+        serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reverted_receipt_reports_reason_from_replay() {
+        let (rpc_url, calls) = common::spawn_rpc(reverted_node).await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-revert-test.json"));
+        let resp = receipt(state).await;
+        let result = &resp["result"];
+        assert_eq!(result["status"], "reverted", "{}", resp);
+        assert_eq!(result["revert"]["reason"], "insufficient funds");
+        assert_eq!(result["revert"]["decoded"]["signature"], "Error(string)");
+
+        // The replay runs the original call at the block it was mined in
+        let calls = calls.lock().unwrap();
+        let replay = calls.iter().find(|c| c["method"] == "eth_call").unwrap();
+        assert_eq!(replay["params"][0]["data"], "0xa9059cbb");
+        assert_eq!(replay["params"][1], "0x10");
+    }
+
+    #[tokio::test]
+    async fn test_successful_receipt_skips_replay() {
+        let (rpc_url, calls) = common::spawn_mock_rpc().await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-revert-ok-test.json"));
+        let resp = receipt(state).await;
+        assert_eq!(resp["result"]["status"], "success", "{}", resp);
+        assert!(calls.lock().unwrap().iter().all(|c| c["method"] != "eth_call"));
+    }
+}
+
+mod rpc_failover {
+    use super::common;
+    use axum::{http::StatusCode, routing::post, Json, Router};
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    const COOLDOWN_MS: u64 = 300;
+
+    // A node answering eth_getBalance with 0x2a, or a 502 gateway page while `down` is set.
+    async fn spawn_flaky_node(down: Arc<AtomicBool>, hits: Arc<AtomicUsize>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let app = Router::new().route(
+            "/",
+            post(move |Json(req): Json<Value>| async move {
+                hits.fetch_add(1, Ordering::SeqCst);
+                if down.load(Ordering::SeqCst) {
+                    return Err((StatusCode::BAD_GATEWAY, "502 Bad Gateway"));
+                }
+                Ok(Json(json!({ "jsonrpc": "2.0", "id": req["id"], "result": "0x2a" })))
+            }),
+        );
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        url
+    }
+
+    async fn balance(state: &AppState) -> Value {
+        common::call(state, "get_balance", json!({ "chain_id": "sei-evm-testnet", "address": "0x000000000000000000000000000000000000dEaD" })).await
+    }
+
+    #[tokio::test]
+    async fn test_failing_primary_is_skipped_then_restored_after_cooldown() {
+        let down = Arc::new(AtomicBool::new(true));
+        let primary_hits = Arc::new(AtomicUsize::new(0));
+        let primary = spawn_flaky_node(down.clone(), primary_hits.clone()).await;
+        let (fallback, fallback_calls) = common::spawn_rpc(|req: &Value| json!({ "jsonrpc": "2.0", "id": req["id"], "result": "0x2a" })).await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), primary.clone())].into(),
+            rpc_fallback_urls: [("sei-evm-testnet".to_string(), vec![fallback.clone()])].into(),
+            rpc_failure_threshold: 2,
+            rpc_unhealthy_cooldown_ms: COOLDOWN_MS,
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-rpc-failover-test.json"));
+
+        // Two failures in a row mark the primary unhealthy
+        assert!(balance(&state).await.get("error").is_some());
+        assert!(balance(&state).await.get("error").is_some());
+        assert_eq!(primary_hits.load(Ordering::SeqCst), 2);
+
+        let resp = balance(&state).await;
+        assert_eq!(resp["result"]["balance"]["amount"], "42", "{}", resp);
+        assert_eq!(primary_hits.load(Ordering::SeqCst), 2, "primary skipped during its cooldown");
+        assert_eq!(fallback_calls.lock().unwrap().len(), 1);
+
+        let resp = common::call(&state, "health_check_chains", json!({ "chain_id": "sei-evm-testnet", "probe": false })).await;
+        let chain = &resp["result"]["chains"][0];
+        assert_eq!(chain["active_url"], fallback, "{}", resp);
+        assert_eq!(chain["endpoints"][0]["role"], "primary");
+        assert_eq!(chain["endpoints"][0]["healthy"], false);
+        assert_eq!(chain["endpoints"][0]["consecutive_failures"], 2);
+        assert_eq!(chain["endpoints"][1]["healthy"], true);
+
+        // After the cooldown the primary is tried again and, now answering, restored
+        down.store(false, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(COOLDOWN_MS + 100)).await;
+        let resp = balance(&state).await;
+        assert_eq!(resp["result"]["balance"]["amount"], "42", "{}", resp);
+        assert_eq!(primary_hits.load(Ordering::SeqCst), 3);
+
+        let resp = common::call(&state, "health_check_chains", json!({ "chain_id": "sei-evm-testnet" })).await;
+        let chain = &resp["result"]["chains"][0];
+        assert_eq!(chain["active_url"], primary, "{}", resp);
+        assert_eq!(chain["endpoints"][0]["healthy"], true);
+        assert_eq!(chain["endpoints"][0]["latest_block"], 42);
+    }
+
+    #[tokio::test]
+    async fn test_raw_rpc_tools_fail_over_too() {
+        let down = Arc::new(AtomicBool::new(true));
+        let primary_hits = Arc::new(AtomicUsize::new(0));
+        let primary = spawn_flaky_node(down, primary_hits.clone()).await;
+        let (fallback, fallback_calls) = common::spawn_rpc(|req: &Value| json!({ "jsonrpc": "2.0", "id": req["id"], "result": "0x2a" })).await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), primary)].into(),
+            rpc_fallback_urls: [("sei-evm-testnet".to_string(), vec![fallback])].into(),
+            rpc_failure_threshold: 2,
+            rpc_unhealthy_cooldown_ms: 60_000,
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-rpc-failover-raw-test.json"));
+        let eth_call = json!({ "chain_id": "sei-evm-testnet", "to": "0x000000000000000000000000000000000000dEaD", "data": "0x" });
+
+        // eth_call_raw talks to the node directly rather than through the client, and still counts failures
+        assert!(common::call(&state, "eth_call_raw", eth_call.clone()).await.get("error").is_some());
+        assert!(common::call(&state, "eth_call_raw", eth_call.clone()).await.get("error").is_some());
+        let resp = common::call(&state, "eth_call_raw", eth_call).await;
+        assert!(resp["error"].is_null(), "{}", resp);
+        assert_eq!(primary_hits.load(Ordering::SeqCst), 2, "primary skipped once unhealthy");
+        assert_eq!(fallback_calls.lock().unwrap().len(), 1);
+    }
+}
+
+mod rpc_headers {
+    use super::common;
+    use axum::{http::HeaderMap, routing::post, Json, Router};
+    use sei_mcp_server_rs::config::{Config, RpcHeaders};
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+
+    const API_KEY: &str = "sk-live-0123456789";
+
+    // A node answering eth_getBalance that records the `x-api-key` header of every request.
+    async fn spawn_node() -> (String, Arc<Mutex<Vec<Option<String>>>>) {
+        let seen: Arc<Mutex<Vec<Option<String>>>> = Arc::default();
+        let recorder = seen.clone();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let app = Router::new().route(
+            "/",
+            post(move |headers: HeaderMap, Json(req): Json<Value>| async move {
+                let key = headers.get("x-api-key").and_then(|v| v.to_str().ok()).map(str::to_string);
+                recorder.lock().unwrap().push(key);
+                Json(json!({ "jsonrpc": "2.0", "id": req["id"], "result": "0x2a" }))
+            }),
+        );
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (url, seen)
+    }
+
+    async fn get_balance(state: &AppState, chain_id: &str) -> Value {
+        common::call(state, "get_balance", json!({ "chain_id": chain_id, "address": "0x000000000000000000000000000000000000dEaD" })).await
+    }
+
+    #[tokio::test]
+    async fn test_headers_go_only_to_their_chains_rpc() {
+        let (keyed_url, keyed_seen) = spawn_node().await;
+        let (plain_url, plain_seen) = spawn_node().await;
+        let config = Config {
+            chain_rpc_urls: [
+                ("sei-evm-mainnet".to_string(), keyed_url),
+                ("sei-evm-testnet".to_string(), plain_url),
+            ]
+            .into(),
+            rpc_headers: [("sei-evm-mainnet".to_string(), RpcHeaders([("x-api-key".to_string(), API_KEY.to_string())].into()))].into(),
+            ..Config::default()
+        };
+        assert!(!format!("{:?}", config).contains(API_KEY), "header values must not appear in debug output");
+        let dir = tempfile::tempdir().unwrap();
+        let state = AppState::new(config, dir.path().join("wallets.json"));
+
+        let resp = get_balance(&state, "sei-evm-mainnet").await;
+        assert_eq!(resp["result"]["balance"]["amount"], "42", "{}", resp);
+        let resp = get_balance(&state, "sei-evm-testnet").await;
+        assert_eq!(resp["result"]["balance"]["amount"], "42", "{}", resp);
+
+        let keyed = keyed_seen.lock().unwrap();
+        assert!(!keyed.is_empty());
+        assert!(keyed.iter().all(|k| k.as_deref() == Some(API_KEY)), "{:?}", keyed);
+        assert!(plain_seen.lock().unwrap().iter().all(Option::is_none));
+    }
+
+    #[tokio::test]
+    async fn test_headers_stay_with_the_state_that_configured_them() {
+        let (url, seen) = spawn_node().await;
+        let keyed = Config {
+            chain_rpc_urls: [("sei-evm-mainnet".to_string(), url.clone())].into(),
+            rpc_headers: [("sei-evm-mainnet".to_string(), RpcHeaders([("x-api-key".to_string(), API_KEY.to_string())].into()))].into(),
+            ..Config::default()
+        };
+        let plain = Config { chain_rpc_urls: [("sei-evm-mainnet".to_string(), url)].into(), ..Config::default() };
+        let dir = tempfile::tempdir().unwrap();
+        let keyed = AppState::new(keyed, dir.path().join("keyed-wallets.json"));
+        let plain = AppState::new(plain, dir.path().join("plain-wallets.json"));
+
+        get_balance(&keyed, "sei-evm-mainnet").await;
+        let before = seen.lock().unwrap().len();
+        let resp = get_balance(&plain, "sei-evm-mainnet").await;
+        assert_eq!(resp["result"]["balance"]["amount"], "42", "{}", resp);
+        let seen = seen.lock().unwrap();
+        assert!(seen[..before].iter().all(|k| k.as_deref() == Some(API_KEY)), "{:?}", seen);
+        assert!(seen[before..].iter().all(Option::is_none), "another state's headers leaked: {:?}", seen);
+    }
+}
+
+mod rpc_passthrough {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::{error_codes, Request};
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    async fn passthrough(allow: bool, arguments: Value) -> (Value, common::Calls, Option<String>) {
+        let (rpc_url, calls) = common::spawn_mock_rpc().await;
+        let dir = tempfile::tempdir().unwrap();
+        let audit_path = dir.path().join("audit.jsonl");
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            audit_log_path: Some(audit_path.clone()),
+            allow_rpc_passthrough: allow,
+            ..Config::default()
+        };
+        let state = AppState::new(config, dir.path().join("wallets.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "rpc_passthrough", "arguments": arguments }
+        }))
+        .unwrap();
+        let resp = serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap();
+        (resp, calls, std::fs::read_to_string(&audit_path).ok())
+    }
+
+    #[tokio::test]
+    async fn test_disabled_by_default() {
+        let (resp, calls, log) = passthrough(false, json!({ "chain_id": "sei-evm-testnet", "method": "eth_gasPrice" })).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("ALLOW_RPC_PASSTHROUGH"));
+        assert!(calls.lock().unwrap().is_empty());
+        assert!(log.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_forwards_method_and_params_and_audits() {
+        let (resp, calls, log) = passthrough(
+            true,
+            json!({ "chain_id": "sei-evm-testnet", "method": "eth_getTransactionCount", "params": ["0x000000000000000000000000000000000000dEaD", "latest"] }),
+        )
+        .await;
+        assert!(resp["error"].is_null(), "{}", resp);
+        assert_eq!(resp["result"]["method"], "eth_getTransactionCount");
+
+        let calls = calls.lock().unwrap();
+        let call = calls.iter().find(|c| c["method"] == "eth_getTransactionCount").unwrap();
+        assert_eq!(call["params"], json!(["0x000000000000000000000000000000000000dEaD", "latest"]));
+
+        let entry: Value = serde_json::from_str(log.unwrap().lines().next().unwrap()).unwrap();
+        assert_eq!(entry["tool"], "rpc_passthrough");
+        assert_eq!(entry["rpc_method"], "eth_getTransactionCount");
+    }
+
+    #[tokio::test]
+    async fn test_raw_broadcast_records_tx_hash() {
+        let (resp, _, log) = passthrough(
+            true,
+            json!({ "chain_id": "sei-evm-testnet", "method": "eth_sendRawTransaction", "params": ["0x02f8"] }),
+        )
+        .await;
+        assert_eq!(resp["result"]["result"], common::TX_HASH, "{}", resp);
+        let entry: Value = serde_json::from_str(log.unwrap().lines().next().unwrap()).unwrap();
+        assert_eq!(entry["tx_hash"], common::TX_HASH);
+    }
+}
+
+mod storage_at {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const WORD: &str = "0x000000000000000000000000a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+
+    async fn storage_at(arguments: Value) -> (Value, common::Calls) {
+        let (rpc_url, calls) = common::spawn_rpc(|req: &Value| match req["method"].as_str() {
+            Some("eth_getStorageAt") => json!({ "jsonrpc": "2.0", "id": req["id"], "result": WORD }),
+            _ => common::default_reply(req),
+        })
+        .await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-storage-test.json"));
+        (common::call(&state, "get_storage_at", arguments).await, calls)
+    }
+
+    #[tokio::test]
+    async fn test_decimal_slot_is_normalized_to_hex() {
+        let (resp, calls) = storage_at(json!({
+            "address": "0x000000000000000000000000000000000000dEaD",
+            "slot": 5,
+            "block": 100
+        }))
+        .await;
+        let result = &resp["result"];
+        assert_eq!(result["slot"], format!("0x{:064x}", 5), "{}", resp);
+        assert_eq!(result["value"], WORD);
+        assert_eq!(result["value_address"], "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+
+        let calls = calls.lock().unwrap();
+        let call = calls.iter().find(|c| c["method"] == "eth_getStorageAt").unwrap();
+        assert_eq!(call["params"][1], format!("0x{:064x}", 5));
+        assert_eq!(call["params"][2], "0x64");
+    }
+
+    #[tokio::test]
+    async fn test_mapping_keys_compute_entry_slot() {
+        let (resp, calls) = storage_at(json!({
+            "address": "0x000000000000000000000000000000000000dEaD",
+            "slot": "0",
+            "mapping_keys": ["0x0000000000000000000000000000000000000001"]
+        }))
+        .await;
+        let expected = "0xada5013122d395ba3c54772283fb069b10426056ef8ca54750cb9bb552a59e7d";
+        assert_eq!(resp["result"]["slot"], expected, "{}", resp);
+        let calls = calls.lock().unwrap();
+        assert!(calls.iter().any(|c| c["method"] == "eth_getStorageAt" && c["params"][1] == expected));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_slot() {
+        let (resp, _) = storage_at(json!({ "address": "0x000000000000000000000000000000000000dEaD", "slot": "zz" })).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+    }
+}
+
+mod transaction_trace {
+    use super::common;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use serde_json::{json, Value};
+
+    const WALLETS: &str = "sei-mcp-transaction-trace-test.json";
+
+    fn method_not_found(req: &Value) -> Value {
+        json!({ "jsonrpc": "2.0", "id": req["id"], "error": { "code": -32601, "message": "the method does not exist/is not available" } })
+    }
+
+    // A parity-style node: no debug namespace, a router call that forwards 5 wei to a recipient
+    async fn parity_node() -> (String, common::Calls) {
+        common::spawn_rpc(|req: &Value| match req["method"].as_str() {
+            Some("debug_traceTransaction") => method_not_found(req),
+            Some("trace_transaction") if req["params"][0] == common::TX_HASH => {
+                let traces = json!([
+                    { "type": "call", "traceAddress": [], "action": { "callType": "call", "from": "0xaa", "to": "0xbb", "value": "0x5" } },
+                    { "type": "call", "traceAddress": [0], "action": { "callType": "call", "from": "0xbb", "to": "0xcc", "value": "0x5" } }
+                ]);
+                json!({ "jsonrpc": "2.0", "id": req["id"], "result": traces })
+            }
+            Some("trace_transaction") => json!({ "jsonrpc": "2.0", "id": req["id"], "result": [] }),
+            _ => common::default_reply(req),
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_trace_transaction_and_nests_internal_calls() {
+        let (rpc_url, calls) = parity_node().await;
+        let state = common::app_state(common::testnet_config(rpc_url), WALLETS);
+        let resp = common::call(&state, "get_transaction_trace", json!({ "chain_id": "sei-evm-testnet", "hash": common::TX_HASH })).await;
+        let result = &resp["result"];
+        assert_eq!(result["method"], "trace_transaction", "{}", resp);
+        assert_eq!(result["trace"]["calls"][0], json!({ "type": "CALL", "from": "0xbb", "to": "0xcc", "value": "5" }));
+        assert_eq!(result["internal_value_transfers"], 1);
+        assert!(result.get("raw_trace").is_none());
+
+        // The missing debug namespace is remembered for the URL
+        let resp = common::call(&state, "get_transaction_trace", json!({ "chain_id": "sei-evm-testnet", "hash": common::TX_HASH, "raw": true })).await;
+        assert!(resp["result"]["raw_trace"].is_array(), "{}", resp);
+        let debug_calls = calls.lock().unwrap().iter().filter(|c| c["method"] == "debug_traceTransaction").count();
+        assert_eq!(debug_calls, 1);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_transaction_is_not_found() {
+        let (rpc_url, _calls) = parity_node().await;
+        let resp = common::call(&common::app_state(common::testnet_config(rpc_url), WALLETS), "get_transaction_trace", json!({ "chain_id": "sei-evm-testnet", "hash": format!("0x{}", "0".repeat(64)) })).await;
+        assert_eq!(resp["error"]["code"], error_codes::NOT_FOUND, "{}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_node_without_tracing_reports_unsupported() {
+        let (rpc_url, _calls) = common::spawn_rpc(|req: &Value| match req["method"].as_str() {
+            Some("debug_traceTransaction" | "trace_transaction") => method_not_found(req),
+            _ => common::default_reply(req),
+        })
+        .await;
+        let resp = common::call(&common::app_state(common::testnet_config(rpc_url), WALLETS), "get_transaction_trace", json!({ "chain_id": "sei-evm-testnet", "hash": common::TX_HASH })).await;
+        assert_eq!(resp["result"]["supported"], false, "{}", resp);
+        assert!(resp["result"]["content"][0]["text"].as_str().unwrap().contains("neither"));
+    }
+}
@@ -0,0 +1,1223 @@
+mod common;
+
+mod amount_units {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const PRIVATE_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    async fn transfer(amount_wei: &str, strict: bool, extra: Value) -> (Value, common::Calls) {
+        let (rpc_url, calls) = common::spawn_mock_rpc().await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            strict_amounts: strict,
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-amount-units-test.json"));
+        let mut arguments = json!({
+            "chain_id": "sei-evm-testnet",
+            "private_key": PRIVATE_KEY,
+            "to_address": "0x000000000000000000000000000000000000dEaD",
+            "amount_wei": amount_wei
+        });
+        arguments.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+        (common::call(&state, "transfer_evm", arguments).await, calls)
+    }
+
+    fn broadcast(calls: &common::Calls) -> bool {
+        calls.lock().unwrap().iter().any(|c| c["method"] == "eth_sendRawTransaction")
+    }
+
+    #[tokio::test]
+    async fn test_decimal_amount_suggests_base_units() {
+        let (resp, calls) = transfer("1.5", false, json!({})).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("1500000000000000000"), "{}", resp);
+        assert!(!broadcast(&calls));
+    }
+
+    #[tokio::test]
+    async fn test_base_unit_amount_sends_without_warning() {
+        let (resp, calls) = transfer("1000000000000000000", true, json!({})).await;
+        assert!(resp["error"].is_null(), "{}", resp);
+        assert!(resp["result"]["amount_warning"].is_null());
+        assert!(broadcast(&calls));
+    }
+
+    #[tokio::test]
+    async fn test_small_amount_warns_by_default() {
+        let (resp, calls) = transfer("100", false, json!({})).await;
+        assert!(resp["error"].is_null(), "{}", resp);
+        assert!(resp["result"]["amount_warning"].as_str().unwrap().contains("100000000000000000000"), "{}", resp);
+        assert!(broadcast(&calls));
+    }
+
+    #[tokio::test]
+    async fn test_small_amount_rejected_when_strict_until_confirmed() {
+        let (resp, calls) = transfer("100", true, json!({})).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("confirm_amount"));
+        assert!(!broadcast(&calls));
+
+        let (resp, calls) = transfer("100", true, json!({ "confirm_amount": true })).await;
+        assert!(resp["error"].is_null(), "{}", resp);
+        assert!(resp["result"]["amount_warning"].is_null());
+        assert!(broadcast(&calls));
+    }
+}
+
+mod broadcast_raw_transaction {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::{error_codes, Request};
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    // The signed transaction from the EIP-155 specification's example
+    const EIP155_TX: &str = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+    const EIP155_SENDER: &str = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F";
+
+    // Broadcasts through a node that accepts everything, or refuses with `rejection` when set.
+    async fn broadcast(rejection: Option<&'static str>, raw: &str) -> (Value, common::Calls) {
+        broadcast_with(Config::default(), rejection, raw).await
+    }
+
+    async fn broadcast_with(config: Config, rejection: Option<&'static str>, raw: &str) -> (Value, common::Calls) {
+        let (rpc_url, calls) = common::spawn_rpc(move |req: &Value| match (req["method"].as_str(), rejection) {
+            (Some("eth_sendRawTransaction"), Some(message)) => json!({
+                "jsonrpc": "2.0",
+                "id": req["id"],
+                "error": { "code": -32000, "message": message }
+            }),
+            _ => common::default_reply(req),
+        })
+        .await;
+        let config = Config { chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(), ..config };
+        let dir = tempfile::tempdir().unwrap();
+        let state = AppState::new(config, dir.path().join("wallets.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "broadcast_raw_transaction", "arguments": { "chain_id": "sei-evm-testnet", "raw_transaction": raw } }
+        }))
+        .unwrap();
+        (serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap(), calls)
+    }
+
+    #[tokio::test]
+    async fn test_accepted_broadcast_returns_hash_and_signer() {
+        let (resp, calls) = broadcast(None, EIP155_TX).await;
+        let result = &resp["result"];
+        assert_eq!(result["tx_hash"], common::TX_HASH, "{}", resp);
+        assert_eq!(result["from"], EIP155_SENDER);
+        assert_eq!(result["nonce"], "9");
+        assert_eq!(result["accepted"], true);
+        let calls = calls.lock().unwrap();
+        assert_eq!(calls[0]["params"][0], EIP155_TX);
+    }
+
+    #[tokio::test]
+    async fn test_rejections_carry_category_and_hint() {
+        let cases = [
+            ("nonce too low: next nonce 10, tx nonce 9", error_codes::RPC_ERROR, "nonce_too_low"),
+            ("insufficient funds for gas * price + value", error_codes::INSUFFICIENT_FUNDS, "insufficient_funds"),
+            ("already known", error_codes::RPC_ERROR, "already_known"),
+            ("replacement transaction underpriced", error_codes::RPC_ERROR, "underpriced"),
+            ("something new", error_codes::RPC_ERROR, "other"),
+        ];
+        for (message, code, category) in cases {
+            let (resp, _) = broadcast(Some(message), EIP155_TX).await;
+            assert_eq!(resp["error"]["code"], code, "{}", resp);
+            assert_eq!(resp["error"]["data"]["rejection"]["category"], category, "{}", resp);
+            assert!(resp["error"]["data"]["rejection"]["hint"].is_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_garbage_is_refused_before_broadcast() {
+        let (resp, calls) = broadcast(None, "0xdeadbeef").await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(calls.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_value_over_the_transfer_cap_is_refused_before_broadcast() {
+        // EIP155_TX sends 10^18 wei
+        let config = Config {
+            max_transfer_amounts: [("sei-evm-testnet".to_string(), "999999999999999999".to_string())].into(),
+            ..Config::default()
+        };
+        let (resp, calls) = broadcast_with(config, None, EIP155_TX).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("exceeds the configured maximum"), "{}", resp);
+        assert!(calls.lock().unwrap().is_empty());
+    }
+}
+
+mod compute_tx_hash {
+    use super::common;
+    use ethers_core::types::transaction::eip2718::TypedTransaction;
+    use ethers_core::types::{Address, Eip1559TransactionRequest};
+    use ethers_core::utils::keccak256;
+    use ethers_signers::{LocalWallet, Signer};
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    // The signed transaction from the EIP-155 specification's example
+    const EIP155_TX: &str = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+    const EIP155_SENDER: &str = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F";
+
+    async fn compute_tx_hash(arguments: Value) -> Value {
+        let state = AppState::new(Config::default(), std::env::temp_dir().join("sei-mcp-compute-tx-hash-test.json"));
+        common::call(&state, "compute_tx_hash", arguments).await
+    }
+
+    fn keccak_hex(raw: &str) -> String {
+        format!("0x{}", hex::encode(keccak256(hex::decode(raw.trim_start_matches("0x")).unwrap())))
+    }
+
+    #[tokio::test]
+    async fn test_legacy_eip155_vector() {
+        let resp = compute_tx_hash(json!({ "raw_transaction": EIP155_TX, "expected_from": EIP155_SENDER })).await;
+        let result = &resp["result"];
+        assert_eq!(result["from"], EIP155_SENDER, "{}", resp);
+        assert_eq!(result["from_matches"], true);
+        assert_eq!(result["hash"], keccak_hex(EIP155_TX));
+        assert_eq!(result["type"], "legacy");
+        assert_eq!(result["chain_id"], 1);
+        assert_eq!(result["nonce"], "9");
+        assert_eq!(result["to"], "0x3535353535353535353535353535353535353535");
+        assert_eq!(result["value"], "1000000000000000000");
+        assert_eq!(result["gas_price"], "20000000000");
+    }
+
+    #[tokio::test]
+    async fn test_eip1559_envelope_recovers_signer() {
+        let wallet: LocalWallet = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(Address::repeat_byte(0x11))
+            .value(5)
+            .nonce(3)
+            .gas(21_000)
+            .max_fee_per_gas(2_000_000_000u64)
+            .max_priority_fee_per_gas(1_000_000_000u64)
+            .chain_id(1328u64)
+            .into();
+        let signature = wallet.sign_transaction(&tx).await.unwrap();
+        let raw = format!("0x{}", hex::encode(tx.rlp_signed(&signature)));
+
+        let resp = compute_tx_hash(json!({ "raw_transaction": raw, "expected_from": EIP155_SENDER })).await;
+        let result = &resp["result"];
+        assert_eq!(result["from"], ethers_core::utils::to_checksum(&wallet.address(), None), "{}", resp);
+        assert_eq!(result["from_matches"], false);
+        assert_eq!(result["hash"], keccak_hex(&raw));
+        assert_eq!(result["type"], "eip1559");
+        assert_eq!(result["chain_id"], 1328);
+        assert_eq!(result["max_fee_per_gas"], "2000000000");
+        assert_eq!(result["max_priority_fee_per_gas"], "1000000000");
+    }
+
+    #[tokio::test]
+    async fn test_garbage_is_invalid_params() {
+        for raw in ["0x", "0xzz", "0xdeadbeef"] {
+            let resp = compute_tx_hash(json!({ "raw_transaction": raw })).await;
+            assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}: {}", raw, resp);
+        }
+    }
+}
+
+mod feegrant {
+    use super::common;
+    use axum::{extract::Path, routing::get, routing::post, Json, Router};
+    use base64::Engine;
+    use sei_mcp_server_rs::blockchain::services::transactions::native_sender_address;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+
+    const SENDER_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    const WALLETS: &str = "sei-mcp-feegrant-test.json";
+    const GRANTER_KEY: &str = "0x8da4ef21b864d2cc526dbdb2a120bd2874c36c9d0a1fb7f8c63d7f7a8b41de8f";
+    const TX_HASH: &str = "A1B2C3";
+
+    type Broadcasts = Arc<Mutex<Vec<cosmrs::Tx>>>;
+
+    // A Cosmos REST node that knows every account and answers broadcasts with `raw_log` failures when set.
+    async fn spawn_rest(fail_with: Option<&'static str>) -> (String, Broadcasts) {
+        let seen: Broadcasts = Arc::default();
+        let recorder = seen.clone();
+        let app = Router::new()
+            .route(
+                "/cosmos/auth/v1beta1/accounts/:address",
+                get(|Path(address): Path<String>| async move {
+                    Json(json!({ "account": { "address": address, "account_number": "7", "sequence": "3" } }))
+                }),
+            )
+            .route(
+                "/cosmos/tx/v1beta1/txs",
+                post(move |Json(req): Json<Value>| async move {
+                    let bytes = base64::engine::general_purpose::STANDARD.decode(req["tx_bytes"].as_str().unwrap()).unwrap();
+                    recorder.lock().unwrap().push(cosmrs::Tx::from_bytes(&bytes).unwrap());
+                    Json(match fail_with {
+                        Some(log) => json!({ "tx_response": { "code": 2, "codespace": "feegrant", "raw_log": log, "txhash": TX_HASH } }),
+                        None => json!({ "tx_response": { "code": 0, "txhash": TX_HASH } }),
+                    })
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (url, seen)
+    }
+
+    fn config(rest_url: String) -> Config {
+        Config {
+            chain_rpc_urls: [("atlantic-2".to_string(), rest_url)].into(),
+            native_chain_id: "atlantic-2".into(),
+            native_denom: "usei".into(),
+            native_bech32_hrp: "sei".into(),
+            native_fee_amount: 5000,
+            native_gas_limit: 200000,
+            ..Config::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transfer_sets_fee_granter() {
+        let (url, seen) = spawn_rest(None).await;
+        let config = config(url);
+        let granter = native_sender_address(&config, GRANTER_KEY).unwrap();
+        let recipient = native_sender_address(&config, SENDER_KEY).unwrap();
+        let resp = common::call(
+            &common::app_state(config, WALLETS),
+            "transfer_sei_with_feegrant",
+            json!({ "private_key": SENDER_KEY, "chain_id": "atlantic-2", "to_address": recipient, "amount_usei": "100", "granter": granter }),
+        )
+        .await;
+        assert_eq!(resp["result"]["transaction_hash"], TX_HASH, "{}", resp);
+
+        let txs = seen.lock().unwrap();
+        let fee = &txs[0].auth_info.fee;
+        assert_eq!(fee.granter.as_ref().map(|g| g.to_string()), Some(granter));
+        assert_eq!(txs[0].body.messages[0].type_url, "/cosmos.bank.v1beta1.MsgSend");
+    }
+
+    #[tokio::test]
+    async fn test_missing_grant_is_reported() {
+        let (url, _) = spawn_rest(Some("fee-grant not found: unauthorized")).await;
+        let config = config(url);
+        let granter = native_sender_address(&config, GRANTER_KEY).unwrap();
+        let resp = common::call(
+            &common::app_state(config, WALLETS),
+            "transfer_sei_with_feegrant",
+            json!({ "private_key": SENDER_KEY, "chain_id": "atlantic-2", "to_address": granter.clone(), "amount_usei": "100", "granter": granter }),
+        )
+        .await;
+        assert_eq!(resp["error"]["code"], error_codes::NOT_FOUND, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("No usable fee grant"), "{}", resp);
+    }
+
+    #[tokio::test]
+    async fn test_grant_fee_allowance_builds_basic_allowance() {
+        let (url, seen) = spawn_rest(None).await;
+        let config = config(url);
+        let grantee = native_sender_address(&config, SENDER_KEY).unwrap();
+        let resp = common::call(
+            &common::app_state(config, WALLETS),
+            "grant_fee_allowance",
+            json!({ "private_key": GRANTER_KEY, "chain_id": "atlantic-2", "grantee": grantee, "spend_limit_usei": "50000", "expiration": "2030-01-01T00:00:00Z" }),
+        )
+        .await;
+        assert_eq!(resp["result"]["spend_limit_usei"], "50000", "{}", resp);
+        let txs = seen.lock().unwrap();
+        assert_eq!(txs[0].body.messages[0].type_url, "/cosmos.feegrant.v1beta1.MsgGrantAllowance");
+        assert!(txs[0].auth_info.fee.granter.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_granter() {
+        let (url, seen) = spawn_rest(None).await;
+        let resp = common::call(
+            &common::app_state(config(url), WALLETS),
+            "transfer_sei_with_feegrant",
+            json!({ "private_key": SENDER_KEY, "chain_id": "atlantic-2", "to_address": "sei1xyz", "amount_usei": "1", "granter": "not-an-address" }),
+        )
+        .await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(seen.lock().unwrap().is_empty());
+    }
+}
+
+mod gas_bump {
+    use super::common;
+    use ethers_core::types::transaction::eip2718::TypedTransaction;
+    use ethers_core::types::U256;
+    use ethers_core::utils::rlp::Rlp;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::Request;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    // Rejects the first `rejections` broadcasts as underpriced, then accepts.
+    async fn underpriced_node(rejections: usize) -> (String, common::Calls) {
+        let sends = Arc::new(AtomicUsize::new(0));
+        common::spawn_rpc(move |req: &Value| {
+            if req["method"] == "eth_sendRawTransaction" && sends.fetch_add(1, Ordering::SeqCst) < rejections {
+                return json!({
+                    "jsonrpc": "2.0",
+                    "id": req["id"],
+                    "error": { "code": -32000, "message": "replacement transaction underpriced" }
+                });
+            }
+            common::default_reply(req)
+        })
+        .await
+    }
+
+    async fn transfer(rpc_url: String, auto_bump: bool) -> Value {
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            gas_bump_percent: 10,
+            max_gas_bumps: 2,
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-gas-bump-test.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": {
+                "name": "transfer_evm",
+                "arguments": {
+                    "private_key": "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+                    "chain_id": "sei-evm-testnet",
+                    "to_address": "0x000000000000000000000000000000000000dEaD",
+                    "amount_wei": "1",
+                    "gas_price": "1000000000",
+                    "auto_bump": auto_bump
+                }
+            }
+        }))
+        .unwrap();
+        serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap()
+    }
+
+    fn broadcasts(calls: &common::Calls) -> Vec<TypedTransaction> {
+        calls
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|c| c["method"] == "eth_sendRawTransaction")
+            .map(|c| {
+                let bytes = hex::decode(c["params"][0].as_str().unwrap().trim_start_matches("0x")).unwrap();
+                TypedTransaction::decode_signed(&Rlp::new(&bytes)).unwrap().0
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_underpriced_send_is_retried_with_bumped_fee() {
+        let (rpc_url, calls) = underpriced_node(1).await;
+        let resp = transfer(rpc_url, true).await;
+        let result = &resp["result"];
+        assert_eq!(result["tx_hash"], common::TX_HASH, "{}", resp);
+        assert_eq!(result["gas_bumps"], 1);
+        assert_eq!(result["gas_price"], "1100000000");
+
+        let sent = broadcasts(&calls);
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[0].nonce(), sent[1].nonce(), "a bump must replace, not queue");
+        assert_eq!(sent[1].gas_price(), Some(U256::from(1_100_000_000u64)));
+    }
+
+    #[tokio::test]
+    async fn test_bumps_are_bounded() {
+        let (rpc_url, calls) = underpriced_node(10).await;
+        let resp = transfer(rpc_url, true).await;
+        assert!(resp["error"]["message"].as_str().unwrap().contains("underpriced"), "{}", resp);
+        // first attempt + MAX_GAS_BUMPS retries
+        assert_eq!(broadcasts(&calls).len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_without_auto_bump_error_is_surfaced() {
+        let (rpc_url, calls) = underpriced_node(1).await;
+        let resp = transfer(rpc_url, false).await;
+        assert!(resp["error"]["message"].as_str().unwrap().contains("underpriced"), "{}", resp);
+        assert_eq!(broadcasts(&calls).len(), 1);
+    }
+}
+
+mod gas_defaults {
+    use super::common;
+    use ethers_core::types::{Transaction, U256};
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::Request;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    // Sends a transfer_evm with `extra` merged into its arguments and returns the signed tx plus whether gas was estimated.
+    async fn send_transfer(config: Config, extra: Value) -> (Transaction, bool) {
+        let (rpc_url, calls) = common::spawn_mock_rpc().await;
+        let config = Config { chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(), ..config };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-gas-defaults.json"));
+        let mut arguments = json!({
+            "chain_id": "sei-evm-testnet",
+            "private_key": "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+            "to_address": "0x000000000000000000000000000000000000dEaD",
+            "amount_wei": "1"
+        });
+        arguments.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "transfer_evm", "arguments": arguments }
+        }))
+        .unwrap();
+        let resp = serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap();
+        assert!(resp["error"].is_null(), "{}", resp);
+
+        let calls = calls.lock().unwrap();
+        let estimated = calls.iter().any(|c| c["method"] == "eth_estimateGas");
+        let raw = calls
+            .iter()
+            .find(|c| c["method"] == "eth_sendRawTransaction")
+            .and_then(|c| c["params"][0].as_str())
+            .unwrap();
+        (ethers_core::utils::rlp::decode(&hex::decode(&raw[2..]).unwrap()).unwrap(), estimated)
+    }
+
+    fn configured() -> Config {
+        Config {
+            default_gas_limits: [("sei-evm-testnet".to_string(), 90_000)].into(),
+            default_gas_prices: [("sei-evm-testnet".to_string(), 2_000_000_000)].into(),
+            ..Config::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_configured_defaults_replace_estimation() {
+        let (tx, estimated) = send_transfer(configured(), json!({})).await;
+        assert!(!estimated);
+        assert_eq!(tx.gas, U256::from(90_000));
+        assert_eq!(tx.gas_price, Some(U256::from(2_000_000_000u64)));
+    }
+
+    #[tokio::test]
+    async fn test_explicit_gas_beats_configured_default() {
+        let (tx, _) = send_transfer(configured(), json!({"gas_limit": "50000"})).await;
+        assert_eq!(tx.gas, U256::from(50_000));
+        assert_eq!(tx.gas_price, Some(U256::from(2_000_000_000u64)));
+    }
+
+    #[tokio::test]
+    async fn test_unconfigured_chain_falls_back_to_rpc() {
+        let (tx, estimated) = send_transfer(Config::default(), json!({})).await;
+        assert!(estimated);
+        assert_eq!(tx.gas, U256::from(0x5208));
+        assert_eq!(tx.gas_price, Some(U256::from(0x3b9aca00u64)));
+    }
+}
+
+mod nonce_resync {
+    use super::common;
+    use ethers_core::types::{Address, U256};
+    use sei_mcp_server_rs::blockchain::nonce_manager::NonceManager;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::Request;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    const SENDER: &str = "0x000000000000000000000000000000000000dEaD";
+
+    // A node whose confirmed transaction count the test can advance, as if sent from another wallet.
+    async fn node(count: Arc<AtomicU64>) -> String {
+        let (url, _) = common::spawn_rpc(move |req: &Value| match req["method"].as_str() {
+            Some("eth_getTransactionCount") => {
+                json!({ "jsonrpc": "2.0", "id": req["id"], "result": format!("0x{:x}", count.load(Ordering::SeqCst)) })
+            }
+            _ => common::default_reply(req),
+        })
+        .await;
+        url
+    }
+
+    #[tokio::test]
+    async fn test_background_resync_catches_external_nonce_advance() {
+        let count = Arc::new(AtomicU64::new(3));
+        let rpc_url = node(count.clone()).await;
+        let manager = NonceManager::new();
+        let sender: Address = SENDER.parse().unwrap();
+
+        assert_eq!(manager.get_next_nonce(sender, &rpc_url).await.unwrap(), U256::from(3));
+        // Our nonce 3 mined, then two transactions were sent from the same key elsewhere
+        count.store(6, Ordering::SeqCst);
+
+        assert_eq!(manager.resync_active(Duration::from_secs(60)).await, 1);
+        assert_eq!(manager.get_next_nonce(sender, &rpc_url).await.unwrap(), U256::from(6));
+    }
+
+    #[tokio::test]
+    async fn test_background_resync_keeps_pending_nonces_and_skips_idle_addresses() {
+        let count = Arc::new(AtomicU64::new(0));
+        let rpc_url = node(count.clone()).await;
+        let manager = NonceManager::new();
+        let sender: Address = SENDER.parse().unwrap();
+        for _ in 0..3 {
+            manager.get_next_nonce(sender, &rpc_url).await.unwrap();
+        }
+
+        // Chain behind the cache: our transactions are still pending, so nothing moves
+        assert_eq!(manager.resync_active(Duration::from_secs(60)).await, 0);
+
+        count.store(10, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(manager.resync_active(Duration::from_millis(1)).await, 0, "idle address must be skipped");
+        assert_eq!(manager.get_next_nonce(sender, &rpc_url).await.unwrap(), U256::from(3));
+    }
+
+    #[tokio::test]
+    async fn test_resync_nonce_tool_resets_cache() {
+        let count = Arc::new(AtomicU64::new(0));
+        let rpc_url = node(count.clone()).await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url.clone())].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-nonce-resync-test.json"));
+        let sender: Address = SENDER.parse().unwrap();
+        for _ in 0..5 {
+            state.nonce_manager.get_next_nonce(sender, &rpc_url).await.unwrap();
+        }
+        // Two of the five were dropped; only three ever made it on chain
+        count.store(3, Ordering::SeqCst);
+
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "resync_nonce", "arguments": { "address": SENDER, "chain_id": "sei-evm-testnet" } }
+        }))
+        .unwrap();
+        let resp = serde_json::to_value(handle_mcp_request(req, state.clone()).await.unwrap()).unwrap();
+        assert_eq!(resp["result"]["previous_nonce"], 5, "{}", resp);
+        assert_eq!(resp["result"]["nonce"], 3);
+        assert_eq!(state.nonce_manager.get_next_nonce(sender, &rpc_url).await.unwrap(), U256::from(3));
+    }
+}
+
+mod preview_transfer {
+    use super::common;
+    use sei_mcp_server_rs::blockchain::services::transactions;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    async fn preview(config: Config, arguments: Value) -> Value {
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-preview-test.json"));
+        common::call(&state, "preview_transfer", arguments).await
+    }
+
+    #[tokio::test]
+    async fn test_evm_preview_prices_transfer_without_sending() {
+        let (rpc_url, calls) = common::spawn_mock_rpc().await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            usd_prices: [("sei-evm-testnet".to_string(), 2.0)].into(),
+            ..Config::default()
+        };
+        let resp = preview(
+            config,
+            json!({
+                "private_key": KEY,
+                "chain_id": "sei-evm-testnet",
+                "to_address": "0x000000000000000000000000000000000000dead",
+                "amount_wei": "1500000000000000000"
+            }),
+        )
+        .await;
+        let result = &resp["result"];
+        assert_eq!(result["to"], "0x000000000000000000000000000000000000dEaD", "{}", resp);
+        assert_eq!(result["amount"]["sei"], "1.5");
+        // eth_estimateGas 21000 at eth_gasPrice 1 gwei (the mock has no fee history, so legacy)
+        assert_eq!(result["estimated_fee"]["base"], "21000000000000");
+        assert_eq!(result["estimated_fee"]["details"]["tx_type"], "legacy");
+        assert_eq!(result["total"]["base"], "1500021000000000000");
+        assert_eq!(result["usd"]["amount"], 3.0);
+        assert_eq!(result["usd"]["total"], 3.0);
+        assert_eq!(result["sent"], false);
+
+        let calls = calls.lock().unwrap();
+        assert!(calls.iter().all(|c| c["method"] != "eth_sendRawTransaction"));
+        assert!(calls.iter().any(|c| c["method"] == "eth_estimateGas"));
+    }
+
+    #[tokio::test]
+    async fn test_native_preview_uses_configured_fee() {
+        let config = Config {
+            native_fee_amount: 20_000,
+            native_gas_limit: 200_000,
+            native_bech32_hrp: "sei".to_string(),
+            native_denom: "usei".to_string(),
+            ..Config::default()
+        };
+        let recipient = transactions::native_sender_address(&config, KEY).unwrap();
+        let resp = preview(
+            config,
+            json!({ "private_key": KEY, "chain_id": "sei-testnet", "to_address": recipient, "amount_usei": "1000000" }),
+        )
+        .await;
+        let result = &resp["result"];
+        assert_eq!(result["kind"], "native", "{}", resp);
+        assert_eq!(result["amount"]["sei"], "1");
+        assert_eq!(result["estimated_fee"]["base"], "20000");
+        assert_eq!(result["total"]["sei"], "1.02");
+        assert_eq!(result["total"]["unit"], "usei");
+        assert!(result["usd"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_preview_enforces_transfer_cap() {
+        let config = Config {
+            max_transfer_amounts: [("sei-evm-testnet".to_string(), "100".to_string())].into(),
+            ..Config::default()
+        };
+        let resp = preview(
+            config,
+            json!({
+                "private_key": KEY,
+                "chain_id": "sei-evm-testnet",
+                "to_address": "0x000000000000000000000000000000000000dead",
+                "amount_wei": "101"
+            }),
+        )
+        .await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+    }
+}
+
+mod quote_fee {
+    use super::common;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    // Against the default mock node: transfers estimate to 21000 gas at a 1 gwei legacy price
+    async fn quote_fee(arguments: Value) -> (Value, common::Calls) {
+        let (rpc_url, calls) = common::spawn_mock_rpc().await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            usd_prices: [("sei-evm-testnet".to_string(), 0.35)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-quote-fee-test.json"));
+        (common::call(&state, "quote_fee", arguments).await, calls)
+    }
+
+    #[tokio::test]
+    async fn test_tx_spec_is_estimated_and_quoted_in_every_denomination() {
+        let arguments = json!({ "chain_id": "sei-evm-testnet", "to_address": "0x000000000000000000000000000000000000dEaD", "value": "7" });
+        let (resp, _calls) = quote_fee(arguments).await;
+        let result = &resp["result"];
+        assert_eq!(result["fee"]["base"], "21000000000000", "{}", resp);
+        assert_eq!(result["fee"]["unit"], "wei");
+        assert_eq!(result["fee"]["sei"], "0.000021");
+        assert_eq!(result["fee"]["usd"], 0.0, "rounded to cents");
+        assert_eq!(result["details"]["gas_limit"], "21000");
+        assert_eq!(result["details"]["max_fee_per_gas_gwei"], "1");
+    }
+
+    #[tokio::test]
+    async fn test_gas_limit_alone_skips_estimation() {
+        let arguments = json!({ "chain_id": "sei-evm-testnet", "gas_limit": "1000000", "gas_price": "1000000000000" });
+        let (resp, calls) = quote_fee(arguments).await;
+        let result = &resp["result"];
+        assert_eq!(result["fee"]["sei"], "1", "{}", resp);
+        assert_eq!(result["fee"]["usd"], 0.35);
+        assert!(result["content"][0]["text"].as_str().unwrap().ends_with("about $0.35"));
+        assert!(calls.lock().unwrap().iter().all(|c| c["method"] != "eth_estimateGas"));
+    }
+
+    #[tokio::test]
+    async fn test_needs_a_transaction_or_gas_limit() {
+        let (resp, _calls) = quote_fee(json!({ "chain_id": "sei-evm-testnet", "value": "7" })).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+    }
+}
+
+mod sign_permit {
+    use super::common;
+    use ethers_core::abi::{encode, Token};
+    use ethers_core::types::transaction::eip712::{Eip712, TypedData};
+    use ethers_core::types::{Address, Signature, H256};
+    use sei_mcp_server_rs::blockchain::services::erc20;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::{error_codes, Request};
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const PRIVATE_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    const OWNER: &str = "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23";
+    const SPENDER: &str = "0x000000000000000000000000000000000000dEaD";
+
+    fn word(bytes: Vec<u8>) -> Value {
+        json!(format!("0x{}", hex::encode(bytes)))
+    }
+
+    // An ERC-2612 token named "Test Token" (domain version "1", no version() getter) on chain 0x530;
+    // the owner's permit nonce is 3. `with_permit: false` drops DOMAIN_SEPARATOR().
+    fn token(with_permit: bool) -> impl Fn(&Value) -> Value + Clone + Send + Sync + 'static {
+        move |req: &Value| {
+            let revert = json!({ "jsonrpc": "2.0", "id": req["id"], "error": { "code": 3, "message": "execution reverted" } });
+            if req["method"] != "eth_call" {
+                return common::default_reply(req);
+            }
+            let to: Address = req["params"][0]["to"].as_str().unwrap().parse().unwrap();
+            let result = match &req["params"][0]["data"].as_str().unwrap()[..10] {
+                "0x3644e515" if with_permit => word(erc20::permit_domain_separator("Test Token", "1", 0x530, to).to_vec()),
+                "0x06fdde03" => word(encode(&[Token::String("Test Token".into())])),
+                "0x7ecebe00" => word(encode(&[Token::Uint(3.into())])),
+                _ => return revert,
+            };
+            json!({ "jsonrpc": "2.0", "id": req["id"], "result": result })
+        }
+    }
+
+    async fn sign_permit(with_permit: bool) -> Value {
+        let (rpc_url, _) = common::spawn_rpc(token(with_permit)).await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-permit-test.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "sign_permit", "arguments": {
+                "private_key": PRIVATE_KEY,
+                "chain_id": "sei-evm-testnet",
+                "contract_address": common::DEPLOYED_ADDRESS,
+                "spender": SPENDER,
+                "value": "1000000",
+                "deadline": 1893456000
+            }}
+        }))
+        .unwrap();
+        serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_permit_uses_on_chain_nonce_and_recovers_to_owner() {
+        let resp = sign_permit(true).await;
+        let result = &resp["result"];
+        assert_eq!(result["nonce"], "3", "{}", resp);
+        assert_eq!(result["deadline"], 1893456000);
+        assert_eq!(result["typed_data"]["domain"]["name"], "Test Token");
+
+        let typed: TypedData = serde_json::from_value(result["typed_data"].clone()).unwrap();
+        let signature: Signature = result["signature"].as_str().unwrap().parse().unwrap();
+        assert_eq!(signature.v, result["v"].as_u64().unwrap());
+        let signer = signature.recover(H256::from(typed.encode_eip712().unwrap())).unwrap();
+        assert_eq!(format!("{:?}", signer), OWNER);
+    }
+
+    #[tokio::test]
+    async fn test_token_without_permit_is_rejected() {
+        let resp = sign_permit(false).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("does not support permit"), "{}", resp);
+    }
+}
+
+mod sign_typed_data {
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::{error_codes, Request};
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const PRIVATE_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+
+    async fn sign(typed_data: Value) -> Value {
+        let state = AppState::new(Config::default(), std::env::temp_dir().join("sei-mcp-typed-data-test.json"));
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "sign_typed_data", "arguments": { "private_key": PRIVATE_KEY, "typed_data": typed_data } }
+        }))
+        .unwrap();
+        serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap()
+    }
+
+    fn permit() -> Value {
+        json!({
+            "types": {
+                "EIP712Domain": [
+                    {"name": "name", "type": "string"},
+                    {"name": "version", "type": "string"},
+                    {"name": "chainId", "type": "uint256"},
+                    {"name": "verifyingContract", "type": "address"}
+                ],
+                "Permit": [
+                    {"name": "owner", "type": "address"},
+                    {"name": "spender", "type": "address"},
+                    {"name": "value", "type": "uint256"},
+                    {"name": "nonce", "type": "uint256"},
+                    {"name": "deadline", "type": "uint256"}
+                ]
+            },
+            "primaryType": "Permit",
+            "domain": {"name": "USD Coin", "version": "2", "chainId": 1328, "verifyingContract": "0x5FbDB2315678afecb367f032d93F642f64180aa3"},
+            "message": {
+                "owner": "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23",
+                "spender": "0x000000000000000000000000000000000000dEaD",
+                "value": "1000000",
+                "nonce": 0,
+                "deadline": 1893456000
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_permit_signature_recovers_to_signer() {
+        let resp = sign(permit()).await;
+        let result = &resp["result"];
+        assert_eq!(result["signer"], "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23", "{}", resp);
+
+        let signature: ethers_core::types::Signature = result["signature"].as_str().unwrap().parse().unwrap();
+        let digest: [u8; 32] = hex::decode(&result["digest"].as_str().unwrap()[2..]).unwrap().try_into().unwrap();
+        let recovered = signature.recover(ethers_core::types::H256::from(digest)).unwrap();
+        assert_eq!(format!("{:?}", recovered), "0x2c7536e3605d9c16a7a3d7b1898e529396a65c23");
+    }
+
+    #[tokio::test]
+    async fn test_malformed_typed_data_is_invalid_params() {
+        let mut typed = permit();
+        typed.as_object_mut().unwrap().remove("domain");
+        let resp = sign(typed).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("missing 'domain'"), "{}", resp);
+    }
+}
+
+mod spending_budget {
+    use super::common;
+    use sei_mcp_server_rs::config::{Config, SpendingBudget};
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    const KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    // Address of KEY
+    const SENDER: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+
+    async fn app_state(budgets: &[(&str, &str)]) -> AppState {
+        let (rpc_url, _calls) = common::spawn_mock_rpc().await;
+        let budgets = budgets
+            .iter()
+            .map(|(key, amount)| (key.to_string(), SpendingBudget { amount: amount.to_string(), window_secs: 3600 }))
+            .collect();
+        let config = Config { spending_budgets: [("sei-evm-testnet".to_string(), budgets)].into(), ..common::testnet_config(rpc_url) };
+        common::app_state(config, "sei-mcp-spending-budget-test.json")
+    }
+
+    async fn transfer(state: &AppState, amount_wei: &str) -> Value {
+        let arguments = json!({
+            "chain_id": "sei-evm-testnet",
+            "private_key": KEY,
+            "to_address": "0x000000000000000000000000000000000000dEaD",
+            "amount_wei": amount_wei,
+            "confirm_amount": true
+        });
+        common::call(state, "transfer_evm", arguments).await
+    }
+
+    async fn status(state: &AppState) -> Value {
+        common::call(state, "get_spending_status", json!({ "chain_id": "sei-evm-testnet", "address": SENDER })).await
+    }
+
+    #[tokio::test]
+    async fn test_transfers_up_to_the_budget_pass_and_beyond_it_are_refused() {
+        let state = app_state(&[("*", "10")]).await;
+        let resp = transfer(&state, "7").await;
+        assert_eq!(resp["result"]["tx_hash"], common::TX_HASH, "{}", resp);
+        let resp = transfer(&state, "3").await;
+        assert!(resp["error"].is_null(), "exactly at budget is allowed: {}", resp);
+
+        let resp = transfer(&state, "1").await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("spending budget exceeded"));
+
+        let resp = status(&state).await;
+        let result = &resp["result"];
+        assert_eq!(result["spent_wei"], "10", "{}", resp);
+        assert_eq!(result["remaining_wei"], "0");
+        assert_eq!(result["window_secs"], 3600);
+        assert!(result["resets_at"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_address_entry_overrides_default_and_unbudgeted_senders_are_unlimited() {
+        let state = app_state(&[("*", "10"), (&SENDER.to_lowercase(), "100")]).await;
+        let resp = transfer(&state, "50").await;
+        assert!(resp["error"].is_null(), "{}", resp);
+        assert_eq!(status(&state).await["result"]["remaining_wei"], "50");
+
+        let state = app_state(&[]).await;
+        let resp = status(&state).await;
+        assert_eq!(resp["result"]["budget_wei"], Value::Null, "{}", resp);
+        assert!(transfer(&state, "1000").await["error"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_deploy_value_draws_on_the_same_budget() {
+        let state = app_state(&[("*", "10")]).await;
+        let deploy = |value: &str| {
+            json!({ "chain_id": "sei-evm-testnet", "private_key": KEY, "bytecode": "0x6080604052", "value": value, "gas_limit": "3000000" })
+        };
+        let resp = common::call(&state, "deploy_contract", deploy("8")).await;
+        assert!(resp["error"].is_null(), "{}", resp);
+        assert_eq!(status(&state).await["result"]["remaining_wei"], "2");
+
+        let resp = common::call(&state, "deploy_contract", deploy("3")).await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("spending budget exceeded"));
+        assert!(transfer(&state, "3").await["error"].is_object(), "transfers share the allowance");
+    }
+}
+
+mod transfer_memo {
+    use super::common;
+    use axum::{extract::Path, routing::get, routing::post, Json, Router};
+    use base64::Engine;
+    use ethers_core::types::Transaction;
+    use sei_mcp_server_rs::blockchain::services::transactions::native_sender_address;
+    use sei_mcp_server_rs::config::Config;
+    use sei_mcp_server_rs::mcp::protocol::error_codes;
+    use serde_json::{json, Value};
+    use std::sync::{Arc, Mutex};
+
+    const SENDER_KEY: &str = "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+    const WALLETS: &str = "sei-mcp-memo-test.json";
+
+    type Broadcasts = Arc<Mutex<Vec<cosmrs::Tx>>>;
+
+    // A Cosmos REST node whose auth params allow memos of at most 32 bytes.
+    async fn spawn_rest() -> (String, Broadcasts) {
+        let seen: Broadcasts = Arc::default();
+        let recorder = seen.clone();
+        let app = Router::new()
+            .route(
+                "/cosmos/auth/v1beta1/params",
+                get(|| async { Json(json!({ "params": { "max_memo_characters": "32", "tx_sig_limit": "7" } })) }),
+            )
+            .route(
+                "/cosmos/auth/v1beta1/accounts/:address",
+                get(|Path(address): Path<String>| async move {
+                    Json(json!({ "account": { "address": address, "account_number": "7", "sequence": "3" } }))
+                }),
+            )
+            .route(
+                "/cosmos/tx/v1beta1/txs",
+                post(move |Json(req): Json<Value>| async move {
+                    let bytes = base64::engine::general_purpose::STANDARD.decode(req["tx_bytes"].as_str().unwrap()).unwrap();
+                    recorder.lock().unwrap().push(cosmrs::Tx::from_bytes(&bytes).unwrap());
+                    Json(json!({ "tx_response": { "code": 0, "txhash": "A1B2C3" } }))
+                }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (url, seen)
+    }
+
+    fn native_config(rest_url: String) -> Config {
+        Config {
+            chain_rpc_urls: [("atlantic-2".to_string(), rest_url)].into(),
+            native_chain_id: "atlantic-2".into(),
+            native_denom: "usei".into(),
+            native_bech32_hrp: "sei".into(),
+            native_fee_amount: 5000,
+            native_gas_limit: 200000,
+            ..Config::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memo_is_signed_into_native_transfer() {
+        let (url, seen) = spawn_rest().await;
+        let config = native_config(url);
+        let recipient = native_sender_address(&config, SENDER_KEY).unwrap();
+        let resp = common::call(
+            &common::app_state(config, WALLETS),
+            "transfer_sei",
+            json!({ "private_key": SENDER_KEY, "chain_id": "atlantic-2", "to_address": recipient, "amount_usei": "5000", "memo": "invoice 42" }),
+        )
+        .await;
+        assert_eq!(resp["result"]["memo"], "invoice 42", "{}", resp);
+        assert_eq!(seen.lock().unwrap()[0].body.memo, "invoice 42");
+    }
+
+    #[tokio::test]
+    async fn test_memo_over_chain_limit_is_rejected() {
+        let (url, seen) = spawn_rest().await;
+        let config = native_config(url);
+        let recipient = native_sender_address(&config, SENDER_KEY).unwrap();
+        let resp = common::call(
+            &common::app_state(config, WALLETS),
+            "transfer_sei",
+            json!({ "private_key": SENDER_KEY, "chain_id": "atlantic-2", "to_address": recipient, "amount_usei": "5000", "memo": "x".repeat(33) }),
+        )
+        .await;
+        assert_eq!(resp["error"]["code"], error_codes::INVALID_PARAMS, "{}", resp);
+        assert!(resp["error"]["message"].as_str().unwrap().contains("at most 32"), "{}", resp);
+        assert!(seen.lock().unwrap().is_empty(), "nothing may be broadcast");
+    }
+
+    #[tokio::test]
+    async fn test_evm_transfer_carries_data() {
+        let (rpc_url, calls) = common::spawn_mock_rpc().await;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            ..Config::default()
+        };
+        let resp = common::call(
+            &common::app_state(config, WALLETS),
+            "transfer_evm",
+            json!({
+                "private_key": SENDER_KEY,
+                "chain_id": "sei-evm-testnet",
+                "to_address": "0x000000000000000000000000000000000000dEaD",
+                "amount_wei": "1000000000000000",
+                "gas_price": "1000000000",
+                "data": "0xc0ffee"
+            }),
+        )
+        .await;
+        assert_eq!(resp["result"]["data"], "0xc0ffee", "{}", resp);
+
+        let calls = calls.lock().unwrap();
+        let raw = calls
+            .iter()
+            .find(|c| c["method"] == "eth_sendRawTransaction")
+            .and_then(|c| c["params"][0].as_str())
+            .unwrap();
+        let tx: Transaction = ethers_core::utils::rlp::decode(&hex::decode(&raw[2..]).unwrap()).unwrap();
+        assert_eq!(tx.input.to_vec(), vec![0xc0, 0xff, 0xee]);
+    }
+}
+
+mod tx_type {
+    use super::common;
+    use ethers_core::types::transaction::eip2718::TypedTransaction;
+    use ethers_core::types::U256;
+    use ethers_core::utils::rlp::Rlp;
+    use sei_mcp_server_rs::config::{Config, TxType};
+    use sei_mcp_server_rs::mcp::handler::handle_mcp_request;
+    use sei_mcp_server_rs::mcp::protocol::Request;
+    use sei_mcp_server_rs::AppState;
+    use serde_json::{json, Value};
+
+    // Node that supports EIP-1559: base fee 1 gwei, median tip 2 wei
+    async fn spawn_1559_node() -> (String, common::Calls) {
+        common::spawn_rpc(|req| {
+            if req["method"] == "eth_feeHistory" {
+                let history = json!({ "baseFeePerGas": ["0x3b9aca00", "0x3b9aca00"], "reward": [["0x2"]], "oldestBlock": "0x1" });
+                return json!({ "jsonrpc": "2.0", "id": req["id"], "result": history });
+            }
+            common::default_reply(req)
+        })
+        .await
+    }
+
+    // Sends a transfer_evm and returns the signed envelope the node received
+    async fn send(node: (String, common::Calls), tx_type: Option<TxType>, gas_price: Option<&str>) -> TypedTransaction {
+        let (rpc_url, calls) = node;
+        let config = Config {
+            chain_rpc_urls: [("sei-evm-testnet".to_string(), rpc_url)].into(),
+            tx_types: tx_type.map(|t| [("sei-evm-testnet".to_string(), t)].into()).unwrap_or_default(),
+            ..Config::default()
+        };
+        let state = AppState::new(config, std::env::temp_dir().join("sei-mcp-tx-type.json"));
+        let mut arguments = json!({
+            "chain_id": "sei-evm-testnet",
+            "private_key": "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+            "to_address": "0x000000000000000000000000000000000000dEaD",
+            "amount_wei": "7"
+        });
+        if let Some(gp) = gas_price {
+            arguments["gas_price"] = json!(gp);
+        }
+        let req: Request = serde_json::from_value(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "tools/call",
+            "params": { "name": "transfer_evm", "arguments": arguments }
+        }))
+        .unwrap();
+        let resp: Value = serde_json::to_value(handle_mcp_request(req, state).await.unwrap()).unwrap();
+        assert!(resp["error"].is_null(), "{}", resp);
+
+        let calls = calls.lock().unwrap();
+        let raw = calls
+            .iter()
+            .find(|c| c["method"] == "eth_sendRawTransaction")
+            .and_then(|c| c["params"][0].as_str())
+            .unwrap();
+        let bytes = hex::decode(&raw[2..]).unwrap();
+        TypedTransaction::decode_signed(&Rlp::new(&bytes)).unwrap().0
+    }
+
+    #[tokio::test]
+    async fn test_auto_uses_eip1559_when_fee_history_is_supported() {
+        let tx = send(spawn_1559_node().await, None, None).await;
+        let TypedTransaction::Eip1559(tx) = tx else { panic!("expected an EIP-1559 envelope, got {:?}", tx) };
+        assert_eq!(tx.max_priority_fee_per_gas, Some(U256::from(2)));
+        assert_eq!(tx.max_fee_per_gas, Some(U256::from(2_000_000_002u64)));
+        assert_eq!(tx.value, Some(U256::from(7)));
+    }
+
+    #[tokio::test]
+    async fn test_auto_falls_back_to_legacy_without_fee_history() {
+        // The default mock answers eth_feeHistory with null
+        let tx = send(common::spawn_mock_rpc().await, None, None).await;
+        assert!(matches!(tx, TypedTransaction::Legacy(_)), "{:?}", tx);
+    }
+
+    #[tokio::test]
+    async fn test_configured_type_overrides_probe() {
+        let node = spawn_1559_node().await;
+        let calls = node.1.clone();
+        let tx = send(node, Some(TxType::Legacy), None).await;
+        assert!(matches!(tx, TypedTransaction::Legacy(_)), "{:?}", tx);
+        assert!(calls.lock().unwrap().iter().all(|c| c["method"] != "eth_feeHistory"));
+
+        let tx = send(spawn_1559_node().await, Some(TxType::Eip1559), Some("5000")).await;
+        let TypedTransaction::Eip1559(tx) = tx else { panic!("expected an EIP-1559 envelope, got {:?}", tx) };
+        assert_eq!(tx.max_fee_per_gas, Some(U256::from(5000)));
+    }
+}